@@ -3,11 +3,12 @@ use bevy::{
     ecs::{
         event::EventReader,
         schedule::IntoSystemConfigs,
-        system::{Res, ResMut, Resource},
+        system::{Local, Res, ResMut, Resource},
     },
     log::{error, info},
     state::condition::in_state,
     state::state::{OnEnter, OnExit},
+    time::{Time, Timer, TimerMode},
 };
 use bevy_serialport::{
     DataBits, FlowControl, Parity, SerialPortPlugin, SerialPortRuntime, SerialPortSetting,
@@ -16,8 +17,12 @@ use bevy_serialport::{
 
 use derive_more::{Deref, DerefMut, From};
 use serialport::{available_ports, SerialPortType};
+use thetawave_interface::player::PlayerDamagedEvent;
 use thetawave_interface::states;
 
+/// Seconds the buttons stay lit after a player takes damage
+const PLAYER_DAMAGED_LED_TIME: f32 = 0.2;
+
 /// Environment variable name of the serial port that handles lights.
 const THETAWAVE_ARCADE_LIGHT_SERIAL_PORT_NAME: &'static str =
     "THETAWAVE_ARCADE_LIGHT_SERIAL_PORT_NAME";
@@ -99,6 +104,13 @@ impl Plugin for ArcadeArduinoPlugin {
                 enter_game_button_leds_system,
             );
 
+            app.add_systems(
+                Update,
+                player_damaged_button_leds_system
+                    .run_if(in_state(states::AppStates::Game))
+                    .run_if(in_state(states::GameStates::Playing)),
+            );
+
             app.add_systems(
                 OnEnter(states::GameStates::Paused),
                 enter_pause_button_leds_system,
@@ -210,6 +222,18 @@ impl ButtonLEDByte {
         .into()
     }
 
+    fn player_damaged() -> Bytes {
+        vec![
+            ButtonLEDByte::On as u8,
+            ButtonLEDByte::On as u8,
+            ButtonLEDByte::On as u8,
+            ButtonLEDByte::On as u8,
+            ButtonLEDByte::On as u8,
+            ButtonLEDByte::EndMarker as u8,
+        ]
+        .into()
+    }
+
     fn enter_pause() -> Bytes {
         vec![
             ButtonLEDByte::Off as u8,
@@ -282,6 +306,30 @@ fn enter_game_button_leds_system(
     serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_game());
 }
 
+/// Briefly lights every button when a player takes damage, then returns to the in-game lighting
+fn player_damaged_button_leds_system(
+    mut serial_resource: ResMut<SerialResource>,
+    arduino_port: Res<ArduinoSerialPort>,
+    mut player_damaged_event: EventReader<PlayerDamagedEvent>,
+    mut lit_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    if player_damaged_event.read().count() > 0 {
+        serial_resource.send_message(&(*arduino_port), ButtonLEDByte::player_damaged());
+        *lit_timer = Some(Timer::from_seconds(
+            PLAYER_DAMAGED_LED_TIME,
+            TimerMode::Once,
+        ));
+    }
+
+    if let Some(timer) = lit_timer.as_mut() {
+        if timer.tick(time.delta()).just_finished() {
+            serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_game());
+            *lit_timer = None;
+        }
+    }
+}
+
 fn enter_pause_button_leds_system(
     mut serial_resource: ResMut<SerialResource>,
     arduino_port: Res<ArduinoSerialPort>,
@@ -1,37 +1,39 @@
 use bevy::{
     asset::Handle,
-    prelude::{Image, Res, Resource},
+    log::error,
+    prelude::{Image, Resource},
+    utils::HashMap,
 };
-use bevy_asset_loader::asset_collection::AssetCollection;
+use bevy_asset_loader::{asset_collection::AssetCollection, mapped::AssetFileStem};
 use thetawave_interface::character::CharacterType;
 
-/// Collection of images for player characters
+/// Collection of images for player characters, keyed by file stem
+///
+/// A character with the key `"captain"` in `characters.ron` uses the images
+/// `captain_character.png` and `captain_character_outline.png`. Both have to be listed under
+/// `"characters"` in `player_assets.assets.ron` to be loaded, a missing image is logged and drawn
+/// with the default handle.
 #[derive(AssetCollection, Resource)]
 pub struct PlayerAssets {
-    #[asset(key = "captain")]
-    pub captain: Handle<Image>,
-    #[asset(key = "juggernaut")]
-    pub juggernaut: Handle<Image>,
-    #[asset(key = "captain_outline")]
-    pub captain_outline: Handle<Image>,
-    #[asset(key = "juggernaut_outline")]
-    pub juggernaut_outline: Handle<Image>,
+    #[asset(key = "characters", collection(typed, mapped))]
+    pub characters: HashMap<AssetFileStem, Handle<Image>>,
 }
 
 impl PlayerAssets {
-    /// Use a CharacterType enum to access an image handle
+    /// Use a CharacterType key to access an image handle
     pub fn get_asset(&self, character_type: &CharacterType) -> Handle<Image> {
-        match character_type {
-            CharacterType::Captain => self.captain.clone(),
-            CharacterType::Juggernaut => self.juggernaut.clone(),
-        }
+        self.get_image(&format!("{}_character", character_type.as_str()))
     }
 
-    /// Use a CharacterType enum to access a character's associated outline image handle
+    /// Use a CharacterType key to access a character's associated outline image handle
     pub fn get_outline_asset(&self, character_type: &CharacterType) -> Handle<Image> {
-        match character_type {
-            CharacterType::Captain => self.captain_outline.clone(),
-            CharacterType::Juggernaut => self.juggernaut_outline.clone(),
-        }
+        self.get_image(&format!("{}_character_outline", character_type.as_str()))
+    }
+
+    fn get_image(&self, file_stem: &str) -> Handle<Image> {
+        self.characters.get(file_stem).cloned().unwrap_or_else(|| {
+            error!("No character image found with file name: {}", file_stem);
+            Handle::default()
+        })
     }
 }
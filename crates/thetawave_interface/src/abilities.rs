@@ -25,7 +25,7 @@ pub enum SlotTwoAbilityType {
     MegaBlast,
 }
 
-/// Hashmaps of ability types to descriptions
+/// Hashmaps of ability types to the string ids of their descriptions
 /// Used for providing information to user on character selection screen
 #[derive(Resource, Deserialize)]
 pub struct AbilityDescriptionsResource {
@@ -1,19 +1,31 @@
 use bevy_math::Vec2;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
 use crate::{
     abilities::{SlotOneAbilityType, SlotTwoAbilityType},
+    game::unlocks::CharacterUnlockCondition,
     health::HealthComponent,
     spawnable::SpawnPosition,
 };
 
-/// The playable character types. To a player, these will have different appearances and abilities.
-#[derive(Deserialize, Clone, Debug, Hash, PartialEq, Eq, EnumIter, Default, Copy)]
-pub enum CharacterType {
-    #[default]
-    Captain,
-    Juggernaut,
+/// String key of a playable character. To a player, these will have different appearances and
+/// abilities. Keys match the entries in `characters.ron` and the file stems of the character's
+/// sprite and outline images in the player asset collection.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct CharacterType(pub String);
+
+impl From<&str> for CharacterType {
+    fn from(key: &str) -> Self {
+        Self(key.to_string())
+    }
+}
+
+impl CharacterType {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 // Stats used to give the player a rough idea of the strengths and weaknesses of the character
@@ -45,8 +57,6 @@ pub struct Character {
     pub collider_dimensions: Vec2,
     /// Density of the collider (mass of collider is proportional to its size)
     pub collider_density: f32,
-    /// Character type
-    pub character_type: CharacterType,
     /// Health of the player
     pub health: usize,
     /// Shields of the player
@@ -79,6 +89,16 @@ pub struct Character {
     pub slot_2_ability: Option<SlotTwoAbilityType>,
     /// Multiplier for how long abilities take to be ready for use again
     pub cooldown_multiplier: f32,
+    /// Seconds the player ignores damage for after being damaged
+    pub invulnerability_time: f32,
+    /// Speed the player is knocked back at when damaged
+    pub knockback_speed: f32,
+    /// Condition that unlocks the character, `None` if it can't be unlocked by playing
+    #[serde(default)]
+    pub unlock_condition: Option<CharacterUnlockCondition>,
+    /// Meta-currency that can be spent to unlock the character, `None` if it can't be bought
+    #[serde(default)]
+    pub unlock_cost: Option<usize>,
 }
 
 impl From<&Character> for HealthComponent {
@@ -0,0 +1,115 @@
+//! Achievements declared in `achievements.ron` and the progress towards them that carries over
+//! between runs.
+use crate::localization::LocalizationResource;
+use crate::spawnable::{ConsumableType, EnemyMobType};
+use bevy_ecs_macros::{Event, Resource};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Gameplay that counts towards an achievement. Each time the condition is met the achievement's
+/// progress goes up by one.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AchievementCondition {
+    /// Destroy enemy mobs, optionally only of one type or only bosses
+    MobsDestroyed {
+        mob_type: Option<EnemyMobType>,
+        bosses_only: bool,
+    },
+    /// Finish runs, optionally only runs that were won
+    RunsEnded { victories_only: bool },
+    /// Win runs without any enemy getting through the bottom gate
+    RunsWonWithoutLeaks,
+    /// Mobs reaching the bottom gate, either allies healing the defense or enemies damaging it
+    MobsReachedBottomGate { healing: bool },
+    /// Collect consumables, optionally only of one type
+    ConsumablesCollected {
+        consumable_type: Option<ConsumableType>,
+    },
+    /// Complete levels without any player taking damage
+    LevelsClearedWithoutDamage,
+}
+
+/// An achievement as declared in `achievements.ron`
+#[derive(Deserialize, Clone, Debug)]
+pub struct AchievementData {
+    /// Key that progress is saved under, must not change once released. Also names the
+    /// `achievement.<key>.name` and `achievement.<key>.description` string ids.
+    pub key: String,
+    pub condition: AchievementCondition,
+    /// Number of times the condition has to be met to unlock the achievement
+    pub goal: usize,
+    /// Progress is only kept for the current run, so the goal has to be met within a single run
+    #[serde(default)]
+    pub per_run: bool,
+}
+
+/// The 'model' of the Achievements Sqlite table. Progress towards each achievement and the
+/// achievements that have been unlocked, keyed by `AchievementData::key`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Resource)]
+pub struct AchievementsProgressResource {
+    pub progress: HashMap<String, usize>,
+    pub unlocked: HashSet<String>,
+}
+
+impl AchievementData {
+    pub fn get_name_id(&self) -> String {
+        format!("achievement.{}.name", self.key)
+    }
+
+    pub fn get_description_id(&self) -> String {
+        format!("achievement.{}.description", self.key)
+    }
+
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
+        localization.get(&self.get_name_id())
+    }
+
+    pub fn get_description(&self, localization: &LocalizationResource) -> String {
+        localization.get(&self.get_description_id())
+    }
+}
+
+impl AchievementsProgressResource {
+    pub fn get_progress(&self, key: &str) -> usize {
+        self.progress.get(key).copied().unwrap_or_default()
+    }
+
+    pub fn is_unlocked(&self, key: &str) -> bool {
+        self.unlocked.contains(key)
+    }
+
+    /// Adds progress towards an achievement that is still locked, returns true if that unlocked it
+    pub fn add_progress(&mut self, achievement: &AchievementData, amount: usize) -> bool {
+        if amount == 0 || self.is_unlocked(&achievement.key) {
+            return false;
+        }
+
+        let progress = self.progress.entry(achievement.key.clone()).or_default();
+        *progress = (*progress + amount).min(achievement.goal);
+
+        if *progress >= achievement.goal {
+            self.unlocked.insert(achievement.key.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Clears progress towards achievements that have to be completed within one run
+    pub fn reset_per_run_progress<'a>(
+        &mut self,
+        achievements: impl IntoIterator<Item = &'a AchievementData>,
+    ) {
+        for achievement in achievements {
+            if achievement.per_run && !self.is_unlocked(&achievement.key) {
+                self.progress.remove(&achievement.key);
+            }
+        }
+    }
+}
+
+/// Sent when an achievement is unlocked, so that it can be announced
+#[derive(Event, Debug, Clone)]
+pub struct AchievementUnlockedEvent {
+    pub key: String,
+}
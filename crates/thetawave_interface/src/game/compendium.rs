@@ -0,0 +1,45 @@
+//! Mobs, items and consumables that have been encountered. The compendium only reveals entries
+//! once they have been encountered in a run.
+use crate::spawnable::{ConsumableType, ItemType, MobType};
+use bevy_ecs_macros::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Something that can be looked up in the compendium
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CompendiumEntry {
+    Mob(MobType),
+    Item(ItemType),
+    Consumable(ConsumableType),
+}
+
+impl CompendiumEntry {
+    /// Key that the encounter is saved under, e.g. `mob/Enemy(Drone)`
+    pub fn key(&self) -> String {
+        match self {
+            CompendiumEntry::Mob(mob_type) => format!("mob/{mob_type:?}"),
+            CompendiumEntry::Item(item_type) => format!("item/{item_type:?}"),
+            CompendiumEntry::Consumable(consumable_type) => {
+                format!("consumable/{consumable_type:?}")
+            }
+        }
+    }
+}
+
+/// The 'model' of the Encounters Sqlite table. Keys of the compendium entries that have been
+/// encountered, which are never forgotten.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Resource)]
+pub struct EncountersResource {
+    pub encountered: HashSet<String>,
+}
+
+impl EncountersResource {
+    pub fn has_encountered(&self, entry: &CompendiumEntry) -> bool {
+        self.encountered.contains(&entry.key())
+    }
+
+    /// Records an encounter, returns true if the entry had not been encountered before
+    pub fn encounter(&mut self, entry: &CompendiumEntry) -> bool {
+        self.encountered.insert(entry.key())
+    }
+}
@@ -1,19 +1,113 @@
 //! Stats for games _before_ the currently running game. A value of 0 typically means that the
 //! corresponding systems are not 'online' to mutate the resources.
+use crate::game::run_history::RunRecord;
+use crate::player::PlayerIDComponent;
+use crate::run::{RunDefeatType, RunOutcomeType};
 use crate::spawnable::EnemyMobType;
 use bevy_ecs_macros::Resource;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::AddAssign;
 
 /// The 'model' of the UserStat Sqlite table. Persisted user stats about past games.
-#[derive(Debug, Default, Clone, derive_more::AddAssign, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UserStat {
     pub total_shots_fired: usize,
     pub total_shots_hit: usize,
+    /// Games lost for any reason, abandoned runs included
     pub total_games_lost: usize,
+    pub total_games_won: usize,
+    /// Games lost because every player was destroyed
+    pub total_games_lost_to_players_destroyed: usize,
+    /// Games lost because the defense objective was destroyed
+    pub total_games_lost_to_defense_destroyed: usize,
+    pub total_levels_completed: usize,
+    /// Most levels completed in a single game
+    pub most_levels_completed: usize,
+    /// Duration of the fastest game won
+    pub fastest_victory_millis: Option<usize>,
 }
+
+impl UserStat {
+    /// Counts the outcome, levels completed and duration of a game that has ended. Abandoned runs
+    /// count as losses.
+    pub fn add_completed_run(&mut self, run: &RunRecord) {
+        match run.outcome {
+            RunOutcomeType::Victory => {
+                self.total_games_won += 1;
+                let duration_millis = (run.duration_secs * 1000.0) as usize;
+                self.fastest_victory_millis = Some(
+                    self.fastest_victory_millis
+                        .map_or(duration_millis, |fastest| fastest.min(duration_millis)),
+                );
+            }
+            RunOutcomeType::Defeat(defeat_type) => {
+                self.total_games_lost += 1;
+                match defeat_type {
+                    RunDefeatType::PlayersDestroyed => {
+                        self.total_games_lost_to_players_destroyed += 1
+                    }
+                    RunDefeatType::DefenseDestroyed => {
+                        self.total_games_lost_to_defense_destroyed += 1
+                    }
+                    RunDefeatType::Abandoned => {}
+                }
+            }
+        }
+        self.total_levels_completed += run.levels_completed;
+        self.most_levels_completed = self.most_levels_completed.max(run.levels_completed);
+    }
+}
+
+/// Totals are added together, while bests keep the better of the two
+impl AddAssign for UserStat {
+    fn add_assign(&mut self, other: Self) {
+        self.total_shots_fired += other.total_shots_fired;
+        self.total_shots_hit += other.total_shots_hit;
+        self.total_games_lost += other.total_games_lost;
+        self.total_games_won += other.total_games_won;
+        self.total_games_lost_to_players_destroyed += other.total_games_lost_to_players_destroyed;
+        self.total_games_lost_to_defense_destroyed += other.total_games_lost_to_defense_destroyed;
+        self.total_levels_completed += other.total_levels_completed;
+        self.most_levels_completed = self.most_levels_completed.max(other.most_levels_completed);
+        self.fastest_victory_millis =
+            match (self.fastest_victory_millis, other.fastest_victory_millis) {
+                (Some(fastest), Some(other_fastest)) => Some(fastest.min(other_fastest)),
+                (fastest, other_fastest) => fastest.or(other_fastest),
+            };
+    }
+}
+
+/// The 'model' of the LevelStats Sqlite table. How a user has done in one level over past games.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LevelStat {
+    /// Games where the level was started
+    pub times_reached: usize,
+    pub times_completed: usize,
+    /// Fastest time the level was completed in
+    pub best_time_millis: Option<usize>,
+}
+
+impl LevelStat {
+    /// Counts one attempt at the level, with the time it took if it was completed
+    pub fn add_attempt(&mut self, completed_time_millis: Option<usize>) {
+        self.times_reached += 1;
+        if let Some(time_millis) = completed_time_millis {
+            self.times_completed += 1;
+            self.best_time_millis = Some(
+                self.best_time_millis
+                    .map_or(time_millis, |best| best.min(time_millis)),
+            );
+        }
+    }
+}
+
 pub type UserStatsByPlayerCacheT = HashMap<usize, UserStat>;
 pub type MobsKilledBy1PlayerCacheT = HashMap<EnemyMobType, usize>;
 pub type MobsKilledByPlayerCacheT = HashMap<usize, MobsKilledBy1PlayerCacheT>;
+/// Level stats keyed by the name of the level
+pub type LevelStatsBy1PlayerCacheT = HashMap<String, LevelStat>;
+pub type LevelStatsByPlayerCacheT = HashMap<usize, LevelStatsBy1PlayerCacheT>;
 /// An in-memory cache of stats for games that have been completed. Keys are "user ids"
 #[derive(Debug, Default, Eq, PartialEq, Resource, derive_more::Deref, derive_more::DerefMut)]
 pub struct UserStatsByPlayerForCompletedGamesCache(pub UserStatsByPlayerCacheT);
@@ -41,5 +135,21 @@ pub struct MobKillsByPlayerForCompletedGames {
 #[derive(Debug, Default, Eq, PartialEq, Resource, derive_more::Deref, derive_more::DerefMut)]
 pub struct MobKillsByPlayerForCurrentGame(pub MobsKilledByPlayerCacheT);
 
+/// An in-memory cache of per-level stats for games that have been completed. Keys are "user ids"
+#[derive(Debug, Default, Eq, PartialEq, Resource, derive_more::Deref, derive_more::DerefMut)]
+pub struct LevelStatsByPlayerForCompletedGames(pub LevelStatsByPlayerCacheT);
+
+/// What a single player did during the currently running game
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct PlayerGameStat {
+    pub user_stat: UserStat,
+    pub mobs_killed: MobsKilledBy1PlayerCacheT,
+}
+
+/// Stats for the currently running game, keyed by the player that caused them rather than their
+/// profile, since co-op players can share a profile.
+#[derive(Debug, Default, Eq, PartialEq, Resource, derive_more::Deref, derive_more::DerefMut)]
+pub struct StatsByPlayerForCurrentGame(pub HashMap<PlayerIDComponent, PlayerGameStat>);
+
 /// The user id of the anonymous/"main" player. IOW "player 1".
 pub const DEFAULT_USER_ID: usize = 0;
@@ -3,5 +3,10 @@
 /// 0 for each new game.
 
 /// Resources/singletons with the within-game/run counts/metrics.
+pub mod achievements;
+pub mod compendium;
 pub mod historical_metrics;
 pub mod options;
+pub mod profiles;
+pub mod run_history;
+pub mod unlocks;
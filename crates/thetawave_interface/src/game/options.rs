@@ -1,13 +1,119 @@
+use bevy_ecs::prelude::Event;
 use bevy_ecs::system::Resource;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::localization::Language;
 
 pub const DEFAULT_OPTIONS_PROFILE_ID: usize = 0;
 
+/// How the game window is shown
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+}
+
+/// Colors that factions, border gradients and health bars are drawn with, so that they can be told
+/// apart with each kind of color blindness
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
 /// The 'model' of the Options Sqlite table.
 /// Defaults the least graphically intense options.
-#[derive(Debug, Default, Clone, Deserialize, Resource)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Resource)]
 pub struct GameOptions {
     pub bloom_enabled: bool,
+    /// Kept when bloom is disabled, so that it is restored when bloom is enabled again
     pub bloom_intensity: f32,
     pub tutorials_enabled: bool,
+    /// Volumes from 0 to 1, scaling the volume that each audio channel is mixed at
+    pub music_volume: f32,
+    pub sound_effects_volume: f32,
+    pub menu_volume: f32,
+    /// Scales the trauma of screen shakes, 0 disables screen shake
+    pub screen_shake_intensity: f32,
+    pub display_mode: DisplayMode,
+    /// Width and height of the window while windowed, the size in `config/display.ron` when unset
+    #[serde(default)]
+    pub window_resolution: Option<(u32, u32)>,
+    /// Key bindings in the format of `config/input.ron`, used instead of it when set
+    pub key_bindings: Option<String>,
+    /// Language of the player-facing text
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub color_palette: ColorPalette,
+    /// Disables screen shake and the bloom of flashing effects like explosions
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Multiplies the size of all UI text and of the HUD around the arena
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
 }
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        GameOptions {
+            bloom_enabled: false,
+            bloom_intensity: 0.0,
+            tutorials_enabled: false,
+            music_volume: 1.0,
+            sound_effects_volume: 1.0,
+            menu_volume: 1.0,
+            screen_shake_intensity: 1.0,
+            display_mode: DisplayMode::default(),
+            window_resolution: None,
+            key_bindings: None,
+            language: Language::default(),
+            color_palette: ColorPalette::default(),
+            reduced_motion: false,
+            ui_scale: default_ui_scale(),
+        }
+    }
+}
+
+impl GameOptions {
+    /// The bloom intensity to render with, 0 when bloom is disabled
+    pub fn get_bloom_intensity(&self) -> f32 {
+        if self.bloom_enabled {
+            self.bloom_intensity
+        } else {
+            0.0
+        }
+    }
+
+    /// The bloom intensity to render flashing effects with, 0 when motion is reduced
+    pub fn get_flash_bloom_intensity(&self) -> f32 {
+        if self.reduced_motion {
+            0.0
+        } else {
+            self.get_bloom_intensity()
+        }
+    }
+
+    /// The amount to scale the trauma of screen shakes by, 0 when motion is reduced
+    pub fn get_screen_shake_intensity(&self) -> f32 {
+        if self.reduced_motion {
+            0.0
+        } else {
+            self.screen_shake_intensity
+        }
+    }
+}
+
+/// Sent to change the game options. The new options are applied right away, and saved for the
+/// active profile when storage is enabled.
+#[derive(Event, Debug, Clone)]
+pub struct GameOptionsChangedEvent(pub GameOptions);
@@ -0,0 +1,92 @@
+//! Named player profiles. A profile's id is the "user id" that stats, options and unlocks are
+//! stored under.
+use bevy_ecs::prelude::Event;
+use bevy_ecs_macros::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::game::historical_metrics::DEFAULT_USER_ID;
+
+/// Longest name a profile can be given
+pub const MAX_PROFILE_NAME_LEN: usize = 16;
+
+/// The 'model' of the Profiles Sqlite table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: usize,
+    pub name: String,
+}
+
+/// Every saved profile and the one selected in the main menu
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ProfilesResource {
+    /// Ordered by when they were created
+    pub profiles: Vec<Profile>,
+    /// Profile of the first player. Options, character unlocks, achievements, encounters and run
+    /// history are only loaded from and saved to this profile. Other players' profiles only get
+    /// their stats, mob kills and level stats.
+    pub active_profile_id: usize,
+}
+
+impl Default for ProfilesResource {
+    fn default() -> Self {
+        ProfilesResource {
+            profiles: vec![],
+            active_profile_id: DEFAULT_USER_ID,
+        }
+    }
+}
+
+impl ProfilesResource {
+    pub fn get_profile(&self, profile_id: usize) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id == profile_id)
+    }
+
+    /// Name of a profile, or a placeholder for one that has been deleted
+    pub fn get_name(&self, profile_id: usize) -> String {
+        self.get_profile(profile_id)
+            .map(|profile| profile.name.clone())
+            .unwrap_or_else(|| format!("Player {}", profile_id + 1))
+    }
+
+    /// Profile for a player that just joined. The first player gets the active profile, other
+    /// players get the first profile that isn't being used.
+    pub fn get_profile_for_new_player(&self, used_profile_ids: &[usize]) -> usize {
+        if used_profile_ids.is_empty() {
+            return self.active_profile_id;
+        }
+
+        self.profiles
+            .iter()
+            .map(|profile| profile.id)
+            .find(|profile_id| !used_profile_ids.contains(profile_id))
+            .unwrap_or(self.active_profile_id)
+    }
+
+    /// The profile `step` places after (or before, if negative) the given profile, wrapping around
+    pub fn cycle_profile(&self, profile_id: usize, step: isize) -> usize {
+        if self.profiles.is_empty() {
+            return profile_id;
+        }
+
+        let idx = self
+            .profiles
+            .iter()
+            .position(|profile| profile.id == profile_id)
+            .unwrap_or_default() as isize;
+
+        self.profiles[(idx + step).rem_euclid(self.profiles.len() as isize) as usize].id
+    }
+}
+
+/// Changes to the saved profiles, applied by the storage plugin
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum ProfileEvent {
+    /// Make a new profile with the given name and select it
+    Create(String),
+    /// Select the profile used in the main menu
+    Select(usize),
+    /// Delete a profile along with everything saved for it. The last profile cannot be deleted.
+    Delete(usize),
+}
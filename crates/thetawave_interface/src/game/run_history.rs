@@ -0,0 +1,75 @@
+use bevy_ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::character::CharacterType;
+use crate::run::{DifficultyType, RunOutcomeType};
+
+/// Number of recent runs kept in `RunHistoryResource`
+pub const RECENT_RUNS_LIMIT: usize = 10;
+
+/// Number of runs kept on each leaderboard
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// The 'model' of the RunHistory Sqlite table. A record of a single run that was played to the end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Seconds since the unix epoch when the run started. Only known once the run is saved.
+    pub start_time: Option<u64>,
+    /// Seconds spent playing the run, not counting time paused
+    pub duration_secs: f32,
+    /// Characters played by each player in the run
+    pub characters: Vec<CharacterType>,
+    /// Key of the premade run that was played
+    pub run_key: String,
+    pub difficulty: DifficultyType,
+    pub levels_completed: usize,
+    pub outcome: RunOutcomeType,
+    /// Money collected by all players
+    pub money_collected: usize,
+    /// Damage taken by all players
+    pub damage_taken: usize,
+    /// Seed generated at the start of the run
+    pub seed: u64,
+    pub score: usize,
+}
+
+/// The best results of a user over all of their saved runs
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PersonalBests {
+    pub total_runs: usize,
+    pub most_levels_completed: usize,
+    pub longest_run_secs: f32,
+    pub most_money_collected: usize,
+    /// Shortest run that ended in a victory, `None` if the user has never won
+    pub fastest_victory_secs: Option<f32>,
+    /// Character played in the most runs
+    pub favorite_character: Option<CharacterType>,
+    /// Highest scoring run, ranked the same way as the leaderboards
+    pub best_run: Option<RunRecord>,
+}
+
+/// The run that most recently ended, waiting to be saved. `None` while a run is being played and
+/// for runs that were restarted or quit to desktop.
+#[derive(Resource, Debug, Default, derive_more::Deref, derive_more::DerefMut)]
+pub struct CompletedRunResource(pub Option<RunRecord>);
+
+/// Previously saved runs, used to compare the most recent run against the player's history
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct RunHistoryResource {
+    /// Most recent runs first
+    pub recent_runs: Vec<RunRecord>,
+    pub personal_bests: PersonalBests,
+}
+
+/// The highest scoring runs of a single run mode, from all users
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaderboard {
+    pub run_key: String,
+    pub difficulty: DifficultyType,
+    /// Highest score first
+    pub runs: Vec<RunRecord>,
+}
+
+/// A leaderboard for every run mode that has been played to the end
+#[derive(Resource, Debug, Default, Clone, PartialEq, derive_more::Deref, derive_more::DerefMut)]
+pub struct LeaderboardsResource(pub Vec<Leaderboard>);
@@ -0,0 +1,107 @@
+//! Character unlocks and the meta-currency that carry over between runs.
+use crate::character::{Character, CharacterType};
+use crate::game::historical_metrics::MobsKilledBy1PlayerCacheT;
+use crate::localization::LocalizationResource;
+use crate::spawnable::{EnemyMobType, MobType};
+use bevy_ecs_macros::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Something a player has to achieve over all of their runs to make a character playable
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum CharacterUnlockCondition {
+    /// Destroy at least `count` of a mob type
+    MobsKilled {
+        mob_type: EnemyMobType,
+        count: usize,
+    },
+    /// Destroy a boss at least once
+    BossDefeated(EnemyMobType),
+}
+
+impl CharacterUnlockCondition {
+    /// Whether the condition has been met with the given lifetime kill counts
+    pub fn is_met(&self, mob_kills: &MobsKilledBy1PlayerCacheT) -> bool {
+        match self {
+            CharacterUnlockCondition::MobsKilled { mob_type, count } => {
+                mob_kills.get(mob_type).copied().unwrap_or_default() >= *count
+            }
+            CharacterUnlockCondition::BossDefeated(mob_type) => {
+                mob_kills.get(mob_type).copied().unwrap_or_default() > 0
+            }
+        }
+    }
+
+    /// Description of the condition to show on a locked character
+    pub fn get_description(&self, localization: &LocalizationResource) -> String {
+        match self {
+            CharacterUnlockCondition::MobsKilled { mob_type, count } => localization.format(
+                "unlock.mobs_killed",
+                &[
+                    ("count", count.to_string()),
+                    ("mob", MobType::Enemy(*mob_type).get_name(localization)),
+                ],
+            ),
+            CharacterUnlockCondition::BossDefeated(mob_type) => localization.format(
+                "unlock.boss_defeated",
+                &[("mob", MobType::Enemy(*mob_type).get_name(localization))],
+            ),
+        }
+    }
+}
+
+/// The 'model' of the CharacterUnlocks and MetaCurrency Sqlite tables. Characters the player has
+/// unlocked and the meta-currency they have left to spend on unlocks.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Resource)]
+pub struct CharacterUnlocksResource {
+    /// Keys of characters that were unlocked by meeting their condition or by purchase
+    pub unlocked_characters: HashSet<CharacterType>,
+    /// Currency earned at the end of each run that can be spent on unlocks
+    pub meta_currency: usize,
+}
+
+impl CharacterUnlocksResource {
+    /// Whether a character can be played. Characters without an unlock condition or cost are
+    /// always available.
+    pub fn is_unlocked(&self, character_type: &CharacterType, character: &Character) -> bool {
+        (character.unlock_condition.is_none() && character.unlock_cost.is_none())
+            || self.unlocked_characters.contains(character_type)
+    }
+
+    /// Spends meta-currency to unlock a character, returns false if it could not be afforded
+    pub fn purchase(&mut self, character_type: &CharacterType, character: &Character) -> bool {
+        match character.unlock_cost {
+            Some(cost) if cost <= self.meta_currency => {
+                self.meta_currency -= cost;
+                self.unlocked_characters.insert(character_type.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Description of what it takes to unlock a character
+    pub fn get_lock_description(
+        &self,
+        character: &Character,
+        localization: &LocalizationResource,
+    ) -> String {
+        let mut requirements = vec![];
+
+        if let Some(condition) = &character.unlock_condition {
+            requirements.push(condition.get_description(localization));
+        }
+
+        if let Some(cost) = character.unlock_cost {
+            requirements.push(localization.format(
+                "unlock.cost",
+                &[
+                    ("cost", cost.to_string()),
+                    ("available", self.meta_currency.to_string()),
+                ],
+            ));
+        }
+
+        requirements.join(&localization.get("unlock.or"))
+    }
+}
@@ -2,14 +2,57 @@ use std::time::Duration;
 
 use bevy_ecs::prelude::{Component, Entity};
 use bevy_ecs_macros::Event;
+use bevy_math::Vec2;
 use bevy_time::{Timer, TimerMode};
 
+use crate::player::PlayerIDComponent;
+
 #[derive(Event)]
 pub struct DamageDealtEvent {
     pub damage: usize,
     pub target: Entity,
+    /// What dealt the damage
+    pub source: DamageSource,
+}
+
+/// The entity that dealt damage in a `DamageDealtEvent`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DamageSource {
+    Player(Entity),
+    Mob(Entity),
+    MobSegment(Entity),
+    /// Projectiles are usually despawned on impact, so their position at impact is kept
+    Projectile {
+        entity: Entity,
+        position: Vec2,
+        /// Entity that fired the projectile
+        source: Entity,
+    },
 }
 
+impl DamageSource {
+    pub fn get_entity(&self) -> Entity {
+        match self {
+            DamageSource::Player(entity)
+            | DamageSource::Mob(entity)
+            | DamageSource::MobSegment(entity)
+            | DamageSource::Projectile { entity, .. } => *entity,
+        }
+    }
+
+    /// The entity responsible for the damage. For projectiles this is whatever fired them.
+    pub fn get_attacker(&self) -> Entity {
+        match self {
+            DamageSource::Projectile { source, .. } => *source,
+            _ => self.get_entity(),
+        }
+    }
+}
+
+/// Remembers the last player to damage an entity, so that destroying it can be credited to them
+#[derive(Component, Default, Debug)]
+pub struct LastDamagedByPlayerComponent(pub Option<PlayerIDComponent>);
+
 /// Tracks health for an entity
 #[derive(Component, Default)]
 pub struct HealthComponent {
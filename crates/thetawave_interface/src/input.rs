@@ -3,7 +3,8 @@
 use bevy_ecs::{component::Component, system::Resource};
 use bevy_reflect::Reflect;
 use leafwing_input_manager::{prelude::InputMap, Actionlike};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
 
 /// Used by players to access their matching menu ui
 /// has a u8 index matching the player (0-3) for a 4 player game
@@ -15,13 +16,16 @@ pub struct MenuExplorer(pub u8);
 pub struct MainMenuExplorer;
 
 /// The input behaviors from the controller/gamepad available while in the menus.
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Deserialize)]
+#[derive(
+    Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize, EnumIter,
+)]
 pub enum MenuAction {
     Confirm,
     JoinKeyboard,
     JoinGamepad,
     Back,
     Reset,
+    Delete,
     ExitPauseMenu,
     PauseGame,
     NavigateUpKeyboard,
@@ -38,7 +42,9 @@ pub enum MenuAction {
 
 /// Player actions during the main game/while fighting mobs. Many of these can be simultaneously
 /// accepted from the gamepad/controller.
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Deserialize)]
+#[derive(
+    Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize, EnumIter,
+)]
 pub enum PlayerAction {
     MoveUp,
     MoveDown,
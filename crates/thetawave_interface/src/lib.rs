@@ -7,6 +7,7 @@ pub mod character_selection;
 pub mod game;
 pub mod health;
 pub mod input;
+pub mod localization;
 pub mod objective;
 pub mod player;
 pub mod run;
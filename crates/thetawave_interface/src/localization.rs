@@ -0,0 +1,68 @@
+//! Player-facing text, looked up by a string id in the string table of the chosen language.
+use bevy_ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum_macros::EnumIter;
+
+/// Languages that the game has a string table for
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// Code of the language, which is also the file stem of its string table
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    /// Name of the language written in that language, so that players can find their own
+    pub fn get_native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+}
+
+/// Text of each string id in a single language
+pub type StringTable = HashMap<String, String>;
+
+/// The string tables of every language. Text missing from the chosen language falls back to
+/// English, and then to the string id itself.
+#[derive(Resource, Debug, Default)]
+pub struct LocalizationResource {
+    pub language: Language,
+    pub string_tables: HashMap<Language, StringTable>,
+}
+
+impl LocalizationResource {
+    /// Text of the string id in the chosen language
+    pub fn get(&self, id: &str) -> String {
+        [self.language, Language::English]
+            .iter()
+            .find_map(|language| self.string_tables.get(language)?.get(id))
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Text of the string id with each `{name}` placeholder replaced by the argument of that name
+    pub fn format(&self, id: &str, args: &[(&str, String)]) -> String {
+        args.iter().fold(self.get(id), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+    }
+}
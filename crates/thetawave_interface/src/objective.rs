@@ -1,6 +1,7 @@
 use bevy_ecs::prelude::Event;
 use serde::Deserialize;
 
+use crate::localization::LocalizationResource;
 use crate::spawnable::{MobSegmentType, MobType};
 
 // Event for sending damage dealt from mob reaching bottom of arena
@@ -19,9 +20,9 @@ pub enum Objective {
 }
 
 impl Objective {
-    pub fn get_name(self) -> String {
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
         match self {
-            Objective::Defense(_) => "Defense".to_string(),
+            Objective::Defense(_) => localization.get("objective.defense"),
         }
     }
 }
@@ -1,9 +1,16 @@
 use crate::character::{Character, CharacterType};
+use crate::health::DamageSource;
 use crate::spawnable::SpawnPosition;
 use bevy_ecs::system::Resource;
-use bevy_ecs::{bundle::Bundle, prelude::Component};
+use bevy_ecs::{
+    bundle::Bundle,
+    prelude::{Component, Entity},
+};
+use bevy_ecs_macros::Event;
 use bevy_math::Vec2;
+use bevy_time::{Timer, TimerMode};
 use derive_more::{Deref, DerefMut};
+use serde::Deserialize;
 
 /// Parameters for how to spawn new players. By default, the player can do anything.
 #[derive(Resource, Debug, Default, Deref, DerefMut)]
@@ -30,6 +37,8 @@ pub struct PlayerData {
     pub character: CharacterType,
     /// Input method of a joined player
     pub input: PlayerInput,
+    /// Profile that the player's stats are saved to
+    pub profile_id: usize,
 }
 
 /// Input method for a player
@@ -48,6 +57,148 @@ impl PlayersResource {
             .filter_map(|player_data| player_data.clone().map(|data| data.input))
             .collect()
     }
+
+    /// The profiles of all joined players
+    pub fn get_used_profile_ids(&self) -> Vec<usize> {
+        self.player_data
+            .iter()
+            .flatten()
+            .map(|data| data.profile_id)
+            .collect()
+    }
+
+    /// The profile of a joined player
+    pub fn get_profile_id(&self, player_id: PlayerIDComponent) -> Option<usize> {
+        self.player_data
+            .get(usize::from(player_id))
+            .cloned()
+            .flatten()
+            .map(|data| data.profile_id)
+    }
+}
+
+/// Rules for how destroyed players can get back into a run, set per difficulty
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct CoopRules {
+    /// Seconds a teammate has to stay near a downed player to revive them
+    pub revive_time: f32,
+    /// Distance a teammate has to be within to count towards reviving a downed player
+    pub revive_distance: f32,
+    /// Maximum number of revives allowed over the course of a run
+    pub max_revives: usize,
+    /// Fraction of max health a player comes back with after being revived
+    pub revive_health_percentage: f32,
+    /// Size of a pool of lives shared by all players, `None` if there is no shared pool
+    pub shared_lives: Option<usize>,
+}
+
+/// Tracks how many revives and shared lives have been spent in the current run
+#[derive(Resource, Debug, Default)]
+pub struct CoopLivesResource {
+    /// Rules of the current run
+    pub rules: CoopRules,
+    /// Number of revives completed in the current run
+    pub revives_used: usize,
+    /// Number of lives taken from the shared pool in the current run
+    pub shared_lives_used: usize,
+}
+
+impl From<CoopRules> for CoopLivesResource {
+    fn from(rules: CoopRules) -> Self {
+        Self {
+            rules,
+            revives_used: 0,
+            shared_lives_used: 0,
+        }
+    }
+}
+
+impl CoopLivesResource {
+    /// Number of revives left in the run
+    pub fn get_revives_remaining(&self) -> usize {
+        self.rules.max_revives.saturating_sub(self.revives_used)
+    }
+
+    /// Number of lives left in the shared pool, `None` if there is no shared pool
+    pub fn get_shared_lives_remaining(&self) -> Option<usize> {
+        self.rules
+            .shared_lives
+            .map(|lives| lives.saturating_sub(self.shared_lives_used))
+    }
+
+    /// Whether a destroyed player can still be downed and revived
+    pub fn can_revive(&self) -> bool {
+        self.get_revives_remaining() > 0
+    }
+
+    /// Takes a life from the shared pool, returns false if there were none left
+    pub fn use_shared_life(&mut self) -> bool {
+        if self
+            .get_shared_lives_remaining()
+            .is_some_and(|lives| lives > 0)
+        {
+            self.shared_lives_used += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Marks a player that has been destroyed and is waiting for a teammate to revive them
+#[derive(Component, Debug)]
+pub struct PlayerDownedComponent {
+    /// Time a teammate has to spend nearby to revive the player
+    pub revive_timer: Timer,
+}
+
+impl PlayerDownedComponent {
+    pub fn new(revive_time: f32) -> Self {
+        Self {
+            revive_timer: Timer::from_seconds(revive_time, TimerMode::Once),
+        }
+    }
+}
+
+/// Gives a player a short window of invulnerability and a knockback after taking damage
+#[derive(Component, Debug)]
+pub struct PlayerInvulnerabilityComponent {
+    /// Knockback speed added to the player's velocity when damaged
+    pub knockback_speed: f32,
+    /// Counts down while the player is invulnerable
+    pub timer: Timer,
+}
+
+impl PlayerInvulnerabilityComponent {
+    pub fn new(invulnerability_time: f32, knockback_speed: f32) -> Self {
+        let mut timer = Timer::from_seconds(invulnerability_time, TimerMode::Once);
+        // players are not invulnerable when spawned, ticking also marks the timer as finished
+        timer.tick(timer.duration());
+
+        Self {
+            knockback_speed,
+            timer,
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    /// Starts a new window of invulnerability
+    pub fn start(&mut self) {
+        self.timer.reset();
+    }
+}
+
+/// Sent when damage is applied to a player, so that feedback such as knockback, sounds, and
+/// lights can react to it
+#[derive(Event, Debug)]
+pub struct PlayerDamagedEvent {
+    pub player_entity: Entity,
+    pub player_id: PlayerIDComponent,
+    pub damage: usize,
+    pub source: DamageSource,
 }
 
 /// Bundle of all player-specific components
@@ -58,6 +209,7 @@ pub struct PlayerBundle {
     attraction: PlayerAttractionComponent,
     outgoing_damage: PlayerOutgoingDamageComponent,
     incoming_damage: PlayerIncomingDamageComponent,
+    invulnerability: PlayerInvulnerabilityComponent,
     inventory: PlayerInventoryComponent,
     flag: PlayerComponent,
 }
@@ -69,6 +221,7 @@ impl From<&Character> for PlayerBundle {
             attraction: character.into(),
             outgoing_damage: character.into(),
             incoming_damage: PlayerIncomingDamageComponent::default(),
+            invulnerability: character.into(),
             inventory: character.into(),
             id: PlayerIDComponent::One,
             flag: PlayerComponent,
@@ -83,7 +236,7 @@ impl PlayerBundle {
 }
 
 /// Identity of a player component, used for syncing UI
-#[derive(Component, Clone, Copy, PartialEq, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PlayerIDComponent {
     One,
     Two,
@@ -215,6 +368,12 @@ impl From<&Character> for PlayerOutgoingDamageComponent {
     }
 }
 
+impl From<&Character> for PlayerInvulnerabilityComponent {
+    fn from(character: &Character) -> Self {
+        Self::new(character.invulnerability_time, character.knockback_speed)
+    }
+}
+
 impl From<&Character> for PlayerInventoryComponent {
     fn from(character: &Character) -> Self {
         Self {
@@ -1,13 +1,20 @@
 use bevy_ecs::prelude::Event;
+use serde::{Deserialize, Serialize};
 
+use crate::localization::LocalizationResource;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RunOutcomeType {
     Victory,
     Defeat(RunDefeatType),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RunDefeatType {
     PlayersDestroyed,
     DefenseDestroyed,
+    /// The players gave up on the run from the pause menu
+    Abandoned,
 }
 
 #[derive(Event)]
@@ -17,3 +24,47 @@ pub struct RunEndEvent {
 
 #[derive(Event)]
 pub struct CyclePhaseEvent;
+
+/// Sent when a boss is about to spawn, so that the players can be warned
+#[derive(Event)]
+pub struct BossWarningEvent {
+    /// Seconds until the boss spawns
+    pub lead_time: f32,
+}
+
+/// How punishing a run is. Each difficulty selects its own set of rules from
+/// `assets/data/difficulties.ron`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DifficultyType {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl DifficultyType {
+    /// Every difficulty, from easiest to hardest
+    pub const ALL: [DifficultyType; 3] = [
+        DifficultyType::Easy,
+        DifficultyType::Normal,
+        DifficultyType::Hard,
+    ];
+
+    /// Gets the difficulty `step` places away, wrapping around at either end
+    pub fn cycle(&self, step: isize) -> DifficultyType {
+        let idx = Self::ALL
+            .iter()
+            .position(|difficulty| difficulty == self)
+            .unwrap_or_default() as isize;
+
+        Self::ALL[(idx + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
+        localization.get(match self {
+            DifficultyType::Easy => "difficulty.easy",
+            DifficultyType::Normal => "difficulty.normal",
+            DifficultyType::Hard => "difficulty.hard",
+        })
+    }
+}
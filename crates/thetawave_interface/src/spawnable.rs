@@ -3,11 +3,14 @@ use std::default::Default;
 use bevy_ecs::{entity::Entity, event::Event};
 use bevy_ecs_macros::Component;
 use bevy_math::{Quat, Vec2};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
+use crate::localization::LocalizationResource;
+use crate::player::PlayerIDComponent;
+
 /// Type that encompasses all spawnable enemy mobs
-#[derive(Deserialize, EnumString, Display, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, EnumString, Display, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum EnemyMobType {
     Pawn,
     Drone,
@@ -65,6 +68,16 @@ pub enum Faction {
     Neutral,
 }
 
+impl Faction {
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
+        localization.get(match self {
+            Faction::Ally => "faction.ally",
+            Faction::Enemy => "faction.enemy",
+            Faction::Neutral => "faction.neutral",
+        })
+    }
+}
+
 /// Type that encompasses all spawnable mobs
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum MobType {
@@ -74,31 +87,44 @@ pub enum MobType {
 }
 
 impl MobType {
-    pub fn get_name(&self) -> String {
+    /// String id of the mob's name. Variants that look the same, like left and right strafers,
+    /// share a name.
+    pub fn get_name_id(&self) -> &'static str {
         match self {
             MobType::Enemy(enemy_type) => match enemy_type {
-                EnemyMobType::Pawn => "Pawn",
-                EnemyMobType::Drone => "Drone",
-                EnemyMobType::StraferRight | EnemyMobType::StraferLeft => "Strafer",
-                EnemyMobType::MissileLauncher => "Missile Launcher",
-                EnemyMobType::Missile => "Missile",
-                EnemyMobType::CrustlingRight | EnemyMobType::CrustlingLeft => "Crustling",
-                EnemyMobType::Ferritharax => "Ferritharax",
-                EnemyMobType::MechaFerritharax => "Mecha-Ferritharax",
-                EnemyMobType::MechaSaucetron => "Mecha-Saucetron",
-                EnemyMobType::Shelly => "Shelly",
+                EnemyMobType::Pawn => "mob.pawn",
+                EnemyMobType::Drone => "mob.drone",
+                EnemyMobType::StraferRight | EnemyMobType::StraferLeft => "mob.strafer",
+                EnemyMobType::MissileLauncher => "mob.missile_launcher",
+                EnemyMobType::Missile => "mob.missile",
+                EnemyMobType::CrustlingRight | EnemyMobType::CrustlingLeft => "mob.crustling",
+                EnemyMobType::Ferritharax => "mob.ferritharax",
+                EnemyMobType::MechaFerritharax => "mob.mecha_ferritharax",
+                EnemyMobType::MechaSaucetron => "mob.mecha_saucetron",
+                EnemyMobType::Shelly => "mob.shelly",
             },
             MobType::Ally(ally_type) => match ally_type {
-                AllyMobType::Hauler2 => "Hauler",
-                AllyMobType::Hauler3 => "Hauler",
-                AllyMobType::TutorialHauler2 => "Hauler",
+                AllyMobType::Hauler2 => "mob.hauler",
+                AllyMobType::Hauler3 => "mob.hauler",
+                AllyMobType::TutorialHauler2 => "mob.hauler",
             },
             MobType::Neutral(neutral_type) => match neutral_type {
-                NeutralMobType::MoneyAsteroid => "Money Asteroid",
-                NeutralMobType::TutorialDrone => "Tutorial Drone",
+                NeutralMobType::MoneyAsteroid => "mob.money_asteroid",
+                NeutralMobType::TutorialDrone => "mob.tutorial_drone",
             },
         }
-        .to_string()
+    }
+
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
+        localization.get(self.get_name_id())
+    }
+
+    pub fn get_faction(&self) -> Faction {
+        match self {
+            MobType::Enemy(_) => Faction::Enemy,
+            MobType::Ally(_) => Faction::Ally,
+            MobType::Neutral(_) => Faction::Neutral,
+        }
     }
 }
 
@@ -223,6 +249,15 @@ pub struct MobDestroyedEvent {
     pub mob_type: MobType,
     pub entity: Entity,
     pub is_boss: bool,
+    /// Player that last damaged the mob, if any
+    pub killed_by: Option<PlayerIDComponent>,
+}
+
+/// Sent when a player collects a consumable
+#[derive(Event)]
+pub struct ConsumableCollectedEvent {
+    pub consumable_type: ConsumableType,
+    pub player_entity: Entity,
 }
 
 #[derive(Event)]
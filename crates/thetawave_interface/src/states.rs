@@ -7,6 +7,13 @@ pub enum AppStates {
     #[default]
     LoadingAssets,
     MainMenu,
+    Leaderboard,
+    Stats,
+    Profiles,
+    Achievements,
+    Options,
+    Controls,
+    Compendium,
     CharacterSelection,
     InitializeRun,
     Game,
@@ -19,11 +26,38 @@ pub enum GameStates {
     #[default]
     Playing,
     Paused,
+    /// The options screen, opened from the pause menu
+    PausedOptions,
+    /// The controls screen, opened from the options screen of the pause menu
+    PausedControls,
+    LevelUpDraft,
+    LevelSummary,
 }
 
 #[derive(Component)]
 pub struct MainMenuCleanup;
 
+#[derive(Component)]
+pub struct LeaderboardCleanup;
+
+#[derive(Component)]
+pub struct StatsCleanup;
+
+#[derive(Component)]
+pub struct ProfilesCleanup;
+
+#[derive(Component)]
+pub struct AchievementsCleanup;
+
+#[derive(Component)]
+pub struct OptionsCleanup;
+
+#[derive(Component)]
+pub struct ControlsCleanup;
+
+#[derive(Component)]
+pub struct CompendiumCleanup;
+
 #[derive(Component)]
 pub struct GameCleanup;
 
@@ -38,3 +72,9 @@ pub struct PauseCleanup;
 
 #[derive(Component)]
 pub struct CharacterSelectionCleanup;
+
+#[derive(Component)]
+pub struct LevelUpDraftCleanup;
+
+#[derive(Component)]
+pub struct LevelSummaryCleanup;
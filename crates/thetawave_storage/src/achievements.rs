@@ -0,0 +1,68 @@
+use crate::backend::StorageError;
+use crate::core::{get_db, ACHIEVEMENTS_TABLE_NAME};
+use bevy::log::error;
+use rusqlite::{params, Result};
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+
+pub(super) fn set_achievements_for_user(
+    user_id: usize,
+    achievements: &AchievementsProgressResource,
+) -> Result<(), StorageError> {
+    let upsert_stmt_raw = format!(
+        "
+    INSERT OR REPLACE INTO {ACHIEVEMENTS_TABLE_NAME} (userId, achievementKey, progress, unlocked)
+    VALUES (?1, ?2, ?3, ?4)"
+    );
+    let mut conn = get_db()?;
+    let tx = conn.transaction()?;
+    {
+        let mut upsert_stmt = tx.prepare(&upsert_stmt_raw)?;
+        let keys = achievements
+            .progress
+            .keys()
+            .chain(achievements.unlocked.iter());
+        for key in keys {
+            upsert_stmt.execute(params![
+                user_id,
+                key,
+                achievements.get_progress(key),
+                achievements.is_unlocked(key)
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub(super) fn _get_achievements_for_user(
+    user_id: usize,
+) -> Result<AchievementsProgressResource, StorageError> {
+    let conn = get_db()?;
+    let stmt_raw = format!(
+        "
+    SELECT achievementKey, progress, unlocked FROM {ACHIEVEMENTS_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let mut achievements = AchievementsProgressResource::default();
+    let mut stmt = conn.prepare(&stmt_raw)?;
+    let mut rows = stmt.query([user_id])?;
+    while let Some(row) = rows.next()? {
+        let key: String = row.get(0)?;
+        if row.get::<usize, bool>(2)? {
+            achievements.unlocked.insert(key.clone());
+        }
+        achievements.progress.insert(key, row.get(1)?);
+    }
+    Ok(achievements)
+}
+
+/// Returns a user's progress towards each achievement and the achievements they have unlocked.
+pub fn get_achievements_for_user(user_id: usize) -> AchievementsProgressResource {
+    _get_achievements_for_user(user_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to get achievements from db. Empty result fallback. {}",
+            e
+        );
+        Default::default()
+    })
+}
@@ -0,0 +1,548 @@
+//! The storage backends that saves can be kept in. The game picks one at startup: a SQLite
+//! database where it is available, otherwise a RON save file (kept in the browser's local storage
+//! on the web).
+use bevy::log::warn;
+use bevy::prelude::Resource;
+use std::collections::BTreeMap;
+use std::env::var_os;
+use std::ffi::OsStr;
+use std::str::FromStr;
+use thetawave_interface::character::CharacterType;
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+use thetawave_interface::game::compendium::EncountersResource;
+use thetawave_interface::game::historical_metrics::{
+    LevelStatsBy1PlayerCacheT, MobsKilledBy1PlayerCacheT, UserStat,
+};
+use thetawave_interface::game::options::GameOptions;
+use thetawave_interface::game::profiles::{Profile, MAX_PROFILE_NAME_LEN};
+use thetawave_interface::game::run_history::{Leaderboard, PersonalBests, RunRecord};
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+use thiserror::Error;
+use web_time::SystemTime;
+
+use crate::file::FileBackend;
+use crate::memory::MemoryBackend;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sqlite::SqliteBackend;
+
+/// Set to `sqlite`, `file` or `memory` to choose where saves are kept
+pub const THETAWAVE_STORAGE_BACKEND_ENVVAR: &'static str = "THETAWAVE_STORAGE_BACKEND";
+
+#[derive(Error, Debug, derive_more::From)]
+pub enum StorageError {
+    #[error("No suitable location found for the save data. Is this a supported platform?")]
+    #[from(ignore)]
+    NoDBPathFound,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Sqlite Error: {0}")]
+    SqliteError(rusqlite::Error),
+    #[error("Failed to access save file: {0}")]
+    LocalFilesystemError(std::io::Error),
+    #[error("Failed to read save file: {0}")]
+    SaveFileReadError(ron::error::SpannedError),
+    #[error("Failed to write save file: {0}")]
+    SaveFileWriteError(ron::Error),
+    #[error("Internal database error. Please report as a bug. {0}")]
+    #[from(ignore)]
+    InternalError(String),
+}
+
+/// Everything that is kept between sessions. Stats, unlocks, achievements and runs are saved
+/// under a "user id", which is the id of the profile they belong to.
+pub trait StorageBackend: Send + Sync {
+    /// Gets the backend ready to be used, creating or upgrading the saves as needed
+    fn setup(&self) -> Result<(), StorageError>;
+
+    /// Every profile, oldest first
+    fn get_profiles(&self) -> Result<Vec<Profile>, StorageError>;
+    /// Saves a new profile with its own default options and returns its id. Names are trimmed
+    /// and must be unique.
+    fn create_profile(&self, name: &str) -> Result<usize, StorageError>;
+    /// Marks a profile as the most recently used, so that it is selected at the next launch
+    fn set_last_used_profile(&self, profile_id: usize) -> Result<(), StorageError>;
+    /// Deletes a profile and everything saved for it. The last remaining profile is never
+    /// deleted.
+    fn delete_profile(&self, profile_id: usize) -> Result<(), StorageError>;
+    fn get_last_used_profile_id(&self) -> Result<Option<usize>, StorageError>;
+
+    fn get_user_stats(&self, user_id: usize) -> Result<Option<UserStat>, StorageError>;
+    fn set_user_stats(&self, user_id: usize, user_stats: &UserStat) -> Result<(), StorageError>;
+    fn get_mob_kills(&self, user_id: usize) -> Result<MobsKilledBy1PlayerCacheT, StorageError>;
+    /// Saves the kill counts of the given mob types, leaving the others as they were
+    fn set_mob_kills(
+        &self,
+        user_id: usize,
+        mob_kills: &MobsKilledBy1PlayerCacheT,
+    ) -> Result<(), StorageError>;
+    fn get_level_stats(&self, user_id: usize) -> Result<LevelStatsBy1PlayerCacheT, StorageError>;
+    /// Saves the stats of the given levels, leaving the others as they were
+    fn set_level_stats(
+        &self,
+        user_id: usize,
+        level_stats: &LevelStatsBy1PlayerCacheT,
+    ) -> Result<(), StorageError>;
+
+    fn get_game_options(&self, profile_id: usize) -> Result<Option<GameOptions>, StorageError>;
+    fn set_game_options(
+        &self,
+        profile_id: usize,
+        game_options: &GameOptions,
+    ) -> Result<(), StorageError>;
+
+    fn get_character_unlocks(
+        &self,
+        user_id: usize,
+    ) -> Result<CharacterUnlocksResource, StorageError>;
+    /// Saves the meta-currency balance and adds the unlocked characters. Characters are never
+    /// locked again.
+    fn set_character_unlocks(
+        &self,
+        user_id: usize,
+        unlocks: &CharacterUnlocksResource,
+    ) -> Result<(), StorageError>;
+    fn get_achievements(
+        &self,
+        user_id: usize,
+    ) -> Result<AchievementsProgressResource, StorageError>;
+    fn set_achievements(
+        &self,
+        user_id: usize,
+        achievements: &AchievementsProgressResource,
+    ) -> Result<(), StorageError>;
+    fn get_encounters(&self, user_id: usize) -> Result<EncountersResource, StorageError>;
+    /// Adds the encountered compendium entries. Encounters are never forgotten.
+    fn add_encounters(
+        &self,
+        user_id: usize,
+        encounters: &EncountersResource,
+    ) -> Result<(), StorageError>;
+
+    /// Saves a run that was played to the end
+    fn add_run(&self, user_id: usize, run: &RunRecord) -> Result<(), StorageError>;
+    /// Up to `limit` of the user's most recent runs, most recent first
+    fn get_recent_runs(&self, user_id: usize, limit: usize)
+        -> Result<Vec<RunRecord>, StorageError>;
+    fn get_personal_bests(&self, user_id: usize) -> Result<PersonalBests, StorageError>;
+    /// The `limit` highest scoring runs of every user for each run mode (run key and difficulty)
+    /// that has been played
+    fn get_leaderboards(&self, limit: usize) -> Result<Vec<Leaderboard>, StorageError>;
+}
+
+/// The backend that everything is saved to, chosen when the game starts
+#[derive(Resource, derive_more::Deref)]
+pub struct StorageResource(pub Box<dyn StorageBackend>);
+
+/// The kinds of storage backend that the game can be started with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackendType {
+    /// A SQLite database in the user's data directory
+    #[cfg(not(target_arch = "wasm32"))]
+    #[default]
+    Sqlite,
+    /// A RON save file in the user's data directory, or the browser's local storage on the web
+    #[cfg_attr(target_arch = "wasm32", default)]
+    File,
+    /// Saves that only last until the game is closed
+    Memory,
+}
+
+impl FromStr for StorageBackendType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            #[cfg(not(target_arch = "wasm32"))]
+            "sqlite" => Ok(Self::Sqlite),
+            "file" => Ok(Self::File),
+            "memory" => Ok(Self::Memory),
+            _ => Err(format!("Unknown storage backend {s:?}")),
+        }
+    }
+}
+
+impl StorageBackendType {
+    /// The backend chosen with `THETAWAVE_STORAGE_BACKEND`, falling back to the platform's
+    /// default
+    pub fn from_env() -> Self {
+        match var_os(OsStr::new(THETAWAVE_STORAGE_BACKEND_ENVVAR)) {
+            Some(osstr) => osstr.to_string_lossy().parse().unwrap_or_else(|e| {
+                warn!("{e}. Using the default storage backend.");
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    /// Opens the backend at its default location
+    pub fn new_backend(&self) -> Result<Box<dyn StorageBackend>, StorageError> {
+        Ok(match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Sqlite => Box::new(SqliteBackend),
+            Self::File => Box::new(FileBackend::at_default_path()?),
+            Self::Memory => Box::new(MemoryBackend::default()),
+        })
+    }
+}
+
+/// Checks that a profile name isn't blank or too long, returning the trimmed name
+pub(super) fn validate_profile_name(name: &str) -> Result<&str, StorageError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(StorageError::InternalError(format!(
+            "Invalid profile name {name:?}"
+        )));
+    }
+    Ok(name)
+}
+
+/// Seconds since the unix epoch, also on the web where `std::time::SystemTime` isn't available
+pub(super) fn now_secs() -> Result<u64, StorageError> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|e| StorageError::InternalError(e.to_string()))?
+        .as_secs())
+}
+
+/// The start time of a run being saved. It is taken from the record if it has one, otherwise it
+/// is worked out from the current time and the duration of the run.
+pub(super) fn get_run_start_time(run: &RunRecord) -> Result<u64, StorageError> {
+    match run.start_time {
+        Some(start_time) => Ok(start_time),
+        None => Ok(now_secs()?.saturating_sub(run.duration_secs as u64)),
+    }
+}
+
+/// The character played the most times, ties going to the character whose key sorts first
+pub(super) fn get_favorite_character<'a>(
+    characters: impl IntoIterator<Item = &'a CharacterType>,
+) -> Option<CharacterType> {
+    let mut times_played: BTreeMap<&CharacterType, usize> = BTreeMap::new();
+    for character in characters {
+        *times_played.entry(character).or_default() += 1;
+    }
+    // `max_by_key` keeps the last of equal maximums, so the keys are walked in reverse
+    times_played
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, n)| *n)
+        .map(|(character, _)| character.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::StorageBackend;
+    use crate::file::FileBackend;
+    use crate::memory::MemoryBackend;
+    use std::ffi::OsString;
+    use thetawave_interface::character::CharacterType;
+    use thetawave_interface::game::achievements::AchievementsProgressResource;
+    use thetawave_interface::game::compendium::EncountersResource;
+    use thetawave_interface::game::historical_metrics::{
+        LevelStat, LevelStatsBy1PlayerCacheT, MobsKilledBy1PlayerCacheT, UserStat, DEFAULT_USER_ID,
+    };
+    use thetawave_interface::game::options::{ColorPalette, DisplayMode, GameOptions};
+    use thetawave_interface::game::profiles::Profile;
+    use thetawave_interface::game::run_history::{PersonalBests, RunRecord};
+    use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+    use thetawave_interface::localization::Language;
+    use thetawave_interface::run::{DifficultyType, RunDefeatType, RunOutcomeType};
+    use thetawave_interface::spawnable::EnemyMobType;
+
+    fn run_record(start_time: u64, outcome: RunOutcomeType, score: usize) -> RunRecord {
+        RunRecord {
+            start_time: Some(start_time),
+            duration_secs: 60.0 + start_time as f32,
+            characters: vec![CharacterType::from("captain")],
+            run_key: "test_run".to_string(),
+            difficulty: DifficultyType::Normal,
+            levels_completed: start_time as usize,
+            outcome,
+            money_collected: 5,
+            damage_taken: 3,
+            seed: u64::MAX,
+            score,
+        }
+    }
+
+    /// Checks that a freshly set up backend saves and returns everything the same way as the
+    /// others
+    fn check_backend(backend: &dyn StorageBackend) {
+        backend.setup().unwrap();
+
+        // there is always a default profile
+        assert_eq!(
+            backend.get_profiles().unwrap(),
+            vec![Profile {
+                id: DEFAULT_USER_ID,
+                name: "Player 1".to_string(),
+            }]
+        );
+        assert_eq!(
+            backend.get_last_used_profile_id().unwrap(),
+            Some(DEFAULT_USER_ID)
+        );
+
+        let ada_id = backend.create_profile(" Ada ").unwrap();
+        assert_ne!(ada_id, DEFAULT_USER_ID);
+        assert!(backend.create_profile("Ada").is_err());
+        assert!(backend.create_profile("  ").is_err());
+        assert_eq!(backend.get_profiles().unwrap()[1].name, "Ada");
+
+        // new profiles have their own default options
+        let default_options = backend.get_game_options(DEFAULT_USER_ID).unwrap();
+        assert!(default_options.is_some());
+        assert_eq!(backend.get_game_options(ada_id).unwrap(), default_options);
+        let game_options = GameOptions {
+            bloom_enabled: false,
+            music_volume: 0.5,
+            display_mode: DisplayMode::BorderlessFullscreen,
+            window_resolution: Some((1600, 900)),
+            key_bindings: Some("()".to_string()),
+            language: Language::Spanish,
+            color_palette: ColorPalette::Tritanopia,
+            reduced_motion: true,
+            ui_scale: 1.25,
+            ..Default::default()
+        };
+        backend.set_game_options(ada_id, &game_options).unwrap();
+        assert_eq!(
+            backend.get_game_options(ada_id).unwrap(),
+            Some(game_options)
+        );
+
+        assert_eq!(backend.get_user_stats(ada_id).unwrap(), None);
+        let user_stats = UserStat {
+            total_shots_fired: 10,
+            total_games_won: 1,
+            fastest_victory_millis: Some(90000),
+            ..Default::default()
+        };
+        backend.set_user_stats(ada_id, &user_stats).unwrap();
+        assert_eq!(backend.get_user_stats(ada_id).unwrap(), Some(user_stats));
+
+        backend
+            .set_mob_kills(
+                ada_id,
+                &MobsKilledBy1PlayerCacheT::from([(EnemyMobType::Drone, 3)]),
+            )
+            .unwrap();
+        backend
+            .set_mob_kills(
+                ada_id,
+                &MobsKilledBy1PlayerCacheT::from([(EnemyMobType::Pawn, 2)]),
+            )
+            .unwrap();
+        assert_eq!(
+            backend.get_mob_kills(ada_id).unwrap(),
+            MobsKilledBy1PlayerCacheT::from([(EnemyMobType::Drone, 3), (EnemyMobType::Pawn, 2)])
+        );
+
+        let level_stats = LevelStatsBy1PlayerCacheT::from([(
+            "Earth".to_string(),
+            LevelStat {
+                times_reached: 2,
+                times_completed: 1,
+                best_time_millis: None,
+            },
+        )]);
+        backend.set_level_stats(ada_id, &level_stats).unwrap();
+        assert_eq!(backend.get_level_stats(ada_id).unwrap(), level_stats);
+
+        backend
+            .set_character_unlocks(
+                ada_id,
+                &CharacterUnlocksResource {
+                    unlocked_characters: [CharacterType::from("juggernaut")].into(),
+                    meta_currency: 40,
+                },
+            )
+            .unwrap();
+        let unlocks = CharacterUnlocksResource {
+            unlocked_characters: [CharacterType::from("money_bags")].into(),
+            meta_currency: 10,
+        };
+        backend.set_character_unlocks(ada_id, &unlocks).unwrap();
+        assert_eq!(
+            backend.get_character_unlocks(ada_id).unwrap(),
+            CharacterUnlocksResource {
+                unlocked_characters: [
+                    CharacterType::from("juggernaut"),
+                    CharacterType::from("money_bags")
+                ]
+                .into(),
+                meta_currency: 10,
+            }
+        );
+
+        let achievements = AchievementsProgressResource {
+            progress: [("veteran".to_string(), 3), ("first_victory".to_string(), 1)].into(),
+            unlocked: ["first_victory".to_string()].into(),
+        };
+        backend.set_achievements(ada_id, &achievements).unwrap();
+        assert_eq!(backend.get_achievements(ada_id).unwrap(), achievements);
+
+        backend
+            .add_encounters(
+                ada_id,
+                &EncountersResource {
+                    encountered: ["mob/Enemy(Drone)".to_string()].into(),
+                },
+            )
+            .unwrap();
+        backend
+            .add_encounters(
+                ada_id,
+                &EncountersResource {
+                    encountered: ["item/EnhancedPlating".to_string()].into(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            backend.get_encounters(ada_id).unwrap(),
+            EncountersResource {
+                encountered: [
+                    "mob/Enemy(Drone)".to_string(),
+                    "item/EnhancedPlating".to_string()
+                ]
+                .into(),
+            }
+        );
+
+        for (start_time, outcome, score) in [
+            (1, RunOutcomeType::Victory, 100),
+            (
+                3,
+                RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
+                300,
+            ),
+            (
+                2,
+                RunOutcomeType::Defeat(RunDefeatType::DefenseDestroyed),
+                200,
+            ),
+        ] {
+            backend
+                .add_run(ada_id, &run_record(start_time, outcome, score))
+                .unwrap();
+        }
+        let recent_runs = backend.get_recent_runs(ada_id, 2).unwrap();
+        assert_eq!(
+            recent_runs,
+            vec![
+                run_record(
+                    3,
+                    RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
+                    300
+                ),
+                run_record(
+                    2,
+                    RunOutcomeType::Defeat(RunDefeatType::DefenseDestroyed),
+                    200
+                ),
+            ]
+        );
+        assert_eq!(
+            backend.get_personal_bests(ada_id).unwrap(),
+            PersonalBests {
+                total_runs: 3,
+                most_levels_completed: 3,
+                longest_run_secs: 63.0,
+                most_money_collected: 5,
+                fastest_victory_secs: Some(61.0),
+                favorite_character: Some(CharacterType::from("captain")),
+                best_run: Some(run_record(
+                    3,
+                    RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
+                    300
+                )),
+            }
+        );
+        assert_eq!(
+            backend.get_personal_bests(DEFAULT_USER_ID).unwrap(),
+            PersonalBests::default()
+        );
+        let leaderboards = backend.get_leaderboards(2).unwrap();
+        assert_eq!(leaderboards.len(), 1);
+        assert_eq!(
+            leaderboards[0]
+                .runs
+                .iter()
+                .map(|run| run.score)
+                .collect::<Vec<usize>>(),
+            vec![300, 200]
+        );
+
+        backend.set_last_used_profile(ada_id).unwrap();
+        assert_eq!(backend.get_last_used_profile_id().unwrap(), Some(ada_id));
+
+        // deleting a profile deletes its saves, but the last profile is kept
+        backend.delete_profile(ada_id).unwrap();
+        assert_eq!(backend.get_profiles().unwrap().len(), 1);
+        assert_eq!(backend.get_user_stats(ada_id).unwrap(), None);
+        assert!(backend.get_recent_runs(ada_id, 10).unwrap().is_empty());
+        assert_eq!(
+            backend.get_encounters(ada_id).unwrap(),
+            EncountersResource::default()
+        );
+        assert_eq!(
+            backend.get_last_used_profile_id().unwrap(),
+            Some(DEFAULT_USER_ID)
+        );
+        assert!(backend.delete_profile(DEFAULT_USER_ID).is_err());
+    }
+
+    #[test]
+    fn test_favorite_character_ties_go_to_the_first_key() {
+        assert_eq!(
+            super::get_favorite_character(&[
+                CharacterType::from("juggernaut"),
+                CharacterType::from("captain"),
+                CharacterType::from("juggernaut"),
+                CharacterType::from("captain"),
+            ]),
+            Some(CharacterType::from("captain"))
+        );
+        assert_eq!(super::get_favorite_character(&[]), None);
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        check_backend(&MemoryBackend::default());
+    }
+
+    #[test]
+    fn test_file_backend() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let save_file_path = base_path.path().join("thetawave_test.ron");
+        check_backend(&FileBackend::new(save_file_path.clone()));
+
+        // everything is still there after a restart
+        let reopened_backend = FileBackend::new(save_file_path);
+        reopened_backend.setup().unwrap();
+        assert_eq!(reopened_backend.get_profiles().unwrap().len(), 1);
+        assert_eq!(
+            reopened_backend
+                .get_character_unlocks(DEFAULT_USER_ID)
+                .unwrap(),
+            CharacterUnlocksResource::default()
+        );
+        assert!(reopened_backend
+            .get_game_options(DEFAULT_USER_ID)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_sqlite_backend() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        crate::core::run_with_patched_env(
+            || check_backend(&crate::sqlite::SqliteBackend),
+            vec![(
+                OsString::from(&crate::core::THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+}
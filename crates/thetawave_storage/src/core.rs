@@ -4,88 +4,77 @@ use rusqlite::Connection;
 use std::env::var_os;
 use std::ffi::OsStr;
 use std::path::PathBuf;
-use thetawave_interface::game::options::DEFAULT_OPTIONS_PROFILE_ID;
-use thiserror::Error;
+
+use crate::backend::StorageError;
+use crate::migrations::run_migrations;
+
 pub(super) const THETAWAVE_DB_PATH_ENVVAR: &'static str = "THETAWAVE_DB_PATH";
 const THETAWAVE_DB_FILE: &'static str = "thetawave.sqlite";
 pub(super) const USERSTAT: &'static str = "UserStat";
 pub(super) const ENEMY_KILL_HISTORY_TABLE_NAME: &'static str = "EnemiesKilled";
 pub(super) const OPTIONS_TABLE_NAME: &'static str = "Options";
+pub(super) const CHARACTER_UNLOCKS_TABLE_NAME: &'static str = "CharacterUnlocks";
+pub(super) const META_CURRENCY_TABLE_NAME: &'static str = "MetaCurrency";
+pub(super) const RUN_HISTORY_TABLE_NAME: &'static str = "RunHistory";
+pub(super) const PROFILES_TABLE_NAME: &'static str = "Profiles";
+pub(super) const ACHIEVEMENTS_TABLE_NAME: &'static str = "Achievements";
+pub(super) const LEVEL_STATS_TABLE_NAME: &'static str = "LevelStats";
+pub(super) const ENCOUNTERS_TABLE_NAME: &'static str = "Encounters";
 
-#[derive(Error, Debug, derive_more::From)]
-pub(super) enum OurDBError {
-    #[error(
-        "No suitable location found for the user stats database. Is this a supported platform?"
-    )]
-    #[from(ignore)]
-    NoDBPathFound,
-    #[error("Sqlite Error: {0}")]
-    SqliteError(rusqlite::Error),
-    #[error("Failed to access sqlite file: {0}")]
-    LocalFilesystemError(std::io::Error),
-    #[error("Internal database error. Please report as a bug. {0}")]
-    #[from(ignore)]
-    InternalError(String),
-}
-
-fn default_db_path() -> Result<PathBuf, OurDBError> {
+/// Directory that saves are written to, created if it doesn't exist yet
+pub(super) fn default_data_dir() -> Result<PathBuf, StorageError> {
     match ProjectDirs::from("org", "thetawave-game", "thetawave") {
         Some(pdirs) => {
             let game_data_dir = pdirs.data_local_dir();
             std::fs::create_dir_all(&game_data_dir)?;
-            Ok(game_data_dir.join(THETAWAVE_DB_FILE))
+            Ok(game_data_dir.to_path_buf())
         }
-        None => Err(OurDBError::NoDBPathFound),
+        None => Err(StorageError::NoDBPathFound),
     }
 }
 
-pub(super) fn setup_db(conn: Connection) -> rusqlite::Result<()> {
-    let create_user_stats_sql = format!(
-        "CREATE TABLE IF NOT EXISTS {USERSTAT} (
-        userId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL ,
-        totalShotsFired  INTEGER NOT NULL DEFAULT 0,
-        totalShotsHit  INTEGER NOT NULL DEFAULT 0,
-        totalGamesLost INTEGER NOT NULL DEFAULT 0
-    )"
-    );
-
-    let create_enemies_killed_table_sql = format!(
-        "CREATE TABLE IF NOT EXISTS {ENEMY_KILL_HISTORY_TABLE_NAME} (
-        userId INTEGER NOT NULL,
-        enemyMobType VARCHAR(255) NOT NULL,
-        nKilled INTEGER NOT NULL DEFAULT 0,
-        PRIMARY KEY (userId, enemyMobType)
-    )"
-    );
-
-    let create_options_table_sql = format!(
-        "CREATE TABLE IF NOT EXISTS {OPTIONS_TABLE_NAME} (
-        optionsProfileId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
-        bloomEnabled BOOLEAN NOT NULL DEFAULT TRUE,
-        bloomIntensity REAL NOT NULL DEFAULT 1.0,
-        tutorialsEnabled BOOLEAN NOT NULL DEFAULT FALSE
-    )"
-    );
-
-    conn.execute(&create_user_stats_sql, []).map(|_| ())?;
-    conn.execute(&create_enemies_killed_table_sql, [])
-        .map(|_| ())?;
-    conn.execute(&create_options_table_sql, []).map(|_| ())?;
-
-    // insert a default options row if it is not in the db
-    let upsert_default_gameops_sql =
-        format!("INSERT OR REPLACE INTO {OPTIONS_TABLE_NAME} (optionsProfileId) VALUES (?1)");
-    conn.execute(&upsert_default_gameops_sql, [DEFAULT_OPTIONS_PROFILE_ID])
-        .map(|_| ())?;
+/// Creates the database if it doesn't exist and upgrades its schema to the latest version
+pub(super) fn setup_db(mut conn: Connection) -> rusqlite::Result<()> {
+    let schema_version = run_migrations(&mut conn)?;
 
-    info!("Created sqlite db");
+    info!("Set up sqlite db at schema version {schema_version}");
     Ok(())
 }
 
-pub(super) fn get_db() -> Result<Connection, OurDBError> {
+pub(super) fn get_db() -> Result<Connection, StorageError> {
     let db_path = match var_os(OsStr::new(THETAWAVE_DB_PATH_ENVVAR)) {
         Some(osstr) => Ok(PathBuf::from(osstr)),
-        None => default_db_path(),
+        None => default_data_dir().map(|data_dir| data_dir.join(THETAWAVE_DB_FILE)),
     }?;
-    Connection::open(db_path).map_err(OurDBError::from)
+    Connection::open(db_path).map_err(StorageError::from)
+}
+
+/// Runs a test with environment variables set, e.g. to point the database at a temporary file
+#[cfg(test)]
+pub(super) fn run_with_patched_env<T, V>(test: T, env_vars: Vec<(V, V)>)
+where
+    T: FnOnce() -> () + std::panic::UnwindSafe,
+    V: AsRef<OsStr>,
+{
+    let old_env_vars: Vec<(std::ffi::OsString, std::ffi::OsString)> = env_vars
+        .iter()
+        .map(|(k, _)| {
+            (
+                std::ffi::OsString::from(k),
+                std::env::var_os(k).unwrap_or_default(),
+            )
+        })
+        .collect();
+    for (k, v) in env_vars.iter() {
+        std::env::set_var(k, v);
+    }
+
+    let result = std::panic::catch_unwind(test);
+
+    for (k, v) in old_env_vars.iter() {
+        std::env::set_var(k, v);
+    }
+    if let Err(err) = result {
+        std::panic::resume_unwind(err);
+    }
 }
@@ -0,0 +1,52 @@
+use crate::backend::StorageError;
+use crate::core::{get_db, ENCOUNTERS_TABLE_NAME};
+use bevy::log::error;
+use rusqlite::{params, Result};
+use thetawave_interface::game::compendium::EncountersResource;
+
+pub(super) fn add_encounters_for_user(
+    user_id: usize,
+    encounters: &EncountersResource,
+) -> Result<(), StorageError> {
+    let insert_stmt_raw = format!(
+        "
+    INSERT OR IGNORE INTO {ENCOUNTERS_TABLE_NAME} (userId, entryKey)
+    VALUES (?1, ?2)"
+    );
+    let mut conn = get_db()?;
+    let tx = conn.transaction()?;
+    {
+        let mut insert_stmt = tx.prepare(&insert_stmt_raw)?;
+        for key in encounters.encountered.iter() {
+            insert_stmt.execute(params![user_id, key])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub(super) fn _get_encounters_for_user(user_id: usize) -> Result<EncountersResource, StorageError> {
+    let conn = get_db()?;
+    let stmt_raw = format!(
+        "
+    SELECT entryKey FROM {ENCOUNTERS_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let encountered = conn
+        .prepare(&stmt_raw)?
+        .query([user_id])?
+        .mapped(|r| r.get::<usize, String>(0))
+        .collect::<Result<_, rusqlite::Error>>()?;
+    Ok(EncountersResource { encountered })
+}
+
+/// Returns the compendium entries that a user has encountered.
+pub fn get_encounters_for_user(user_id: usize) -> EncountersResource {
+    _get_encounters_for_user(user_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to get encounters from db. Empty result fallback. {}",
+            e
+        );
+        Default::default()
+    })
+}
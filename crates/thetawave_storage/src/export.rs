@@ -0,0 +1,595 @@
+//! Export of everything saved for a profile to JSON or CSV, and import of those files back into
+//! the database. Unlike the rest of the crate, these are meant to be called outside of the game,
+//! e.g. by the CLI, so errors are returned instead of logged.
+use crate::backend::{StorageBackendType, StorageError, THETAWAVE_STORAGE_BACKEND_ENVVAR};
+use crate::core::{get_db, OPTIONS_TABLE_NAME, PROFILES_TABLE_NAME};
+use crate::migrations::{run_migrations, LATEST_SCHEMA_VERSION};
+use crate::profiles::{insert_profile, PROFILE_DATA_TABLES};
+use bevy::log::warn;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use std::fmt::Display;
+use std::str::FromStr;
+use thetawave_interface::game::profiles::MAX_PROFILE_NAME_LEN;
+use thiserror::Error;
+
+/// First field of the CSV records holding the profile itself, the other records start with the
+/// name of their table
+const CSV_PROFILE_RECORD: &str = "Profile";
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Failed to read or write JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Failed to read or write CSV: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Invalid export: {0}")]
+    InvalidExport(String),
+    #[error("No profile with id {0}")]
+    NoSuchProfile(usize),
+    #[error("A profile named {0:?} already exists")]
+    NameTaken(String),
+    #[error(
+        "Exports and imports only work with the sqlite storage backend, but {} chooses {0:?}",
+        THETAWAVE_STORAGE_BACKEND_ENVVAR
+    )]
+    UnsupportedBackend(StorageBackendType),
+}
+
+impl From<StorageError> for ExportError {
+    fn from(value: StorageError) -> Self {
+        ExportError::Database(value.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for ExportError {
+    fn from(value: rusqlite::Error) -> Self {
+        ExportError::Database(value.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    /// Every record starts with the name of its table, so that a spreadsheet can be filtered by
+    /// table. The first record of each table is its header.
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            _ => Err(format!("Unknown export format {s:?}, expected json or csv")),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => write!(f, "json"),
+            ExportFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// What to do when an imported profile has the same name as a saved profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportConflict {
+    /// Fail the import, leaving the saved profile alone
+    #[default]
+    Abort,
+    /// Import as a new profile with a number added to its name
+    Rename,
+    /// Replace everything saved for the existing profile
+    Overwrite,
+}
+
+impl FromStr for ImportConflict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "abort" => Ok(ImportConflict::Abort),
+            "rename" => Ok(ImportConflict::Rename),
+            "overwrite" => Ok(ImportConflict::Overwrite),
+            _ => Err(format!(
+                "Unknown conflict handling {s:?}, expected abort, rename or overwrite"
+            )),
+        }
+    }
+}
+
+/// Rows of one table that belong to a profile, without the column holding the profile's id
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TableExport {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Everything saved for a profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileExport {
+    /// Version of the schema the profile was exported from
+    pub schema_version: usize,
+    pub name: String,
+    pub tables: Vec<TableExport>,
+}
+
+impl ProfileExport {
+    pub fn write(&self, format: ExportFormat) -> Result<String, ExportError> {
+        match format {
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ExportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    pub fn read(contents: &str, format: ExportFormat) -> Result<Self, ExportError> {
+        match format {
+            ExportFormat::Json => Ok(serde_json::from_str(contents)?),
+            ExportFormat::Csv => Self::from_csv(contents),
+        }
+    }
+
+    fn to_csv(&self) -> Result<String, ExportError> {
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+
+        writer.write_record([CSV_PROFILE_RECORD, "schemaVersion", "name"])?;
+        writer.write_record([
+            CSV_PROFILE_RECORD,
+            self.schema_version.to_string().as_str(),
+            self.name.as_str(),
+        ])?;
+
+        for table in self.tables.iter() {
+            writer.write_record(
+                [table.name.as_str()]
+                    .into_iter()
+                    .chain(table.columns.iter().map(String::as_str)),
+            )?;
+            for row in table.rows.iter() {
+                writer.write_record(
+                    [table.name.clone()]
+                        .into_iter()
+                        .chain(row.iter().map(csv_field_from_value)),
+                )?;
+            }
+        }
+
+        String::from_utf8(
+            writer
+                .into_inner()
+                .map_err(|e| ExportError::InvalidExport(e.to_string()))?,
+        )
+        .map_err(|e| ExportError::InvalidExport(e.to_string()))
+    }
+
+    fn from_csv(contents: &str) -> Result<Self, ExportError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(contents.as_bytes());
+
+        let mut profile_fields: Option<(usize, String)> = None;
+        let mut seen_profile_header = false;
+        let mut tables: Vec<TableExport> = vec![];
+
+        for record in reader.records() {
+            let record = record?;
+            let Some(record_name) = record.get(0) else {
+                continue;
+            };
+            let fields = record.iter().skip(1);
+
+            if record_name == CSV_PROFILE_RECORD {
+                if !seen_profile_header {
+                    seen_profile_header = true;
+                } else if let (Some(schema_version), Some(name)) = (record.get(1), record.get(2)) {
+                    let schema_version = schema_version.parse::<usize>().map_err(|e| {
+                        ExportError::InvalidExport(format!("Invalid schema version. {e}"))
+                    })?;
+                    profile_fields = Some((schema_version, name.to_string()));
+                }
+            } else if let Some(table) = tables.iter_mut().find(|table| table.name == record_name) {
+                table.rows.push(fields.map(value_from_csv_field).collect());
+            } else {
+                tables.push(TableExport {
+                    name: record_name.to_string(),
+                    columns: fields.map(str::to_string).collect(),
+                    rows: vec![],
+                });
+            }
+        }
+
+        let (schema_version, name) = profile_fields
+            .ok_or_else(|| ExportError::InvalidExport("Missing the profile record".to_string()))?;
+        Ok(ProfileExport {
+            schema_version,
+            name,
+            tables,
+        })
+    }
+}
+
+fn csv_field_from_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => (*b as u8).to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// CSV fields are imported as text, which sqlite converts to the type of the column. Empty fields
+/// are null.
+fn value_from_csv_field(field: &str) -> Value {
+    if field.is_empty() {
+        Value::Null
+    } else {
+        Value::String(field.to_string())
+    }
+}
+
+fn value_from_sql(value: SqlValue) -> Result<Value, ExportError> {
+    match value {
+        SqlValue::Null => Ok(Value::Null),
+        SqlValue::Integer(i) => Ok(Value::from(i)),
+        SqlValue::Real(f) => Ok(Number::from_f64(f).map_or(Value::Null, Value::Number)),
+        SqlValue::Text(s) => Ok(Value::String(s)),
+        SqlValue::Blob(_) => Err(ExportError::InvalidExport(
+            "Binary columns cannot be exported".to_string(),
+        )),
+    }
+}
+
+fn sql_from_value(value: &Value) -> Result<SqlValue, ExportError> {
+    match value {
+        Value::Null => Ok(SqlValue::Null),
+        Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        Value::Number(n) => Ok(match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        }),
+        Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        other => Err(ExportError::InvalidExport(format!(
+            "Unexpected value {other}"
+        ))),
+    }
+}
+
+/// Columns of a table that are exported. The profile id column is left out since profiles get a
+/// new id when they are imported, as are ids that are only used to tell rows apart.
+fn get_exported_columns(
+    conn: &Connection,
+    table_name: &str,
+    id_column: &str,
+) -> Result<Vec<String>, ExportError> {
+    let columns = conn
+        .prepare(&format!(
+            "SELECT name, pk FROM pragma_table_info('{table_name}')"
+        ))?
+        .query([])?
+        .mapped(|row| Ok((row.get::<usize, String>(0)?, row.get::<usize, usize>(1)?)))
+        .collect::<Result<Vec<(String, usize)>, rusqlite::Error>>()?;
+
+    let n_primary_key_columns = columns.iter().filter(|(_, pk)| *pk > 0).count();
+
+    Ok(columns
+        .into_iter()
+        .filter(|(name, pk)| name != id_column && !(*pk > 0 && n_primary_key_columns == 1))
+        .map(|(name, _)| name)
+        .collect())
+}
+
+fn export_profile_from_conn(
+    conn: &Connection,
+    profile_id: usize,
+) -> Result<ProfileExport, ExportError> {
+    let name: String = conn
+        .query_row(
+            &format!("SELECT name FROM {PROFILES_TABLE_NAME} WHERE profileId=?1"),
+            [profile_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or(ExportError::NoSuchProfile(profile_id))?;
+
+    let mut tables = vec![];
+    for (table_name, id_column) in PROFILE_DATA_TABLES {
+        let columns = get_exported_columns(conn, table_name, id_column)?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM {table_name} WHERE {id_column}=?1 ORDER BY rowid",
+            columns.join(", ")
+        ))?;
+        let rows = stmt
+            .query([profile_id])?
+            .mapped(|row| {
+                (0..columns.len())
+                    .map(|i| row.get::<usize, SqlValue>(i))
+                    .collect::<Result<Vec<SqlValue>, rusqlite::Error>>()
+            })
+            .collect::<Result<Vec<Vec<SqlValue>>, rusqlite::Error>>()?
+            .into_iter()
+            .map(|row| row.into_iter().map(value_from_sql).collect())
+            .collect::<Result<Vec<Vec<Value>>, ExportError>>()?;
+
+        tables.push(TableExport {
+            name: table_name.to_string(),
+            columns,
+            rows,
+        });
+    }
+
+    Ok(ProfileExport {
+        schema_version: LATEST_SCHEMA_VERSION,
+        name,
+        tables,
+    })
+}
+
+/// Name for an imported profile that doesn't clash with a saved profile, e.g. "Ada (2)"
+fn get_unused_profile_name(conn: &Connection, name: &str) -> Result<String, ExportError> {
+    let mut n = 2;
+    loop {
+        let suffix = format!(" ({n})");
+        let base: String = name
+            .trim()
+            .chars()
+            .take(MAX_PROFILE_NAME_LEN.saturating_sub(suffix.chars().count()))
+            .collect();
+        let candidate = format!("{}{suffix}", base.trim_end());
+        if get_profile_id_by_name(conn, &candidate)?.is_none() {
+            return Ok(candidate);
+        }
+        n += 1;
+    }
+}
+
+fn get_profile_id_by_name(conn: &Connection, name: &str) -> Result<Option<usize>, ExportError> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT profileId FROM {PROFILES_TABLE_NAME} WHERE name=?1"),
+            [name.trim()],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn import_profile_to_conn(
+    conn: &mut Connection,
+    export: &ProfileExport,
+    on_conflict: ImportConflict,
+) -> Result<usize, ExportError> {
+    if export.schema_version > LATEST_SCHEMA_VERSION {
+        return Err(ExportError::InvalidExport(format!(
+            "Exported from a newer version of the game (schema version {})",
+            export.schema_version
+        )));
+    }
+
+    let tx = conn.transaction()?;
+
+    let profile_id = match (get_profile_id_by_name(&tx, &export.name)?, on_conflict) {
+        (None, _) => insert_profile(&tx, &export.name)?,
+        (Some(_), ImportConflict::Abort) => {
+            return Err(ExportError::NameTaken(export.name.clone()));
+        }
+        (Some(_), ImportConflict::Rename) => {
+            let name = get_unused_profile_name(&tx, &export.name)?;
+            insert_profile(&tx, &name)?
+        }
+        (Some(profile_id), ImportConflict::Overwrite) => {
+            // options are replaced rather than deleted, so the profile always has some
+            for (table_name, id_column) in PROFILE_DATA_TABLES
+                .into_iter()
+                .filter(|(table_name, _)| *table_name != OPTIONS_TABLE_NAME)
+            {
+                tx.execute(
+                    &format!("DELETE FROM {table_name} WHERE {id_column}=?1"),
+                    [profile_id],
+                )?;
+            }
+            profile_id
+        }
+    };
+
+    for table in export.tables.iter() {
+        let Some((table_name, id_column)) = PROFILE_DATA_TABLES
+            .into_iter()
+            .find(|(table_name, _)| *table_name == table.name)
+        else {
+            warn!("Skipping unknown table {} in import", table.name);
+            continue;
+        };
+
+        // columns from older schemas keep their defaults
+        let current_columns = get_exported_columns(&tx, table_name, id_column)?;
+        let column_idxs: Vec<usize> = table
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| current_columns.contains(column))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let stmt_raw = format!(
+            "INSERT OR REPLACE INTO {table_name} ({id_column}{}) VALUES (?1{})",
+            column_idxs
+                .iter()
+                .map(|idx| format!(", {}", table.columns[*idx]))
+                .collect::<String>(),
+            (0..column_idxs.len())
+                .map(|i| format!(", ?{}", i + 2))
+                .collect::<String>(),
+        );
+        let mut stmt = tx.prepare(&stmt_raw)?;
+
+        for row in table.rows.iter() {
+            let mut values = vec![SqlValue::Integer(profile_id as i64)];
+            for idx in column_idxs.iter() {
+                values.push(sql_from_value(row.get(*idx).unwrap_or(&Value::Null))?);
+            }
+            stmt.execute(params_from_iter(values))?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(profile_id)
+}
+
+/// Saves made with the file or memory backend aren't in the database, so exporting or importing
+/// would silently use the wrong saves
+fn check_backend_is_sqlite(backend_type: StorageBackendType) -> Result<(), ExportError> {
+    match backend_type {
+        StorageBackendType::Sqlite => Ok(()),
+        _ => Err(ExportError::UnsupportedBackend(backend_type)),
+    }
+}
+
+/// Opens the database, creating and upgrading it first since this can run without the game
+fn get_migrated_db() -> Result<Connection, ExportError> {
+    check_backend_is_sqlite(StorageBackendType::from_env())?;
+    let mut conn = get_db()?;
+    run_migrations(&mut conn)?;
+    Ok(conn)
+}
+
+/// Returns everything saved for a profile in the given format
+pub fn export_profile(profile_id: usize, format: ExportFormat) -> Result<String, ExportError> {
+    export_profile_from_conn(&get_migrated_db()?, profile_id)?.write(format)
+}
+
+/// Saves an exported profile and returns its id
+pub fn import_profile(
+    contents: &str,
+    format: ExportFormat,
+    on_conflict: ImportConflict,
+) -> Result<usize, ExportError> {
+    let export = ProfileExport::read(contents, format)?;
+    import_profile_to_conn(&mut get_migrated_db()?, &export, on_conflict)
+}
+
+/// Returns the id of the profile with the given name
+pub fn find_profile_id(name: &str) -> Result<Option<usize>, ExportError> {
+    get_profile_id_by_name(&get_migrated_db()?, name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        check_backend_is_sqlite, export_profile_from_conn, import_profile_to_conn, ExportError,
+        ExportFormat, ImportConflict, ProfileExport,
+    };
+    use crate::backend::StorageBackendType;
+    use crate::core::{PROFILES_TABLE_NAME, RUN_HISTORY_TABLE_NAME, USERSTAT};
+    use crate::migrations::run_migrations;
+    use rusqlite::{params, Connection};
+    use thetawave_interface::game::historical_metrics::DEFAULT_USER_ID;
+
+    fn db_with_saves() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn.execute_batch(&format!(
+            "
+        INSERT INTO {USERSTAT} (userId, totalShotsFired, totalShotsHit, totalGamesLost)
+            VALUES ({DEFAULT_USER_ID}, 10, 4, 1);
+        INSERT INTO {RUN_HISTORY_TABLE_NAME} (
+            userId, startTime, durationSecs, characters, runKey, difficulty, levelsCompleted,
+            outcome, moneyCollected, damageTaken, seed, score
+        )
+        VALUES ({DEFAULT_USER_ID}, 100, 61.5, 'captain,juggernaut', 'premade, \"quoted\"',
+            'Hard', 2, 'Victory', 30, 7, 42, 9001);"
+        ))
+        .unwrap();
+        conn
+    }
+
+    fn count_rows(conn: &Connection, table_name: &str, user_id: usize) -> usize {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {table_name} WHERE userId=?1"),
+            [user_id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_export_round_trips_through_every_format() {
+        let conn = db_with_saves();
+        let export = export_profile_from_conn(&conn, DEFAULT_USER_ID).unwrap();
+
+        for format in [ExportFormat::Json, ExportFormat::Csv] {
+            let exported = export.write(format).unwrap();
+            let mut new_conn = Connection::open_in_memory().unwrap();
+            run_migrations(&mut new_conn).unwrap();
+
+            let profile_id = import_profile_to_conn(
+                &mut new_conn,
+                &ProfileExport::read(&exported, format).unwrap(),
+                ImportConflict::Overwrite,
+            )
+            .unwrap();
+
+            assert_eq!(profile_id, DEFAULT_USER_ID);
+            assert_eq!(
+                export_profile_from_conn(&new_conn, profile_id).unwrap(),
+                export,
+                "{format} export changed the saves"
+            );
+        }
+    }
+
+    #[test]
+    fn test_import_name_conflicts() {
+        let mut conn = db_with_saves();
+        let export = export_profile_from_conn(&conn, DEFAULT_USER_ID).unwrap();
+
+        assert!(matches!(
+            import_profile_to_conn(&mut conn, &export, ImportConflict::Abort),
+            Err(ExportError::NameTaken(_))
+        ));
+
+        let renamed_id =
+            import_profile_to_conn(&mut conn, &export, ImportConflict::Rename).unwrap();
+        assert_ne!(renamed_id, DEFAULT_USER_ID);
+        let renamed_name: String = conn
+            .query_row(
+                &format!("SELECT name FROM {PROFILES_TABLE_NAME} WHERE profileId=?1"),
+                params![renamed_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(renamed_name, "Player 1 (2)");
+        assert_eq!(count_rows(&conn, RUN_HISTORY_TABLE_NAME, renamed_id), 1);
+
+        // overwriting replaces the saved runs rather than adding to them
+        import_profile_to_conn(&mut conn, &export, ImportConflict::Overwrite).unwrap();
+        assert_eq!(
+            count_rows(&conn, RUN_HISTORY_TABLE_NAME, DEFAULT_USER_ID),
+            1
+        );
+        assert_eq!(count_rows(&conn, USERSTAT, DEFAULT_USER_ID), 1);
+    }
+
+    #[test]
+    fn test_only_the_sqlite_backend_can_be_exported() {
+        assert!(check_backend_is_sqlite(StorageBackendType::Sqlite).is_ok());
+        for backend_type in [StorageBackendType::File, StorageBackendType::Memory] {
+            assert!(matches!(
+                check_backend_is_sqlite(backend_type),
+                Err(ExportError::UnsupportedBackend(unsupported)) if unsupported == backend_type
+            ));
+        }
+    }
+}
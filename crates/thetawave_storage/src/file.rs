@@ -0,0 +1,256 @@
+//! A storage backend that keeps everything in a single RON save file. On the web the save is kept
+//! in the browser's local storage, under the file's path.
+use ron::ser::PrettyConfig;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+use thetawave_interface::game::compendium::EncountersResource;
+use thetawave_interface::game::historical_metrics::{
+    LevelStatsBy1PlayerCacheT, MobsKilledBy1PlayerCacheT, UserStat,
+};
+use thetawave_interface::game::options::GameOptions;
+use thetawave_interface::game::profiles::Profile;
+use thetawave_interface::game::run_history::{Leaderboard, PersonalBests, RunRecord};
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+
+use crate::backend::{StorageBackend, StorageError};
+use crate::memory::{MemoryBackend, SavedData};
+
+/// Overrides the path of the save file
+pub const THETAWAVE_SAVE_FILE_PATH_ENVVAR: &'static str = "THETAWAVE_SAVE_FILE_PATH";
+const THETAWAVE_SAVE_FILE: &'static str = "thetawave.ron";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_save_file_path() -> Result<PathBuf, StorageError> {
+    match std::env::var_os(THETAWAVE_SAVE_FILE_PATH_ENVVAR) {
+        Some(osstr) => Ok(PathBuf::from(osstr)),
+        None => crate::core::default_data_dir().map(|data_dir| data_dir.join(THETAWAVE_SAVE_FILE)),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn default_save_file_path() -> Result<PathBuf, StorageError> {
+    Ok(PathBuf::from(THETAWAVE_SAVE_FILE))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_save_file(path: &Path) -> Result<Option<String>, StorageError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_save_file(path: &Path, contents: &str) -> Result<(), StorageError> {
+    // the old save is only replaced once the new one has been written in full
+    let temp_path = path.with_extension("ron.tmp");
+    std::fs::write(&temp_path, contents)?;
+    std::fs::rename(temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, StorageError> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or(StorageError::NoDBPathFound)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_save_file(path: &Path) -> Result<Option<String>, StorageError> {
+    local_storage()?
+        .get_item(&path.to_string_lossy())
+        .map_err(|e| StorageError::InternalError(format!("{e:?}")))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_save_file(path: &Path, contents: &str) -> Result<(), StorageError> {
+    local_storage()?
+        .set_item(&path.to_string_lossy(), contents)
+        .map_err(|e| StorageError::InternalError(format!("{e:?}")))
+}
+
+/// Keeps the saves in memory and writes all of them to the save file after every change
+pub struct FileBackend {
+    path: PathBuf,
+    memory: MemoryBackend,
+    /// Set once the save file has been read. Nothing is written before then, so that a save file
+    /// that can't be read is never overwritten.
+    loaded: AtomicBool,
+}
+
+impl FileBackend {
+    pub fn new(path: PathBuf) -> Self {
+        FileBackend {
+            path,
+            memory: MemoryBackend::default(),
+            loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Opens the save file in the user's data directory, or at `THETAWAVE_SAVE_FILE_PATH`
+    pub fn at_default_path() -> Result<Self, StorageError> {
+        Ok(Self::new(default_save_file_path()?))
+    }
+
+    fn save(&self) -> Result<(), StorageError> {
+        if !self.loaded.load(Ordering::SeqCst) {
+            return Err(StorageError::InternalError(format!(
+                "Not writing to {:?} because it was never read",
+                self.path
+            )));
+        }
+        let contents = ron::ser::to_string_pretty(&*self.memory.lock()?, PrettyConfig::default())?;
+        write_save_file(&self.path, &contents)
+    }
+}
+
+impl StorageBackend for FileBackend {
+    fn setup(&self) -> Result<(), StorageError> {
+        if let Some(contents) = read_save_file(&self.path)? {
+            *self.memory.lock()? = ron::de::from_str::<SavedData>(&contents)?;
+        }
+        self.loaded.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn get_profiles(&self) -> Result<Vec<Profile>, StorageError> {
+        self.memory.get_profiles()
+    }
+
+    fn create_profile(&self, name: &str) -> Result<usize, StorageError> {
+        let profile_id = self.memory.create_profile(name)?;
+        self.save()?;
+        Ok(profile_id)
+    }
+
+    fn set_last_used_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        self.memory.set_last_used_profile(profile_id)?;
+        self.save()
+    }
+
+    fn delete_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        self.memory.delete_profile(profile_id)?;
+        self.save()
+    }
+
+    fn get_last_used_profile_id(&self) -> Result<Option<usize>, StorageError> {
+        self.memory.get_last_used_profile_id()
+    }
+
+    fn get_user_stats(&self, user_id: usize) -> Result<Option<UserStat>, StorageError> {
+        self.memory.get_user_stats(user_id)
+    }
+
+    fn set_user_stats(&self, user_id: usize, user_stats: &UserStat) -> Result<(), StorageError> {
+        self.memory.set_user_stats(user_id, user_stats)?;
+        self.save()
+    }
+
+    fn get_mob_kills(&self, user_id: usize) -> Result<MobsKilledBy1PlayerCacheT, StorageError> {
+        self.memory.get_mob_kills(user_id)
+    }
+
+    fn set_mob_kills(
+        &self,
+        user_id: usize,
+        mob_kills: &MobsKilledBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        self.memory.set_mob_kills(user_id, mob_kills)?;
+        self.save()
+    }
+
+    fn get_level_stats(&self, user_id: usize) -> Result<LevelStatsBy1PlayerCacheT, StorageError> {
+        self.memory.get_level_stats(user_id)
+    }
+
+    fn set_level_stats(
+        &self,
+        user_id: usize,
+        level_stats: &LevelStatsBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        self.memory.set_level_stats(user_id, level_stats)?;
+        self.save()
+    }
+
+    fn get_game_options(&self, profile_id: usize) -> Result<Option<GameOptions>, StorageError> {
+        self.memory.get_game_options(profile_id)
+    }
+
+    fn set_game_options(
+        &self,
+        profile_id: usize,
+        game_options: &GameOptions,
+    ) -> Result<(), StorageError> {
+        self.memory.set_game_options(profile_id, game_options)?;
+        self.save()
+    }
+
+    fn get_character_unlocks(
+        &self,
+        user_id: usize,
+    ) -> Result<CharacterUnlocksResource, StorageError> {
+        self.memory.get_character_unlocks(user_id)
+    }
+
+    fn set_character_unlocks(
+        &self,
+        user_id: usize,
+        unlocks: &CharacterUnlocksResource,
+    ) -> Result<(), StorageError> {
+        self.memory.set_character_unlocks(user_id, unlocks)?;
+        self.save()
+    }
+
+    fn get_achievements(
+        &self,
+        user_id: usize,
+    ) -> Result<AchievementsProgressResource, StorageError> {
+        self.memory.get_achievements(user_id)
+    }
+
+    fn set_achievements(
+        &self,
+        user_id: usize,
+        achievements: &AchievementsProgressResource,
+    ) -> Result<(), StorageError> {
+        self.memory.set_achievements(user_id, achievements)?;
+        self.save()
+    }
+
+    fn get_encounters(&self, user_id: usize) -> Result<EncountersResource, StorageError> {
+        self.memory.get_encounters(user_id)
+    }
+
+    fn add_encounters(
+        &self,
+        user_id: usize,
+        encounters: &EncountersResource,
+    ) -> Result<(), StorageError> {
+        self.memory.add_encounters(user_id, encounters)?;
+        self.save()
+    }
+
+    fn add_run(&self, user_id: usize, run: &RunRecord) -> Result<(), StorageError> {
+        self.memory.add_run(user_id, run)?;
+        self.save()
+    }
+
+    fn get_recent_runs(
+        &self,
+        user_id: usize,
+        limit: usize,
+    ) -> Result<Vec<RunRecord>, StorageError> {
+        self.memory.get_recent_runs(user_id, limit)
+    }
+
+    fn get_personal_bests(&self, user_id: usize) -> Result<PersonalBests, StorageError> {
+        self.memory.get_personal_bests(user_id)
+    }
+
+    fn get_leaderboards(&self, limit: usize) -> Result<Vec<Leaderboard>, StorageError> {
+        self.memory.get_leaderboards(limit)
+    }
+}
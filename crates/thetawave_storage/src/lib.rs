@@ -1,7 +1,32 @@
 /// CRUD operations to persist data to disk so that it can be safely+portably retrieved across user sessions and
-/// thetawave releases. There are public functions to read data (exposing as few db implementation details as possible),
-/// while all upserts/mutations/deletions are handled via a Bevy plugin.
+/// thetawave releases. Saves go through a `StorageBackend`: the SQLite database, a RON save file, or memory. There
+/// are public functions to read data (exposing as few db implementation details as possible), while all
+/// upserts/mutations/deletions are handled via a Bevy plugin.
+pub mod backend;
+pub mod file;
+pub mod memory;
+pub mod plugin;
+
+// sqlite isn't available in the browser, where saves go to the file backend instead
+#[cfg(not(target_arch = "wasm32"))]
+pub mod achievements;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod core;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod encounters;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod migrations;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod options;
-pub mod plugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod profiles;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod run_history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sqlite;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod unlocks;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod user_stats;
@@ -0,0 +1,458 @@
+//! A storage backend that keeps everything in memory. The same saves are written out by the file
+//! backend.
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+use thetawave_interface::game::compendium::EncountersResource;
+use thetawave_interface::game::historical_metrics::{
+    LevelStatsBy1PlayerCacheT, LevelStatsByPlayerCacheT, MobsKilledBy1PlayerCacheT,
+    MobsKilledByPlayerCacheT, UserStat, UserStatsByPlayerCacheT, DEFAULT_USER_ID,
+};
+use thetawave_interface::game::options::{GameOptions, DEFAULT_OPTIONS_PROFILE_ID};
+use thetawave_interface::game::profiles::Profile;
+use thetawave_interface::game::run_history::{Leaderboard, PersonalBests, RunRecord};
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+use thetawave_interface::run::RunOutcomeType;
+
+use crate::backend::{
+    get_favorite_character, get_run_start_time, validate_profile_name, StorageBackend, StorageError,
+};
+
+/// Options saved for new profiles, matching the defaults of the sqlite Options table
+fn default_saved_game_options() -> GameOptions {
+    GameOptions {
+        bloom_enabled: true,
+        bloom_intensity: 1.0,
+        ..Default::default()
+    }
+}
+
+/// Orders runs from the highest score down, breaking ties with the shortest duration
+fn compare_leaderboard_rank(a: &RunRecord, b: &RunRecord) -> Ordering {
+    b.score
+        .cmp(&a.score)
+        .then(a.duration_secs.total_cmp(&b.duration_secs))
+}
+
+/// A run along with the user that played it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SavedRun {
+    user_id: usize,
+    run: RunRecord,
+}
+
+/// Everything saved by the memory and file backends. Fields missing from older save files are
+/// filled in with their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(super) struct SavedData {
+    /// Oldest first
+    profiles: Vec<Profile>,
+    last_used_profile_id: usize,
+    user_stats: UserStatsByPlayerCacheT,
+    mob_kills: MobsKilledByPlayerCacheT,
+    level_stats: LevelStatsByPlayerCacheT,
+    game_options: HashMap<usize, GameOptions>,
+    character_unlocks: HashMap<usize, CharacterUnlocksResource>,
+    achievements: HashMap<usize, AchievementsProgressResource>,
+    encounters: HashMap<usize, EncountersResource>,
+    /// Oldest saved first
+    runs: Vec<SavedRun>,
+}
+
+impl Default for SavedData {
+    fn default() -> Self {
+        SavedData {
+            profiles: vec![Profile {
+                id: DEFAULT_USER_ID,
+                name: "Player 1".to_string(),
+            }],
+            last_used_profile_id: DEFAULT_USER_ID,
+            user_stats: UserStatsByPlayerCacheT::default(),
+            mob_kills: MobsKilledByPlayerCacheT::default(),
+            level_stats: LevelStatsByPlayerCacheT::default(),
+            game_options: HashMap::from([(
+                DEFAULT_OPTIONS_PROFILE_ID,
+                default_saved_game_options(),
+            )]),
+            character_unlocks: HashMap::default(),
+            achievements: HashMap::default(),
+            encounters: HashMap::default(),
+            runs: vec![],
+        }
+    }
+}
+
+impl SavedData {
+    fn has_profile(&self, profile_id: usize) -> bool {
+        self.profiles.iter().any(|profile| profile.id == profile_id)
+    }
+
+    fn create_profile(&mut self, name: &str) -> Result<usize, StorageError> {
+        let name = validate_profile_name(name)?;
+        if self.profiles.iter().any(|profile| profile.name == name) {
+            return Err(StorageError::InternalError(format!(
+                "A profile named {name:?} already exists"
+            )));
+        }
+
+        let profile_id = self
+            .profiles
+            .iter()
+            .map(|profile| profile.id + 1)
+            .max()
+            .unwrap_or_default();
+        self.profiles.push(Profile {
+            id: profile_id,
+            name: name.to_string(),
+        });
+        self.game_options
+            .insert(profile_id, default_saved_game_options());
+        self.last_used_profile_id = profile_id;
+        Ok(profile_id)
+    }
+
+    fn set_last_used_profile(&mut self, profile_id: usize) {
+        if self.has_profile(profile_id) {
+            self.last_used_profile_id = profile_id;
+        }
+    }
+
+    fn delete_profile(&mut self, profile_id: usize) -> Result<(), StorageError> {
+        if self.profiles.len() <= 1 {
+            return Err(StorageError::InternalError(
+                "Cannot delete the last profile".to_string(),
+            ));
+        }
+
+        self.profiles.retain(|profile| profile.id != profile_id);
+        self.user_stats.remove(&profile_id);
+        self.mob_kills.remove(&profile_id);
+        self.level_stats.remove(&profile_id);
+        self.game_options.remove(&profile_id);
+        self.character_unlocks.remove(&profile_id);
+        self.achievements.remove(&profile_id);
+        self.encounters.remove(&profile_id);
+        self.runs
+            .retain(|saved_run| saved_run.user_id != profile_id);
+        Ok(())
+    }
+
+    /// The last used profile, or the oldest profile if it was deleted
+    fn get_last_used_profile_id(&self) -> Option<usize> {
+        if self.has_profile(self.last_used_profile_id) {
+            Some(self.last_used_profile_id)
+        } else {
+            self.profiles.first().map(|profile| profile.id)
+        }
+    }
+
+    fn set_character_unlocks(&mut self, user_id: usize, unlocks: &CharacterUnlocksResource) {
+        let saved_unlocks = self.character_unlocks.entry(user_id).or_default();
+        saved_unlocks
+            .unlocked_characters
+            .extend(unlocks.unlocked_characters.iter().cloned());
+        saved_unlocks.meta_currency = unlocks.meta_currency;
+    }
+
+    fn set_achievements(&mut self, user_id: usize, achievements: &AchievementsProgressResource) {
+        let saved_achievements = self.achievements.entry(user_id).or_default();
+        let keys = achievements
+            .progress
+            .keys()
+            .chain(achievements.unlocked.iter());
+        for key in keys {
+            saved_achievements
+                .progress
+                .insert(key.clone(), achievements.get_progress(key));
+            if achievements.is_unlocked(key) {
+                saved_achievements.unlocked.insert(key.clone());
+            } else {
+                saved_achievements.unlocked.remove(key);
+            }
+        }
+    }
+
+    fn add_run(&mut self, user_id: usize, run: &RunRecord) -> Result<(), StorageError> {
+        let run = RunRecord {
+            start_time: Some(get_run_start_time(run)?),
+            ..run.clone()
+        };
+        self.runs.push(SavedRun { user_id, run });
+        Ok(())
+    }
+
+    fn get_recent_runs(&self, user_id: usize, limit: usize) -> Vec<RunRecord> {
+        let mut runs: Vec<&RunRecord> = self
+            .runs
+            .iter()
+            .rev()
+            .filter(|saved_run| saved_run.user_id == user_id)
+            .map(|saved_run| &saved_run.run)
+            .collect();
+        // runs saved later come first among runs that started at the same time
+        runs.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+        runs.into_iter().take(limit).cloned().collect()
+    }
+
+    fn get_personal_bests(&self, user_id: usize) -> PersonalBests {
+        let user_runs = || {
+            self.runs
+                .iter()
+                .filter(move |saved_run| saved_run.user_id == user_id)
+                .map(|saved_run| &saved_run.run)
+        };
+        let bests = user_runs().fold(PersonalBests::default(), |bests, run| PersonalBests {
+            total_runs: bests.total_runs + 1,
+            most_levels_completed: bests.most_levels_completed.max(run.levels_completed),
+            longest_run_secs: bests.longest_run_secs.max(run.duration_secs),
+            most_money_collected: bests.most_money_collected.max(run.money_collected),
+            fastest_victory_secs: match run.outcome {
+                RunOutcomeType::Victory => Some(
+                    bests
+                        .fastest_victory_secs
+                        .map_or(run.duration_secs, |fastest| fastest.min(run.duration_secs)),
+                ),
+                RunOutcomeType::Defeat(_) => bests.fastest_victory_secs,
+            },
+            ..bests
+        });
+        PersonalBests {
+            favorite_character: get_favorite_character(
+                user_runs().flat_map(|run| run.characters.iter()),
+            ),
+            // the first of equally ranked runs, like the leaderboards
+            best_run: user_runs()
+                .min_by(|a, b| compare_leaderboard_rank(a, b))
+                .cloned(),
+            ..bests
+        }
+    }
+
+    fn get_leaderboards(&self, limit: usize) -> Vec<Leaderboard> {
+        let mut leaderboards: Vec<Leaderboard> = vec![];
+        for saved_run in self.runs.iter() {
+            let run = &saved_run.run;
+            match leaderboards.iter_mut().find(|leaderboard| {
+                leaderboard.run_key == run.run_key && leaderboard.difficulty == run.difficulty
+            }) {
+                Some(leaderboard) => leaderboard.runs.push(run.clone()),
+                None => leaderboards.push(Leaderboard {
+                    run_key: run.run_key.clone(),
+                    difficulty: run.difficulty,
+                    runs: vec![run.clone()],
+                }),
+            }
+        }
+
+        // ordered the same way as the sqlite backend, which sorts difficulties by name
+        leaderboards.sort_by_key(|leaderboard| {
+            (
+                leaderboard.run_key.clone(),
+                format!("{:?}", leaderboard.difficulty),
+            )
+        });
+        for leaderboard in leaderboards.iter_mut() {
+            leaderboard.runs.sort_by(compare_leaderboard_rank);
+            leaderboard.runs.truncate(limit);
+        }
+        leaderboards
+    }
+}
+
+/// Saves that only last as long as the backend, used for tests and when no other backend can be
+/// opened
+#[derive(Default)]
+pub struct MemoryBackend {
+    data: Mutex<SavedData>,
+}
+
+impl MemoryBackend {
+    pub(super) fn lock(&self) -> Result<MutexGuard<SavedData>, StorageError> {
+        self.data
+            .lock()
+            .map_err(|e| StorageError::InternalError(e.to_string()))
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn setup(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn get_profiles(&self) -> Result<Vec<Profile>, StorageError> {
+        Ok(self.lock()?.profiles.clone())
+    }
+
+    fn create_profile(&self, name: &str) -> Result<usize, StorageError> {
+        self.lock()?.create_profile(name)
+    }
+
+    fn set_last_used_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        self.lock()?.set_last_used_profile(profile_id);
+        Ok(())
+    }
+
+    fn delete_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        self.lock()?.delete_profile(profile_id)
+    }
+
+    fn get_last_used_profile_id(&self) -> Result<Option<usize>, StorageError> {
+        Ok(self.lock()?.get_last_used_profile_id())
+    }
+
+    fn get_user_stats(&self, user_id: usize) -> Result<Option<UserStat>, StorageError> {
+        Ok(self.lock()?.user_stats.get(&user_id).cloned())
+    }
+
+    fn set_user_stats(&self, user_id: usize, user_stats: &UserStat) -> Result<(), StorageError> {
+        self.lock()?.user_stats.insert(user_id, user_stats.clone());
+        Ok(())
+    }
+
+    fn get_mob_kills(&self, user_id: usize) -> Result<MobsKilledBy1PlayerCacheT, StorageError> {
+        Ok(self
+            .lock()?
+            .mob_kills
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn set_mob_kills(
+        &self,
+        user_id: usize,
+        mob_kills: &MobsKilledBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        self.lock()?
+            .mob_kills
+            .entry(user_id)
+            .or_default()
+            .extend(mob_kills.iter().map(|(mob_type, n)| (*mob_type, *n)));
+        Ok(())
+    }
+
+    fn get_level_stats(&self, user_id: usize) -> Result<LevelStatsBy1PlayerCacheT, StorageError> {
+        Ok(self
+            .lock()?
+            .level_stats
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn set_level_stats(
+        &self,
+        user_id: usize,
+        level_stats: &LevelStatsBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        self.lock()?
+            .level_stats
+            .entry(user_id)
+            .or_default()
+            .extend(level_stats.clone());
+        Ok(())
+    }
+
+    fn get_game_options(&self, profile_id: usize) -> Result<Option<GameOptions>, StorageError> {
+        Ok(self.lock()?.game_options.get(&profile_id).cloned())
+    }
+
+    fn set_game_options(
+        &self,
+        profile_id: usize,
+        game_options: &GameOptions,
+    ) -> Result<(), StorageError> {
+        self.lock()?
+            .game_options
+            .insert(profile_id, game_options.clone());
+        Ok(())
+    }
+
+    fn get_character_unlocks(
+        &self,
+        user_id: usize,
+    ) -> Result<CharacterUnlocksResource, StorageError> {
+        Ok(self
+            .lock()?
+            .character_unlocks
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn set_character_unlocks(
+        &self,
+        user_id: usize,
+        unlocks: &CharacterUnlocksResource,
+    ) -> Result<(), StorageError> {
+        self.lock()?.set_character_unlocks(user_id, unlocks);
+        Ok(())
+    }
+
+    fn get_achievements(
+        &self,
+        user_id: usize,
+    ) -> Result<AchievementsProgressResource, StorageError> {
+        Ok(self
+            .lock()?
+            .achievements
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn set_achievements(
+        &self,
+        user_id: usize,
+        achievements: &AchievementsProgressResource,
+    ) -> Result<(), StorageError> {
+        self.lock()?.set_achievements(user_id, achievements);
+        Ok(())
+    }
+
+    fn get_encounters(&self, user_id: usize) -> Result<EncountersResource, StorageError> {
+        Ok(self
+            .lock()?
+            .encounters
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn add_encounters(
+        &self,
+        user_id: usize,
+        encounters: &EncountersResource,
+    ) -> Result<(), StorageError> {
+        self.lock()?
+            .encounters
+            .entry(user_id)
+            .or_default()
+            .encountered
+            .extend(encounters.encountered.iter().cloned());
+        Ok(())
+    }
+
+    fn add_run(&self, user_id: usize, run: &RunRecord) -> Result<(), StorageError> {
+        self.lock()?.add_run(user_id, run)
+    }
+
+    fn get_recent_runs(
+        &self,
+        user_id: usize,
+        limit: usize,
+    ) -> Result<Vec<RunRecord>, StorageError> {
+        Ok(self.lock()?.get_recent_runs(user_id, limit))
+    }
+
+    fn get_personal_bests(&self, user_id: usize) -> Result<PersonalBests, StorageError> {
+        Ok(self.lock()?.get_personal_bests(user_id))
+    }
+
+    fn get_leaderboards(&self, limit: usize) -> Result<Vec<Leaderboard>, StorageError> {
+        Ok(self.lock()?.get_leaderboards(limit))
+    }
+}
@@ -0,0 +1,448 @@
+use crate::core::{
+    ACHIEVEMENTS_TABLE_NAME, CHARACTER_UNLOCKS_TABLE_NAME, ENCOUNTERS_TABLE_NAME,
+    ENEMY_KILL_HISTORY_TABLE_NAME, LEVEL_STATS_TABLE_NAME, META_CURRENCY_TABLE_NAME,
+    OPTIONS_TABLE_NAME, PROFILES_TABLE_NAME, RUN_HISTORY_TABLE_NAME, USERSTAT,
+};
+use bevy::log::info;
+use rusqlite::{params, Connection, OptionalExtension, Result, Transaction};
+use thetawave_interface::game::historical_metrics::DEFAULT_USER_ID;
+use thetawave_interface::game::options::DEFAULT_OPTIONS_PROFILE_ID;
+
+pub(super) const SCHEMA_VERSION_TABLE_NAME: &'static str = "SchemaVersion";
+
+/// Upgrades the schema by one version. Migrations must never be edited once released, changes to
+/// the schema go in a new migration at the end of `MIGRATIONS`.
+type Migration = fn(&Transaction) -> Result<()>;
+
+/// `MIGRATIONS[i]` upgrades the schema from version `i` to version `i + 1`
+const MIGRATIONS: [Migration; 12] = [
+    create_initial_tables,
+    create_character_unlocks_tables,
+    create_run_history_table,
+    add_difficulty_and_score_to_run_history,
+    create_profiles_table,
+    create_achievements_table,
+    add_audio_display_and_input_options,
+    add_victories_and_level_stats,
+    add_window_resolution_option,
+    create_encounters_table,
+    add_language_option,
+    add_accessibility_options,
+];
+
+/// The version of the schema once every migration has been applied
+pub(super) const LATEST_SCHEMA_VERSION: usize = MIGRATIONS.len();
+
+fn create_initial_tables(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {USERSTAT} (
+        userId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL ,
+        totalShotsFired  INTEGER NOT NULL DEFAULT 0,
+        totalShotsHit  INTEGER NOT NULL DEFAULT 0,
+        totalGamesLost INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS {ENEMY_KILL_HISTORY_TABLE_NAME} (
+        userId INTEGER NOT NULL,
+        enemyMobType VARCHAR(255) NOT NULL,
+        nKilled INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (userId, enemyMobType)
+    );
+    CREATE TABLE IF NOT EXISTS {OPTIONS_TABLE_NAME} (
+        optionsProfileId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+        bloomEnabled BOOLEAN NOT NULL DEFAULT TRUE,
+        bloomIntensity REAL NOT NULL DEFAULT 1.0,
+        tutorialsEnabled BOOLEAN NOT NULL DEFAULT FALSE
+    );"
+    ))?;
+
+    // insert a default options row, leaving any saved options alone
+    tx.execute(
+        &format!("INSERT OR IGNORE INTO {OPTIONS_TABLE_NAME} (optionsProfileId) VALUES (?1)"),
+        [DEFAULT_OPTIONS_PROFILE_ID],
+    )?;
+    Ok(())
+}
+
+fn create_character_unlocks_tables(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {CHARACTER_UNLOCKS_TABLE_NAME} (
+        userId INTEGER NOT NULL,
+        characterKey VARCHAR(255) NOT NULL,
+        PRIMARY KEY (userId, characterKey)
+    );
+    CREATE TABLE IF NOT EXISTS {META_CURRENCY_TABLE_NAME} (
+        userId INTEGER PRIMARY KEY NOT NULL,
+        balance INTEGER NOT NULL DEFAULT 0
+    );"
+    ))
+}
+
+fn create_run_history_table(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {RUN_HISTORY_TABLE_NAME} (
+        runId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+        userId INTEGER NOT NULL,
+        startTime INTEGER NOT NULL,
+        durationSecs REAL NOT NULL,
+        characters TEXT NOT NULL,
+        runKey VARCHAR(255) NOT NULL,
+        levelsCompleted INTEGER NOT NULL DEFAULT 0,
+        outcome VARCHAR(255) NOT NULL,
+        moneyCollected INTEGER NOT NULL DEFAULT 0,
+        damageTaken INTEGER NOT NULL DEFAULT 0,
+        seed INTEGER NOT NULL
+    );"
+    ))
+}
+
+fn add_difficulty_and_score_to_run_history(tx: &Transaction) -> Result<()> {
+    // runs saved before difficulties were recorded were played on the default difficulty
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {RUN_HISTORY_TABLE_NAME}
+        ADD COLUMN difficulty VARCHAR(255) NOT NULL DEFAULT 'Normal';
+    ALTER TABLE {RUN_HISTORY_TABLE_NAME}
+        ADD COLUMN score INTEGER NOT NULL DEFAULT 0;"
+    ))
+}
+
+fn create_profiles_table(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {PROFILES_TABLE_NAME} (
+        profileId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+        name VARCHAR(255) NOT NULL UNIQUE,
+        lastUsed INTEGER NOT NULL DEFAULT 0
+    );"
+    ))?;
+
+    // everything saved before profiles existed belongs to the default user
+    tx.execute(
+        &format!("INSERT OR IGNORE INTO {PROFILES_TABLE_NAME} (profileId, name) VALUES (?1, ?2)"),
+        params![DEFAULT_USER_ID, "Player 1"],
+    )?;
+    Ok(())
+}
+
+fn create_achievements_table(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {ACHIEVEMENTS_TABLE_NAME} (
+        userId INTEGER NOT NULL,
+        achievementKey VARCHAR(255) NOT NULL,
+        progress INTEGER NOT NULL DEFAULT 0,
+        unlocked BOOLEAN NOT NULL DEFAULT FALSE,
+        PRIMARY KEY (userId, achievementKey)
+    );"
+    ))
+}
+
+fn add_audio_display_and_input_options(tx: &Transaction) -> Result<()> {
+    // key bindings are only saved once they have been changed from `config/input.ron`
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN musicVolume REAL NOT NULL DEFAULT 1.0;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN soundEffectsVolume REAL NOT NULL DEFAULT 1.0;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN menuVolume REAL NOT NULL DEFAULT 1.0;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN screenShakeIntensity REAL NOT NULL DEFAULT 1.0;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN displayMode VARCHAR(255) NOT NULL DEFAULT 'Windowed';
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN keyBindings TEXT;"
+    ))
+}
+
+fn add_victories_and_level_stats(tx: &Transaction) -> Result<()> {
+    // users that have never won have no fastest victory
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN totalGamesWon INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN totalGamesLostToPlayersDestroyed INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN totalGamesLostToDefenseDestroyed INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN totalLevelsCompleted INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN mostLevelsCompleted INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE {USERSTAT}
+        ADD COLUMN fastestVictoryMillis INTEGER;
+    CREATE TABLE IF NOT EXISTS {LEVEL_STATS_TABLE_NAME} (
+        userId INTEGER NOT NULL,
+        levelName VARCHAR(255) NOT NULL,
+        timesReached INTEGER NOT NULL DEFAULT 0,
+        timesCompleted INTEGER NOT NULL DEFAULT 0,
+        bestTimeMillis INTEGER,
+        PRIMARY KEY (userId, levelName)
+    );"
+    ))
+}
+
+fn add_window_resolution_option(tx: &Transaction) -> Result<()> {
+    // the size in `config/display.ron` is used until a resolution is chosen
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN windowWidth INTEGER;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN windowHeight INTEGER;"
+    ))
+}
+
+fn create_encounters_table(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    CREATE TABLE IF NOT EXISTS {ENCOUNTERS_TABLE_NAME} (
+        userId INTEGER NOT NULL,
+        entryKey VARCHAR(255) NOT NULL,
+        PRIMARY KEY (userId, entryKey)
+    );"
+    ))
+}
+
+fn add_language_option(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN language VARCHAR(255) NOT NULL DEFAULT 'en';"
+    ))
+}
+
+fn add_accessibility_options(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(&format!(
+        "
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN colorPalette VARCHAR(255) NOT NULL DEFAULT 'Standard';
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN reducedMotion BOOLEAN NOT NULL DEFAULT FALSE;
+    ALTER TABLE {OPTIONS_TABLE_NAME}
+        ADD COLUMN uiScale REAL NOT NULL DEFAULT 1.0;"
+    ))
+}
+
+fn table_exists(conn: &Connection, table_name: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+        [table_name],
+        |row| row.get::<usize, usize>(0),
+    )
+    .map(|count| count > 0)
+}
+
+/// Databases created before the schema was versioned only have the tables of the first migration
+fn get_unversioned_schema_version(conn: &Connection) -> Result<usize> {
+    Ok(if table_exists(conn, USERSTAT)? { 1 } else { 0 })
+}
+
+/// Returns the version of the database's schema, `0` for a new database
+pub(super) fn get_schema_version(conn: &Connection) -> Result<usize> {
+    if !table_exists(conn, SCHEMA_VERSION_TABLE_NAME)? {
+        return get_unversioned_schema_version(conn);
+    }
+
+    conn.query_row(
+        &format!("SELECT version FROM {SCHEMA_VERSION_TABLE_NAME} WHERE id=0"),
+        [],
+        |row| row.get::<usize, usize>(0),
+    )
+    .optional()
+    .map(|version| version.unwrap_or_default())
+}
+
+fn set_schema_version(tx: &Transaction, version: usize) -> Result<()> {
+    tx.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {SCHEMA_VERSION_TABLE_NAME} (
+            id INTEGER PRIMARY KEY CHECK (id = 0),
+            version INTEGER NOT NULL
+        )"
+        ),
+        [],
+    )?;
+    tx.execute(
+        &format!("INSERT OR REPLACE INTO {SCHEMA_VERSION_TABLE_NAME} (id, version) VALUES (0, ?1)"),
+        [version],
+    )?;
+    Ok(())
+}
+
+/// Applies every migration the database has not had yet, in order. Each migration is applied in
+/// its own transaction along with the version bump, so a failed migration can be retried at the
+/// next launch. Returns the schema version after migrating.
+pub(super) fn run_migrations(conn: &mut Connection) -> Result<usize> {
+    let current_version = get_schema_version(conn)?;
+
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+        let tx = conn.transaction()?;
+        migration(&tx)?;
+        set_schema_version(&tx, version + 1)?;
+        tx.commit()?;
+        info!("Migrated db schema to version {}", version + 1);
+    }
+
+    Ok(current_version.max(LATEST_SCHEMA_VERSION))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_schema_version, run_migrations, LATEST_SCHEMA_VERSION, MIGRATIONS,
+        SCHEMA_VERSION_TABLE_NAME,
+    };
+    use crate::core::{OPTIONS_TABLE_NAME, PROFILES_TABLE_NAME, USERSTAT};
+    use rusqlite::Connection;
+
+    /// Creates a database that went through the first `version` migrations. Unversioned databases
+    /// are made like the ones created before the schema had a version.
+    fn db_at_schema_version(version: usize, versioned: bool) -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        for migration in MIGRATIONS.iter().take(version) {
+            let tx = conn.transaction().unwrap();
+            migration(&tx).unwrap();
+            tx.commit().unwrap();
+        }
+        if versioned {
+            let tx = conn.transaction().unwrap();
+            super::set_schema_version(&tx, version).unwrap();
+            tx.commit().unwrap();
+        }
+        conn
+    }
+
+    /// Every table with its columns' names, types, constraints and defaults
+    fn get_schema(conn: &Connection) -> Vec<(String, Vec<String>)> {
+        let mut statement = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
+            .unwrap();
+        let table_names: Vec<String> = statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        table_names
+            .into_iter()
+            .map(|table_name| {
+                let mut statement = conn
+                    .prepare(&format!(
+                        "SELECT name, type, \"notnull\", dflt_value, pk
+                        FROM pragma_table_info('{table_name}') ORDER BY name"
+                    ))
+                    .unwrap();
+                let columns = statement
+                    .query_map([], |row| {
+                        Ok(format!(
+                            "{} {} {} {:?} {}",
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, bool>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                            row.get::<_, usize>(4)?,
+                        ))
+                    })
+                    .unwrap()
+                    .collect::<Result<_, _>>()
+                    .unwrap();
+                (table_name, columns)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_upgrade_from_every_old_schema() {
+        let mut new_conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut new_conn).unwrap();
+        let latest_schema = get_schema(&new_conn);
+
+        // databases from before the schema was versioned only have the first migration's tables
+        let old_dbs = (0..=LATEST_SCHEMA_VERSION)
+            .map(|version| (version, true))
+            .chain([(0, false), (1, false)]);
+
+        for (version, versioned) in old_dbs {
+            let mut conn = db_at_schema_version(version, versioned);
+            assert_eq!(get_schema_version(&conn).unwrap(), version);
+
+            assert_eq!(run_migrations(&mut conn).unwrap(), LATEST_SCHEMA_VERSION);
+            assert_eq!(get_schema_version(&conn).unwrap(), LATEST_SCHEMA_VERSION);
+            assert_eq!(
+                get_schema(&conn),
+                latest_schema,
+                "upgrading from version {version} (versioned: {versioned})"
+            );
+
+            // running the migrations again is a no-op
+            assert_eq!(run_migrations(&mut conn).unwrap(), LATEST_SCHEMA_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_from_unversioned_db_keeps_saves() {
+        let mut conn = db_at_schema_version(1, false);
+        conn.execute(
+            &format!("INSERT INTO {USERSTAT} (userId, totalShotsFired) VALUES (0, 42)"),
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let total_shots_fired: usize = conn
+            .query_row(
+                &format!("SELECT totalShotsFired FROM {USERSTAT} WHERE userId=0"),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(total_shots_fired, 42);
+
+        // everything saved before profiles existed belongs to the default profile
+        let default_profile_name: String = conn
+            .query_row(
+                &format!("SELECT name FROM {PROFILES_TABLE_NAME} WHERE profileId=0"),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(default_profile_name, "Player 1");
+    }
+    #[test]
+    fn test_migrations_keep_saved_options() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn.execute(
+            &format!("UPDATE {OPTIONS_TABLE_NAME} SET bloomIntensity=0.5"),
+            [],
+        )
+        .unwrap();
+
+        // the next launch runs the migrations again
+        run_migrations(&mut conn).unwrap();
+
+        let bloom_intensity: f64 = conn
+            .query_row(
+                &format!("SELECT bloomIntensity FROM {OPTIONS_TABLE_NAME}"),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(bloom_intensity, 0.5);
+        assert_eq!(
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM {SCHEMA_VERSION_TABLE_NAME}"),
+                [],
+                |row| row.get::<usize, usize>(0),
+            )
+            .unwrap(),
+            1
+        );
+    }
+}
@@ -1,30 +1,123 @@
-use crate::core::{get_db, OurDBError, OPTIONS_TABLE_NAME};
+use crate::backend::StorageError;
+use crate::core::{get_db, OPTIONS_TABLE_NAME};
 use bevy::log::error;
-use rusqlite::Result;
+use rusqlite::{params, Result};
 
-use thetawave_interface::game::options::GameOptions;
+use thetawave_interface::game::options::{ColorPalette, DisplayMode, GameOptions};
+use thetawave_interface::localization::Language;
 
-fn _get_game_options(options_profile_id: usize) -> Result<Option<GameOptions>, OurDBError> {
+fn display_mode_to_db_str(display_mode: &DisplayMode) -> &'static str {
+    match display_mode {
+        DisplayMode::Windowed => "Windowed",
+        DisplayMode::BorderlessFullscreen => "BorderlessFullscreen",
+    }
+}
+
+fn display_mode_from_db_str(display_mode: &str) -> Result<DisplayMode, StorageError> {
+    match display_mode {
+        "Windowed" => Ok(DisplayMode::Windowed),
+        "BorderlessFullscreen" => Ok(DisplayMode::BorderlessFullscreen),
+        _ => Err(StorageError::InternalError(format!(
+            "Unknown display mode in db: {display_mode}"
+        ))),
+    }
+}
+
+fn color_palette_to_db_str(color_palette: &ColorPalette) -> &'static str {
+    match color_palette {
+        ColorPalette::Standard => "Standard",
+        ColorPalette::Deuteranopia => "Deuteranopia",
+        ColorPalette::Protanopia => "Protanopia",
+        ColorPalette::Tritanopia => "Tritanopia",
+    }
+}
+
+fn color_palette_from_db_str(color_palette: &str) -> Result<ColorPalette, StorageError> {
+    match color_palette {
+        "Standard" => Ok(ColorPalette::Standard),
+        "Deuteranopia" => Ok(ColorPalette::Deuteranopia),
+        "Protanopia" => Ok(ColorPalette::Protanopia),
+        "Tritanopia" => Ok(ColorPalette::Tritanopia),
+        _ => Err(StorageError::InternalError(format!(
+            "Unknown color palette in db: {color_palette}"
+        ))),
+    }
+}
+
+fn language_from_db_str(language: &str) -> Result<Language, StorageError> {
+    Language::from_code(language)
+        .ok_or_else(|| StorageError::InternalError(format!("Unknown language in db: {language}")))
+}
+
+pub(super) fn set_game_options(
+    options_profile_id: usize,
+    game_options: &GameOptions,
+) -> Result<(), StorageError> {
+    let conn = get_db()?;
+    let stmt_raw = format!(
+        "
+    INSERT OR REPLACE INTO {OPTIONS_TABLE_NAME} (
+        optionsProfileId, bloomEnabled, bloomIntensity, tutorialsEnabled, musicVolume,
+        soundEffectsVolume, menuVolume, screenShakeIntensity, displayMode, keyBindings,
+        windowWidth, windowHeight, language, colorPalette, reducedMotion, uiScale
+    )
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+    );
+    conn.prepare(&stmt_raw)?.execute(params![
+        options_profile_id,
+        game_options.bloom_enabled,
+        game_options.bloom_intensity,
+        game_options.tutorials_enabled,
+        game_options.music_volume,
+        game_options.sound_effects_volume,
+        game_options.menu_volume,
+        game_options.screen_shake_intensity,
+        display_mode_to_db_str(&game_options.display_mode),
+        game_options.key_bindings,
+        game_options.window_resolution.map(|(width, _)| width),
+        game_options.window_resolution.map(|(_, height)| height),
+        game_options.language.code(),
+        color_palette_to_db_str(&game_options.color_palette),
+        game_options.reduced_motion,
+        game_options.ui_scale,
+    ])?;
+    Ok(())
+}
+
+pub(super) fn _get_game_options(
+    options_profile_id: usize,
+) -> Result<Option<GameOptions>, StorageError> {
     let conn = get_db()?;
     let stmt_raw = format!(
         "
-    SELECT bloomEnabled, bloomIntensity, tutorialsEnabled FROM {OPTIONS_TABLE_NAME}
+    SELECT bloomEnabled, bloomIntensity, tutorialsEnabled, musicVolume, soundEffectsVolume,
+        menuVolume, screenShakeIntensity, displayMode, keyBindings, windowWidth, windowHeight,
+        language, colorPalette, reducedMotion, uiScale
+    FROM {OPTIONS_TABLE_NAME}
     WHERE optionsProfileId=?1
         "
     );
     let mut stmt = conn.prepare(&stmt_raw)?;
     let mut rows = stmt.query([options_profile_id])?;
     match rows.next()? {
-        Some(r) => {
-            let bloom_enabled = r.get(0)?;
-            let bloom_intensity = r.get(1)?;
-            let tutorials_enabled = r.get(2)?;
-            Ok(Some(GameOptions {
-                bloom_enabled,
-                bloom_intensity,
-                tutorials_enabled,
-            }))
-        }
+        Some(r) => Ok(Some(GameOptions {
+            bloom_enabled: r.get(0)?,
+            bloom_intensity: r.get(1)?,
+            tutorials_enabled: r.get(2)?,
+            music_volume: r.get(3)?,
+            sound_effects_volume: r.get(4)?,
+            menu_volume: r.get(5)?,
+            screen_shake_intensity: r.get(6)?,
+            display_mode: display_mode_from_db_str(&r.get::<usize, String>(7)?)?,
+            key_bindings: r.get(8)?,
+            window_resolution: r
+                .get::<usize, Option<u32>>(9)?
+                .zip(r.get::<usize, Option<u32>>(10)?),
+            language: language_from_db_str(&r.get::<usize, String>(11)?)?,
+            color_palette: color_palette_from_db_str(&r.get::<usize, String>(12)?)?,
+            reduced_motion: r.get(13)?,
+            ui_scale: r.get(14)?,
+        })),
 
         None => Ok(None),
     }
@@ -1,81 +1,351 @@
 use bevy::app::{App, Plugin};
 use bevy::log::{error, info, warn};
-use bevy::prelude::{Res, ResMut};
+use bevy::prelude::{resource_changed, EventReader, IntoSystemConfigs, Res, ResMut, Update};
 use bevy::state::state::{OnEnter, OnExit};
 /// Exposes a single Plugin that links the game and our persistence layer.
-use thetawave_interface::game::options::{GameOptions, DEFAULT_OPTIONS_PROFILE_ID};
+use thetawave_interface::game::options::{GameOptions, GameOptionsChangedEvent};
 
-use crate::options::get_game_options;
-use crate::user_stats::{
-    get_mob_killed_counts_for_user, get_user_stats, set_user_stats_for_user_id,
-};
+use crate::backend::{StorageBackendType, StorageError, StorageResource};
+use crate::memory::MemoryBackend;
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+use thetawave_interface::game::compendium::EncountersResource;
 use thetawave_interface::game::historical_metrics::{
+    LevelStatsByPlayerCacheT, LevelStatsByPlayerForCompletedGames,
     MobKillsByPlayerForCompletedGames, MobsKilledByPlayerCacheT, UserStatsByPlayerCacheT,
     UserStatsByPlayerForCompletedGamesCache, DEFAULT_USER_ID,
 };
+use thetawave_interface::game::profiles::{ProfileEvent, ProfilesResource};
+use thetawave_interface::game::run_history::{
+    CompletedRunResource, LeaderboardsResource, RunHistoryResource, LEADERBOARD_SIZE,
+    RECENT_RUNS_LIMIT,
+};
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
 use thetawave_interface::states;
 
-use super::core::{get_db, setup_db};
-use super::user_stats::set_mob_killed_count_for_user;
-
-/// Persist some user-specific stats and game state to a local SQLite database.
+/// Persist some user-specific stats and game state to the storage backend chosen at startup.
 pub struct DBPlugin;
 
+/// Logs a failed read, falling back to a default so that broken saves never stop the game
+fn or_default_on_error<T: Default>(result: Result<T, StorageError>, what: &str) -> T {
+    result.unwrap_or_else(|e| {
+        error!("Failed to read {what} from storage. Default fallback. {e}");
+        T::default()
+    })
+}
+
 fn flush_user_stats_for_completed_games_to_db(
+    storage: Res<StorageResource>,
     shot_counters_for_current_game: Res<UserStatsByPlayerForCompletedGamesCache>,
 ) {
-    if let Some(user_stats) = (**shot_counters_for_current_game).get(&DEFAULT_USER_ID) {
-        set_user_stats_for_user_id(DEFAULT_USER_ID, user_stats).unwrap_or_else(|e| {
-            error!(
-                "Failed to flush per-run/game metrics to the database. Skipping. {}",
-                e
-            )
-        });
+    for (user_id, user_stats) in (**shot_counters_for_current_game).iter() {
+        storage
+            .set_user_stats(*user_id, user_stats)
+            .unwrap_or_else(|e| {
+                error!(
+                    "Failed to flush per-run/game metrics to the database. Skipping. {}",
+                    e
+                )
+            });
     }
 }
 fn flush_mobs_killed_for_completed_games_counters_to_db(
+    storage: Res<StorageResource>,
     mobs_killed_for_current_game: Res<MobKillsByPlayerForCompletedGames>,
 ) {
     info!(
         "Flushing mob kills to db {:?}",
         **mobs_killed_for_current_game
     );
-    if let Some(mob_kills) = (**mobs_killed_for_current_game).get(&DEFAULT_USER_ID) {
-        for (mob_type, n_killed) in mob_kills {
-            set_mob_killed_count_for_user(DEFAULT_USER_ID, &mob_type, n_killed.clone())
-                .unwrap_or_else(|e| error!("Error incrementing mob kill count: {e}"));
-        }
+    for (user_id, mob_kills) in (**mobs_killed_for_current_game).iter() {
+        storage
+            .set_mob_kills(*user_id, mob_kills)
+            .unwrap_or_else(|e| error!("Error incrementing mob kill count: {e}"));
+    }
+}
+fn flush_level_stats_for_completed_games_to_db(
+    storage: Res<StorageResource>,
+    level_stats: Res<LevelStatsByPlayerForCompletedGames>,
+) {
+    for (user_id, user_level_stats) in (**level_stats).iter() {
+        storage
+            .set_level_stats(*user_id, user_level_stats)
+            .unwrap_or_else(|e| error!("Error saving level stats: {e}"));
+    }
+}
+fn flush_character_unlocks_to_db(
+    storage: Res<StorageResource>,
+    character_unlocks: Res<CharacterUnlocksResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    storage
+        .set_character_unlocks(profiles.active_profile_id, &character_unlocks)
+        .unwrap_or_else(|e| error!("Failed to flush character unlocks to the database. {e}"));
+}
+fn flush_achievements_to_db(
+    storage: Res<StorageResource>,
+    achievements: Res<AchievementsProgressResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    storage
+        .set_achievements(profiles.active_profile_id, &achievements)
+        .unwrap_or_else(|e| error!("Failed to flush achievements to the database. {e}"));
+}
+fn flush_encounters_to_db(
+    storage: Res<StorageResource>,
+    encounters: Res<EncountersResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    storage
+        .add_encounters(profiles.active_profile_id, &encounters)
+        .unwrap_or_else(|e| error!("Failed to flush encounters to the database. {e}"));
+}
+fn flush_completed_run_to_db(
+    storage: Res<StorageResource>,
+    mut completed_run: ResMut<CompletedRunResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    if let Some(run) = completed_run.take() {
+        storage
+            .add_run(profiles.active_profile_id, &run)
+            .unwrap_or_else(|e| error!("Failed to save the completed run to the database. {e}"));
+    }
+}
+fn save_changed_game_options_system(
+    storage: Res<StorageResource>,
+    mut game_options_changed_event_reader: EventReader<GameOptionsChangedEvent>,
+    profiles: Res<ProfilesResource>,
+) {
+    // only the newest options need saving
+    if let Some(GameOptionsChangedEvent(game_options)) =
+        game_options_changed_event_reader.read().last()
+    {
+        storage
+            .set_game_options(profiles.active_profile_id, game_options)
+            .unwrap_or_else(|e| error!("Failed to save game options to the database. {e}"));
+    }
+}
+/// Saves changes to profiles and selects the profile that was created or selected. The cached
+/// stats of a deleted profile are dropped so they aren't flushed back to the db.
+fn apply_profile_events_system(
+    storage: Res<StorageResource>,
+    mut profile_event_reader: EventReader<ProfileEvent>,
+    mut profiles: ResMut<ProfilesResource>,
+    mut user_stats_cache: ResMut<UserStatsByPlayerForCompletedGamesCache>,
+    mut mob_kills_cache: ResMut<MobKillsByPlayerForCompletedGames>,
+    mut level_stats_cache: ResMut<LevelStatsByPlayerForCompletedGames>,
+) {
+    for event in profile_event_reader.read() {
+        let selected_profile_id = match event {
+            ProfileEvent::Create(name) => {
+                storage.create_profile(name).map(Some).unwrap_or_else(|e| {
+                    error!("Failed to create profile. {e}");
+                    None
+                })
+            }
+            ProfileEvent::Select(profile_id) => storage
+                .set_last_used_profile(*profile_id)
+                .map(|_| Some(*profile_id))
+                .unwrap_or_else(|e| {
+                    error!("Failed to select profile. {e}");
+                    None
+                }),
+            ProfileEvent::Delete(profile_id) => {
+                match storage.delete_profile(*profile_id) {
+                    Ok(()) => {
+                        user_stats_cache.remove(profile_id);
+                        mob_kills_cache.remove(profile_id);
+                        level_stats_cache.remove(profile_id);
+                    }
+                    Err(e) => error!("Failed to delete profile. {e}"),
+                }
+                None
+            }
+        };
+
+        *profiles = ProfilesResource {
+            profiles: or_default_on_error(storage.get_profiles(), "profiles"),
+            active_profile_id: selected_profile_id
+                .unwrap_or_else(|| get_last_used_profile_id(&storage)),
+        };
     }
 }
 impl Plugin for DBPlugin {
     fn build(&self, app: &mut App) {
+        let backend_type = StorageBackendType::from_env();
+        info!("Using the {backend_type:?} storage backend");
+        let backend = backend_type.new_backend().unwrap_or_else(|e| {
+            error!("Failed to open the {backend_type:?} storage backend. Saves will only last until the game is closed. {e}");
+            Box::new(MemoryBackend::default())
+        });
+        app.insert_resource(StorageResource(backend));
+
         app.add_systems(OnEnter(states::AppStates::LoadingAssets), db_setup_system);
         app.add_systems(
             OnExit(states::AppStates::LoadingAssets),
             (
-                load_user_stats_cache_from_db,
-                load_mob_kills_cache_from_db,
-                load_game_options_from_db,
-            ),
+                load_profiles_from_db,
+                (
+                    load_user_stats_cache_from_db,
+                    load_mob_kills_cache_from_db,
+                    load_level_stats_cache_from_db,
+                    load_game_options_from_db,
+                    load_character_unlocks_from_db,
+                    load_achievements_from_db,
+                    load_encounters_from_db,
+                    load_run_history_from_db,
+                    load_leaderboards_from_db,
+                ),
+            )
+                .chain(),
         );
+        // everything saved for the active profile is reloaded when another profile is selected
+        app.add_systems(
+            Update,
+            (
+                save_changed_game_options_system,
+                apply_profile_events_system,
+                (
+                    load_game_options_from_db,
+                    load_character_unlocks_from_db,
+                    load_achievements_from_db,
+                    load_encounters_from_db,
+                    load_run_history_from_db,
+                )
+                    .run_if(resource_changed::<ProfilesResource>),
+            )
+                .chain(),
+        );
+        // stats, unlocks and the run itself are saved however the run ended. Stats are kept for
+        // every joined profile, everything else for the active profile, which is the first
+        // player's.
         app.add_systems(
             OnExit(states::AppStates::GameOver),
             (
                 flush_user_stats_for_completed_games_to_db,
                 flush_mobs_killed_for_completed_games_counters_to_db,
+                flush_level_stats_for_completed_games_to_db,
+                flush_character_unlocks_to_db,
+                flush_achievements_to_db,
+                flush_encounters_to_db,
+                (
+                    flush_completed_run_to_db,
+                    (load_run_history_from_db, load_leaderboards_from_db),
+                )
+                    .chain(),
+            ),
+        );
+        app.add_systems(
+            OnExit(states::AppStates::Victory),
+            (
+                flush_user_stats_for_completed_games_to_db,
+                flush_mobs_killed_for_completed_games_counters_to_db,
+                flush_level_stats_for_completed_games_to_db,
+                flush_character_unlocks_to_db,
+                flush_achievements_to_db,
+                flush_encounters_to_db,
+                (
+                    flush_completed_run_to_db,
+                    (load_run_history_from_db, load_leaderboards_from_db),
+                )
+                    .chain(),
             ),
         );
+        // unlocks can also be bought in character selection
+        app.add_systems(
+            OnExit(states::AppStates::CharacterSelection),
+            flush_character_unlocks_to_db,
+        );
     }
 }
 
-fn load_game_options_from_db(mut game_options: ResMut<GameOptions>) {
-    if let Some(db_game_options) = get_game_options(DEFAULT_OPTIONS_PROFILE_ID) {
+/// The last used profile, falling back to the default profile
+fn get_last_used_profile_id(storage: &StorageResource) -> usize {
+    or_default_on_error(storage.get_last_used_profile_id(), "the last used profile")
+        .unwrap_or(DEFAULT_USER_ID)
+}
+
+fn load_profiles_from_db(storage: Res<StorageResource>, mut profiles: ResMut<ProfilesResource>) {
+    *profiles = ProfilesResource {
+        profiles: or_default_on_error(storage.get_profiles(), "profiles"),
+        active_profile_id: get_last_used_profile_id(&storage),
+    };
+}
+
+fn load_game_options_from_db(
+    storage: Res<StorageResource>,
+    mut game_options: ResMut<GameOptions>,
+    profiles: Res<ProfilesResource>,
+) {
+    if let Some(db_game_options) = or_default_on_error(
+        storage.get_game_options(profiles.active_profile_id),
+        "game options",
+    ) {
         *game_options = db_game_options;
     }
 }
 
+fn load_character_unlocks_from_db(
+    storage: Res<StorageResource>,
+    mut character_unlocks: ResMut<CharacterUnlocksResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    *character_unlocks = or_default_on_error(
+        storage.get_character_unlocks(profiles.active_profile_id),
+        "character unlocks",
+    );
+}
+
+fn load_achievements_from_db(
+    storage: Res<StorageResource>,
+    mut achievements: ResMut<AchievementsProgressResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    *achievements = or_default_on_error(
+        storage.get_achievements(profiles.active_profile_id),
+        "achievements",
+    );
+}
+
+fn load_encounters_from_db(
+    storage: Res<StorageResource>,
+    mut encounters: ResMut<EncountersResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    *encounters = or_default_on_error(
+        storage.get_encounters(profiles.active_profile_id),
+        "encounters",
+    );
+}
+
+fn load_run_history_from_db(
+    storage: Res<StorageResource>,
+    mut run_history: ResMut<RunHistoryResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    *run_history = RunHistoryResource {
+        recent_runs: or_default_on_error(
+            storage.get_recent_runs(profiles.active_profile_id, RECENT_RUNS_LIMIT),
+            "recent runs",
+        ),
+        personal_bests: or_default_on_error(
+            storage.get_personal_bests(profiles.active_profile_id),
+            "personal bests",
+        ),
+    };
+}
+
+fn load_leaderboards_from_db(
+    storage: Res<StorageResource>,
+    mut leaderboards: ResMut<LeaderboardsResource>,
+) {
+    **leaderboards =
+        or_default_on_error(storage.get_leaderboards(LEADERBOARD_SIZE), "leaderboards");
+}
+
 fn load_user_stats_cache_from_db(
+    storage: Res<StorageResource>,
     mut user_stats_cache: ResMut<UserStatsByPlayerForCompletedGamesCache>,
+    profiles: Res<ProfilesResource>,
 ) {
     if !user_stats_cache.is_empty() {
         warn!(
@@ -83,80 +353,161 @@ fn load_user_stats_cache_from_db(
             user_stats_cache
         );
     }
-    **user_stats_cache =
-        UserStatsByPlayerCacheT::from([(0, get_user_stats(DEFAULT_USER_ID).unwrap_or_default())]);
+    **user_stats_cache = profiles
+        .profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.id,
+                or_default_on_error(storage.get_user_stats(profile.id), "user stats")
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<UserStatsByPlayerCacheT>();
 }
-fn load_mob_kills_cache_from_db(mut mob_kills_cache: ResMut<MobKillsByPlayerForCompletedGames>) {
+fn load_mob_kills_cache_from_db(
+    storage: Res<StorageResource>,
+    mut mob_kills_cache: ResMut<MobKillsByPlayerForCompletedGames>,
+    profiles: Res<ProfilesResource>,
+) {
     if !(**mob_kills_cache).is_empty() {
         warn!(
             "evicting data from the in-memory mob kills cache. Is this right? {:?}",
             mob_kills_cache
         );
     }
-    (**mob_kills_cache) =
-        MobsKilledByPlayerCacheT::from([(0, get_mob_killed_counts_for_user(DEFAULT_USER_ID))]);
+    (**mob_kills_cache) = profiles
+        .profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.id,
+                or_default_on_error(storage.get_mob_kills(profile.id), "mob kill counts"),
+            )
+        })
+        .collect::<MobsKilledByPlayerCacheT>();
 }
-fn db_setup_system() {
-    match get_db() {
-        Ok(conn) => setup_db(conn).unwrap_or_else(|e| {
-            error!("{e}");
-        }),
-        Err(e) => {
-            error!("{e}");
-        }
-    };
+fn load_level_stats_cache_from_db(
+    storage: Res<StorageResource>,
+    mut level_stats_cache: ResMut<LevelStatsByPlayerForCompletedGames>,
+    profiles: Res<ProfilesResource>,
+) {
+    **level_stats_cache = profiles
+        .profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.id,
+                or_default_on_error(storage.get_level_stats(profile.id), "level stats"),
+            )
+        })
+        .collect::<LevelStatsByPlayerCacheT>();
+}
+fn db_setup_system(storage: Res<StorageResource>) {
+    storage.setup().unwrap_or_else(|e| {
+        error!("{e}");
+    });
 }
 
 #[cfg(test)]
 mod test {
-    use crate::core::THETAWAVE_DB_PATH_ENVVAR;
+    use crate::achievements::{get_achievements_for_user, set_achievements_for_user};
+    use crate::core::{run_with_patched_env, THETAWAVE_DB_PATH_ENVVAR};
+    use crate::options::get_game_options;
     use crate::plugin::DBPlugin;
-    use crate::user_stats::{get_mob_killed_counts_for_user, get_user_stats};
+    use crate::profiles::{create_profile, delete_profile, get_profiles};
+    use crate::run_history::{
+        add_run_for_user, get_leaderboards, get_personal_bests_for_user, get_recent_runs_for_user,
+    };
+    use crate::unlocks::{get_character_unlocks_for_user, set_character_unlocks_for_user};
+    use crate::user_stats::{
+        get_level_stats_for_user, get_mob_killed_counts_for_user, get_user_stats,
+        set_user_stats_for_user_id,
+    };
     use bevy::log::{Level, LogPlugin};
-    use bevy::prelude::{default, App, NextState, OnEnter, ResMut};
+    use bevy::prelude::{default, App, Events, NextState, OnEnter, ResMut};
     use bevy::state::app::{AppExtStates, StatesPlugin};
     use bevy::MinimalPlugins;
-    use std::ffi::{OsStr, OsString};
+    use std::ffi::OsString;
     use tempdir;
+    use thetawave_interface::character::CharacterType;
+    use thetawave_interface::game::achievements::AchievementsProgressResource;
+    use thetawave_interface::game::compendium::EncountersResource;
     use thetawave_interface::game::historical_metrics::{
+        LevelStat, LevelStatsBy1PlayerCacheT, LevelStatsByPlayerForCompletedGames,
         MobKillsByPlayerForCompletedGames, MobsKilledBy1PlayerCacheT, MobsKilledByPlayerCacheT,
         UserStat, UserStatsByPlayerForCompletedGamesCache, DEFAULT_USER_ID,
     };
-    use thetawave_interface::game::options::GameOptions;
+    use thetawave_interface::game::options::{
+        ColorPalette, DisplayMode, GameOptions, GameOptionsChangedEvent,
+    };
+    use thetawave_interface::game::profiles::{Profile, ProfileEvent, ProfilesResource};
+    use thetawave_interface::game::run_history::{
+        CompletedRunResource, Leaderboard, LeaderboardsResource, PersonalBests, RunHistoryResource,
+        RunRecord,
+    };
+    use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+    use thetawave_interface::localization::Language;
+    use thetawave_interface::run::{DifficultyType, RunDefeatType, RunOutcomeType};
     use thetawave_interface::spawnable::EnemyMobType;
     use thetawave_interface::states::AppStates;
 
-    fn run_with_patched_env<T, V>(test: T, env_vars: Vec<(V, V)>)
-    where
-        T: FnOnce() -> () + std::panic::UnwindSafe,
-        V: AsRef<OsStr>,
-    {
-        let old_env_vars: Vec<(OsString, OsString)> = env_vars
-            .iter()
-            .map(|(k, _)| (OsString::from(k), std::env::var_os(k).unwrap_or_default()))
-            .collect();
-        for (k, v) in env_vars.iter() {
-            std::env::set_var(k, v);
-        }
+    #[test]
+    fn test_recover_resources_from_db_after_mock_program_restart() {
+        // Use temp paths for an ephemeral db an isolated, reproducible tests
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_can_flush_caches_to_db,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    #[test]
+    fn test_character_unlocks_round_trip_through_db() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_character_unlocks_round_trip_through_db,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    fn _test_character_unlocks_round_trip_through_db() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
 
-        let result = std::panic::catch_unwind(test);
+        assert_eq!(
+            get_character_unlocks_for_user(DEFAULT_USER_ID),
+            CharacterUnlocksResource::default()
+        );
 
-        for (k, v) in old_env_vars.iter() {
-            std::env::set_var(k, v);
-        }
-        if let Err(err) = result {
-            std::panic::resume_unwind(err);
-        }
+        let unlocks = CharacterUnlocksResource {
+            unlocked_characters: [CharacterType::from("juggernaut")].into(),
+            meta_currency: 40,
+        };
+        set_character_unlocks_for_user(DEFAULT_USER_ID, &unlocks).unwrap();
+        // writing the same unlocks again should not fail on the primary key
+        set_character_unlocks_for_user(DEFAULT_USER_ID, &unlocks).unwrap();
+
+        assert_eq!(get_character_unlocks_for_user(DEFAULT_USER_ID), unlocks);
     }
 
     #[test]
-    fn test_recover_resources_from_db_after_mock_program_restart() {
-        // Use temp paths for an ephemeral db an isolated, reproducible tests
+    fn test_achievements_round_trip_through_db() {
         let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
         let temp_file_path = base_path.path().join("thetawave_test.sqlite");
 
         run_with_patched_env(
-            _test_can_flush_caches_to_db,
+            _test_achievements_round_trip_through_db,
             vec![(
                 OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
                 OsString::from(temp_file_path),
@@ -164,6 +515,309 @@ mod test {
         )
     }
 
+    fn _test_achievements_round_trip_through_db() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
+
+        assert_eq!(
+            get_achievements_for_user(DEFAULT_USER_ID),
+            AchievementsProgressResource::default()
+        );
+
+        let mut achievements = AchievementsProgressResource {
+            progress: [
+                ("drone_hunter".to_string(), 40),
+                ("first_victory".to_string(), 1),
+            ]
+            .into(),
+            unlocked: ["first_victory".to_string()].into(),
+        };
+        set_achievements_for_user(DEFAULT_USER_ID, &achievements).unwrap();
+        assert_eq!(get_achievements_for_user(DEFAULT_USER_ID), achievements);
+
+        // progress is overwritten rather than added to
+        achievements.progress.insert("drone_hunter".to_string(), 55);
+        set_achievements_for_user(DEFAULT_USER_ID, &achievements).unwrap();
+        assert_eq!(get_achievements_for_user(DEFAULT_USER_ID), achievements);
+    }
+
+    #[test]
+    fn test_changed_game_options_are_saved() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_changed_game_options_are_saved,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    fn _test_changed_game_options_are_saved() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
+
+        assert_eq!(
+            get_game_options(DEFAULT_USER_ID),
+            Some(GameOptions {
+                bloom_enabled: true,
+                bloom_intensity: 1.0,
+                ..Default::default()
+            })
+        );
+
+        // the bloom intensity is kept while bloom is disabled
+        let game_options = GameOptions {
+            bloom_enabled: false,
+            bloom_intensity: 0.5,
+            tutorials_enabled: true,
+            music_volume: 0.25,
+            sound_effects_volume: 0.75,
+            menu_volume: 0.0,
+            screen_shake_intensity: 0.5,
+            display_mode: DisplayMode::BorderlessFullscreen,
+            window_resolution: Some((1920, 1080)),
+            key_bindings: Some("(menu_keyboard: [])".to_string()),
+            language: Language::Spanish,
+            color_palette: ColorPalette::Deuteranopia,
+            reduced_motion: true,
+            ui_scale: 1.5,
+        };
+        app.world_mut()
+            .resource_mut::<Events<GameOptionsChangedEvent>>()
+            .send(GameOptionsChangedEvent(game_options.clone()));
+        app.update();
+
+        assert_eq!(get_game_options(DEFAULT_USER_ID), Some(game_options));
+    }
+
+    #[test]
+    fn test_run_history_round_trip_through_db() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_run_history_round_trip_through_db,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    fn _test_run_history_round_trip_through_db() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
+
+        assert_eq!(
+            get_personal_bests_for_user(DEFAULT_USER_ID),
+            PersonalBests::default()
+        );
+
+        let defeat = RunRecord {
+            start_time: Some(1_000),
+            duration_secs: 120.0,
+            characters: vec![
+                CharacterType::from("captain"),
+                CharacterType::from("juggernaut"),
+            ],
+            run_key: "test_run".to_string(),
+            difficulty: DifficultyType::Normal,
+            levels_completed: 1,
+            outcome: RunOutcomeType::Defeat(RunDefeatType::DefenseDestroyed),
+            money_collected: 30,
+            damage_taken: 200,
+            seed: u64::MAX,
+            score: 12_000,
+        };
+        let victory = RunRecord {
+            start_time: Some(2_000),
+            duration_secs: 300.0,
+            characters: vec![CharacterType::from("captain")],
+            levels_completed: 3,
+            outcome: RunOutcomeType::Victory,
+            money_collected: 20,
+            seed: 7,
+            score: 9_000,
+            ..defeat.clone()
+        };
+        let hard_defeat = RunRecord {
+            start_time: Some(500),
+            difficulty: DifficultyType::Hard,
+            score: 1_000,
+            ..defeat.clone()
+        };
+        add_run_for_user(DEFAULT_USER_ID, &defeat).unwrap();
+        add_run_for_user(DEFAULT_USER_ID, &victory).unwrap();
+        add_run_for_user(DEFAULT_USER_ID, &hard_defeat).unwrap();
+
+        assert_eq!(
+            get_recent_runs_for_user(DEFAULT_USER_ID, 10),
+            vec![victory.clone(), defeat.clone(), hard_defeat.clone()]
+        );
+        assert_eq!(get_recent_runs_for_user(DEFAULT_USER_ID, 1), vec![victory]);
+        assert_eq!(
+            get_personal_bests_for_user(DEFAULT_USER_ID),
+            PersonalBests {
+                total_runs: 3,
+                most_levels_completed: 3,
+                longest_run_secs: 300.0,
+                most_money_collected: 30,
+                fastest_victory_secs: Some(300.0),
+                favorite_character: Some(CharacterType::from("captain")),
+                best_run: Some(defeat.clone()),
+            }
+        );
+        // leaderboards are split by run mode and sorted by score
+        assert_eq!(
+            get_leaderboards(1),
+            vec![
+                Leaderboard {
+                    run_key: "test_run".to_string(),
+                    difficulty: DifficultyType::Hard,
+                    runs: vec![hard_defeat],
+                },
+                Leaderboard {
+                    run_key: "test_run".to_string(),
+                    difficulty: DifficultyType::Normal,
+                    runs: vec![defeat],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profiles_keep_their_saves_separate() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_profiles_keep_their_saves_separate,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    fn _test_profiles_keep_their_saves_separate() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
+
+        let unlocks = CharacterUnlocksResource {
+            unlocked_characters: [CharacterType::from("juggernaut")].into(),
+            meta_currency: 40,
+        };
+        set_character_unlocks_for_user(DEFAULT_USER_ID, &unlocks).unwrap();
+
+        // selecting a new profile loads its (empty) saves
+        app.world_mut()
+            .resource_mut::<Events<ProfileEvent>>()
+            .send(ProfileEvent::Create("Ada".to_string()));
+        app.update();
+
+        let ada = get_profiles()
+            .into_iter()
+            .find(|profile| profile.name == "Ada")
+            .unwrap();
+        assert_eq!(
+            app.world().resource::<ProfilesResource>().active_profile_id,
+            ada.id
+        );
+        assert_eq!(
+            *app.world().resource::<CharacterUnlocksResource>(),
+            CharacterUnlocksResource::default()
+        );
+
+        // names are unique
+        assert!(create_profile("Ada").is_err());
+
+        set_user_stats_for_user_id(
+            ada.id,
+            &UserStat {
+                total_shots_fired: 3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // selecting the default profile again loads its saves
+        app.world_mut()
+            .resource_mut::<Events<ProfileEvent>>()
+            .send(ProfileEvent::Select(DEFAULT_USER_ID));
+        app.update();
+        assert_eq!(*app.world().resource::<CharacterUnlocksResource>(), unlocks);
+        assert_eq!(get_user_stats(DEFAULT_USER_ID), None);
+
+        // deleting a profile deletes its saves, but the last profile is kept
+        delete_profile(ada.id).unwrap();
+        assert_eq!(get_user_stats(ada.id), None);
+        assert_eq!(
+            get_profiles(),
+            vec![Profile {
+                id: DEFAULT_USER_ID,
+                name: "Player 1".to_string(),
+            }]
+        );
+        assert!(delete_profile(DEFAULT_USER_ID).is_err());
+    }
+
+    #[test]
+    fn test_victories_are_flushed_to_db() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let temp_file_path = base_path.path().join("thetawave_test.sqlite");
+
+        run_with_patched_env(
+            _test_victories_are_flushed_to_db,
+            vec![(
+                OsString::from(&THETAWAVE_DB_PATH_ENVVAR),
+                OsString::from(temp_file_path),
+            )],
+        )
+    }
+
+    fn _test_victories_are_flushed_to_db() {
+        let mut app = _minimal_app_for_db_plugin_tests();
+        app.update();
+
+        let user_stat = UserStat {
+            total_games_won: 1,
+            total_levels_completed: 3,
+            most_levels_completed: 3,
+            fastest_victory_millis: Some(90000),
+            ..Default::default()
+        };
+        let level_stats = LevelStatsBy1PlayerCacheT::from([(
+            "Earth".to_string(),
+            LevelStat {
+                times_reached: 1,
+                times_completed: 1,
+                best_time_millis: Some(30000),
+            },
+        )]);
+
+        // the caches are loaded from the db when leaving the loading state
+        app.world_mut()
+            .resource_mut::<NextState<AppStates>>()
+            .set(AppStates::Victory);
+        app.update();
+        app.world_mut()
+            .resource_mut::<UserStatsByPlayerForCompletedGamesCache>()
+            .insert(DEFAULT_USER_ID, user_stat.clone());
+        app.world_mut()
+            .resource_mut::<LevelStatsByPlayerForCompletedGames>()
+            .insert(DEFAULT_USER_ID, level_stats.clone());
+        app.world_mut()
+            .resource_mut::<NextState<AppStates>>()
+            .set(AppStates::MainMenu);
+        app.update();
+
+        assert_eq!(get_user_stats(DEFAULT_USER_ID), Some(user_stat));
+        assert_eq!(get_level_stats_for_user(DEFAULT_USER_ID), level_stats);
+    }
+
     fn set_loading_assets(mut s: ResMut<NextState<AppStates>>) {
         (*s).set(AppStates::LoadingAssets);
     }
@@ -207,6 +861,7 @@ mod test {
                 total_shots_fired: TOTAL_SHOTS_FIRED,
                 total_shots_hit: TOTAL_SHOTS_HIT,
                 total_games_lost: N_GAMES_LOST,
+                ..Default::default()
             },
         );
     }
@@ -222,7 +877,17 @@ mod test {
             })
             .insert_resource(MobKillsByPlayerForCompletedGames::default())
             .insert_resource(UserStatsByPlayerForCompletedGamesCache::default())
-            .insert_resource(GameOptions::default());
+            .insert_resource(LevelStatsByPlayerForCompletedGames::default())
+            .insert_resource(GameOptions::default())
+            .insert_resource(CharacterUnlocksResource::default())
+            .insert_resource(CompletedRunResource::default())
+            .insert_resource(RunHistoryResource::default())
+            .insert_resource(LeaderboardsResource::default())
+            .insert_resource(ProfilesResource::default())
+            .insert_resource(AchievementsProgressResource::default())
+            .insert_resource(EncountersResource::default())
+            .add_event::<ProfileEvent>()
+            .add_event::<GameOptionsChangedEvent>();
         app
     }
     fn _test_can_flush_caches_to_db() {
@@ -262,6 +927,7 @@ mod test {
                 total_shots_fired: TOTAL_SHOTS_FIRED,
                 total_shots_hit: TOTAL_SHOTS_HIT,
                 total_games_lost: N_GAMES_PLAYED,
+                ..Default::default()
             }
         );
         assert_eq!(
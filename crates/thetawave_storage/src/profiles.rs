@@ -0,0 +1,145 @@
+use crate::backend::{now_secs, validate_profile_name, StorageError};
+use crate::core::{
+    get_db, ACHIEVEMENTS_TABLE_NAME, CHARACTER_UNLOCKS_TABLE_NAME, ENCOUNTERS_TABLE_NAME,
+    ENEMY_KILL_HISTORY_TABLE_NAME, LEVEL_STATS_TABLE_NAME, META_CURRENCY_TABLE_NAME,
+    OPTIONS_TABLE_NAME, PROFILES_TABLE_NAME, RUN_HISTORY_TABLE_NAME, USERSTAT,
+};
+use bevy::log::error;
+use rusqlite::{params, OptionalExtension, Result, Transaction};
+use thetawave_interface::game::historical_metrics::DEFAULT_USER_ID;
+use thetawave_interface::game::profiles::Profile;
+
+/// Every table with rows saved for a profile, and the column holding the profile's id
+pub(super) const PROFILE_DATA_TABLES: [(&str, &str); 9] = [
+    (USERSTAT, "userId"),
+    (ENEMY_KILL_HISTORY_TABLE_NAME, "userId"),
+    (OPTIONS_TABLE_NAME, "optionsProfileId"),
+    (CHARACTER_UNLOCKS_TABLE_NAME, "userId"),
+    (META_CURRENCY_TABLE_NAME, "userId"),
+    (RUN_HISTORY_TABLE_NAME, "userId"),
+    (ACHIEVEMENTS_TABLE_NAME, "userId"),
+    (LEVEL_STATS_TABLE_NAME, "userId"),
+    (ENCOUNTERS_TABLE_NAME, "userId"),
+];
+
+/// Adds a profile row and default options for it as part of a larger transaction
+pub(super) fn insert_profile(tx: &Transaction, name: &str) -> Result<usize, StorageError> {
+    let name = validate_profile_name(name)?;
+    tx.execute(
+        &format!("INSERT INTO {PROFILES_TABLE_NAME} (name, lastUsed) VALUES (?1, ?2)"),
+        params![name, now_secs()? as i64],
+    )?;
+    let profile_id = tx.last_insert_rowid() as usize;
+    tx.execute(
+        &format!("INSERT OR IGNORE INTO {OPTIONS_TABLE_NAME} (optionsProfileId) VALUES (?1)"),
+        [profile_id],
+    )?;
+    Ok(profile_id)
+}
+
+/// Saves a new profile with its own default options and returns its id. Names are trimmed and
+/// must be unique.
+pub(super) fn create_profile(name: &str) -> Result<usize, StorageError> {
+    let mut conn = get_db()?;
+    let tx = conn.transaction()?;
+    let profile_id = insert_profile(&tx, name)?;
+    tx.commit()?;
+    Ok(profile_id)
+}
+
+/// Marks a profile as the most recently used, so that it is selected at the next launch
+pub(super) fn set_last_used_profile(profile_id: usize) -> Result<(), StorageError> {
+    let conn = get_db()?;
+    conn.execute(
+        &format!("UPDATE {PROFILES_TABLE_NAME} SET lastUsed=?2 WHERE profileId=?1"),
+        params![profile_id, now_secs()? as i64],
+    )?;
+    Ok(())
+}
+
+/// Deletes a profile and everything saved for it. The last remaining profile is never deleted.
+pub(super) fn delete_profile(profile_id: usize) -> Result<(), StorageError> {
+    let mut conn = get_db()?;
+    let tx = conn.transaction()?;
+
+    let n_profiles: usize = tx.query_row(
+        &format!("SELECT COUNT(*) FROM {PROFILES_TABLE_NAME}"),
+        [],
+        |row| row.get(0),
+    )?;
+    if n_profiles <= 1 {
+        return Err(StorageError::InternalError(
+            "Cannot delete the last profile".to_string(),
+        ));
+    }
+
+    for (table_name, id_column) in PROFILE_DATA_TABLES
+        .into_iter()
+        .chain([(PROFILES_TABLE_NAME, "profileId")])
+    {
+        tx.execute(
+            &format!("DELETE FROM {table_name} WHERE {id_column}=?1"),
+            [profile_id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub(super) fn _get_profiles() -> Result<Vec<Profile>, StorageError> {
+    let conn = get_db()?;
+    let stmt_raw = format!(
+        "
+    SELECT profileId, name FROM {PROFILES_TABLE_NAME}
+    ORDER BY profileId"
+    );
+    let profiles = conn
+        .prepare(&stmt_raw)?
+        .query([])?
+        .mapped(|row| {
+            Ok(Profile {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })
+        .collect::<Result<_, rusqlite::Error>>()?;
+    Ok(profiles)
+}
+
+/// Returns every saved profile, oldest first.
+pub fn get_profiles() -> Vec<Profile> {
+    _get_profiles().unwrap_or_else(|e| {
+        error!(
+            "Failed to get profiles from db. Empty result fallback. {}",
+            e
+        );
+        vec![]
+    })
+}
+
+pub(super) fn _get_last_used_profile_id() -> Result<Option<usize>, StorageError> {
+    let conn = get_db()?;
+    let profile_id = conn
+        .query_row(
+            &format!(
+                "SELECT profileId FROM {PROFILES_TABLE_NAME} ORDER BY lastUsed DESC, profileId LIMIT 1"
+            ),
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(profile_id)
+}
+
+/// Returns the profile that was used most recently, falling back to the default profile.
+pub fn get_last_used_profile_id() -> usize {
+    _get_last_used_profile_id()
+        .unwrap_or_else(|e| {
+            error!(
+                "Failed to get the last used profile from db. Default profile fallback. {}",
+                e
+            );
+            None
+        })
+        .unwrap_or(DEFAULT_USER_ID)
+}
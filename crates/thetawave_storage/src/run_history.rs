@@ -0,0 +1,271 @@
+use crate::backend::{get_favorite_character, get_run_start_time, StorageError};
+use crate::core::{get_db, RUN_HISTORY_TABLE_NAME};
+use bevy::log::error;
+use rusqlite::{params, Result, Row};
+use thetawave_interface::character::CharacterType;
+use thetawave_interface::game::run_history::{Leaderboard, PersonalBests, RunRecord};
+use thetawave_interface::run::{DifficultyType, RunDefeatType, RunOutcomeType};
+
+/// Characters are stored in a single column, joined by this separator
+const CHARACTERS_SEPARATOR: &str = ",";
+
+fn outcome_to_db_str(outcome: &RunOutcomeType) -> &'static str {
+    match outcome {
+        RunOutcomeType::Victory => "Victory",
+        RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed) => "PlayersDestroyed",
+        RunOutcomeType::Defeat(RunDefeatType::DefenseDestroyed) => "DefenseDestroyed",
+        RunOutcomeType::Defeat(RunDefeatType::Abandoned) => "Abandoned",
+    }
+}
+
+fn outcome_from_db_str(outcome: &str) -> Result<RunOutcomeType, StorageError> {
+    match outcome {
+        "Victory" => Ok(RunOutcomeType::Victory),
+        "PlayersDestroyed" => Ok(RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed)),
+        "DefenseDestroyed" => Ok(RunOutcomeType::Defeat(RunDefeatType::DefenseDestroyed)),
+        "Abandoned" => Ok(RunOutcomeType::Defeat(RunDefeatType::Abandoned)),
+        _ => Err(StorageError::InternalError(format!(
+            "Unknown run outcome {outcome}"
+        ))),
+    }
+}
+
+fn difficulty_to_db_str(difficulty: &DifficultyType) -> &'static str {
+    match difficulty {
+        DifficultyType::Easy => "Easy",
+        DifficultyType::Normal => "Normal",
+        DifficultyType::Hard => "Hard",
+    }
+}
+
+fn difficulty_from_db_str(difficulty: &str) -> Result<DifficultyType, StorageError> {
+    match difficulty {
+        "Easy" => Ok(DifficultyType::Easy),
+        "Normal" => Ok(DifficultyType::Normal),
+        "Hard" => Ok(DifficultyType::Hard),
+        _ => Err(StorageError::InternalError(format!(
+            "Unknown difficulty {difficulty}"
+        ))),
+    }
+}
+
+/// Columns read by `run_record_from_row`, in order
+const RUN_RECORD_COLUMNS: &str = "startTime, durationSecs, characters, runKey, difficulty,
+        levelsCompleted, outcome, moneyCollected, damageTaken, seed, score";
+
+/// Saves a run, see `get_run_start_time` for when it is recorded as having started
+pub(super) fn add_run_for_user(user_id: usize, run: &RunRecord) -> Result<(), StorageError> {
+    let start_time = get_run_start_time(run)?;
+    let characters = run
+        .characters
+        .iter()
+        .map(|character| character.as_str())
+        .collect::<Vec<&str>>()
+        .join(CHARACTERS_SEPARATOR);
+
+    let stmt_raw = format!(
+        "
+    INSERT INTO {RUN_HISTORY_TABLE_NAME} (
+        userId, startTime, durationSecs, characters, runKey, difficulty, levelsCompleted,
+        outcome, moneyCollected, damageTaken, seed, score
+    )
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"
+    );
+    let conn = get_db()?;
+    conn.prepare(&stmt_raw)?.execute(params![
+        user_id,
+        start_time as i64,
+        f64::from(run.duration_secs),
+        characters,
+        run.run_key,
+        difficulty_to_db_str(&run.difficulty),
+        run.levels_completed,
+        outcome_to_db_str(&run.outcome),
+        run.money_collected,
+        run.damage_taken,
+        // sqlite only has signed integers, so the seed's bits are stored as-is
+        run.seed as i64,
+        run.score,
+    ])?;
+    Ok(())
+}
+
+fn characters_from_db_str(characters: &str) -> Vec<CharacterType> {
+    characters
+        .split(CHARACTERS_SEPARATOR)
+        .filter(|key| !key.is_empty())
+        .map(CharacterType::from)
+        .collect()
+}
+
+fn run_record_from_row(row: &Row) -> Result<RunRecord, StorageError> {
+    Ok(RunRecord {
+        start_time: Some(row.get::<usize, i64>(0)? as u64),
+        duration_secs: row.get::<usize, f64>(1)? as f32,
+        characters: characters_from_db_str(&row.get::<usize, String>(2)?),
+        run_key: row.get(3)?,
+        difficulty: difficulty_from_db_str(&row.get::<usize, String>(4)?)?,
+        levels_completed: row.get(5)?,
+        outcome: outcome_from_db_str(&row.get::<usize, String>(6)?)?,
+        money_collected: row.get(7)?,
+        damage_taken: row.get(8)?,
+        seed: row.get::<usize, i64>(9)? as u64,
+        score: row.get(10)?,
+    })
+}
+
+pub(super) fn _get_recent_runs_for_user(
+    user_id: usize,
+    limit: usize,
+) -> Result<Vec<RunRecord>, StorageError> {
+    let stmt_raw = format!(
+        "
+    SELECT {RUN_RECORD_COLUMNS}
+    FROM {RUN_HISTORY_TABLE_NAME}
+    WHERE userId=?1
+    ORDER BY startTime DESC, runId DESC
+    LIMIT ?2"
+    );
+    let conn = get_db()?;
+    let mut stmt = conn.prepare(&stmt_raw)?;
+    let mut rows = stmt.query(params![user_id, limit])?;
+    let mut runs = vec![];
+    while let Some(row) = rows.next()? {
+        runs.push(run_record_from_row(row)?);
+    }
+    Ok(runs)
+}
+
+/// Returns up to `limit` of the user's most recent runs, most recent first.
+pub fn get_recent_runs_for_user(user_id: usize, limit: usize) -> Vec<RunRecord> {
+    _get_recent_runs_for_user(user_id, limit).unwrap_or_else(|e| {
+        error!(
+            "Failed to get recent runs from db. Empty result fallback. {}",
+            e
+        );
+        vec![]
+    })
+}
+
+pub(super) fn _get_personal_bests_for_user(user_id: usize) -> Result<PersonalBests, StorageError> {
+    let stmt_raw = format!(
+        "
+    SELECT
+        COUNT(*),
+        COALESCE(MAX(levelsCompleted), 0),
+        COALESCE(MAX(durationSecs), 0.0),
+        COALESCE(MAX(moneyCollected), 0),
+        MIN(CASE WHEN outcome='Victory' THEN durationSecs END)
+    FROM {RUN_HISTORY_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    // ranked the same way as the leaderboards
+    let best_run_stmt_raw = format!(
+        "
+    SELECT {RUN_RECORD_COLUMNS}
+    FROM {RUN_HISTORY_TABLE_NAME}
+    WHERE userId=?1
+    ORDER BY score DESC, durationSecs ASC, runId ASC
+    LIMIT 1"
+    );
+    let characters_stmt_raw = format!(
+        "
+    SELECT characters
+    FROM {RUN_HISTORY_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let conn = get_db()?;
+    let personal_bests = conn.query_row(&stmt_raw, [user_id], |row| {
+        Ok(PersonalBests {
+            total_runs: row.get(0)?,
+            most_levels_completed: row.get(1)?,
+            longest_run_secs: row.get::<usize, f64>(2)? as f32,
+            most_money_collected: row.get(3)?,
+            fastest_victory_secs: row.get::<usize, Option<f64>>(4)?.map(|secs| secs as f32),
+            ..Default::default()
+        })
+    })?;
+
+    let mut best_run_stmt = conn.prepare(&best_run_stmt_raw)?;
+    let mut best_run_rows = best_run_stmt.query([user_id])?;
+    let best_run = match best_run_rows.next()? {
+        Some(row) => Some(run_record_from_row(row)?),
+        None => None,
+    };
+
+    let mut characters_stmt = conn.prepare(&characters_stmt_raw)?;
+    let mut characters_rows = characters_stmt.query([user_id])?;
+    let mut characters = vec![];
+    while let Some(row) = characters_rows.next()? {
+        characters.extend(characters_from_db_str(&row.get::<usize, String>(0)?));
+    }
+
+    Ok(PersonalBests {
+        favorite_character: get_favorite_character(&characters),
+        best_run,
+        ..personal_bests
+    })
+}
+
+/// Returns the best results over all of the user's saved runs.
+pub fn get_personal_bests_for_user(user_id: usize) -> PersonalBests {
+    _get_personal_bests_for_user(user_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to get personal bests from db. Default result fallback. {}",
+            e
+        );
+        Default::default()
+    })
+}
+
+pub(super) fn _get_leaderboards(limit: usize) -> Result<Vec<Leaderboard>, StorageError> {
+    let run_modes_stmt_raw = format!(
+        "
+    SELECT DISTINCT runKey, difficulty
+    FROM {RUN_HISTORY_TABLE_NAME}
+    ORDER BY runKey, difficulty"
+    );
+    let runs_stmt_raw = format!(
+        "
+    SELECT {RUN_RECORD_COLUMNS}
+    FROM {RUN_HISTORY_TABLE_NAME}
+    WHERE runKey=?1 AND difficulty=?2
+    ORDER BY score DESC, durationSecs ASC
+    LIMIT ?3"
+    );
+    let conn = get_db()?;
+    let mut run_modes_stmt = conn.prepare(&run_modes_stmt_raw)?;
+    let mut runs_stmt = conn.prepare(&runs_stmt_raw)?;
+
+    let mut leaderboards = vec![];
+    let mut run_modes = run_modes_stmt.query([])?;
+    while let Some(run_mode) = run_modes.next()? {
+        let run_key: String = run_mode.get(0)?;
+        let difficulty: String = run_mode.get(1)?;
+
+        let mut runs = vec![];
+        let mut rows = runs_stmt.query(params![run_key, difficulty, limit])?;
+        while let Some(row) = rows.next()? {
+            runs.push(run_record_from_row(row)?);
+        }
+
+        leaderboards.push(Leaderboard {
+            run_key,
+            difficulty: difficulty_from_db_str(&difficulty)?,
+            runs,
+        });
+    }
+    Ok(leaderboards)
+}
+
+/// Returns the `limit` highest scoring runs of every user for each run mode (run key and
+/// difficulty) that has been played.
+pub fn get_leaderboards(limit: usize) -> Vec<Leaderboard> {
+    _get_leaderboards(limit).unwrap_or_else(|e| {
+        error!(
+            "Failed to get leaderboards from db. Empty result fallback. {}",
+            e
+        );
+        vec![]
+    })
+}
@@ -0,0 +1,168 @@
+//! The storage backend for the local SQLite database, made up of the queries in the other modules.
+use thetawave_interface::game::achievements::AchievementsProgressResource;
+use thetawave_interface::game::compendium::EncountersResource;
+use thetawave_interface::game::historical_metrics::{
+    LevelStatsBy1PlayerCacheT, MobsKilledBy1PlayerCacheT, UserStat,
+};
+use thetawave_interface::game::options::GameOptions;
+use thetawave_interface::game::profiles::Profile;
+use thetawave_interface::game::run_history::{Leaderboard, PersonalBests, RunRecord};
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+
+use crate::achievements::{_get_achievements_for_user, set_achievements_for_user};
+use crate::backend::{StorageBackend, StorageError};
+use crate::core::{get_db, setup_db};
+use crate::encounters::{_get_encounters_for_user, add_encounters_for_user};
+use crate::options::{_get_game_options, set_game_options};
+use crate::profiles::{
+    _get_last_used_profile_id, _get_profiles, create_profile, delete_profile, set_last_used_profile,
+};
+use crate::run_history::{
+    _get_leaderboards, _get_personal_bests_for_user, _get_recent_runs_for_user, add_run_for_user,
+};
+use crate::unlocks::{_get_character_unlocks_for_user, set_character_unlocks_for_user};
+use crate::user_stats::{
+    _get_level_stats_for_user, _get_mob_killed_counts_for_user, _get_user_stats,
+    set_level_stat_for_user, set_mob_killed_count_for_user, set_user_stats_for_user_id,
+};
+
+/// Saves to the database at `THETAWAVE_DB_PATH`, or in the user's data directory
+pub struct SqliteBackend;
+
+impl StorageBackend for SqliteBackend {
+    fn setup(&self) -> Result<(), StorageError> {
+        Ok(setup_db(get_db()?)?)
+    }
+
+    fn get_profiles(&self) -> Result<Vec<Profile>, StorageError> {
+        _get_profiles()
+    }
+
+    fn create_profile(&self, name: &str) -> Result<usize, StorageError> {
+        create_profile(name)
+    }
+
+    fn set_last_used_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        set_last_used_profile(profile_id)
+    }
+
+    fn delete_profile(&self, profile_id: usize) -> Result<(), StorageError> {
+        delete_profile(profile_id)
+    }
+
+    fn get_last_used_profile_id(&self) -> Result<Option<usize>, StorageError> {
+        _get_last_used_profile_id()
+    }
+
+    fn get_user_stats(&self, user_id: usize) -> Result<Option<UserStat>, StorageError> {
+        _get_user_stats(user_id)
+    }
+
+    fn set_user_stats(&self, user_id: usize, user_stats: &UserStat) -> Result<(), StorageError> {
+        set_user_stats_for_user_id(user_id, user_stats)
+    }
+
+    fn get_mob_kills(&self, user_id: usize) -> Result<MobsKilledBy1PlayerCacheT, StorageError> {
+        _get_mob_killed_counts_for_user(user_id)
+    }
+
+    fn set_mob_kills(
+        &self,
+        user_id: usize,
+        mob_kills: &MobsKilledBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        for (mob_type, n_killed) in mob_kills {
+            set_mob_killed_count_for_user(user_id, mob_type, *n_killed)?;
+        }
+        Ok(())
+    }
+
+    fn get_level_stats(&self, user_id: usize) -> Result<LevelStatsBy1PlayerCacheT, StorageError> {
+        _get_level_stats_for_user(user_id)
+    }
+
+    fn set_level_stats(
+        &self,
+        user_id: usize,
+        level_stats: &LevelStatsBy1PlayerCacheT,
+    ) -> Result<(), StorageError> {
+        for (level_name, level_stat) in level_stats {
+            set_level_stat_for_user(user_id, level_name, level_stat)?;
+        }
+        Ok(())
+    }
+
+    fn get_game_options(&self, profile_id: usize) -> Result<Option<GameOptions>, StorageError> {
+        _get_game_options(profile_id)
+    }
+
+    fn set_game_options(
+        &self,
+        profile_id: usize,
+        game_options: &GameOptions,
+    ) -> Result<(), StorageError> {
+        set_game_options(profile_id, game_options)
+    }
+
+    fn get_character_unlocks(
+        &self,
+        user_id: usize,
+    ) -> Result<CharacterUnlocksResource, StorageError> {
+        _get_character_unlocks_for_user(user_id)
+    }
+
+    fn set_character_unlocks(
+        &self,
+        user_id: usize,
+        unlocks: &CharacterUnlocksResource,
+    ) -> Result<(), StorageError> {
+        set_character_unlocks_for_user(user_id, unlocks)
+    }
+
+    fn get_achievements(
+        &self,
+        user_id: usize,
+    ) -> Result<AchievementsProgressResource, StorageError> {
+        _get_achievements_for_user(user_id)
+    }
+
+    fn set_achievements(
+        &self,
+        user_id: usize,
+        achievements: &AchievementsProgressResource,
+    ) -> Result<(), StorageError> {
+        set_achievements_for_user(user_id, achievements)
+    }
+
+    fn get_encounters(&self, user_id: usize) -> Result<EncountersResource, StorageError> {
+        _get_encounters_for_user(user_id)
+    }
+
+    fn add_encounters(
+        &self,
+        user_id: usize,
+        encounters: &EncountersResource,
+    ) -> Result<(), StorageError> {
+        add_encounters_for_user(user_id, encounters)
+    }
+
+    fn add_run(&self, user_id: usize, run: &RunRecord) -> Result<(), StorageError> {
+        add_run_for_user(user_id, run)
+    }
+
+    fn get_recent_runs(
+        &self,
+        user_id: usize,
+        limit: usize,
+    ) -> Result<Vec<RunRecord>, StorageError> {
+        _get_recent_runs_for_user(user_id, limit)
+    }
+
+    fn get_personal_bests(&self, user_id: usize) -> Result<PersonalBests, StorageError> {
+        _get_personal_bests_for_user(user_id)
+    }
+
+    fn get_leaderboards(&self, limit: usize) -> Result<Vec<Leaderboard>, StorageError> {
+        _get_leaderboards(limit)
+    }
+}
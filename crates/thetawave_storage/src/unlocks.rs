@@ -0,0 +1,80 @@
+use crate::backend::StorageError;
+use crate::core::{get_db, CHARACTER_UNLOCKS_TABLE_NAME, META_CURRENCY_TABLE_NAME};
+use bevy::log::error;
+use rusqlite::{params, Result};
+use thetawave_interface::character::CharacterType;
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+
+pub(super) fn set_character_unlocks_for_user(
+    user_id: usize,
+    unlocks: &CharacterUnlocksResource,
+) -> Result<(), StorageError> {
+    let insert_unlock_stmt_raw = format!(
+        "
+    INSERT OR IGNORE INTO {CHARACTER_UNLOCKS_TABLE_NAME} (userId, characterKey)
+    VALUES (?1, ?2)"
+    );
+    let upsert_balance_stmt_raw = format!(
+        "
+    INSERT INTO {META_CURRENCY_TABLE_NAME} (userId, balance)
+    VALUES (?1, ?2)
+    ON CONFLICT(userId) DO UPDATE SET balance=excluded.balance"
+    );
+    let mut conn = get_db()?;
+    let tx = conn.transaction()?;
+    {
+        let mut insert_unlock_stmt = tx.prepare(&insert_unlock_stmt_raw)?;
+        for character_type in unlocks.unlocked_characters.iter() {
+            insert_unlock_stmt.execute(params![user_id, character_type.as_str()])?;
+        }
+        tx.prepare(&upsert_balance_stmt_raw)?
+            .execute(params![user_id, unlocks.meta_currency])?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub(super) fn _get_character_unlocks_for_user(
+    user_id: usize,
+) -> Result<CharacterUnlocksResource, StorageError> {
+    let conn = get_db()?;
+    let unlocks_stmt_raw = format!(
+        "
+    SELECT characterKey FROM {CHARACTER_UNLOCKS_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let unlocked_characters = conn
+        .prepare(&unlocks_stmt_raw)?
+        .query([user_id])?
+        .mapped(|r| r.get::<usize, String>(0))
+        .map(|key| key.map(CharacterType))
+        .collect::<Result<_, rusqlite::Error>>()?;
+
+    let balance_stmt_raw = format!(
+        "
+    SELECT balance FROM {META_CURRENCY_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let mut balance_stmt = conn.prepare(&balance_stmt_raw)?;
+    let mut rows = balance_stmt.query([user_id])?;
+    let meta_currency = match rows.next()? {
+        Some(r) => r.get(0)?,
+        None => 0,
+    };
+
+    Ok(CharacterUnlocksResource {
+        unlocked_characters,
+        meta_currency,
+    })
+}
+
+/// Returns the characters a user has unlocked and their meta-currency balance.
+pub fn get_character_unlocks_for_user(user_id: usize) -> CharacterUnlocksResource {
+    _get_character_unlocks_for_user(user_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to get character unlocks from db. Empty result fallback. {}",
+            e
+        );
+        Default::default()
+    })
+}
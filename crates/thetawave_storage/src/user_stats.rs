@@ -1,19 +1,29 @@
-use crate::core::{get_db, OurDBError, ENEMY_KILL_HISTORY_TABLE_NAME, USERSTAT};
+use crate::backend::StorageError;
+use crate::core::{get_db, ENEMY_KILL_HISTORY_TABLE_NAME, LEVEL_STATS_TABLE_NAME, USERSTAT};
 use bevy::log::{error, info};
 use rusqlite::{params, Result};
 use thetawave_interface::spawnable::EnemyMobType;
 
-use thetawave_interface::game::historical_metrics::{MobsKilledBy1PlayerCacheT, UserStat};
+use thetawave_interface::game::historical_metrics::{
+    LevelStat, LevelStatsBy1PlayerCacheT, MobsKilledBy1PlayerCacheT, UserStat,
+};
 
 pub(super) fn set_user_stats_for_user_id(
     user_id: usize,
     user_stats: &UserStat,
-) -> Result<(), OurDBError> {
+) -> Result<(), StorageError> {
     let stmt_raw = format!(
         "
-    INSERT OR REPLACE INTO {USERSTAT} (userId, totalShotsFired, totalGamesLost, totalShotsHit)
-    VALUES (?1,  ?2, ?3, ?4)
-    ON CONFLICT DO UPDATE SET totalShotsFired=?2, totalGamesLost=?3, totalShotsHit=?4"
+    INSERT OR REPLACE INTO {USERSTAT} (
+        userId, totalShotsFired, totalGamesLost, totalShotsHit, totalGamesWon,
+        totalGamesLostToPlayersDestroyed, totalGamesLostToDefenseDestroyed, totalLevelsCompleted,
+        mostLevelsCompleted, fastestVictoryMillis
+    )
+    VALUES (?1,  ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+    ON CONFLICT DO UPDATE SET totalShotsFired=?2, totalGamesLost=?3, totalShotsHit=?4,
+        totalGamesWon=?5, totalGamesLostToPlayersDestroyed=?6,
+        totalGamesLostToDefenseDestroyed=?7, totalLevelsCompleted=?8, mostLevelsCompleted=?9,
+        fastestVictoryMillis=?10"
     );
     let conn = get_db()?;
     info!(
@@ -25,14 +35,23 @@ pub(super) fn set_user_stats_for_user_id(
         user_stats.total_shots_fired,
         user_stats.total_games_lost,
         user_stats.total_shots_hit,
+        user_stats.total_games_won,
+        user_stats.total_games_lost_to_players_destroyed,
+        user_stats.total_games_lost_to_defense_destroyed,
+        user_stats.total_levels_completed,
+        user_stats.most_levels_completed,
+        user_stats.fastest_victory_millis,
     ])?;
     Ok(())
 }
-fn _get_user_stats(user_id: usize) -> Result<Option<UserStat>, OurDBError> {
+pub(super) fn _get_user_stats(user_id: usize) -> Result<Option<UserStat>, StorageError> {
     let conn = get_db()?;
     let stmt_raw = format!(
         "
-    SELECT totalGamesLost, totalShotsFired, totalShotsHit FROM  {USERSTAT}
+    SELECT totalGamesLost, totalShotsFired, totalShotsHit, totalGamesWon,
+        totalGamesLostToPlayersDestroyed, totalGamesLostToDefenseDestroyed, totalLevelsCompleted,
+        mostLevelsCompleted, fastestVictoryMillis
+    FROM  {USERSTAT}
     WHERE userId=?1"
     );
     let mut stmt = conn.prepare(&stmt_raw)?;
@@ -46,6 +65,12 @@ fn _get_user_stats(user_id: usize) -> Result<Option<UserStat>, OurDBError> {
                 total_games_lost,
                 total_shots_fired,
                 total_shots_hit,
+                total_games_won: r.get(3)?,
+                total_games_lost_to_players_destroyed: r.get(4)?,
+                total_games_lost_to_defense_destroyed: r.get(5)?,
+                total_levels_completed: r.get(6)?,
+                most_levels_completed: r.get(7)?,
+                fastest_victory_millis: r.get(8)?,
             }))
         }
 
@@ -68,7 +93,7 @@ pub(super) fn set_mob_killed_count_for_user(
     user_id: usize,
     mob_type: &EnemyMobType,
     amount: usize,
-) -> Result<(), OurDBError> {
+) -> Result<(), StorageError> {
     let stmt_raw = format!(
         "
     INSERT OR REPLACE INTO {ENEMY_KILL_HISTORY_TABLE_NAME} (userId, enemyMobType, nKilled)
@@ -81,9 +106,9 @@ pub(super) fn set_mob_killed_count_for_user(
     Ok(())
 }
 
-fn _get_mob_killed_counts_for_user(
+pub(super) fn _get_mob_killed_counts_for_user(
     user_id: usize,
-) -> Result<MobsKilledBy1PlayerCacheT, OurDBError> {
+) -> Result<MobsKilledBy1PlayerCacheT, StorageError> {
     let stmt_raw = format!(
         "
     SELECT enemyMobType, nKilled FROM  {ENEMY_KILL_HISTORY_TABLE_NAME} 
@@ -104,12 +129,12 @@ fn _get_mob_killed_counts_for_user(
         .map(|(mob, n)| {
             Ok((
                 mob.parse::<EnemyMobType>().map_err(|e| {
-                    OurDBError::InternalError(format!("Failed to read mob data from db {}", e))
+                    StorageError::InternalError(format!("Failed to read mob data from db {}", e))
                 })?,
                 n,
             ))
         })
-        .collect::<Result<Vec<(EnemyMobType, usize)>, OurDBError>>()?
+        .collect::<Result<Vec<(EnemyMobType, usize)>, StorageError>>()?
         .into_iter()
         // The DB primary key guarantees that translating from a vec -> hashmap doesnt lose elements
         .collect())
@@ -124,3 +149,64 @@ pub fn get_mob_killed_counts_for_user(user_id: usize) -> MobsKilledBy1PlayerCach
         Default::default()
     })
 }
+
+pub(super) fn set_level_stat_for_user(
+    user_id: usize,
+    level_name: &str,
+    level_stat: &LevelStat,
+) -> Result<(), StorageError> {
+    let stmt_raw = format!(
+        "
+    INSERT OR REPLACE INTO {LEVEL_STATS_TABLE_NAME} (
+        userId, levelName, timesReached, timesCompleted, bestTimeMillis
+    )
+    VALUES (?1, ?2, ?3, ?4, ?5)"
+    );
+    let conn = get_db()?;
+    conn.prepare(&stmt_raw)?.execute(params![
+        user_id,
+        level_name,
+        level_stat.times_reached,
+        level_stat.times_completed,
+        level_stat.best_time_millis,
+    ])?;
+    Ok(())
+}
+
+pub(super) fn _get_level_stats_for_user(
+    user_id: usize,
+) -> Result<LevelStatsBy1PlayerCacheT, StorageError> {
+    let stmt_raw = format!(
+        "
+    SELECT levelName, timesReached, timesCompleted, bestTimeMillis FROM {LEVEL_STATS_TABLE_NAME}
+    WHERE userId=?1"
+    );
+    let conn = get_db()?;
+    let mut stmt = conn.prepare(&stmt_raw)?;
+    let rows = stmt.query([user_id])?;
+    Ok(rows
+        .mapped(|r| {
+            Ok((
+                r.get::<usize, String>(0)?,
+                LevelStat {
+                    times_reached: r.get(1)?,
+                    times_completed: r.get(2)?,
+                    best_time_millis: r.get(3)?,
+                },
+            ))
+        })
+        // The DB primary key guarantees that translating from a vec -> hashmap doesnt lose elements
+        .collect::<Result<LevelStatsBy1PlayerCacheT, rusqlite::Error>>()?)
+}
+
+/// Returns how the user has done in each level, keyed by level name, for games that have already
+/// been completed and flushed to the db.
+pub fn get_level_stats_for_user(user_id: usize) -> LevelStatsBy1PlayerCacheT {
+    _get_level_stats_for_user(user_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to get level stats from db. Empty result fallback. {}",
+            e
+        );
+        Default::default()
+    })
+}
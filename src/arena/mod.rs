@@ -4,6 +4,7 @@
 use barrier::spawn_barriers_system;
 use bevy::{
     app::{App, Plugin, Update},
+    math::Vec2,
     prelude::{in_state, IntoSystemConfigs, OnEnter},
 };
 use thetawave_interface::{objective::MobReachedBottomGateEvent, states};
@@ -16,6 +17,10 @@ use self::gate::{despawn_gates_system, spawn_despawn_gates_system};
 
 pub(crate) use self::barrier::ArenaBarrierComponent;
 
+/// Distance from the center of the arena to its right and top edges. Everything inside of these
+/// edges is on screen.
+pub(crate) const ARENA_HALF_SIZE: Vec2 = Vec2::new(500.0, 360.0);
+
 /// Plugin that spawns a rectangular boundary for the main game play area and fires off
 /// `MobReachedBottomGateEvent` at the right times
 pub(super) struct ArenaPlugin;
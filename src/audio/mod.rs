@@ -1,14 +1,21 @@
 //! Exposes a plugin that starts, stops, and modulates in-game audio when events are emitted
 use bevy::prelude::{
-    in_state, not, App, EventReader, IntoSystemConfigs, Plugin, Res, Resource, Startup, Update,
+    in_state, not, resource_changed, App, EventReader, IntoSystemConfigs, Plugin, Res, Resource,
+    Update,
 };
 use bevy_kira_audio::prelude::{AudioApp, AudioChannel, AudioControl, AudioEasing, AudioTween};
 use thetawave_assets::GameAudioAssets;
 use thetawave_interface::{
     audio::{ChangeBackgroundMusicEvent, PlaySoundEffectEvent},
+    game::options::GameOptions,
     states::AppStates,
 };
 
+/// Volumes that the audio channels are mixed at when their volume options are at the maximum
+const BACKGROUND_MUSIC_VOLUME: f64 = 0.20;
+const MENU_VOLUME: f64 = 0.05;
+const SOUND_EFFECTS_VOLUME: f64 = 0.80;
+
 /// Starts, stops, and modulates in-game audio when we receive a
 /// `thetawave_interface::audio::PlaySoundEffectEvent` or
 /// `thetawave_interface::audio::ChangeBackgroundMusicEvent`.
@@ -23,7 +30,10 @@ impl Plugin for ThetawaveAudioPlugin {
             .add_audio_channel::<MenuAudioChannel>()
             .add_audio_channel::<SoundEffectsAudioChannel>();
 
-        app.add_systems(Startup, set_audio_volume_system);
+        app.add_systems(
+            Update,
+            set_audio_volume_system.run_if(resource_changed::<GameOptions>),
+        );
 
         app.add_systems(
             Update,
@@ -41,15 +51,18 @@ pub struct MenuAudioChannel;
 #[derive(Resource)]
 pub struct SoundEffectsAudioChannel;
 
-/// Sets the volume of the audio channels to "sane defaults"
+/// Sets the volume of the audio channels from the volume options
 fn set_audio_volume_system(
     background_audio_channel: Res<AudioChannel<BackgroundMusicAudioChannel>>,
     menu_audio_channel: Res<AudioChannel<MenuAudioChannel>>,
     effects_audio_channel: Res<AudioChannel<SoundEffectsAudioChannel>>,
+    game_options: Res<GameOptions>,
 ) {
-    background_audio_channel.set_volume(0.20);
-    menu_audio_channel.set_volume(0.05);
-    effects_audio_channel.set_volume(0.80);
+    background_audio_channel
+        .set_volume(BACKGROUND_MUSIC_VOLUME * f64::from(game_options.music_volume));
+    menu_audio_channel.set_volume(MENU_VOLUME * f64::from(game_options.menu_volume));
+    effects_audio_channel
+        .set_volume(SOUND_EFFECTS_VOLUME * f64::from(game_options.sound_effects_volume));
 }
 
 /// Play sound effects when we receive events. This should be called every frame for snappy audio.
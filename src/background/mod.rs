@@ -293,13 +293,13 @@ fn create_background_system(
     let star_color = Color::srgb(
         1.0 + rng.gen_range(backgrounds_res.star_color_range.clone())
             * backgrounds_res.star_bloom_brightness
-            * game_options.bloom_intensity,
+            * game_options.get_bloom_intensity(),
         1.0 + rng.gen_range(backgrounds_res.star_color_range.clone())
             * backgrounds_res.star_bloom_brightness
-            * game_options.bloom_intensity,
+            * game_options.get_bloom_intensity(),
         1.0 + rng.gen_range(backgrounds_res.star_color_range.clone())
             * backgrounds_res.star_bloom_brightness
-            * game_options.bloom_intensity,
+            * game_options.get_bloom_intensity(),
     );
 
     // Emissive colored star material for bloom
@@ -16,6 +16,7 @@ use bevy::{
 };
 
 use thetawave_interface::camera::ScreenShakeEvent;
+use thetawave_interface::game::options::GameOptions;
 use thetawave_interface::health::DamageDealtEvent;
 use thetawave_interface::player::PlayerComponent;
 
@@ -31,17 +32,21 @@ pub(super) struct ScreenShakeComponent {
     pub shake_intensity: Vec3,
 }
 
-/// System for reading screen shake events and adding trauma from the events to the screen shake component
+/// System for reading screen shake events and adding trauma from the events to the screen shake
+/// component, scaled by the screen shake intensity option and disabled by reduced motion
 pub(super) fn add_trauma_system(
     mut screen_shake_event_reader: EventReader<ScreenShakeEvent>,
     mut camera_2d_query: Query<
         (&mut ScreenShakeComponent, &mut Transform),
         (With<Camera2d>, Without<Camera3d>),
     >,
+    game_options: Res<GameOptions>,
 ) {
     for _event in screen_shake_event_reader.read() {
         if let Ok((mut screen_shake, _transform)) = camera_2d_query.get_single_mut() {
-            screen_shake.trauma = (screen_shake.trauma + _event.trauma).min(1.0);
+            screen_shake.trauma = (screen_shake.trauma
+                + _event.trauma * game_options.get_screen_shake_intensity())
+            .min(1.0);
         };
     }
 }
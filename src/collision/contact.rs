@@ -131,10 +131,12 @@ pub(super) fn contact_collision_system(
                     // send two sorted collision events, swapping the position of the mobs in the struct
                     collision_event_writer.send(SortedCollisionEvent::MobToMobContact {
                         mob_entity_1: colliding_entities.primary,
+                        mob_entity_2: colliding_entities.secondary,
                         mob_damage_2: mob_component_2.collision_damage,
                     });
                     collision_event_writer.send(SortedCollisionEvent::MobToMobContact {
                         mob_entity_1: colliding_entities.secondary,
+                        mob_entity_2: colliding_entities.primary,
                         mob_damage_2: mob_component_1.collision_damage,
                     });
                     continue 'collision_events;
@@ -226,12 +228,14 @@ pub(super) fn contact_collision_system(
                     collision_event_writer.send(
                         SortedCollisionEvent::MobSegmentToMobSegmentContact {
                             mob_segment_entity_1: colliding_entities.primary,
+                            mob_segment_entity_2: colliding_entities.secondary,
                             mob_segment_damage_2: mob_segment_component_2.collision_damage,
                         },
                     );
                     collision_event_writer.send(
                         SortedCollisionEvent::MobSegmentToMobSegmentContact {
                             mob_segment_entity_1: colliding_entities.secondary,
+                            mob_segment_entity_2: colliding_entities.primary,
                             mob_segment_damage_2: mob_segment_component_1.collision_damage,
                         },
                     );
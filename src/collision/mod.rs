@@ -113,6 +113,7 @@ pub enum SortedCollisionEvent {
     // Mob to mob
     MobToMobContact {
         mob_entity_1: Entity,
+        mob_entity_2: Entity,
         mob_damage_2: usize,
     },
     MobToMobSegmentContact {
@@ -125,6 +126,7 @@ pub enum SortedCollisionEvent {
     // Mob segment to mob segment
     MobSegmentToMobSegmentContact {
         mob_segment_entity_1: Entity,
+        mob_segment_entity_2: Entity,
         mob_segment_damage_2: usize,
     },
 
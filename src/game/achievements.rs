@@ -0,0 +1,353 @@
+//! Tracks progress towards the achievements declared in `achievements.ron` from gameplay events.
+use bevy::prelude::{
+    in_state, info, App, EventReader, EventWriter, IntoSystemConfigs, OnEnter, Plugin, Res, ResMut,
+    Resource, State, Update,
+};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thetawave_interface::game::achievements::{
+    AchievementCondition, AchievementData, AchievementUnlockedEvent, AchievementsProgressResource,
+};
+use thetawave_interface::game::run_history::CompletedRunResource;
+use thetawave_interface::objective::{DefenseInteraction, MobReachedBottomGateEvent};
+use thetawave_interface::player::PlayerDamagedEvent;
+use thetawave_interface::run::{RunDefeatType, RunOutcomeType};
+use thetawave_interface::spawnable::{ConsumableCollectedEvent, MobDestroyedEvent, MobType};
+use thetawave_interface::states::AppStates;
+
+use crate::run::CurrentRunProgressResource;
+
+/// Every achievement, in the order they are listed in
+#[derive(Resource, Deserialize)]
+pub struct AchievementsResource {
+    pub achievements: Vec<AchievementData>,
+}
+
+impl AchievementsResource {
+    pub fn get_achievement(&self, key: &str) -> Option<&AchievementData> {
+        self.achievements
+            .iter()
+            .find(|achievement| achievement.key == key)
+    }
+
+    /// Adds progress to every achievement with a matching condition, returns the keys of the
+    /// achievements that were unlocked by it
+    fn add_progress(
+        &self,
+        progress: &mut AchievementsProgressResource,
+        amount: usize,
+        matches: impl Fn(&AchievementCondition) -> bool,
+    ) -> Vec<String> {
+        self.achievements
+            .iter()
+            .filter(|achievement| matches(&achievement.condition))
+            .filter(|achievement| progress.add_progress(achievement, amount))
+            .map(|achievement| achievement.key.clone())
+            .collect()
+    }
+}
+
+/// What has happened so far in the current run that achievements depend on
+#[derive(Resource, Default)]
+struct AchievementsRunTrackerResource {
+    /// Number of completed levels that have already been checked for damage
+    levels_checked: usize,
+    /// Whether a player has taken damage since the last completed level
+    damaged_this_level: bool,
+    /// Whether an enemy has got through the bottom gate this run
+    leaked_this_run: bool,
+}
+
+impl AchievementsRunTrackerResource {
+    /// Returns the number of levels completed since the last check without any damage taken
+    fn check_levels_cleared_without_damage(
+        &mut self,
+        run_res: &CurrentRunProgressResource,
+    ) -> usize {
+        let newly_completed = run_res
+            .completed_levels
+            .len()
+            .saturating_sub(self.levels_checked);
+        self.levels_checked = run_res.completed_levels.len();
+
+        if newly_completed == 0 {
+            return 0;
+        }
+
+        let damaged = self.damaged_this_level;
+        self.damaged_this_level = false;
+        if damaged {
+            0
+        } else {
+            newly_completed
+        }
+    }
+}
+
+/// Keeps `AchievementsProgressResource` up to date during runs. Persisting it is left to the
+/// storage plugin.
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(
+            from_bytes::<AchievementsResource>(include_bytes!(
+                "../../assets/data/achievements.ron"
+            ))
+            .expect("Failed to parse AchievementsResource from 'achievements.ron'"),
+        );
+        app.init_resource::<AchievementsProgressResource>();
+        app.init_resource::<AchievementsRunTrackerResource>();
+        app.add_event::<AchievementUnlockedEvent>();
+
+        app.add_systems(
+            OnEnter(AppStates::InitializeRun),
+            reset_run_achievements_system,
+        );
+        app.add_systems(
+            Update,
+            track_achievements_system.run_if(in_state(AppStates::Game)),
+        );
+        app.add_systems(
+            OnEnter(AppStates::GameOver),
+            track_run_end_achievements_system,
+        );
+        app.add_systems(
+            OnEnter(AppStates::Victory),
+            track_run_end_achievements_system,
+        );
+    }
+}
+
+fn send_unlocked_events(
+    unlocked_keys: Vec<String>,
+    achievements_res: &AchievementsResource,
+    achievement_unlocked_event_writer: &mut EventWriter<AchievementUnlockedEvent>,
+) {
+    for key in unlocked_keys {
+        if let Some(achievement) = achievements_res.get_achievement(&key) {
+            info!("Unlocked achievement {}", achievement.key);
+        }
+        achievement_unlocked_event_writer.send(AchievementUnlockedEvent { key });
+    }
+}
+
+/// Starts a new run without the progress made towards single run achievements in the last one
+fn reset_run_achievements_system(
+    achievements_res: Res<AchievementsResource>,
+    mut progress: ResMut<AchievementsProgressResource>,
+    mut tracker: ResMut<AchievementsRunTrackerResource>,
+) {
+    progress.reset_per_run_progress(&achievements_res.achievements);
+    *tracker = AchievementsRunTrackerResource::default();
+}
+
+/// Adds progress for enemies destroyed, mobs reaching the bottom gate, consumables collected and
+/// levels completed without taking damage
+#[allow(clippy::too_many_arguments)]
+fn track_achievements_system(
+    achievements_res: Res<AchievementsResource>,
+    mut progress: ResMut<AchievementsProgressResource>,
+    mut tracker: ResMut<AchievementsRunTrackerResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    mut mob_destroyed_event_reader: EventReader<MobDestroyedEvent>,
+    mut mob_reached_bottom_event_reader: EventReader<MobReachedBottomGateEvent>,
+    mut consumable_collected_event_reader: EventReader<ConsumableCollectedEvent>,
+    mut player_damaged_event_reader: EventReader<PlayerDamagedEvent>,
+    mut achievement_unlocked_event_writer: EventWriter<AchievementUnlockedEvent>,
+) {
+    let mut unlocked_keys = vec![];
+
+    for event in mob_destroyed_event_reader.read() {
+        if let MobType::Enemy(enemy_type) = event.mob_type {
+            unlocked_keys.extend(
+                achievements_res.add_progress(&mut progress, 1, |condition| match condition {
+                    AchievementCondition::MobsDestroyed {
+                        mob_type,
+                        bosses_only,
+                    } => {
+                        (mob_type.is_none() || *mob_type == Some(enemy_type))
+                            && (event.is_boss || !bosses_only)
+                    }
+                    _ => false,
+                }),
+            );
+        }
+    }
+
+    for event in mob_reached_bottom_event_reader.read() {
+        let healed = match event.defense_interaction {
+            DefenseInteraction::Heal(_) => true,
+            DefenseInteraction::Damage(_) => {
+                tracker.leaked_this_run = true;
+                false
+            }
+        };
+        unlocked_keys.extend(
+            achievements_res.add_progress(&mut progress, 1, |condition| match condition {
+                AchievementCondition::MobsReachedBottomGate { healing } => *healing == healed,
+                _ => false,
+            }),
+        );
+    }
+
+    for event in consumable_collected_event_reader.read() {
+        unlocked_keys.extend(
+            achievements_res.add_progress(&mut progress, 1, |condition| match condition {
+                AchievementCondition::ConsumablesCollected { consumable_type } => {
+                    consumable_type.is_none()
+                        || consumable_type.as_ref() == Some(&event.consumable_type)
+                }
+                _ => false,
+            }),
+        );
+    }
+
+    if player_damaged_event_reader.read().count() > 0 {
+        tracker.damaged_this_level = true;
+    }
+
+    let levels_cleared = tracker.check_levels_cleared_without_damage(&run_res);
+    unlocked_keys.extend(achievements_res.add_progress(
+        &mut progress,
+        levels_cleared,
+        |condition| matches!(condition, AchievementCondition::LevelsClearedWithoutDamage),
+    ));
+
+    send_unlocked_events(
+        unlocked_keys,
+        &achievements_res,
+        &mut achievement_unlocked_event_writer,
+    );
+}
+
+/// Whether a run that just ended adds progress to an achievement with the given condition.
+/// Abandoned runs are counted as losses in the stats, but don't count towards achievements.
+fn run_end_counts_towards(
+    condition: &AchievementCondition,
+    victory: bool,
+    abandoned: bool,
+    leaked: bool,
+) -> bool {
+    match condition {
+        AchievementCondition::RunsEnded { victories_only } => {
+            !abandoned && (victory || !victories_only)
+        }
+        AchievementCondition::RunsWonWithoutLeaks => victory && !leaked,
+        _ => false,
+    }
+}
+
+/// Adds progress for the run that just ended, including the last level if it was won
+fn track_run_end_achievements_system(
+    achievements_res: Res<AchievementsResource>,
+    mut progress: ResMut<AchievementsProgressResource>,
+    mut tracker: ResMut<AchievementsRunTrackerResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    completed_run: Res<CompletedRunResource>,
+    app_state: Res<State<AppStates>>,
+    mut achievement_unlocked_event_writer: EventWriter<AchievementUnlockedEvent>,
+) {
+    let victory = matches!(app_state.get(), AppStates::Victory);
+    // the run is recorded when the game state is exited, before this runs
+    let abandoned = matches!(
+        completed_run.as_ref().map(|run| run.outcome),
+        Some(RunOutcomeType::Defeat(RunDefeatType::Abandoned))
+    );
+    let mut unlocked_keys = vec![];
+
+    let levels_cleared = tracker.check_levels_cleared_without_damage(&run_res);
+    unlocked_keys.extend(achievements_res.add_progress(
+        &mut progress,
+        levels_cleared,
+        |condition| matches!(condition, AchievementCondition::LevelsClearedWithoutDamage),
+    ));
+
+    unlocked_keys.extend(
+        achievements_res.add_progress(&mut progress, 1, |condition| {
+            run_end_counts_towards(condition, victory, abandoned, tracker.leaked_this_run)
+        }),
+    );
+
+    send_unlocked_events(
+        unlocked_keys,
+        &achievements_res,
+        &mut achievement_unlocked_event_writer,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run_end_counts_towards, AchievementsResource};
+    use ron::de::from_bytes;
+    use thetawave_interface::game::achievements::{
+        AchievementCondition, AchievementsProgressResource,
+    };
+
+    fn get_achievements_res() -> AchievementsResource {
+        from_bytes::<AchievementsResource>(include_bytes!("../../assets/data/achievements.ron"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_achievements_unlock_once_their_goal_is_met() {
+        let achievements_res = get_achievements_res();
+        let mut progress = AchievementsProgressResource::default();
+        let is_runs_ended = |condition: &AchievementCondition| {
+            matches!(condition, AchievementCondition::RunsEnded { .. })
+        };
+
+        let unlocked = achievements_res.add_progress(&mut progress, 9, is_runs_ended);
+        assert!(unlocked.contains(&"first_victory".to_string()));
+        assert!(!unlocked.contains(&"veteran".to_string()));
+        assert_eq!(progress.get_progress("veteran"), 9);
+
+        // progress stops at the goal and unlocked achievements are only announced once
+        let unlocked = achievements_res.add_progress(&mut progress, 5, is_runs_ended);
+        assert_eq!(unlocked, vec!["veteran".to_string()]);
+        assert_eq!(progress.get_progress("veteran"), 10);
+        assert!(achievements_res
+            .add_progress(&mut progress, 1, is_runs_ended)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_per_run_progress_is_reset_until_unlocked() {
+        let achievements_res = get_achievements_res();
+        let mut progress = AchievementsProgressResource::default();
+        let is_clean_level = |condition: &AchievementCondition| {
+            matches!(condition, AchievementCondition::LevelsClearedWithoutDamage)
+        };
+
+        achievements_res.add_progress(&mut progress, 2, is_clean_level);
+        progress.reset_per_run_progress(&achievements_res.achievements);
+        assert_eq!(progress.get_progress("flawless"), 0);
+        assert!(progress.is_unlocked("untouchable"));
+
+        achievements_res.add_progress(&mut progress, 3, is_clean_level);
+        progress.reset_per_run_progress(&achievements_res.achievements);
+        assert!(progress.is_unlocked("flawless"));
+        assert_eq!(progress.get_progress("flawless"), 3);
+    }
+
+    #[test]
+    fn test_abandoned_runs_do_not_count_as_ended() {
+        let runs_ended = AchievementCondition::RunsEnded {
+            victories_only: false,
+        };
+        let victories = AchievementCondition::RunsEnded {
+            victories_only: true,
+        };
+
+        assert!(run_end_counts_towards(&runs_ended, false, false, false));
+        assert!(!run_end_counts_towards(&runs_ended, false, true, false));
+        assert!(!run_end_counts_towards(&victories, false, false, false));
+        assert!(run_end_counts_towards(&victories, true, false, false));
+        assert!(!run_end_counts_towards(
+            &AchievementCondition::RunsWonWithoutLeaks,
+            true,
+            false,
+            true
+        ));
+    }
+}
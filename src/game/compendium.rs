@@ -0,0 +1,48 @@
+//! Records the mobs, items and consumables that show up in runs, revealing them in the compendium.
+use bevy::prelude::{in_state, App, EventReader, IntoSystemConfigs, Plugin, ResMut, Update};
+use thetawave_interface::game::compendium::{CompendiumEntry, EncountersResource};
+use thetawave_interface::spawnable::{SpawnItemEvent, SpawnMobEvent};
+use thetawave_interface::states::AppStates;
+
+use crate::spawnable::SpawnConsumableEvent;
+
+/// Keeps `EncountersResource` up to date during runs. Persisting it is left to the storage
+/// plugin.
+pub struct CompendiumPlugin;
+
+impl Plugin for CompendiumPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EncountersResource>();
+
+        app.add_systems(
+            Update,
+            record_encounters_system.run_if(in_state(AppStates::Game)),
+        );
+    }
+}
+
+/// Anything that is spawned during a run counts as encountered
+fn record_encounters_system(
+    mut encounters: ResMut<EncountersResource>,
+    mut spawn_mob_event_reader: EventReader<SpawnMobEvent>,
+    mut spawn_item_event_reader: EventReader<SpawnItemEvent>,
+    mut spawn_consumable_event_reader: EventReader<SpawnConsumableEvent>,
+) {
+    let entries = spawn_mob_event_reader
+        .read()
+        .map(|event| CompendiumEntry::Mob(event.mob_type.clone()))
+        .chain(
+            spawn_item_event_reader
+                .read()
+                .map(|event| CompendiumEntry::Item(event.item_type.clone())),
+        )
+        .chain(
+            spawn_consumable_event_reader
+                .read()
+                .map(|event| CompendiumEntry::Consumable(event.consumable_type.clone())),
+        );
+
+    for entry in entries {
+        encounters.encounter(&entry);
+    }
+}
@@ -1,14 +1,17 @@
 /// Expose all of the mutations for the within-game metric counters via a bevy plugin.
 use crate::collision::SortedCollisionEvent;
+use crate::run::CurrentRunProgressResource;
 use crate::spawnable::FireWeaponEvent;
-use bevy::prelude::{debug, App, Entity, EventReader, OnEnter, Plugin, Query, ResMut, Update};
-use thetawave_interface::player::PlayerIDComponent;
+use bevy::prelude::{debug, App, Entity, EventReader, OnEnter, Plugin, Query, Res, ResMut, Update};
+use thetawave_interface::player::{PlayerIDComponent, PlayersResource};
 
 use std::collections::HashMap;
 use thetawave_interface::game::historical_metrics::{
-    MobKillsByPlayerForCompletedGames, MobKillsByPlayerForCurrentGame, UserStat,
+    LevelStatsByPlayerForCompletedGames, MobKillsByPlayerForCompletedGames,
+    MobKillsByPlayerForCurrentGame, StatsByPlayerForCurrentGame,
     UserStatsByPlayerForCompletedGamesCache, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
 };
+use thetawave_interface::game::run_history::CompletedRunResource;
 use thetawave_interface::spawnable::{MobDestroyedEvent, MobType};
 use thetawave_interface::states::AppStates;
 
@@ -22,11 +25,13 @@ impl Plugin for CountingMetricsPlugin {
         app.insert_resource(MobKillsByPlayerForCurrentGame::default());
         app.insert_resource(UserStatsByPlayerForCompletedGamesCache::default());
         app.insert_resource(UserStatsByPlayerForCurrentGameCache::default());
+        app.insert_resource(LevelStatsByPlayerForCompletedGames::default());
+        app.insert_resource(StatsByPlayerForCurrentGame::default());
         app.add_systems(
             Update,
             (
                 inc_in_memory_mob_destroyed_for_current_game_cache,
-                count_shots_fired_by_player_1_system,
+                count_shots_fired_by_players_system,
                 inc_in_memory_projectile_hits_counter_system,
             ),
         );
@@ -34,10 +39,8 @@ impl Plugin for CountingMetricsPlugin {
             OnEnter(AppStates::Game),
             roll_current_game_counters_into_completed_game_metrics,
         );
-        app.add_systems(
-            OnEnter(AppStates::GameOver),
-            inc_completed_games_played_counter,
-        );
+        app.add_systems(OnEnter(AppStates::GameOver), count_completed_game_system);
+        app.add_systems(OnEnter(AppStates::Victory), count_completed_game_system);
     }
 }
 
@@ -51,101 +54,142 @@ fn inc_usize_map<T: std::hash::Hash + std::cmp::Eq>(map: &mut HashMap<T, usize>,
         }
     }
 }
-fn inc_completed_games_played_counter(
+/// Profile that a player's stats are counted under. Players that haven't joined through character
+/// selection are counted under the default profile.
+fn get_player_profile_id(
+    players_resource: &PlayersResource,
+    player_id: PlayerIDComponent,
+) -> usize {
+    players_resource
+        .get_profile_id(player_id)
+        .unwrap_or(DEFAULT_USER_ID)
+}
+
+/// Profiles of every player in the game. Shared outcomes such as victories are counted for all of
+/// them.
+pub(crate) fn get_joined_profile_ids(players_resource: &PlayersResource) -> Vec<usize> {
+    let mut profile_ids = players_resource.get_used_profile_ids();
+    profile_ids.sort_unstable();
+    profile_ids.dedup();
+    if profile_ids.is_empty() {
+        profile_ids.push(DEFAULT_USER_ID);
+    }
+    profile_ids
+}
+
+/// Counts the outcome of the game that just ended, and how far it got through each of its
+/// levels, for every player in it
+fn count_completed_game_system(
     mut user_stats: ResMut<UserStatsByPlayerForCompletedGamesCache>,
+    mut level_stats: ResMut<LevelStatsByPlayerForCompletedGames>,
+    completed_run: Res<CompletedRunResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    players_resource: Res<PlayersResource>,
 ) {
-    (**user_stats)
-        .entry(DEFAULT_USER_ID)
-        .or_default()
-        .total_games_lost += 1;
+    let Some(run) = &**completed_run else {
+        return;
+    };
+
+    for profile_id in get_joined_profile_ids(&players_resource) {
+        (**user_stats)
+            .entry(profile_id)
+            .or_default()
+            .add_completed_run(run);
+
+        let profile_level_stats = (**level_stats).entry(profile_id).or_default();
+        for level in run_res.completed_levels.iter() {
+            profile_level_stats
+                .entry(level.name.clone())
+                .or_default()
+                .add_attempt(Some(level.level_time.elapsed().as_millis() as usize));
+        }
+        if let Some(level) = &run_res.current_level {
+            profile_level_stats
+                .entry(level.name.clone())
+                .or_default()
+                .add_attempt(None);
+        }
+    }
 }
 
+/// Credits destroyed enemies to the player that last damaged them. Enemies that weren't destroyed
+/// by a player aren't counted.
 fn inc_in_memory_mob_destroyed_for_current_game_cache(
     mut mobs_destroyed_counters_by_player: ResMut<MobKillsByPlayerForCurrentGame>,
+    mut stats_by_player: ResMut<StatsByPlayerForCurrentGame>,
     mut mob_destroyed_event_reader: EventReader<MobDestroyedEvent>,
+    players_resource: Res<PlayersResource>,
 ) {
-    let player_1_mob_counters = (**mobs_destroyed_counters_by_player)
-        .entry(DEFAULT_USER_ID)
-        .or_insert_with(Default::default);
     for event in mob_destroyed_event_reader.read() {
-        if let MobType::Enemy(enemy_type) = &event.mob_type {
-            inc_usize_map(player_1_mob_counters, *enemy_type);
+        if let (MobType::Enemy(enemy_type), Some(player_id)) = (&event.mob_type, event.killed_by) {
+            let profile_id = get_player_profile_id(&players_resource, player_id);
+            let mob_counters = (**mobs_destroyed_counters_by_player)
+                .entry(profile_id)
+                .or_default();
+            inc_usize_map(mob_counters, *enemy_type);
+            inc_usize_map(
+                &mut stats_by_player.entry(player_id).or_default().mobs_killed,
+                *enemy_type,
+            );
         }
     }
 }
-fn find_player_1<'a, 'b: 'a>(
-    player_query: &'a Query<(Entity, &'b PlayerIDComponent)>,
-) -> Option<Entity> {
-    player_query
-        .iter()
-        .find(|(_, id)| matches!(id, PlayerIDComponent::One))
-        .map(|(entity, _)| entity)
-}
-fn mob_projectile_collision_originates_from_entity(
-    collision: &SortedCollisionEvent,
-    entity: &Entity,
-) -> bool {
-    if let Some(projectile_source_id) = match collision {
+fn get_mob_projectile_collision_source(collision: &SortedCollisionEvent) -> Option<Entity> {
+    match collision {
         SortedCollisionEvent::MobToProjectileIntersection {
             projectile_source, ..
-        } => Some(projectile_source),
+        } => Some(*projectile_source),
         SortedCollisionEvent::MobToProjectileContact {
             projectile_source, ..
-        } => Some(projectile_source),
+        } => Some(*projectile_source),
         _ => None,
-    } {
-        return *projectile_source_id == *entity;
     }
-    false
 }
 fn inc_in_memory_projectile_hits_counter_system(
     mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
+    mut stats_by_player: ResMut<StatsByPlayerForCurrentGame>,
     mut collision_event_reader: EventReader<SortedCollisionEvent>,
-    player_query: Query<(Entity, &PlayerIDComponent)>,
+    player_query: Query<&PlayerIDComponent>,
+    players_resource: Res<PlayersResource>,
 ) {
-    if let Some(player_1_entity_id) = find_player_1(&player_query) {
-        let n_player_1_hit_shots = collision_event_reader
-            .read()
-            .filter(|c| mob_projectile_collision_originates_from_entity(c, &player_1_entity_id))
-            .count();
-        if let Some(ref mut player_1_user_stats) =
-            (**current_game_user_stats).get_mut(&DEFAULT_USER_ID)
+    for collision in collision_event_reader.read() {
+        if let Some(player_id) = get_mob_projectile_collision_source(collision)
+            .and_then(|source| player_query.get(source).ok())
         {
-            player_1_user_stats.total_shots_hit += n_player_1_hit_shots;
+            let profile_id = get_player_profile_id(&players_resource, *player_id);
+            if let Some(ref mut user_stats) = (**current_game_user_stats).get_mut(&profile_id) {
+                user_stats.total_shots_hit += 1;
+            }
+            stats_by_player
+                .entry(*player_id)
+                .or_default()
+                .user_stat
+                .total_shots_hit += 1;
         }
     }
 }
 
-fn count_shots_fired_by_player_1_system(
+fn count_shots_fired_by_players_system(
     mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
+    mut stats_by_player: ResMut<StatsByPlayerForCurrentGame>,
     mut fire_weapon_event_reader: EventReader<FireWeaponEvent>,
-    query: Query<(Entity, &PlayerIDComponent)>,
+    player_query: Query<&PlayerIDComponent>,
+    players_resource: Res<PlayersResource>,
 ) {
-    let maybe_player_1_entity_id = query
-        .iter()
-        .find(|(_, id)| matches!(id, PlayerIDComponent::One))
-        .map(|(x, _)| x);
-    let n_p1_shots_fired = match maybe_player_1_entity_id {
-        Some(player_1) => fire_weapon_event_reader
-            .read()
-            .filter(|x| x.source_entity == player_1)
-            .count(),
-        None => 0,
-    };
-    if n_p1_shots_fired > 0 {
-        debug!(
-            "Incrementing total player 1 shots by {}, n_p1_shots_fired",
-            n_p1_shots_fired
-        );
-        current_game_user_stats
-            .entry(DEFAULT_USER_ID)
-            .and_modify(|x| {
-                x.total_shots_fired += n_p1_shots_fired;
-            })
-            .or_insert_with(|| UserStat {
-                total_shots_fired: n_p1_shots_fired,
-                ..Default::default()
-            });
+    for event in fire_weapon_event_reader.read() {
+        if let Ok(player_id) = player_query.get(event.source_entity) {
+            let profile_id = get_player_profile_id(&players_resource, *player_id);
+            debug!("Incrementing total shots for profile {}", profile_id);
+            current_game_user_stats
+                .entry(profile_id)
+                .or_default()
+                .total_shots_fired += 1;
+            stats_by_player
+                .entry(*player_id)
+                .or_default()
+                .user_stat
+                .total_shots_fired += 1;
+        }
     }
 }
 /// Analagous to "log rolling" except we merge counters and add integers.
@@ -154,6 +198,7 @@ fn roll_current_game_counters_into_completed_game_metrics(
     mut mobs_destroyed_counters_by_player: ResMut<MobKillsByPlayerForCurrentGame>,
     mut historical_games_shot_counts: ResMut<UserStatsByPlayerForCompletedGamesCache>,
     mut historical_games_enemy_mob_kill_counts: ResMut<MobKillsByPlayerForCompletedGames>,
+    mut stats_by_player: ResMut<StatsByPlayerForCurrentGame>,
 ) {
     debug!(
         "mobs_destroyed_counters_by_player : {:?}",
@@ -181,6 +226,7 @@ fn roll_current_game_counters_into_completed_game_metrics(
             .or_insert(current_game_stats.clone());
     }
     current_game_user_stats.clear();
+    stats_by_player.clear();
 }
 
 #[cfg(test)]
@@ -190,18 +236,28 @@ mod test {
     use crate::collision::SortedCollisionEvent;
     use crate::game::counters::plugin::CountingMetricsPlugin;
     use crate::player::{CharactersResource, PlayerPlugin};
+    use crate::run::level::Level;
+    use crate::run::CurrentRunProgressResource;
     use crate::spawnable::FireWeaponEvent;
     use bevy::input::InputPlugin;
     use bevy::math::Vec2;
-    use bevy::prelude::{App, Component, Events};
+    use bevy::prelude::{App, Component, Events, NextState};
     use bevy::state::app::{AppExtStates, StatesPlugin};
+    use bevy::time::Stopwatch;
     use bevy::MinimalPlugins;
+    use std::collections::VecDeque;
     use thetawave_interface::audio::SoundEffectType;
     use thetawave_interface::character::{Character, CharacterType};
     use thetawave_interface::game::historical_metrics::{
-        MobKillsByPlayerForCurrentGame, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
+        LevelStat, LevelStatsByPlayerForCompletedGames, MobKillsByPlayerForCurrentGame,
+        StatsByPlayerForCurrentGame, UserStat, UserStatsByPlayerForCompletedGamesCache,
+        UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
+    };
+    use thetawave_interface::game::run_history::{CompletedRunResource, RunRecord};
+    use thetawave_interface::player::{
+        PlayerBundle, PlayerData, PlayerIDComponent, PlayerInput, PlayersResource,
     };
-    use thetawave_interface::player::PlayerBundle;
+    use thetawave_interface::run::{DifficultyType, RunOutcomeType};
     use thetawave_interface::spawnable::{
         EnemyMobType, Faction, MobDestroyedEvent, MobType, ProjectileType, SpawnPosition,
     };
@@ -241,6 +297,7 @@ mod test {
             mob_type: MobType::Enemy(EnemyMobType::Drone),
             entity,
             is_boss: false,
+            killed_by: Some(PlayerIDComponent::One),
         });
         app.update();
         let got_mob_kills = app
@@ -252,6 +309,48 @@ mod test {
         assert_eq!(got_mob_kills.get(&EnemyMobType::Drone).unwrap(), &1);
     }
     #[test]
+    fn test_mob_kills_are_credited_to_the_killing_player() {
+        let mut app = base_app_required_for_counting_metrics();
+        let player_data = |profile_id| {
+            Some(PlayerData {
+                character: CharacterType::from("captain"),
+                input: PlayerInput::Keyboard,
+                profile_id,
+            })
+        };
+        app.insert_resource(PlayersResource {
+            player_data: vec![player_data(3), player_data(5)],
+        });
+
+        let entity = app.world_mut().spawn(NullComponent::default()).id();
+        for killed_by in [Some(PlayerIDComponent::Two), None] {
+            app.world_mut().send_event(MobDestroyedEvent {
+                mob_type: MobType::Enemy(EnemyMobType::Drone),
+                entity,
+                is_boss: false,
+                killed_by,
+            });
+        }
+        app.update();
+
+        let mob_kills = app.world().resource::<MobKillsByPlayerForCurrentGame>();
+        assert!(mob_kills.get(&3).is_none());
+        assert_eq!(
+            mob_kills.get(&5).unwrap().get(&EnemyMobType::Drone),
+            Some(&1)
+        );
+        let stats_by_player = app.world().resource::<StatsByPlayerForCurrentGame>();
+        assert!(stats_by_player.get(&PlayerIDComponent::One).is_none());
+        assert_eq!(
+            stats_by_player
+                .get(&PlayerIDComponent::Two)
+                .unwrap()
+                .mobs_killed
+                .get(&EnemyMobType::Drone),
+            Some(&1)
+        );
+    }
+    #[test]
     fn test_increment_player_1_shot_counter() {
         let mut app = base_app_required_for_counting_metrics();
 
@@ -260,7 +359,7 @@ mod test {
             .get_resource::<CharactersResource>()
             .unwrap()
             .characters
-            .get(&CharacterType::Captain)
+            .get(&CharacterType::from("captain"))
             .cloned()
             .unwrap();
         let player_1: PlayerBundle = PlayerBundle::from(&player_1_character);
@@ -313,4 +412,74 @@ mod test {
             .total_shots_fired;
         assert_eq!(n_p1_shots_fired_2, 2);
     }
+
+    fn level_named(name: &str) -> Level {
+        Level {
+            completed_phases: VecDeque::new(),
+            current_phase: None,
+            queued_phases: VecDeque::new(),
+            objective: None,
+            level_time: Stopwatch::new(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_victory_is_counted_with_its_levels() {
+        let mut app = base_app_required_for_counting_metrics();
+        app.insert_resource(CompletedRunResource(Some(RunRecord {
+            start_time: None,
+            duration_secs: 90.0,
+            characters: vec![CharacterType::from("captain")],
+            run_key: "test".to_string(),
+            difficulty: DifficultyType::Normal,
+            levels_completed: 2,
+            outcome: RunOutcomeType::Victory,
+            money_collected: 0,
+            damage_taken: 0,
+            seed: 0,
+            score: 0,
+        })));
+        app.insert_resource(CurrentRunProgressResource {
+            completed_levels: VecDeque::from([level_named("first"), level_named("second")]),
+            ..Default::default()
+        });
+        app.world_mut()
+            .resource_mut::<NextState<AppStates>>()
+            .set(AppStates::Victory);
+        app.update();
+
+        let user_stat = app
+            .world()
+            .resource::<UserStatsByPlayerForCompletedGamesCache>()
+            .get(&DEFAULT_USER_ID)
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            user_stat,
+            UserStat {
+                total_games_won: 1,
+                total_levels_completed: 2,
+                most_levels_completed: 2,
+                fastest_victory_millis: Some(90000),
+                ..Default::default()
+            }
+        );
+
+        let level_stats = app
+            .world()
+            .resource::<LevelStatsByPlayerForCompletedGames>()
+            .get(&DEFAULT_USER_ID)
+            .cloned()
+            .unwrap();
+        assert_eq!(level_stats.len(), 2);
+        assert_eq!(
+            level_stats.get("second"),
+            Some(&LevelStat {
+                times_reached: 1,
+                times_completed: 1,
+                best_time_millis: Some(0),
+            })
+        );
+    }
 }
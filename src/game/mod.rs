@@ -1,8 +1,13 @@
 //! `thetawave` game module
 use bevy::app::{App, Plugin};
 use ron::de::from_bytes;
+use thetawave_interface::game::options::GameOptionsChangedEvent;
+use thetawave_interface::game::profiles::{ProfileEvent, ProfilesResource};
+pub mod achievements;
+pub mod compendium;
 pub mod counters;
 mod resources;
+pub mod unlocks;
 
 pub use self::resources::GameParametersResource;
 
@@ -16,5 +21,8 @@ impl Plugin for GamePlugin {
             ))
             .unwrap(),
         );
+        app.init_resource::<ProfilesResource>();
+        app.add_event::<ProfileEvent>();
+        app.add_event::<GameOptionsChangedEvent>();
     }
 }
@@ -22,7 +22,7 @@ pub struct GameParametersResource {
     pub sprite_scale: f32,
     /// Threshold to set velocity to zero
     pub stop_threshold: f32,
-    /// Range of mouse scanning
+    /// Distance from the scanner cursor that mobs are scanned within
     pub scan_range: f32,
     /// Maximum amount of player inputs to the game
     max_players: u8,
@@ -0,0 +1,130 @@
+//! Awards the meta-currency and character unlocks that carry over between runs.
+use bevy::prelude::{info, App, OnEnter, Plugin, Res, ResMut, State};
+use thetawave_interface::game::historical_metrics::{
+    MobKillsByPlayerForCompletedGames, MobKillsByPlayerForCurrentGame, MobsKilledBy1PlayerCacheT,
+    MobsKilledByPlayerCacheT,
+};
+use thetawave_interface::game::profiles::ProfilesResource;
+use thetawave_interface::game::unlocks::CharacterUnlocksResource;
+use thetawave_interface::player::PlayersResource;
+use thetawave_interface::states::AppStates;
+
+use super::counters::plugin::get_joined_profile_ids;
+use crate::player::CharactersResource;
+use crate::run::CurrentRunProgressResource;
+
+/// Meta-currency earned for each enemy mob destroyed in a run
+const META_CURRENCY_PER_KILL: usize = 1;
+/// Meta-currency earned for each level completed in a run
+const META_CURRENCY_PER_LEVEL: usize = 25;
+/// Meta-currency earned for winning a run
+const META_CURRENCY_FOR_VICTORY: usize = 100;
+
+/// Keeps `CharacterUnlocksResource` up to date at the end of each run. Persisting it is left to
+/// the storage plugin.
+pub struct UnlocksPlugin;
+
+impl Plugin for UnlocksPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CharacterUnlocksResource::default());
+        app.add_systems(
+            OnEnter(AppStates::GameOver),
+            award_meta_currency_and_unlocks_system,
+        );
+        app.add_systems(
+            OnEnter(AppStates::Victory),
+            award_meta_currency_and_unlocks_system,
+        );
+    }
+}
+
+/// Enemy mobs destroyed in the current game by every player in it, whichever profile their kills
+/// are counted under
+fn count_run_kills(
+    current_game_mob_kills: &MobsKilledByPlayerCacheT,
+    profile_ids: &[usize],
+) -> usize {
+    profile_ids
+        .iter()
+        .filter_map(|profile_id| current_game_mob_kills.get(profile_id))
+        .flat_map(|kills| kills.values())
+        .sum()
+}
+
+/// Pays out meta-currency for the run that just ended, counting the kills of every player, and
+/// unlocks every character whose unlock condition is met by the active profile's lifetime kills.
+#[allow(clippy::too_many_arguments)]
+fn award_meta_currency_and_unlocks_system(
+    mut character_unlocks: ResMut<CharacterUnlocksResource>,
+    characters_res: Res<CharactersResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    app_state: Res<State<AppStates>>,
+    current_game_mob_kills: Res<MobKillsByPlayerForCurrentGame>,
+    completed_games_mob_kills: Res<MobKillsByPlayerForCompletedGames>,
+    profiles: Res<ProfilesResource>,
+    players_resource: Res<PlayersResource>,
+) {
+    let run_kills = count_run_kills(
+        &current_game_mob_kills,
+        &get_joined_profile_ids(&players_resource),
+    );
+
+    let earned = run_kills * META_CURRENCY_PER_KILL
+        + run_res.completed_levels.len() * META_CURRENCY_PER_LEVEL
+        + if matches!(app_state.get(), AppStates::Victory) {
+            META_CURRENCY_FOR_VICTORY
+        } else {
+            0
+        };
+    character_unlocks.meta_currency += earned;
+    info!("Earned {} meta-currency", earned);
+
+    // the current game is only rolled into the completed games when the next game starts
+    let mut lifetime_kills: MobsKilledBy1PlayerCacheT = completed_games_mob_kills
+        .get(&profiles.active_profile_id)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(current_game_kills) = current_game_mob_kills.get(&profiles.active_profile_id) {
+        for (mob_type, n_killed) in current_game_kills {
+            *lifetime_kills.entry(*mob_type).or_default() += n_killed;
+        }
+    }
+
+    for (character_type, character) in characters_res.characters.iter() {
+        if let Some(condition) = &character.unlock_condition {
+            if condition.is_met(&lifetime_kills)
+                && character_unlocks
+                    .unlocked_characters
+                    .insert(character_type.clone())
+            {
+                info!("Unlocked character {}", character.name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::count_run_kills;
+    use thetawave_interface::game::historical_metrics::MobsKilledByPlayerCacheT;
+    use thetawave_interface::spawnable::EnemyMobType;
+
+    #[test]
+    fn test_run_kills_count_every_joined_profile() {
+        let current_game_mob_kills = MobsKilledByPlayerCacheT::from([
+            (
+                3,
+                [(EnemyMobType::Drone, 2), (EnemyMobType::Pawn, 1)]
+                    .into_iter()
+                    .collect(),
+            ),
+            (5, [(EnemyMobType::Drone, 4)].into_iter().collect()),
+            // a profile that isn't in the run
+            (7, [(EnemyMobType::Drone, 10)].into_iter().collect()),
+        ]);
+
+        assert_eq!(count_run_kills(&current_game_mob_kills, &[3, 5]), 7);
+        assert_eq!(count_run_kills(&current_game_mob_kills, &[5]), 4);
+        assert_eq!(count_run_kills(&current_game_mob_kills, &[0]), 0);
+    }
+}
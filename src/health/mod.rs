@@ -4,7 +4,8 @@ use bevy::prelude::{
     App, Entity, EventReader, EventWriter, Plugin, Query, Res, Time, Transform, Update,
 };
 use thetawave_interface::{
-    health::{DamageDealtEvent, HealthComponent},
+    health::{DamageDealtEvent, HealthComponent, LastDamagedByPlayerComponent},
+    player::{PlayerDamagedEvent, PlayerIDComponent, PlayerInvulnerabilityComponent},
     spawnable::{EffectType, TextEffectType},
 };
 /// Includes systems to decrease a player's health and regenerate their shields over time.
@@ -13,6 +14,7 @@ pub(super) struct HealthPlugin;
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DamageDealtEvent>()
+            .add_event::<PlayerDamagedEvent>()
             .add_systems(Update, (damage_system, regenerate_shields_system));
     }
 }
@@ -24,14 +26,47 @@ fn regenerate_shields_system(mut health_query: Query<&mut HealthComponent>, time
     }
 }
 
-/// Receive damage dealt events, apply damage, and spawn effects
+/// Receive damage dealt events, apply damage, and spawn effects. Players ignore damage while
+/// invulnerable and become invulnerable after each hit. Entities that can be destroyed by players
+/// remember which player damaged them last.
 fn damage_system(
     mut damage_dealt_events: EventReader<DamageDealtEvent>,
-    mut health_query: Query<(Entity, &Transform, &mut HealthComponent)>,
+    mut health_query: Query<(
+        Entity,
+        &Transform,
+        &mut HealthComponent,
+        Option<(&PlayerIDComponent, &mut PlayerInvulnerabilityComponent)>,
+        Option<&mut LastDamagedByPlayerComponent>,
+    )>,
+    player_id_query: Query<&PlayerIDComponent>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
+    mut player_damaged_event_writer: EventWriter<PlayerDamagedEvent>,
 ) {
     for event in damage_dealt_events.read() {
-        if let Ok((_entity, transform, mut health_component)) = health_query.get_mut(event.target) {
+        if let Ok((entity, transform, mut health_component, maybe_player, maybe_last_damaged_by)) =
+            health_query.get_mut(event.target)
+        {
+            if let Some((player_id, mut invulnerability)) = maybe_player {
+                if invulnerability.is_invulnerable() {
+                    continue;
+                }
+                invulnerability.start();
+
+                player_damaged_event_writer.send(PlayerDamagedEvent {
+                    player_entity: entity,
+                    player_id: *player_id,
+                    damage: event.damage,
+                    source: event.source,
+                });
+            }
+
+            if let (Some(mut last_damaged_by), Ok(attacker_id)) = (
+                maybe_last_damaged_by,
+                player_id_query.get(event.source.get_attacker()),
+            ) {
+                last_damaged_by.0 = Some(*attacker_id);
+            }
+
             // take damage from health
             health_component.take_damage(event.damage);
 
@@ -49,3 +84,93 @@ fn damage_system(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::damage_system;
+    use crate::spawnable::SpawnEffectEvent;
+    use bevy::app::App;
+    use bevy::prelude::{Entity, Events, Transform, Update};
+    use bevy::MinimalPlugins;
+    use thetawave_interface::health::{DamageDealtEvent, DamageSource, HealthComponent};
+    use thetawave_interface::player::{
+        PlayerDamagedEvent, PlayerIDComponent, PlayerInvulnerabilityComponent,
+    };
+
+    fn minimal_app_for_damage() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<DamageDealtEvent>()
+            .add_event::<PlayerDamagedEvent>()
+            .add_event::<SpawnEffectEvent>()
+            .add_systems(Update, damage_system);
+        app
+    }
+
+    /// Sends two hits on the target within the same frame
+    fn hit_twice(app: &mut App, target: Entity) {
+        let attacker = app.world_mut().spawn_empty().id();
+        for _ in 0..2 {
+            app.world_mut().send_event(DamageDealtEvent {
+                damage: 10,
+                target,
+                source: DamageSource::Mob(attacker),
+            });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn test_player_is_damaged_once_per_invulnerability_window() {
+        let mut app = minimal_app_for_damage();
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                HealthComponent::new(100, 0, 1.0),
+                PlayerIDComponent::One,
+                PlayerInvulnerabilityComponent::new(1.0, 0.0),
+            ))
+            .id();
+
+        hit_twice(&mut app, player);
+
+        let health = app.world().get::<HealthComponent>(player).unwrap();
+        assert_eq!(health.get_health(), 90);
+        assert!(app
+            .world()
+            .get::<PlayerInvulnerabilityComponent>(player)
+            .unwrap()
+            .is_invulnerable());
+
+        let player_damaged_events: Vec<&PlayerDamagedEvent> = app
+            .world()
+            .resource::<Events<PlayerDamagedEvent>>()
+            .iter_current_update_events()
+            .collect();
+        assert_eq!(player_damaged_events.len(), 1);
+        assert_eq!(player_damaged_events[0].player_entity, player);
+        assert_eq!(player_damaged_events[0].damage, 10);
+    }
+
+    #[test]
+    fn test_mobs_take_every_hit() {
+        let mut app = minimal_app_for_damage();
+        let mob = app
+            .world_mut()
+            .spawn((Transform::default(), HealthComponent::new(100, 0, 1.0)))
+            .id();
+
+        hit_twice(&mut app, mob);
+
+        let health = app.world().get::<HealthComponent>(mob).unwrap();
+        assert_eq!(health.get_health(), 80);
+        assert_eq!(
+            app.world()
+                .resource::<Events<PlayerDamagedEvent>>()
+                .iter_current_update_events()
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,437 @@
+//! Exposes a plugin that awards the players experience for destroying mobs and pauses the game
+//! into an upgrade draft each time they level up.
+use bevy::prelude::{
+    in_state, App, Children, EventReader, EventWriter, IntoSystemConfigs, NextState, OnEnter,
+    Plugin, Query, Res, ResMut, Resource, Time, Timer, TimerMode, Update, With, Without,
+};
+use bevy_rapier2d::prelude::RapierConfiguration;
+use leafwing_input_manager::prelude::ActionState;
+use rand::{seq::SliceRandom, Rng};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use std::collections::HashMap;
+use thetawave_interface::{
+    abilities::{AbilityCooldownComponent, AbilitySlotIDComponent},
+    audio::{PlaySoundEffectEvent, SoundEffectType},
+    health::HealthComponent,
+    input::PlayerAction,
+    localization::LocalizationResource,
+    player::{
+        PlayerComponent, PlayerDownedComponent, PlayerIDComponent, PlayerMovementComponent,
+        PlayerOutgoingDamageComponent,
+    },
+    spawnable::{EnemyMobType, MobDestroyedEvent, MobType},
+    states::{AppStates, GameStates},
+};
+
+use crate::{run::RunRngResource, GameUpdateSet};
+
+/// Seconds before draft inputs are accepted, so that players firing when the draft opens don't
+/// immediately confirm an upgrade
+const DRAFT_INPUT_DELAY: f32 = 0.5;
+
+/// Awards experience and runs the upgrade draft between levels.
+pub(super) struct LevelUpPlugin;
+
+impl Plugin for LevelUpPlugin {
+    fn build(&self, app: &mut App) {
+        let level_up_res =
+            from_bytes::<LevelUpResource>(include_bytes!("../../assets/data/level_up.ron"))
+                .unwrap();
+
+        app.insert_resource(ExperienceResource::new(
+            level_up_res.base_experience_to_level,
+        ))
+        .insert_resource(level_up_res)
+        .init_resource::<UpgradeDraftResource>();
+
+        app.add_systems(OnEnter(AppStates::InitializeRun), reset_experience_system);
+
+        app.add_systems(
+            Update,
+            (gain_experience_system, start_upgrade_draft_system)
+                .chain()
+                .in_set(GameUpdateSet::ChangeState)
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+
+        app.add_systems(
+            Update,
+            upgrade_draft_input_system
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::LevelUpDraft)),
+        );
+    }
+}
+
+/// How rare an upgrade is, used to weight how often it is offered in a draft
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum UpgradeRarity {
+    Common,
+    Rare,
+    Epic,
+}
+
+/// Stat change applied to a player when an upgrade is picked
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum UpgradeType {
+    /// Adds to the number of projectiles fired by the player's weapon
+    ProjectileCount(usize),
+    /// Adds to the damage dealt by the player's weapon
+    WeaponDamage(usize),
+    /// Multiplies the cooldown of the player's weapon (slot one) ability
+    FireRate(f32),
+    /// Multiplies the cooldown of the player's special (slot two) ability
+    AbilityCooldown(f32),
+    /// Multiplies the speed of the player's projectiles
+    ProjectileSpeed(f32),
+    /// Adds to the player's max health and heals them by the same amount
+    MaxHealth(usize),
+    /// Multiplies the player's acceleration, deceleration, and max speed
+    MovementSpeed(f32),
+}
+
+/// An upgrade that can be offered in a draft
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct UpgradeData {
+    /// Names the `level_up.upgrade.<key>.name` and `level_up.upgrade.<key>.description` string ids
+    pub key: String,
+    pub rarity: UpgradeRarity,
+    pub upgrade_type: UpgradeType,
+}
+
+impl UpgradeData {
+    pub fn get_name_id(&self) -> String {
+        format!("level_up.upgrade.{}.name", self.key)
+    }
+
+    pub fn get_description_id(&self) -> String {
+        format!("level_up.upgrade.{}.description", self.key)
+    }
+
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
+        localization.get(&self.get_name_id())
+    }
+
+    pub fn get_description(&self, localization: &LocalizationResource) -> String {
+        localization.get(&self.get_description_id())
+    }
+}
+
+/// Experience rules and the upgrade pool, loaded from `level_up.ron`
+#[derive(Resource, Deserialize)]
+pub(crate) struct LevelUpResource {
+    /// Experience awarded for destroying each type of enemy mob
+    pub mob_experience: HashMap<EnemyMobType, usize>,
+    /// Experience awarded for enemy mobs missing from `mob_experience`
+    pub default_mob_experience: usize,
+    /// Multiplies the experience awarded for destroying a boss
+    pub boss_experience_multiplier: usize,
+    /// Experience needed to reach the first level up
+    pub base_experience_to_level: usize,
+    /// Multiplies the experience needed for each following level up
+    pub experience_growth: f32,
+    /// Number of upgrades offered to each player in a draft
+    pub choices_per_draft: usize,
+    /// Relative chance of an upgrade of each rarity being offered
+    pub rarity_weights: HashMap<UpgradeRarity, f32>,
+    /// Every upgrade that can be offered in a draft
+    pub upgrades: Vec<UpgradeData>,
+}
+
+impl LevelUpResource {
+    /// Experience awarded for a destroyed mob, only enemy mobs award experience
+    fn get_experience(&self, event: &MobDestroyedEvent) -> usize {
+        if let MobType::Enemy(enemy_type) = &event.mob_type {
+            let experience = self
+                .mob_experience
+                .get(enemy_type)
+                .copied()
+                .unwrap_or(self.default_mob_experience);
+
+            if event.is_boss {
+                experience * self.boss_experience_multiplier
+            } else {
+                experience
+            }
+        } else {
+            0
+        }
+    }
+
+    /// Picks distinct upgrades from the pool, weighted by rarity
+    fn roll_upgrades(&self, rng: &mut impl Rng) -> Vec<UpgradeData> {
+        self.upgrades
+            .choose_multiple_weighted(rng, self.choices_per_draft, |upgrade| {
+                self.rarity_weights
+                    .get(&upgrade.rarity)
+                    .copied()
+                    .unwrap_or(0.0)
+            })
+            .map(|upgrades| upgrades.cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Experience shared by all players over the course of a run
+#[derive(Resource, Debug)]
+pub(crate) struct ExperienceResource {
+    /// Experience gained towards the next level
+    pub experience: usize,
+    /// Experience needed to reach the next level
+    pub experience_to_next_level: usize,
+    /// Current level of the players
+    pub level: usize,
+    /// Level ups that have not been drafted yet
+    pub pending_level_ups: usize,
+}
+
+impl ExperienceResource {
+    fn new(experience_to_next_level: usize) -> Self {
+        Self {
+            experience: 0,
+            experience_to_next_level,
+            level: 1,
+            pending_level_ups: 0,
+        }
+    }
+
+    /// Adds experience, queueing a level up for each threshold passed
+    fn gain(&mut self, experience: usize, growth: f32) {
+        self.experience += experience;
+
+        while self.experience_to_next_level > 0 && self.experience >= self.experience_to_next_level
+        {
+            self.experience -= self.experience_to_next_level;
+            self.level += 1;
+            self.pending_level_ups += 1;
+            self.experience_to_next_level =
+                (self.experience_to_next_level as f32 * growth).ceil() as usize;
+        }
+    }
+}
+
+/// Upgrades offered to a single player in the current draft
+#[derive(Debug)]
+pub(crate) struct PlayerUpgradeDraft {
+    pub player_id: PlayerIDComponent,
+    pub options: Vec<UpgradeData>,
+    /// Index of the highlighted option
+    pub selected: usize,
+    /// Whether the player has locked in the highlighted option
+    pub confirmed: bool,
+}
+
+/// The draft that is open while in `GameStates::LevelUpDraft`
+#[derive(Resource, Default)]
+pub(crate) struct UpgradeDraftResource {
+    pub drafts: Vec<PlayerUpgradeDraft>,
+    /// Ignores inputs for a short time after the draft opens
+    pub input_delay: Timer,
+}
+
+/// Resets experience at the start of each run
+fn reset_experience_system(
+    mut experience_res: ResMut<ExperienceResource>,
+    level_up_res: Res<LevelUpResource>,
+    mut draft_res: ResMut<UpgradeDraftResource>,
+) {
+    *experience_res = ExperienceResource::new(level_up_res.base_experience_to_level);
+    *draft_res = UpgradeDraftResource::default();
+}
+
+/// Awards experience for each destroyed mob
+fn gain_experience_system(
+    mut mob_destroyed_event_reader: EventReader<MobDestroyedEvent>,
+    mut experience_res: ResMut<ExperienceResource>,
+    level_up_res: Res<LevelUpResource>,
+) {
+    for event in mob_destroyed_event_reader.read() {
+        experience_res.gain(
+            level_up_res.get_experience(event),
+            level_up_res.experience_growth,
+        );
+    }
+}
+
+/// Opens an upgrade draft for every standing player when a level up is pending
+fn start_upgrade_draft_system(
+    mut experience_res: ResMut<ExperienceResource>,
+    level_up_res: Res<LevelUpResource>,
+    mut draft_res: ResMut<UpgradeDraftResource>,
+    mut run_rng: ResMut<RunRngResource>,
+    player_query: Query<
+        &PlayerIDComponent,
+        (With<PlayerComponent>, Without<PlayerDownedComponent>),
+    >,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    if experience_res.pending_level_ups == 0 || player_query.is_empty() {
+        return;
+    }
+
+    experience_res.pending_level_ups -= 1;
+
+    // players roll in order, so that a run's seed gives each of them the same options
+    let mut player_ids: Vec<PlayerIDComponent> = player_query.iter().copied().collect();
+    player_ids.sort_by_key(|player_id| usize::from(*player_id));
+
+    let drafts: Vec<PlayerUpgradeDraft> = player_ids
+        .into_iter()
+        .map(|player_id| PlayerUpgradeDraft {
+            player_id,
+            options: level_up_res.roll_upgrades(&mut run_rng.0),
+            selected: 0,
+            confirmed: false,
+        })
+        .collect();
+
+    *draft_res = UpgradeDraftResource {
+        drafts,
+        input_delay: Timer::from_seconds(DRAFT_INPUT_DELAY, TimerMode::Once),
+    };
+
+    next_game_state.set(GameStates::LevelUpDraft);
+
+    // suspend the physics engine
+    rapier_config.physics_pipeline_active = false;
+    rapier_config.query_pipeline_active = false;
+}
+
+/// Lets each player move between and confirm their upgrade options. Once every player has
+/// confirmed, the upgrades are applied and the game resumes.
+#[allow(clippy::too_many_arguments)]
+fn upgrade_draft_input_system(
+    mut draft_res: ResMut<UpgradeDraftResource>,
+    mut player_query: Query<(
+        &PlayerIDComponent,
+        &ActionState<PlayerAction>,
+        &mut PlayerOutgoingDamageComponent,
+        &mut PlayerMovementComponent,
+        &mut HealthComponent,
+        &Children,
+    )>,
+    mut ability_query: Query<(&mut AbilityCooldownComponent, &AbilitySlotIDComponent)>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    time: Res<Time>,
+) {
+    draft_res.input_delay.tick(time.delta());
+    if !draft_res.input_delay.finished() {
+        return;
+    }
+
+    for draft in draft_res.drafts.iter_mut() {
+        let Some((_, action_state, ..)) = player_query
+            .iter()
+            .find(|(player_id, ..)| **player_id == draft.player_id)
+        else {
+            // players that are no longer around can't pick an upgrade
+            draft.confirmed = true;
+            continue;
+        };
+
+        if draft.confirmed || draft.options.is_empty() {
+            draft.confirmed = true;
+            continue;
+        }
+
+        let n_options = draft.options.len();
+        if action_state.just_pressed(&PlayerAction::MoveLeft) {
+            draft.selected = (draft.selected + n_options - 1) % n_options;
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ButtonSelect,
+            });
+        } else if action_state.just_pressed(&PlayerAction::MoveRight) {
+            draft.selected = (draft.selected + 1) % n_options;
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ButtonSelect,
+            });
+        } else if action_state.just_pressed(&PlayerAction::SlotOneAbility) {
+            draft.confirmed = true;
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ButtonConfirm,
+            });
+        }
+    }
+
+    if !draft_res.drafts.iter().all(|draft| draft.confirmed) {
+        return;
+    }
+
+    for draft in draft_res.drafts.drain(..) {
+        let Some(upgrade) = draft.options.get(draft.selected) else {
+            continue;
+        };
+
+        for (player_id, _, mut player_damage, mut player_movement, mut health, children) in
+            player_query.iter_mut()
+        {
+            if *player_id != draft.player_id {
+                continue;
+            }
+
+            match upgrade.upgrade_type {
+                UpgradeType::ProjectileCount(count) => player_damage.projectile_count += count,
+                UpgradeType::WeaponDamage(damage) => player_damage.weapon_damage += damage,
+                UpgradeType::ProjectileSpeed(multiplier) => {
+                    player_damage.projectile_speed *= multiplier
+                }
+                UpgradeType::MaxHealth(health_increase) => {
+                    health.increase_max_health(health_increase);
+                    health.heal(health_increase);
+                }
+                UpgradeType::MovementSpeed(multiplier) => {
+                    player_movement.acceleration *= multiplier;
+                    player_movement.deceleration *= multiplier;
+                    player_movement.speed *= multiplier;
+                }
+                UpgradeType::FireRate(multiplier) | UpgradeType::AbilityCooldown(multiplier) => {
+                    let upgraded_slot = if matches!(upgrade.upgrade_type, UpgradeType::FireRate(_))
+                    {
+                        AbilitySlotIDComponent::One
+                    } else {
+                        AbilitySlotIDComponent::Two
+                    };
+
+                    for child in children.iter() {
+                        if let Ok((mut ability_cooldown, slot_id)) = ability_query.get_mut(*child) {
+                            if *slot_id == upgraded_slot {
+                                ability_cooldown.base_cooldown_time *= multiplier;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    next_game_state.set(GameStates::Playing);
+
+    // resume the physics engine
+    rapier_config.physics_pipeline_active = true;
+    rapier_config.query_pipeline_active = true;
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExperienceResource;
+
+    #[test]
+    fn test_experience_queues_a_level_up_for_each_threshold_passed() {
+        let mut experience = ExperienceResource::new(10);
+
+        experience.gain(9, 2.0);
+        assert_eq!(experience.pending_level_ups, 0);
+
+        // 10 to reach level 2, then 20 to reach level 3, leaving 4 experience left over
+        experience.gain(25, 2.0);
+        assert_eq!(experience.level, 3);
+        assert_eq!(experience.pending_level_ups, 2);
+        assert_eq!(experience.experience, 4);
+        assert_eq!(experience.experience_to_next_level, 40);
+    }
+}
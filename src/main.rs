@@ -24,6 +24,7 @@ mod camera;
 mod collision;
 mod game;
 mod health;
+mod level_up;
 mod loot;
 mod options;
 mod player;
@@ -122,13 +123,23 @@ fn main() {
     #[cfg(target_arch = "wasm32")]
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
+    let opts =
+        options::GameInitCLIOptions::from_environ_on_supported_platforms_with_default_fallback();
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "cli", feature = "storage"))]
+    if let Some(command) = &opts.save_data {
+        if let Err(e) = command.run() {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     generate_config_files();
 
     let display_config = get_display_config();
 
-    let opts =
-        options::GameInitCLIOptions::from_environ_on_supported_platforms_with_default_fallback();
     let mut app = build_app(
         our_default_plugins(display_config, &opts),
         our_game_plugins(&opts),
@@ -192,6 +203,10 @@ impl PluginGroup for ThetawaveGamePlugins {
             .add(animation::SpriteAnimationPlugin)
             .add(states::StatesPlugin)
             .add(game::counters::plugin::CountingMetricsPlugin)
+            .add(game::unlocks::UnlocksPlugin)
+            .add(game::achievements::AchievementsPlugin)
+            .add(game::compendium::CompendiumPlugin)
+            .add(level_up::LevelUpPlugin)
             .add(health::HealthPlugin)
             .add(weapon::WeaponPlugin)
             .add(
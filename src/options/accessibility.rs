@@ -0,0 +1,97 @@
+//! Colors of the color palette options, and systems to apply the other accessibility options.
+use bevy::{
+    color::{
+        palettes::css::{AQUA, CRIMSON, RED},
+        Srgba,
+    },
+    ecs::system::{Res, ResMut},
+    ui::UiScale,
+};
+use thetawave_interface::{
+    game::options::{ColorPalette, GameOptions},
+    spawnable::Faction,
+};
+
+/// Colors that are drawn differently for each color palette. Faction and border gradient colors
+/// are multiplied over their sprites, which are already colored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PaletteColors {
+    pub ally: Srgba,
+    pub enemy: Srgba,
+    pub neutral: Srgba,
+    pub warning_gradient: Srgba,
+    pub defense_gradient: Srgba,
+    pub health: Srgba,
+    pub shields: Srgba,
+    pub boss_health: Srgba,
+}
+
+impl PaletteColors {
+    pub(crate) fn get_faction_color(&self, faction: &Faction) -> Srgba {
+        match faction {
+            Faction::Ally => self.ally,
+            Faction::Enemy => self.enemy,
+            Faction::Neutral => self.neutral,
+        }
+    }
+}
+
+/// Multiplies each channel of a color by the tint's, like sprite colors are
+pub(crate) fn tint_color(color: Srgba, tint: Srgba) -> Srgba {
+    Srgba::new(
+        color.red * tint.red,
+        color.green * tint.green,
+        color.blue * tint.blue,
+        color.alpha * tint.alpha,
+    )
+}
+
+const STANDARD_COLORS: PaletteColors = PaletteColors {
+    ally: Srgba::WHITE,
+    enemy: Srgba::WHITE,
+    neutral: Srgba::WHITE,
+    warning_gradient: Srgba::WHITE,
+    defense_gradient: Srgba::WHITE,
+    health: CRIMSON,
+    shields: AQUA,
+    boss_health: RED,
+};
+
+/// Orange against blue, which can be told apart without telling red from green
+const RED_GREEN_SAFE_COLORS: PaletteColors = PaletteColors {
+    ally: Srgba::rgb(0.35, 0.7, 1.0),
+    enemy: Srgba::rgb(1.0, 0.6, 0.0),
+    neutral: Srgba::WHITE,
+    warning_gradient: Srgba::rgb(1.0, 0.6, 0.0),
+    defense_gradient: Srgba::rgb(0.35, 0.7, 1.0),
+    health: Srgba::rgb(0.9, 0.6, 0.0),
+    shields: Srgba::rgb(0.35, 0.7, 0.9),
+    boss_health: Srgba::rgb(0.9, 0.6, 0.0),
+};
+
+/// Red against teal, which can be told apart without telling blue from yellow
+const BLUE_YELLOW_SAFE_COLORS: PaletteColors = PaletteColors {
+    ally: Srgba::rgb(0.0, 0.8, 0.75),
+    enemy: Srgba::rgb(1.0, 0.3, 0.4),
+    neutral: Srgba::WHITE,
+    warning_gradient: Srgba::rgb(1.0, 0.3, 0.4),
+    defense_gradient: Srgba::rgb(0.0, 0.8, 0.75),
+    health: Srgba::rgb(0.85, 0.2, 0.35),
+    shields: Srgba::rgb(0.0, 0.65, 0.6),
+    boss_health: Srgba::rgb(0.85, 0.2, 0.35),
+};
+
+pub(crate) fn get_palette_colors(color_palette: ColorPalette) -> &'static PaletteColors {
+    match color_palette {
+        ColorPalette::Standard => &STANDARD_COLORS,
+        ColorPalette::Deuteranopia | ColorPalette::Protanopia => &RED_GREEN_SAFE_COLORS,
+        ColorPalette::Tritanopia => &BLUE_YELLOW_SAFE_COLORS,
+    }
+}
+
+/// Scales all UI text and `Val::Px` sizes by the UI scale option
+pub(super) fn apply_ui_scale_system(game_options: Res<GameOptions>, mut ui_scale: ResMut<UiScale>) {
+    if ui_scale.0 != game_options.ui_scale {
+        ui_scale.0 = game_options.ui_scale;
+    }
+}
@@ -2,12 +2,13 @@
 use crate::game::GameParametersResource;
 use bevy::{
     input::ButtonInput,
-    prelude::{Camera2d, Entity, KeyCode, NonSend, Query, Res, With},
+    prelude::{Camera2d, Entity, EventWriter, KeyCode, NonSend, Query, Res, With},
     render::camera::OrthographicProjection,
     window::{PrimaryWindow, Window, WindowMode},
     winit::WinitWindows,
 };
 use serde::Deserialize;
+use thetawave_interface::game::options::{DisplayMode, GameOptions, GameOptionsChangedEvent};
 use winit::window::Icon;
 
 /// Display settings of the window
@@ -37,34 +38,53 @@ impl From<DisplayConfig> for Window {
     }
 }
 
-// TODO: fix this function, doesn't toggle back to windowed correctly
-/// Toggles the window between full screen and windowed on key press
+/// Toggles the display mode option between full screen and windowed on key press
 pub(super) fn toggle_fullscreen_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    game_options: Res<GameOptions>,
+    mut game_options_changed_event_writer: EventWriter<GameOptionsChangedEvent>,
 ) {
-    // get primary window
-    //let window = windows.get_primary_mut().unwrap();
-    let mut primary_window = window_query.get_single_mut().unwrap();
     // get input for toggling full screen
-    let fullscreen_input = keyboard_input.just_released(KeyCode::KeyF);
-
-    // set window mode to the mode it's not in
-    if fullscreen_input {
-        let new_mode = match primary_window.mode {
-            WindowMode::BorderlessFullscreen { .. } => {
-                primary_window.set_maximized(false);
-                WindowMode::Windowed
-            }
-            WindowMode::Windowed => {
-                primary_window.set_maximized(true);
-                WindowMode::BorderlessFullscreen
-            }
-            _ => primary_window.mode,
+    if keyboard_input.just_released(KeyCode::KeyF) {
+        // set display mode to the mode it's not in
+        let display_mode = match game_options.display_mode {
+            DisplayMode::Windowed => DisplayMode::BorderlessFullscreen,
+            DisplayMode::BorderlessFullscreen => DisplayMode::Windowed,
         };
 
+        game_options_changed_event_writer.send(GameOptionsChangedEvent(GameOptions {
+            display_mode,
+            ..game_options.clone()
+        }));
+    }
+}
+
+// TODO: fix this function, doesn't switch back to windowed correctly
+/// Sets the window mode to the display mode option, and the window size to the resolution option
+/// while windowed
+pub(super) fn apply_display_mode_system(
+    game_options: Res<GameOptions>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut primary_window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let new_mode = match game_options.display_mode {
+        DisplayMode::Windowed => WindowMode::Windowed,
+        DisplayMode::BorderlessFullscreen => WindowMode::BorderlessFullscreen,
+    };
+
+    if primary_window.mode != new_mode {
+        primary_window.set_maximized(new_mode != WindowMode::Windowed);
         primary_window.mode = new_mode;
     }
+
+    if let (WindowMode::Windowed, Some((width, height))) =
+        (new_mode, game_options.window_resolution)
+    {
+        primary_window.resolution.set(width as f32, height as f32);
+    }
 }
 
 /// Toggles a zoomed out camera perspective on key press
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::InputMap;
 use leafwing_input_manager::{prelude::ActionState, InputManagerBundle};
-use serde::Deserialize;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use thetawave_interface::game::options::GameOptions;
 use thetawave_interface::input::{InputsResource, MainMenuExplorer, MenuAction, PlayerAction};
 
 /// Spawns entity to track navigation over menus
@@ -14,7 +17,46 @@ pub fn spawn_menu_explorer_system(mut commands: Commands, inputs_res: Res<Inputs
         .insert(MainMenuExplorer);
 }
 
-#[derive(Deserialize)]
+/// Rebuilds `InputsResource` from the key bindings saved in the game options, or from
+/// `config/input.ron` when none are saved. Players that join afterwards use the new bindings.
+pub(super) fn apply_key_bindings_system(
+    game_options: Res<GameOptions>,
+    mut applied_key_bindings: Local<Option<String>>,
+    mut inputs_res: ResMut<InputsResource>,
+    mut menu_explorer_query: Query<&mut InputMap<MenuAction>, With<MainMenuExplorer>>,
+) {
+    // `config/input.ron` is already applied when the game starts
+    if *applied_key_bindings == game_options.key_bindings {
+        return;
+    }
+    *applied_key_bindings = game_options.key_bindings.clone();
+
+    let bindings = get_current_input_bindings(&game_options);
+
+    *inputs_res = InputsResource::from(bindings);
+    for mut input_map in menu_explorer_query.iter_mut() {
+        *input_map = inputs_res.menu.clone();
+    }
+}
+
+/// The key bindings saved in the game options, or the bindings from `config/input.ron` when none
+/// are saved
+pub(crate) fn get_current_input_bindings(game_options: &GameOptions) -> InputBindings {
+    match game_options
+        .key_bindings
+        .as_deref()
+        .map(ron::from_str::<InputBindings>)
+    {
+        Some(Ok(bindings)) => bindings,
+        Some(Err(e)) => {
+            error!("Failed to parse saved key bindings, using config/input.ron. {e}");
+            get_input_bindings()
+        }
+        None => get_input_bindings(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InputBindings {
     pub menu_keyboard: Vec<(MenuAction, KeyCode)>,
     pub menu_gamepad: Vec<(MenuAction, GamepadButtonType)>,
@@ -23,6 +65,182 @@ pub struct InputBindings {
     pub player_mouse: Vec<(PlayerAction, MouseButton)>,
 }
 
+/// The kinds of device that actions can be bound to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum InputDevice {
+    Keyboard,
+    Mouse,
+    Gamepad,
+}
+
+/// Any action that can be rebound
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum BindableAction {
+    Menu(MenuAction),
+    Player(PlayerAction),
+}
+
+/// A single key or button that an action is bound to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum BoundInput {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl BoundInput {
+    pub(crate) fn device(&self) -> InputDevice {
+        match self {
+            BoundInput::Key(_) => InputDevice::Keyboard,
+            BoundInput::Mouse(_) => InputDevice::Mouse,
+            BoundInput::Gamepad(_) => InputDevice::Gamepad,
+        }
+    }
+}
+
+fn inputs_bound_to<A: PartialEq, B: Copy>(bindings: &[(A, B)], action: A) -> Vec<B> {
+    bindings
+        .iter()
+        .filter(|(bound_action, _)| *bound_action == action)
+        .map(|(_, input)| *input)
+        .collect()
+}
+
+/// Replaces every binding of the action with the input
+fn rebind_to<A: PartialEq + Copy, B>(bindings: &mut Vec<(A, B)>, action: A, input: B) {
+    bindings.retain(|(bound_action, _)| *bound_action != action);
+    bindings.push((action, input));
+}
+
+impl InputBindings {
+    /// The bindings that ship with the game, as written to `config/input.ron` when it is missing
+    pub(crate) fn default_bindings() -> Self {
+        ron::de::from_bytes::<InputBindings>(include_bytes!("input.ron"))
+            .expect("Failed to parse the default InputBindings from 'input.ron'")
+    }
+
+    /// The inputs of one device that the action is bound to. Menus can't be navigated with mouse
+    /// buttons, so menu actions have no mouse bindings.
+    pub(crate) fn get_bindings(
+        &self,
+        action: BindableAction,
+        device: InputDevice,
+    ) -> Vec<BoundInput> {
+        match (action, device) {
+            (BindableAction::Menu(action), InputDevice::Keyboard) => {
+                inputs_bound_to(&self.menu_keyboard, action)
+                    .into_iter()
+                    .map(BoundInput::Key)
+                    .collect()
+            }
+            (BindableAction::Menu(_), InputDevice::Mouse) => vec![],
+            (BindableAction::Menu(action), InputDevice::Gamepad) => {
+                inputs_bound_to(&self.menu_gamepad, action)
+                    .into_iter()
+                    .map(BoundInput::Gamepad)
+                    .collect()
+            }
+            (BindableAction::Player(action), InputDevice::Keyboard) => {
+                inputs_bound_to(&self.player_keyboard, action)
+                    .into_iter()
+                    .map(BoundInput::Key)
+                    .collect()
+            }
+            (BindableAction::Player(action), InputDevice::Mouse) => {
+                inputs_bound_to(&self.player_mouse, action)
+                    .into_iter()
+                    .map(BoundInput::Mouse)
+                    .collect()
+            }
+            (BindableAction::Player(action), InputDevice::Gamepad) => {
+                inputs_bound_to(&self.player_gamepad, action)
+                    .into_iter()
+                    .map(BoundInput::Gamepad)
+                    .collect()
+            }
+        }
+    }
+
+    /// Binds the action to the input in place of its other bindings on the input's device.
+    /// Returns false if the action can't be bound to that device.
+    pub(crate) fn rebind(&mut self, action: BindableAction, input: BoundInput) -> bool {
+        match (action, input) {
+            (BindableAction::Menu(action), BoundInput::Key(key)) => {
+                rebind_to(&mut self.menu_keyboard, action, key)
+            }
+            (BindableAction::Menu(_), BoundInput::Mouse(_)) => return false,
+            (BindableAction::Menu(action), BoundInput::Gamepad(button)) => {
+                rebind_to(&mut self.menu_gamepad, action, button)
+            }
+            (BindableAction::Player(action), BoundInput::Key(key)) => {
+                rebind_to(&mut self.player_keyboard, action, key)
+            }
+            (BindableAction::Player(action), BoundInput::Mouse(button)) => {
+                rebind_to(&mut self.player_mouse, action, button)
+            }
+            (BindableAction::Player(action), BoundInput::Gamepad(button)) => {
+                rebind_to(&mut self.player_gamepad, action, button)
+            }
+        }
+        true
+    }
+
+    /// Removes every binding of the action on the device
+    pub(crate) fn unbind(&mut self, action: BindableAction, device: InputDevice) {
+        match (action, device) {
+            (BindableAction::Menu(action), InputDevice::Keyboard) => {
+                self.menu_keyboard.retain(|(a, _)| *a != action)
+            }
+            (BindableAction::Menu(_), InputDevice::Mouse) => {}
+            (BindableAction::Menu(action), InputDevice::Gamepad) => {
+                self.menu_gamepad.retain(|(a, _)| *a != action)
+            }
+            (BindableAction::Player(action), InputDevice::Keyboard) => {
+                self.player_keyboard.retain(|(a, _)| *a != action)
+            }
+            (BindableAction::Player(action), InputDevice::Mouse) => {
+                self.player_mouse.retain(|(a, _)| *a != action)
+            }
+            (BindableAction::Player(action), InputDevice::Gamepad) => {
+                self.player_gamepad.retain(|(a, _)| *a != action)
+            }
+        }
+    }
+
+    /// Other actions of the same kind (menu or player) that are bound to the input. Actions that
+    /// share an input in the default bindings are used on different screens, like confirming and
+    /// joining, so they don't conflict.
+    pub(crate) fn get_conflicts(
+        &self,
+        action: BindableAction,
+        input: BoundInput,
+        defaults: &InputBindings,
+    ) -> Vec<BindableAction> {
+        let other_actions: Vec<BindableAction> = match action {
+            BindableAction::Menu(_) => MenuAction::iter().map(BindableAction::Menu).collect(),
+            BindableAction::Player(_) => PlayerAction::iter().map(BindableAction::Player).collect(),
+        };
+        let device = input.device();
+        let default_inputs = defaults.get_bindings(action, device);
+
+        other_actions
+            .into_iter()
+            .filter(|other| *other != action)
+            .filter(|other| self.get_bindings(*other, device).contains(&input))
+            .filter(|other| {
+                !defaults
+                    .get_bindings(*other, device)
+                    .iter()
+                    .any(|default_input| default_inputs.contains(default_input))
+            })
+            .collect()
+    }
+
+    pub(crate) fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, PrettyConfig::default())
+    }
+}
+
 impl From<InputBindings> for InputsResource {
     fn from(bindings: InputBindings) -> Self {
         InputsResource {
@@ -37,19 +255,105 @@ impl From<InputBindings> for InputsResource {
     }
 }
 
+/// The bindings in `config/input.ron`. The file is edited by the player, so the default bindings
+/// are used if it can't be read.
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) fn get_input_bindings() -> InputBindings {
     use ron::from_str;
     use std::{env::current_dir, fs::read_to_string};
 
-    let config_path = current_dir().unwrap().join("config");
+    let config_path = current_dir().unwrap().join("config").join("input.ron");
 
-    from_str::<InputBindings>(&read_to_string(config_path.join("input.ron")).unwrap()).unwrap()
+    match read_to_string(config_path).map(|contents| from_str::<InputBindings>(&contents)) {
+        Ok(Ok(bindings)) => bindings,
+        Ok(Err(e)) => {
+            error!("Failed to parse config/input.ron, using the default bindings. {e}");
+            InputBindings::default_bindings()
+        }
+        Err(e) => {
+            error!("Failed to read config/input.ron, using the default bindings. {e}");
+            InputBindings::default_bindings()
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 pub(super) fn get_input_bindings() -> InputBindings {
-    use ron::de::from_bytes;
+    InputBindings::default_bindings()
+}
+
+/// Writes rebound bindings to `config/input.ron`, so that they are also used by profiles without
+/// saved bindings
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save_input_bindings(bindings: &InputBindings) {
+    use std::env::current_dir;
+
+    let config_path = current_dir().unwrap().join("config").join("input.ron");
+
+    match bindings.to_ron() {
+        Ok(contents) => std::fs::write(config_path, contents)
+            .unwrap_or_else(|e| error!("Failed to write config/input.ron. {e}")),
+        Err(e) => error!("Failed to serialize key bindings. {e}"),
+    }
+}
+
+/// There are no config files on the web, bindings are only saved with the game options
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save_input_bindings(_bindings: &InputBindings) {}
+
+#[cfg(test)]
+mod test {
+    use super::{BindableAction, BoundInput, InputBindings, InputDevice};
+    use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode};
+    use thetawave_interface::input::{MenuAction, PlayerAction};
 
-    from_bytes::<InputBindings>(include_bytes!("input.ron")).unwrap()
+    #[test]
+    fn test_rebinding_replaces_the_bindings_of_one_device() {
+        let mut bindings = InputBindings::default_bindings();
+        let move_up = BindableAction::Player(PlayerAction::MoveUp);
+
+        assert!(bindings.rebind(move_up, BoundInput::Key(KeyCode::KeyI)));
+        assert_eq!(
+            bindings.get_bindings(move_up, InputDevice::Keyboard),
+            vec![BoundInput::Key(KeyCode::KeyI)]
+        );
+        assert_eq!(
+            bindings.get_bindings(move_up, InputDevice::Gamepad),
+            vec![BoundInput::Gamepad(GamepadButtonType::DPadUp)]
+        );
+        assert!(!bindings.rebind(
+            BindableAction::Menu(MenuAction::Confirm),
+            BoundInput::Mouse(bevy::input::mouse::MouseButton::Left)
+        ));
+
+        // the bindings survive being saved
+        let saved = bindings.to_ron().unwrap();
+        assert_eq!(ron::from_str::<InputBindings>(&saved).unwrap(), bindings);
+    }
+
+    #[test]
+    fn test_only_new_shared_bindings_conflict() {
+        let defaults = InputBindings::default_bindings();
+        let mut bindings = defaults.clone();
+        let move_down = BindableAction::Player(PlayerAction::MoveDown);
+        let key_w = BoundInput::Key(KeyCode::KeyW);
+
+        assert!(bindings
+            .get_conflicts(move_down, key_w, &defaults)
+            .is_empty());
+        bindings.rebind(move_down, key_w);
+        assert_eq!(
+            bindings.get_conflicts(move_down, key_w, &defaults),
+            vec![BindableAction::Player(PlayerAction::MoveUp)]
+        );
+
+        // confirming and joining share enter by default
+        assert!(bindings
+            .get_conflicts(
+                BindableAction::Menu(MenuAction::Confirm),
+                BoundInput::Key(KeyCode::Enter),
+                &defaults
+            )
+            .is_empty());
+    }
 }
@@ -0,0 +1,126 @@
+//! Loads the string tables shipped in `assets/data/locales` and switches between them when the
+//! language option changes.
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use strum::IntoEnumIterator;
+use thetawave_interface::game::options::GameOptions;
+use thetawave_interface::localization::{Language, LocalizationResource, StringTable};
+
+/// Contents of the string table file of a language
+fn get_string_table_bytes(language: Language) -> &'static [u8] {
+    match language {
+        Language::English => include_bytes!("../../assets/data/locales/en.ron"),
+        Language::Spanish => include_bytes!("../../assets/data/locales/es.ron"),
+    }
+}
+
+/// String tables of every language, with English text chosen
+pub(super) fn get_localization_resource() -> LocalizationResource {
+    LocalizationResource {
+        language: Language::default(),
+        string_tables: Language::iter()
+            .map(|language| {
+                (
+                    language,
+                    from_bytes::<StringTable>(get_string_table_bytes(language)).unwrap(),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Switches to the language chosen in the game options
+pub(super) fn apply_language_system(
+    game_options: Res<GameOptions>,
+    mut localization: ResMut<LocalizationResource>,
+) {
+    if localization.language != game_options.language {
+        localization.language = game_options.language;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::get_localization_resource;
+    use crate::game::achievements::AchievementsResource;
+    use crate::level_up::LevelUpResource;
+    use ron::de::from_bytes;
+    use std::collections::BTreeSet;
+    use strum::IntoEnumIterator;
+    use thetawave_interface::localization::Language;
+
+    /// Names of the `{name}` placeholders in a string
+    fn get_placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_language_has_every_string() {
+        let localization = get_localization_resource();
+        let english = &localization.string_tables[&Language::English];
+
+        for language in Language::iter() {
+            let string_table = &localization.string_tables[&language];
+            for (id, english_text) in english.iter() {
+                let Some(text) = string_table.get(id) else {
+                    panic!("{language:?} is missing {id}");
+                };
+                assert_eq!(
+                    get_placeholders(text),
+                    get_placeholders(english_text),
+                    "{language:?} has different placeholders in {id}"
+                );
+            }
+            for id in string_table.keys() {
+                assert!(english.contains_key(id), "{language:?} has unknown {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_data_files_only_use_known_strings() {
+        let localization = get_localization_resource();
+        let english = &localization.string_tables[&Language::English];
+
+        let level_up =
+            from_bytes::<LevelUpResource>(include_bytes!("../../assets/data/level_up.ron"))
+                .unwrap();
+        let achievements = from_bytes::<AchievementsResource>(include_bytes!(
+            "../../assets/data/achievements.ron"
+        ))
+        .unwrap();
+
+        let ids = level_up
+            .upgrades
+            .iter()
+            .flat_map(|upgrade| [upgrade.get_name_id(), upgrade.get_description_id()])
+            .chain(achievements.achievements.iter().flat_map(|achievement| {
+                [achievement.get_name_id(), achievement.get_description_id()]
+            }));
+        for id in ids {
+            assert!(english.contains_key(&id), "English is missing {id}");
+        }
+    }
+
+    #[test]
+    fn test_missing_strings_fall_back_to_english() {
+        let mut localization = get_localization_resource();
+        localization.language = Language::Spanish;
+        localization
+            .string_tables
+            .get_mut(&Language::Spanish)
+            .unwrap()
+            .remove("mob.drone");
+
+        assert_eq!(localization.get("mob.drone"), "Drone");
+        assert_eq!(localization.get("mob.pawn"), "Peón");
+        assert_eq!(localization.get("missing.id"), "missing.id");
+        assert_eq!(
+            localization.format("game_over.score", &[("score", "10".to_string())]),
+            "Puntuación: 10"
+        );
+    }
+}
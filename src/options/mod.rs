@@ -3,9 +3,10 @@ use bevy::{
     app::{App, Plugin, Startup, Update},
     core_pipeline::{core_2d::Camera2d, core_3d::Camera3d, tonemapping::Tonemapping},
     ecs::{
+        event::EventReader,
         query::{With, Without},
-        schedule::IntoSystemConfigs,
-        system::{Query, ResMut, Resource},
+        schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+        system::{Query, Res, ResMut, Resource},
     },
     log::error,
     prelude::Deref,
@@ -14,13 +15,17 @@ use bevy::{
 };
 use leafwing_input_manager::prelude::InputManagerPlugin;
 use thetawave_interface::{
-    game::options::GameOptions,
+    game::options::{GameOptions, GameOptionsChangedEvent},
     input::{InputsResource, MenuAction},
     states,
 };
 
+pub(super) mod accessibility;
 pub(super) mod display;
-mod input;
+pub(super) mod input;
+mod localization;
+#[cfg(all(not(target_arch = "wasm32"), feature = "cli", feature = "storage"))]
+pub(super) mod save_data;
 
 use input::get_input_bindings;
 use std::default::Default;
@@ -29,9 +34,13 @@ use std::fs::{DirBuilder, File};
 use std::io::Write;
 use std::path::PathBuf;
 
-use self::display::{set_window_icon, toggle_fullscreen_system, toggle_zoom_system};
+use self::accessibility::apply_ui_scale_system;
+use self::display::{
+    apply_display_mode_system, set_window_icon, toggle_fullscreen_system, toggle_zoom_system,
+};
 
-use self::input::spawn_menu_explorer_system;
+use self::input::{apply_key_bindings_system, spawn_menu_explorer_system};
+use self::localization::{apply_language_system, get_localization_resource};
 
 #[cfg_attr(
     all(not(target_arch = "wasm32"), feature = "cli"),
@@ -52,6 +61,10 @@ pub struct GameInitCLIOptions {
     /// whether to use instructions, serial port IO, etc. specific to deploying on an arcade
     /// machine. This should almost never be enabled.
     pub arcade: bool,
+    #[cfg(all(not(target_arch = "wasm32"), feature = "cli", feature = "storage"))]
+    #[argh(subcommand)]
+    /// export or import saves instead of starting the game
+    pub save_data: Option<save_data::SaveDataCommand>,
 }
 impl GameInitCLIOptions {
     pub fn from_environ_on_supported_platforms_with_default_fallback() -> Self {
@@ -64,8 +77,9 @@ impl GameInitCLIOptions {
     }
 }
 
+/// Applies the graphics options to the cameras
 fn apply_game_options_system(
-    mut game_options: ResMut<GameOptions>,
+    game_options: Res<GameOptions>,
     mut camera_2d_query: Query<
         (&mut Camera, &mut Tonemapping),
         (With<Camera2d>, Without<Camera3d>),
@@ -88,13 +102,24 @@ fn apply_game_options_system(
         } else {
             *tonemapping_2d = Tonemapping::None;
             *tonemapping_3d = Tonemapping::None;
-            game_options.bloom_intensity = 0.0;
         }
     } else {
         error!("Failed to get singleton 2d and 3d cameras to apply game opts");
     }
 }
 
+/// Replaces the game options with the newest changed options
+fn change_game_options_system(
+    mut game_options_changed_event_reader: EventReader<GameOptionsChangedEvent>,
+    mut game_options: ResMut<GameOptions>,
+) {
+    if let Some(GameOptionsChangedEvent(changed_game_options)) =
+        game_options_changed_event_reader.read().last()
+    {
+        *game_options = changed_game_options.clone();
+    }
+}
+
 /// Whether we are playing on an arcade machine. This affects some different UI elements.
 /// Generally this will be set at app startup either inferred or explicitly provided as a game
 /// startup parameter, and should probably not be mutated during the game.
@@ -114,6 +139,7 @@ impl Plugin for OptionsPlugin {
         app.insert_resource(InputsResource::from(get_input_bindings()));
         app.insert_resource(PlayingOnArcadeResource(self.arcade));
         app.insert_resource(GameOptions::default());
+        app.insert_resource(get_localization_resource());
 
         app.add_systems(Startup, spawn_menu_explorer_system);
 
@@ -122,6 +148,23 @@ impl Plugin for OptionsPlugin {
 
         app.add_systems(Update, toggle_fullscreen_system);
 
+        // options are applied as soon as they change, including when they are loaded from a save
+        app.add_systems(
+            Update,
+            (
+                change_game_options_system,
+                (
+                    apply_game_options_system,
+                    apply_display_mode_system,
+                    apply_key_bindings_system,
+                    apply_language_system,
+                    apply_ui_scale_system,
+                )
+                    .run_if(resource_changed::<GameOptions>),
+            )
+                .chain(),
+        );
+
         app.add_systems(
             Update,
             toggle_zoom_system.run_if(in_state(states::AppStates::Game)),
@@ -134,7 +177,8 @@ impl Plugin for OptionsPlugin {
     }
 }
 
-/// Creates config file in config directory from config file in this directory
+/// Creates config file in config directory from config file in this directory, unless the player
+/// already has one
 macro_rules! confgen {
     ( $($filename:expr),* ) => {
         {
@@ -148,16 +192,18 @@ macro_rules! confgen {
             $({
                 let default = include_bytes!($filename);
                 let file_path = conf_dir.join($filename);
-                let mut file = File::create(file_path)
-                    .expect(concat!("Confgen failed: could not create config file ", $filename, "."));
-                file.write_all(default)
-                    .expect(concat!("Confgen failed: could not write config file ", $filename, "."));
+                if !file_path.exists() {
+                    let mut file = File::create(file_path)
+                        .expect(concat!("Confgen failed: could not create config file ", $filename, "."));
+                    file.write_all(default)
+                        .expect(concat!("Confgen failed: could not write config file ", $filename, "."));
+                }
             })*
         }
     }
 }
 
-/// Generates the display config file
+/// Generates the display and input config files that don't exist yet
 pub(super) fn generate_config_files() {
     confgen!("display.ron");
     confgen!("input.ron");
@@ -175,4 +221,31 @@ mod cli_tests {
             Some(std::path::PathBuf::from("myassets/"))
         );
     }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn test_cli_parse_save_data_subcommand() {
+        use super::save_data::{ImportCommand, SaveDataCommand};
+        use thetawave_storage::export::{ExportFormat, ImportConflict};
+
+        assert_eq!(
+            super::GameInitCLIOptions::from_args(
+                &["thetawave"],
+                &["import", "saves.csv", "--on-conflict", "rename"]
+            )
+            .unwrap()
+            .save_data,
+            Some(SaveDataCommand::Import(ImportCommand {
+                input: std::path::PathBuf::from("saves.csv"),
+                format: None,
+                on_conflict: ImportConflict::Rename,
+            }))
+        );
+        assert!(super::GameInitCLIOptions::from_args(
+            &["thetawave"],
+            &["export", "--format", "xml"]
+        )
+        .is_err());
+        assert_eq!("CSV".parse::<ExportFormat>(), Ok(ExportFormat::Csv));
+    }
 }
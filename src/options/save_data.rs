@@ -0,0 +1,87 @@
+//! CLI subcommands to move a profile's saves between machines, or into a spreadsheet.
+use argh::FromArgs;
+use std::fs::{read_to_string, write};
+use std::path::PathBuf;
+use thetawave_storage::export::{
+    export_profile, find_profile_id, import_profile, ExportFormat, ImportConflict,
+};
+use thetawave_storage::profiles::get_last_used_profile_id;
+
+#[derive(FromArgs, Debug, PartialEq, Eq)]
+#[argh(subcommand)]
+pub enum SaveDataCommand {
+    Export(ExportCommand),
+    Import(ImportCommand),
+}
+
+#[derive(FromArgs, Debug, PartialEq, Eq)]
+/// export a profile's stats, kill counts, options and run history instead of starting the game
+#[argh(subcommand, name = "export")]
+pub struct ExportCommand {
+    #[argh(option)]
+    /// name of the profile to export. Defaults to the most recently used profile.
+    pub profile: Option<String>,
+    #[argh(option, default = "ExportFormat::Json")]
+    /// format of the export, json or csv. Defaults to json.
+    pub format: ExportFormat,
+    #[argh(option, short = 'o')]
+    /// file to write the export to. Defaults to stdout.
+    pub output: Option<PathBuf>,
+}
+
+#[derive(FromArgs, Debug, PartialEq, Eq)]
+/// import a profile exported with the export subcommand instead of starting the game
+#[argh(subcommand, name = "import")]
+pub struct ImportCommand {
+    #[argh(positional)]
+    /// file to import the profile from
+    pub input: PathBuf,
+    #[argh(option)]
+    /// format of the file, json or csv. Defaults to the file's extension.
+    pub format: Option<ExportFormat>,
+    #[argh(option, default = "ImportConflict::Abort")]
+    /// what to do when a profile with the same name is already saved: abort, rename or
+    /// overwrite. Defaults to abort.
+    pub on_conflict: ImportConflict,
+}
+
+impl SaveDataCommand {
+    pub fn run(&self) -> Result<(), String> {
+        match self {
+            SaveDataCommand::Export(command) => {
+                let profile_id = match &command.profile {
+                    Some(name) => find_profile_id(name)
+                        .map_err(|e| e.to_string())?
+                        .ok_or_else(|| format!("No profile named {name:?}"))?,
+                    None => get_last_used_profile_id(),
+                };
+                let export =
+                    export_profile(profile_id, command.format).map_err(|e| e.to_string())?;
+
+                match &command.output {
+                    Some(path) => write(path, export).map_err(|e| e.to_string()),
+                    None => {
+                        println!("{export}");
+                        Ok(())
+                    }
+                }
+            }
+            SaveDataCommand::Import(command) => {
+                let format = command.format.unwrap_or_else(|| {
+                    command
+                        .input
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .and_then(|extension| extension.parse().ok())
+                        .unwrap_or_default()
+                });
+                let contents = read_to_string(&command.input).map_err(|e| e.to_string())?;
+
+                let profile_id = import_profile(&contents, format, command.on_conflict)
+                    .map_err(|e| e.to_string())?;
+                println!("Imported profile {profile_id}");
+                Ok(())
+            }
+        }
+    }
+}
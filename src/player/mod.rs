@@ -29,8 +29,10 @@ use self::{
             standard_weapon_ability_system, start_charge_ability_system,
             update_charge_ability_system,
         },
+        hit::{player_damaged_feedback_system, player_invulnerability_system},
         movement::{player_movement_system, player_tilt_system},
         player_death_system, players_reset_system,
+        revive::player_revive_system,
         upgrades::scale_ability_cooldowns_system,
     },
 };
@@ -76,6 +78,9 @@ impl Plugin for PlayerPlugin {
             Update,
             (
                 player_death_system,
+                player_revive_system.after(player_death_system),
+                player_invulnerability_system,
+                player_damaged_feedback_system,
                 player_movement_system.in_set(GameUpdateSet::Movement),
                 player_tilt_system.in_set(GameUpdateSet::Movement),
                 player_ability_cooldown_system,
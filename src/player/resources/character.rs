@@ -9,3 +9,59 @@ pub struct CharactersResource {
     /// Names mapped to characters for all characters
     pub characters: HashMap<CharacterType, Character>,
 }
+
+impl CharactersResource {
+    /// All character keys in the order they are presented to players
+    pub fn get_character_types(&self) -> Vec<CharacterType> {
+        let mut character_types: Vec<CharacterType> = self.characters.keys().cloned().collect();
+        character_types.sort();
+        character_types
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CharactersResource;
+    use bevy::utils::HashMap;
+    use ron::de::from_bytes;
+    use serde::Deserialize;
+    use std::path::Path;
+
+    /// The entries of a dynamic assets file that are used for player images
+    #[derive(Deserialize)]
+    enum ManifestAsset {
+        Files { paths: Vec<String> },
+    }
+
+    #[derive(Deserialize)]
+    struct AssetsManifest(HashMap<String, ManifestAsset>);
+
+    #[test]
+    fn test_every_character_has_images_in_the_assets_manifest() {
+        let characters =
+            from_bytes::<CharactersResource>(include_bytes!("../../../assets/data/characters.ron"))
+                .unwrap();
+        let manifest = from_bytes::<AssetsManifest>(include_bytes!(
+            "../../../assets/player_assets.assets.ron"
+        ))
+        .unwrap();
+
+        let ManifestAsset::Files { paths } = &manifest.0["characters"];
+        let file_stems: Vec<&str> = paths
+            .iter()
+            .filter_map(|path| Path::new(path).file_stem()?.to_str())
+            .collect();
+
+        for character_type in characters.characters.keys() {
+            for file_stem in [
+                format!("{}_character", character_type.as_str()),
+                format!("{}_character_outline", character_type.as_str()),
+            ] {
+                assert!(
+                    file_stems.contains(&file_stem.as_str()),
+                    "{file_stem} is missing from player_assets.assets.ron"
+                );
+            }
+        }
+    }
+}
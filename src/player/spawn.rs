@@ -108,7 +108,7 @@ pub(super) fn spawn_players_system(
             let mut player_entity = commands.spawn_empty();
             player_entity
                 .insert(SpriteBundle {
-                    texture: player_assets.get_asset(&character.character_type),
+                    texture: player_assets.get_asset(&player_data.character),
                     ..Default::default()
                 })
                 .insert(RigidBody::Dynamic)
@@ -169,7 +169,7 @@ pub(super) fn spawn_players_system(
                 player_entity.with_children(|parent| {
                     parent
                         .spawn(SpriteBundle {
-                            texture: player_assets.get_outline_asset(&character.character_type),
+                            texture: player_assets.get_outline_asset(&player_data.character),
                             sprite: Sprite {
                                 color: if matches!(player_id, PlayerIDComponent::One) {
                                     Color::srgb(0.7, 0.0, 0.0)
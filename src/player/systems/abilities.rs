@@ -1,5 +1,6 @@
 use bevy::ecs::entity::Entity;
 use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::query::Without;
 use bevy::ecs::system::{Query, Res};
 use bevy::hierarchy::Children;
 use bevy::math::Vec2;
@@ -14,8 +15,8 @@ use thetawave_interface::abilities::{
 };
 use thetawave_interface::input::PlayerAction;
 use thetawave_interface::player::{
-    PlayerIDComponent, PlayerIncomingDamageComponent, PlayerMovementComponent,
-    PlayerOutgoingDamageComponent,
+    PlayerDownedComponent, PlayerIDComponent, PlayerIncomingDamageComponent,
+    PlayerMovementComponent, PlayerOutgoingDamageComponent,
 };
 use thetawave_interface::weapon::WeaponProjectileData;
 
@@ -35,12 +36,15 @@ pub(in crate::player) fn player_ability_cooldown_system(
 /// and the player has the ability's respective input pressed, sends an ActivateAbilityEvent
 /// and resets the ability's cooldown timer
 pub(in crate::player) fn player_ability_input_system(
-    player_input_query: Query<(
-        &ActionState<PlayerAction>,
-        &PlayerOutgoingDamageComponent,
-        &PlayerIDComponent,
-        &Children,
-    )>,
+    player_input_query: Query<
+        (
+            &ActionState<PlayerAction>,
+            &PlayerOutgoingDamageComponent,
+            &PlayerIDComponent,
+            &Children,
+        ),
+        Without<PlayerDownedComponent>,
+    >,
     mut ability_query: Query<(&mut AbilityCooldownComponent, &AbilitySlotIDComponent)>,
     mut ability_event_writer: EventWriter<ActivateAbilityEvent>,
 ) {
@@ -0,0 +1,86 @@
+//! Systems for feedback when a player takes damage
+
+use bevy::color::Alpha;
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::query::Without;
+use bevy::ecs::system::{Query, Res};
+use bevy::math::Vec3Swizzles;
+use bevy::sprite::Sprite;
+use bevy::time::Time;
+use bevy::transform::components::Transform;
+use bevy_rapier2d::dynamics::Velocity;
+use thetawave_interface::audio::{PlaySoundEffectEvent, SoundEffectType};
+use thetawave_interface::health::DamageSource;
+use thetawave_interface::player::{
+    PlayerDamagedEvent, PlayerDownedComponent, PlayerInvulnerabilityComponent,
+};
+
+/// Seconds between each flash of an invulnerable player's sprite
+const INVULNERABILITY_FLASH_INTERVAL: f32 = 0.1;
+/// Transparency of an invulnerable player's sprite while it is flashed out
+const INVULNERABILITY_FLASH_ALPHA: f32 = 0.3;
+
+/// Ticks invulnerability timers and flashes the sprites of invulnerable players
+pub(in crate::player) fn player_invulnerability_system(
+    mut player_query: Query<
+        (&mut PlayerInvulnerabilityComponent, &mut Sprite),
+        Without<PlayerDownedComponent>,
+    >,
+    time: Res<Time>,
+) {
+    for (mut invulnerability, mut sprite) in player_query.iter_mut() {
+        invulnerability.timer.tick(time.delta());
+
+        if invulnerability.is_invulnerable() {
+            let flashed_out = (invulnerability.timer.elapsed_secs()
+                / INVULNERABILITY_FLASH_INTERVAL) as usize
+                % 2
+                == 0;
+
+            sprite.color.set_alpha(if flashed_out {
+                INVULNERABILITY_FLASH_ALPHA
+            } else {
+                1.0
+            });
+        } else if invulnerability.timer.just_finished() {
+            sprite.color.set_alpha(1.0);
+        }
+    }
+}
+
+/// Knocks damaged players away from whatever damaged them and plays a hit sound
+pub(in crate::player) fn player_damaged_feedback_system(
+    mut player_damaged_event_reader: EventReader<PlayerDamagedEvent>,
+    mut player_query: Query<
+        (&Transform, &mut Velocity, &PlayerInvulnerabilityComponent),
+        Without<PlayerDownedComponent>,
+    >,
+    source_query: Query<&Transform>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+) {
+    for event in player_damaged_event_reader.read() {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: SoundEffectType::PlayerHit,
+        });
+
+        let Ok((transform, mut velocity, invulnerability)) =
+            player_query.get_mut(event.player_entity)
+        else {
+            continue;
+        };
+
+        // projectiles are likely gone by now, so their position at impact is sent with the event
+        let source_position = match event.source {
+            DamageSource::Projectile { position, .. } => Some(position),
+            source => source_query
+                .get(source.get_entity())
+                .ok()
+                .map(|source_transform| source_transform.translation.xy()),
+        };
+
+        if let Some(source_position) = source_position {
+            velocity.linvel += (transform.translation.xy() - source_position).normalize_or_zero()
+                * invulnerability.knockback_speed;
+        }
+    }
+}
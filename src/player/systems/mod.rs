@@ -1,49 +1,92 @@
 //! Systems for managing players
 
 pub mod abilities;
+pub mod hit;
 pub mod movement;
+pub mod revive;
 pub mod upgrades;
 
 use crate::{game::GameParametersResource, spawnable::SpawnEffectEvent};
 
+use bevy::color::Alpha;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::event::EventWriter;
-use bevy::ecs::query::With;
+use bevy::ecs::query::{Has, With};
 use bevy::ecs::system::{Commands, Query, Res, ResMut};
 use bevy::hierarchy::DespawnRecursiveExt;
 use bevy::math::Vec3;
+use bevy::sprite::Sprite;
 use bevy::transform::components::Transform;
 use bevy::utils::default;
+use bevy_rapier2d::dynamics::Velocity;
 use thetawave_interface::audio::{PlaySoundEffectEvent, SoundEffectType};
 use thetawave_interface::health::HealthComponent;
-use thetawave_interface::player::PlayerComponent;
+use thetawave_interface::player::{
+    CoopLivesResource, PlayerComponent, PlayerDownedComponent, PlayerMovementComponent,
+};
 use thetawave_interface::run::{RunDefeatType, RunEndEvent, RunOutcomeType};
 use thetawave_interface::spawnable::EffectType;
 
 use super::PlayersResource;
 
+/// Transparency of a player's sprite while they are downed
+pub(super) const DOWNED_PLAYER_ALPHA: f32 = 0.35;
+
 /// Handle player reaching zero health
+///
+/// A destroyed player is downed if a teammate is still standing and revives remain, otherwise
+/// they come back with a life from the shared pool if there is one, and are despawned if not.
+/// Downed players are handled the same way once the run runs out of revives.
 pub(super) fn player_death_system(
     mut commands: Commands,
     mut effect_event_writer: EventWriter<SpawnEffectEvent>,
-    player_query: Query<(Entity, &Transform, &HealthComponent), With<PlayerComponent>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut HealthComponent,
+            &mut PlayerMovementComponent,
+            &mut Velocity,
+            &mut Sprite,
+            Has<PlayerDownedComponent>,
+        ),
+        With<PlayerComponent>,
+    >,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
     game_parameters: Res<GameParametersResource>,
     mut run_end_event_writer: EventWriter<RunEndEvent>,
+    mut coop_lives: ResMut<CoopLivesResource>,
 ) {
-    // end the game if no players are alive
-    if player_query.iter().count() == 0 {
+    // end the game if no players are left standing
+    if player_query.iter().all(|(.., is_downed)| is_downed) {
         run_end_event_writer.send(RunEndEvent {
             outcome: RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
         });
     }
 
+    // collect the players that are alive and not waiting to be revived
+    let standing_players: Vec<Entity> = player_query
+        .iter()
+        .filter_map(|(entity, _, health, _, _, _, is_downed)| {
+            (!is_downed && !health.is_dead()).then_some(entity)
+        })
+        .collect();
+
     // handle death of player entities
-    for (entity, transform, health) in player_query.iter() {
-        if health.is_dead() {
-            // despawn the player
-            commands.entity(entity).despawn_recursive();
+    for (entity, transform, mut health, mut movement, mut velocity, mut sprite, is_downed) in
+        player_query.iter_mut()
+    {
+        if !health.is_dead() {
+            continue;
+        }
 
+        if is_downed {
+            // downed players already exploded, they wait to be revived while there are revives left
+            if coop_lives.can_revive() {
+                continue;
+            }
+            commands.entity(entity).remove::<PlayerDownedComponent>();
+        } else {
             // spawn explosion effect
             effect_event_writer.send(SpawnEffectEvent {
                 effect_type: EffectType::MobExplosion,
@@ -63,6 +106,27 @@ pub(super) fn player_death_system(
             sound_effect_event_writer.send(PlaySoundEffectEvent {
                 sound_effect_type: SoundEffectType::PlayerExplosion,
             });
+
+            if coop_lives.can_revive() && standing_players.iter().any(|other| *other != entity) {
+                // down the player so that a teammate can revive them
+                movement.movement_enabled = false;
+                *velocity = Velocity::zero();
+                sprite.color.set_alpha(DOWNED_PLAYER_ALPHA);
+                commands
+                    .entity(entity)
+                    .insert(PlayerDownedComponent::new(coop_lives.rules.revive_time));
+                continue;
+            }
+        }
+
+        if coop_lives.use_shared_life() {
+            // bring the player straight back with a life from the shared pool
+            health.full_heal();
+            movement.movement_enabled = true;
+            sprite.color.set_alpha(1.0);
+        } else {
+            // despawn the player
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
@@ -70,3 +134,213 @@ pub(super) fn player_death_system(
 pub(super) fn players_reset_system(mut players_resource: ResMut<PlayersResource>) {
     *players_resource = PlayersResource::default();
 }
+
+#[cfg(test)]
+mod test {
+    use super::{player_death_system, revive::player_revive_system};
+    use crate::{game::GameParametersResource, spawnable::SpawnEffectEvent};
+    use bevy::app::{App, Update};
+    use bevy::ecs::entity::Entity;
+    use bevy::ecs::event::{Event, Events};
+    use bevy::ecs::schedule::IntoSystemConfigs;
+    use bevy::math::Vec2;
+    use bevy::sprite::Sprite;
+    use bevy::transform::components::Transform;
+    use bevy::MinimalPlugins;
+    use bevy_rapier2d::dynamics::Velocity;
+    use ron::de::from_bytes;
+    use thetawave_interface::audio::PlaySoundEffectEvent;
+    use thetawave_interface::health::HealthComponent;
+    use thetawave_interface::player::{
+        CoopLivesResource, CoopRules, PlayerComponent, PlayerDownedComponent,
+        PlayerMovementComponent,
+    };
+    use thetawave_interface::run::{RunDefeatType, RunEndEvent, RunOutcomeType};
+
+    fn base_app_for_player_deaths(rules: CoopRules) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<SpawnEffectEvent>()
+            .add_event::<PlaySoundEffectEvent>()
+            .add_event::<RunEndEvent>()
+            .insert_resource(
+                from_bytes::<GameParametersResource>(include_bytes!(
+                    "../../../assets/data/game_parameters.ron"
+                ))
+                .unwrap(),
+            )
+            .insert_resource(CoopLivesResource::from(rules))
+            .add_systems(
+                Update,
+                (
+                    player_death_system,
+                    player_revive_system.after(player_death_system),
+                ),
+            );
+
+        app
+    }
+
+    fn coop_rules(max_revives: usize, shared_lives: Option<usize>) -> CoopRules {
+        CoopRules {
+            // revives finish on the first frame that a teammate is nearby
+            revive_time: 0.0,
+            revive_distance: 100.0,
+            max_revives,
+            revive_health_percentage: 0.5,
+            shared_lives,
+        }
+    }
+
+    fn spawn_player(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                PlayerComponent,
+                Transform::from_translation(position.extend(0.0)),
+                HealthComponent::new(10, 0, 0.0),
+                PlayerMovementComponent {
+                    acceleration: Vec2::ONE,
+                    deceleration: Vec2::ONE,
+                    speed: Vec2::ONE,
+                    movement_enabled: true,
+                },
+                Velocity::zero(),
+                Sprite::default(),
+            ))
+            .id()
+    }
+
+    fn destroy_player(app: &mut App, player: Entity) {
+        app.world_mut()
+            .get_mut::<HealthComponent>(player)
+            .unwrap()
+            .take_damage(10);
+    }
+
+    fn count_events<E: Event>(app: &App) -> usize {
+        app.world()
+            .resource::<Events<E>>()
+            .iter_current_update_events()
+            .count()
+    }
+
+    fn players_destroyed_sent(app: &App) -> bool {
+        app.world()
+            .resource::<Events<RunEndEvent>>()
+            .iter_current_update_events()
+            .any(|event| event.outcome == RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed))
+    }
+
+    #[test]
+    fn test_player_is_downed_and_revived_by_a_nearby_teammate() {
+        let mut app = base_app_for_player_deaths(coop_rules(1, None));
+        let downed_player = spawn_player(&mut app, Vec2::ZERO);
+        let teammate = spawn_player(&mut app, Vec2::new(500.0, 0.0));
+
+        destroy_player(&mut app, downed_player);
+        app.update();
+        assert!(app
+            .world()
+            .get::<PlayerDownedComponent>(downed_player)
+            .is_some());
+        assert!(
+            !app.world()
+                .get::<PlayerMovementComponent>(downed_player)
+                .unwrap()
+                .movement_enabled
+        );
+        assert_eq!(count_events::<SpawnEffectEvent>(&app), 1);
+
+        // the teammate is too far away to revive them
+        app.update();
+        assert!(app
+            .world()
+            .get::<PlayerDownedComponent>(downed_player)
+            .is_some());
+
+        app.world_mut()
+            .get_mut::<Transform>(teammate)
+            .unwrap()
+            .translation = Vec2::new(50.0, 0.0).extend(0.0);
+        app.update();
+        assert!(app
+            .world()
+            .get::<PlayerDownedComponent>(downed_player)
+            .is_none());
+        assert_eq!(
+            app.world()
+                .get::<HealthComponent>(downed_player)
+                .unwrap()
+                .get_health(),
+            5
+        );
+        assert_eq!(app.world().resource::<CoopLivesResource>().revives_used, 1);
+        assert!(!players_destroyed_sent(&app));
+    }
+
+    #[test]
+    fn test_shared_life_brings_the_last_player_back() {
+        let mut app = base_app_for_player_deaths(coop_rules(0, Some(1)));
+        let player = spawn_player(&mut app, Vec2::ZERO);
+
+        destroy_player(&mut app, player);
+        app.update();
+        let health = app.world().get::<HealthComponent>(player).unwrap();
+        assert!(!health.is_dead());
+        assert_eq!(
+            app.world()
+                .resource::<CoopLivesResource>()
+                .shared_lives_used,
+            1
+        );
+
+        // the pool is empty now
+        destroy_player(&mut app, player);
+        app.update();
+        assert!(app.world().get_entity(player).is_none());
+
+        app.update();
+        assert!(players_destroyed_sent(&app));
+    }
+
+    #[test]
+    fn test_downed_player_explodes_once_when_revives_run_out() {
+        let mut app = base_app_for_player_deaths(coop_rules(1, None));
+        let downed_player = spawn_player(&mut app, Vec2::ZERO);
+        spawn_player(&mut app, Vec2::new(500.0, 0.0));
+
+        destroy_player(&mut app, downed_player);
+        app.update();
+        assert_eq!(count_events::<SpawnEffectEvent>(&app), 1);
+        assert_eq!(count_events::<PlaySoundEffectEvent>(&app), 1);
+
+        // revives are spent elsewhere before the downed player is reached
+        app.world_mut()
+            .resource_mut::<CoopLivesResource>()
+            .revives_used = 1;
+        app.update();
+        assert!(app.world().get_entity(downed_player).is_none());
+        assert_eq!(count_events::<SpawnEffectEvent>(&app), 0);
+        assert_eq!(count_events::<PlaySoundEffectEvent>(&app), 0);
+    }
+
+    #[test]
+    fn test_players_destroyed_once_no_player_is_standing() {
+        let mut app = base_app_for_player_deaths(coop_rules(1, None));
+        let first_player = spawn_player(&mut app, Vec2::ZERO);
+        let second_player = spawn_player(&mut app, Vec2::new(500.0, 0.0));
+
+        destroy_player(&mut app, first_player);
+        app.update();
+        app.update();
+        assert!(!players_destroyed_sent(&app));
+
+        // nobody is left to down the last player
+        destroy_player(&mut app, second_player);
+        app.update();
+        assert!(app.world().get_entity(second_player).is_none());
+
+        app.update();
+        assert!(players_destroyed_sent(&app));
+    }
+}
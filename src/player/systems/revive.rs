@@ -0,0 +1,74 @@
+//! Systems for reviving downed players in co-op
+
+use bevy::color::Alpha;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::EventWriter;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::sprite::Sprite;
+use bevy::time::Time;
+use bevy::transform::components::Transform;
+use thetawave_interface::audio::{PlaySoundEffectEvent, SoundEffectType};
+use thetawave_interface::health::HealthComponent;
+use thetawave_interface::player::{
+    CoopLivesResource, PlayerComponent, PlayerDownedComponent, PlayerMovementComponent,
+};
+
+/// Progresses the revive of downed players while a standing teammate stays close to them
+///
+/// The revive resets when the teammate moves out of range. Players that are downed when the run
+/// runs out of revives are left to `player_death_system`.
+pub(in crate::player) fn player_revive_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut coop_lives: ResMut<CoopLivesResource>,
+    mut downed_query: Query<(
+        Entity,
+        &Transform,
+        &mut PlayerDownedComponent,
+        &mut HealthComponent,
+        &mut PlayerMovementComponent,
+        &mut Sprite,
+    )>,
+    standing_query: Query<&Transform, (With<PlayerComponent>, Without<PlayerDownedComponent>)>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+) {
+    for (entity, transform, mut downed, mut health, mut movement, mut sprite) in
+        downed_query.iter_mut()
+    {
+        if !coop_lives.can_revive() {
+            continue;
+        }
+
+        let teammate_in_range = standing_query.iter().any(|teammate_transform| {
+            teammate_transform
+                .translation
+                .truncate()
+                .distance(transform.translation.truncate())
+                <= coop_lives.rules.revive_distance
+        });
+
+        if !teammate_in_range {
+            downed.revive_timer.reset();
+            continue;
+        }
+
+        downed.revive_timer.tick(time.delta());
+
+        if downed.revive_timer.just_finished() {
+            coop_lives.revives_used += 1;
+
+            health.heal(
+                (health.get_max_health() as f32 * coop_lives.rules.revive_health_percentage).ceil()
+                    as usize,
+            );
+            movement.movement_enabled = true;
+            sprite.color.set_alpha(1.0);
+            commands.entity(entity).remove::<PlayerDownedComponent>();
+
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ConsumablePickup,
+            });
+        }
+    }
+}
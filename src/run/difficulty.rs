@@ -0,0 +1,22 @@
+//! Data for the rules that change with the difficulty of a run
+use bevy::prelude::Resource;
+use serde::Deserialize;
+use std::collections::HashMap;
+use thetawave_interface::{player::CoopRules, run::DifficultyType};
+
+/// Rules for each difficulty, loaded from `difficulties.ron`
+#[derive(Resource, Deserialize)]
+pub(crate) struct DifficultiesResource {
+    pub difficulties: HashMap<DifficultyType, DifficultyData>,
+}
+
+/// Rules that apply to a run of a single difficulty
+#[derive(Deserialize, Clone)]
+pub(crate) struct DifficultyData {
+    /// Revive and shared lives rules
+    pub coop: CoopRules,
+}
+
+/// The difficulty that the next run will be played on
+#[derive(Resource, Default)]
+pub(crate) struct SelectedDifficultyResource(pub DifficultyType);
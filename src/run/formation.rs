@@ -1,13 +1,33 @@
 use bevy::{
+    color::Color,
     log::error,
     math::{Quat, Vec2},
-    prelude::{Event, EventReader, EventWriter, Resource},
+    prelude::{
+        BuildChildren, Children, Commands, Component, DespawnRecursiveExt, Entity, Event,
+        EventReader, EventWriter, Query, Res, Resource, SpatialBundle, Transform, Visibility, With,
+    },
+    sprite::{Sprite, SpriteBundle},
+    time::{Time, Timer, TimerMode},
+    utils::default,
 };
+use rand::Rng;
 use serde::Deserialize;
-use std::collections::HashMap;
-use thetawave_interface::spawnable::{SpawnMobEvent, SpawnableType};
+use std::{collections::HashMap, f32::consts::FRAC_PI_4};
+use thetawave_interface::{
+    game::options::GameOptions,
+    spawnable::{SpawnMobEvent, SpawnableType},
+    states::GameCleanup,
+};
+
+use crate::{arena::ARENA_HALF_SIZE, spawnable::SpawnConsumableEvent, tools::weighted_rng};
 
-use crate::{spawnable::SpawnConsumableEvent, tools::weighted_rng};
+/// Distance from the edge of the arena that markers of mobs coming from off screen are shown at
+const TELEGRAPH_EDGE_MARGIN: f32 = 30.0;
+const TELEGRAPH_MARKER_SIZE: f32 = 24.0;
+const TELEGRAPH_MARKER_COLOR: Color = Color::srgba(1.0, 0.25, 0.2, 0.8);
+const TELEGRAPH_MARKER_Z: f32 = 20.0;
+/// Number of times per second that the markers blink
+const TELEGRAPH_BLINK_RATE: f32 = 4.0;
 
 /// Resource for storing collections of formations of spawnables
 #[derive(Resource, Deserialize)]
@@ -16,7 +36,7 @@ pub struct FormationPoolsResource {
 }
 
 impl FormationPoolsResource {
-    pub fn get_random_formation(&self, pool_key: String) -> Option<Formation> {
+    pub fn get_random_formation(&self, pool_key: String, rng: &mut impl Rng) -> Option<Formation> {
         let formation_pool = match self.formation_pools.get(&pool_key) {
             Some(pool) => pool,
             None => {
@@ -27,7 +47,7 @@ impl FormationPoolsResource {
 
         let weights = formation_pool.iter().map(|x| x.weight).collect();
 
-        let random_idx = weighted_rng(weights);
+        let random_idx = weighted_rng(weights, rng);
 
         formation_pool.get(random_idx).cloned()
     }
@@ -94,17 +114,116 @@ impl Formation {
 pub struct SpawnFormationEvent {
     //pub formation_pool: FormationPoolType,
     pub formation: Formation,
+    /// Seconds that the formation is telegraphed for before it spawns
+    pub lead_time: f32,
+}
+
+/// A formation waiting to spawn, shown by a marker (child entity) where each of its mobs will
+/// come from
+#[derive(Component)]
+pub struct FormationTelegraphComponent {
+    formation: Formation,
+    timer: Timer,
+}
+
+/// Marks where a mob of an upcoming formation will come from
+#[derive(Component)]
+pub struct FormationTelegraphMarkerComponent;
+
+/// Spawns a telegraph of the formation, with its markers kept inside of the arena
+fn spawn_formation_telegraph(commands: &mut Commands, formation: Formation, lead_time: f32) {
+    let marker_positions: Vec<Vec2> = formation
+        .formation_spawnables
+        .iter()
+        .filter(|formation_spawnable| {
+            matches!(formation_spawnable.spawnable_type, SpawnableType::Mob(_))
+        })
+        .map(|formation_spawnable| {
+            formation_spawnable.position.clamp(
+                -ARENA_HALF_SIZE + TELEGRAPH_EDGE_MARGIN,
+                ARENA_HALF_SIZE - TELEGRAPH_EDGE_MARGIN,
+            )
+        })
+        .collect();
+
+    commands
+        .spawn(SpatialBundle::default())
+        .insert(FormationTelegraphComponent {
+            formation,
+            timer: Timer::from_seconds(lead_time, TimerMode::Once),
+        })
+        .insert(GameCleanup)
+        .with_children(|parent| {
+            for position in marker_positions {
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: TELEGRAPH_MARKER_COLOR,
+                            custom_size: Some(Vec2::splat(TELEGRAPH_MARKER_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(position.extend(TELEGRAPH_MARKER_Z))
+                            .with_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+                        ..default()
+                    })
+                    .insert(FormationTelegraphMarkerComponent);
+            }
+        });
 }
 
-/// Manages spawning of formations
+/// Manages spawning of formations, telegraphing them first if they have a lead time
 pub fn spawn_formation_system(
+    mut commands: Commands,
     mut spawn_formation: EventReader<SpawnFormationEvent>,
     mut spawn_consumable: EventWriter<SpawnConsumableEvent>,
     mut spawn_mob: EventWriter<SpawnMobEvent>,
 ) {
     for event in spawn_formation.read() {
-        event
-            .formation
-            .spawn_formation(&mut spawn_consumable, &mut spawn_mob);
+        if event.lead_time > 0.0 {
+            spawn_formation_telegraph(&mut commands, event.formation.clone(), event.lead_time);
+        } else {
+            event
+                .formation
+                .spawn_formation(&mut spawn_consumable, &mut spawn_mob);
+        }
+    }
+}
+
+/// Blinks the markers of telegraphed formations, and spawns each formation once its lead time
+/// is up
+pub fn formation_telegraph_system(
+    mut commands: Commands,
+    mut telegraph_query: Query<(Entity, &mut FormationTelegraphComponent, Option<&Children>)>,
+    mut marker_query: Query<&mut Visibility, With<FormationTelegraphMarkerComponent>>,
+    mut spawn_consumable: EventWriter<SpawnConsumableEvent>,
+    mut spawn_mob: EventWriter<SpawnMobEvent>,
+    game_options: Res<GameOptions>,
+    time: Res<Time>,
+) {
+    for (entity, mut telegraph, children) in telegraph_query.iter_mut() {
+        telegraph.timer.tick(time.delta());
+
+        if telegraph.timer.finished() {
+            telegraph
+                .formation
+                .spawn_formation(&mut spawn_consumable, &mut spawn_mob);
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // markers stay lit when motion is reduced
+        let visibility = if game_options.reduced_motion
+            || (telegraph.timer.elapsed_secs() * TELEGRAPH_BLINK_RATE).fract() < 0.5
+        {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        for child in children.into_iter().flatten() {
+            if let Ok(mut marker_visibility) = marker_query.get_mut(*child) {
+                *marker_visibility = visibility;
+            }
+        }
     }
 }
@@ -0,0 +1,103 @@
+//! Tracks the stats of the current run so that a `RunRecord` can be saved when it ends
+use bevy::prelude::{EventReader, Query, Res, ResMut, Resource, Time};
+use std::collections::HashMap;
+use thetawave_interface::{
+    game::run_history::{CompletedRunResource, RunRecord},
+    player::{PlayerDamagedEvent, PlayerIDComponent, PlayerInventoryComponent, PlayersResource},
+    run::RunOutcomeType,
+};
+
+use super::{
+    difficulty::SelectedDifficultyResource, score::ScoreResource, CurrentRunProgressResource,
+    RestartingRunResource, RunRngResource,
+};
+
+/// Stats for the run being played
+#[derive(Resource, Debug, Default)]
+pub(super) struct RunStatsTrackerResource {
+    /// Seconds spent playing the run
+    pub duration_secs: f32,
+    /// Most money held by each player, players lose their money when they are despawned
+    pub money_by_player: HashMap<usize, usize>,
+    pub damage_taken: usize,
+    /// Seed of the `RunRngResource`, kept when the run is restarted
+    pub seed: u64,
+    /// Set when the run ends, including runs abandoned from the pause menu. Runs without an outcome
+    /// were restarted or quit to desktop.
+    pub outcome: Option<RunOutcomeType>,
+}
+
+/// Starts tracking a new run
+pub(super) fn start_run_stats_system(
+    mut run_stats: ResMut<RunStatsTrackerResource>,
+    mut completed_run: ResMut<CompletedRunResource>,
+    mut restarting_run: ResMut<RestartingRunResource>,
+    mut run_rng: ResMut<RunRngResource>,
+) {
+    *run_stats = RunStatsTrackerResource {
+        seed: if restarting_run.0 {
+            run_stats.seed
+        } else {
+            rand::random()
+        },
+        ..Default::default()
+    };
+    *run_rng = RunRngResource::from_seed(run_stats.seed);
+    restarting_run.0 = false;
+    **completed_run = None;
+}
+
+/// Accumulates the run's duration, money collected, and damage taken
+pub(super) fn track_run_stats_system(
+    mut run_stats: ResMut<RunStatsTrackerResource>,
+    mut player_damaged_event_reader: EventReader<PlayerDamagedEvent>,
+    player_query: Query<(&PlayerIDComponent, &PlayerInventoryComponent)>,
+    time: Res<Time>,
+) {
+    run_stats.duration_secs += time.delta_seconds();
+
+    for event in player_damaged_event_reader.read() {
+        run_stats.damage_taken += event.damage;
+    }
+
+    for (player_id, inventory) in player_query.iter() {
+        let money = run_stats
+            .money_by_player
+            .entry(usize::from(*player_id))
+            .or_default();
+        *money = (*money).max(inventory.money);
+    }
+}
+
+/// Turns the tracked stats into a record of the run once it has ended
+pub(super) fn record_completed_run_system(
+    run_stats: Res<RunStatsTrackerResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    selected_difficulty: Res<SelectedDifficultyResource>,
+    score_res: Res<ScoreResource>,
+    players_resource: Res<PlayersResource>,
+    mut completed_run: ResMut<CompletedRunResource>,
+) {
+    let Some(outcome) = run_stats.outcome else {
+        return;
+    };
+
+    **completed_run = Some(RunRecord {
+        start_time: None,
+        duration_secs: run_stats.duration_secs,
+        characters: players_resource
+            .player_data
+            .iter()
+            .flatten()
+            .map(|player_data| player_data.character.clone())
+            .collect(),
+        run_key: run_res.run_key.clone(),
+        difficulty: selected_difficulty.0,
+        levels_completed: run_res.completed_levels.len(),
+        outcome,
+        money_collected: run_stats.money_by_player.values().sum(),
+        damage_taken: run_stats.damage_taken,
+        seed: run_stats.seed,
+        score: score_res.score,
+    });
+}
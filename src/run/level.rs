@@ -1,6 +1,6 @@
 //! Mainly exposes `Level` for moving between different phases of the same level, and progressing
 //! between levels.
-use crate::run::level_phase::LevelPhaseType;
+use crate::run::level_phase::{LevelPhaseType, SpawnWarnings};
 use crate::run::tutorial::modify_player_spawn_params_for_lesson_phase;
 use bevy::{
     log::info,
@@ -9,18 +9,20 @@ use bevy::{
     time::{Stopwatch, Time, Timer},
 };
 use leafwing_input_manager::prelude::ActionState;
+use rand::rngs::StdRng;
 use serde::Deserialize;
 use std::{
     collections::{HashMap, VecDeque},
     time::Duration,
 };
 use thetawave_interface::input::PlayerAction;
+use thetawave_interface::localization::LocalizationResource;
 use thetawave_interface::player::InputRestrictionsAtSpawn;
 use thetawave_interface::{
     audio::{BGMusicType, ChangeBackgroundMusicEvent, PlaySoundEffectEvent},
     objective::{MobReachedBottomGateEvent, Objective},
     player::PlayerComponent,
-    run::CyclePhaseEvent,
+    run::{BossWarningEvent, CyclePhaseEvent},
     spawnable::{MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
 };
 
@@ -40,7 +42,11 @@ pub struct LevelPhase {
     pub phase_type: LevelPhaseType,
     /// music to play during phase
     pub bg_music_transition: Option<BGMusicTransition>,
+    /// string id of the text shown when the phase starts
     pub intro_text: Option<String>,
+    /// how far ahead the players are warned of the phase's spawns
+    #[serde(default)]
+    pub spawn_warnings: SpawnWarnings,
 }
 
 /// Background music transition
@@ -94,9 +100,15 @@ pub struct Level {
 }
 
 impl Level {
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
         if let Some(objective) = &self.objective {
-            format!("{}: {}", self.name, objective.clone().get_name())
+            localization.format(
+                "level.name_with_objective",
+                &[
+                    ("level", self.name.clone()),
+                    ("objective", objective.get_name(localization)),
+                ],
+            )
         } else {
             self.name.clone()
         }
@@ -166,11 +178,14 @@ impl Level {
         mob_reached_bottom_event: &mut EventReader<MobReachedBottomGateEvent>,
         mob_segment_destroyed_event: &mut EventReader<MobSegmentDestroyedEvent>,
         play_sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+        boss_warning_event_writer: &mut EventWriter<BossWarningEvent>,
         mut player_spawn_params: ResMut<InputRestrictionsAtSpawn>,
+        rng: &mut StdRng,
     ) -> bool {
         self.level_time.tick(time.delta());
 
         if let Some(mut modified_current_phase) = self.current_phase.clone() {
+            let spawn_warnings = modified_current_phase.spawn_warnings;
             let phase_completed = match &mut modified_current_phase.phase_type {
                 LevelPhaseType::FormationSpawn {
                     phase_timer,
@@ -184,6 +199,8 @@ impl Level {
                         spawn_formation_event_writer,
                         formations_res,
                         formation_pool.to_string(),
+                        spawn_warnings.formation_lead_time,
+                        rng,
                     );
 
                     Self::tick_phase_timer(phase_timer, time)
@@ -207,7 +224,17 @@ impl Level {
                             true
                         }
                     } else {
+                        let warning_pending = spawn_timer.elapsed().is_zero()
+                            || spawn_timer.remaining_secs() > spawn_warnings.boss_lead_time;
                         spawn_timer.tick(time.delta());
+                        if warning_pending
+                            && spawn_warnings.boss_lead_time > 0.0
+                            && spawn_timer.remaining_secs() <= spawn_warnings.boss_lead_time
+                        {
+                            boss_warning_event_writer.send(BossWarningEvent {
+                                lead_time: spawn_timer.remaining_secs(),
+                            });
+                        }
                         if spawn_timer.just_finished() {
                             spawn_mob_event_writer.send(SpawnMobEvent {
                                 mob_type: mob_type.clone(),
@@ -270,13 +297,16 @@ impl Level {
         spawn_formation_event_writer: &mut EventWriter<SpawnFormationEvent>,
         formations_res: &FormationPoolsResource,
         formation_key: String,
+        lead_time: f32,
+        rng: &mut StdRng,
     ) {
         spawn_timer.tick(time.delta());
 
         if spawn_timer.just_finished() {
-            if let Some(formation) = formations_res.get_random_formation(formation_key) {
+            if let Some(formation) = formations_res.get_random_formation(formation_key, rng) {
                 spawn_formation_event_writer.send(SpawnFormationEvent {
                     formation: formation.clone(),
+                    lead_time,
                 });
                 spawn_timer.set_duration(Duration::from_secs_f32(formation.period));
                 spawn_timer.reset();
@@ -2,8 +2,29 @@ use crate::run::tutorial::TutorialLesson;
 use bevy::math::Vec2;
 use bevy::prelude::Timer;
 use serde::Deserialize;
+use thetawave_interface::localization::LocalizationResource;
 use thetawave_interface::spawnable::MobType;
 
+/// How far ahead of a phase's spawns the players are warned of them. A lead time of 0 turns that
+/// warning off.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct SpawnWarnings {
+    /// Seconds that markers are shown where a formation is about to spawn before it spawns
+    pub formation_lead_time: f32,
+    /// Seconds before a boss spawns that the warning banner is shown
+    pub boss_lead_time: f32,
+}
+
+impl Default for SpawnWarnings {
+    fn default() -> Self {
+        Self {
+            formation_lead_time: 1.0,
+            boss_lead_time: 3.0,
+        }
+    }
+}
+
 /// Describes a distinct portion of the level
 #[derive(Deserialize, Clone, Debug)]
 pub enum LevelPhaseType {
@@ -26,14 +47,19 @@ pub enum LevelPhaseType {
 }
 
 impl LevelPhaseType {
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
         match self {
-            LevelPhaseType::FormationSpawn { .. } => "Formation Invasion".to_string(),
-            LevelPhaseType::Break { .. } => "Break".to_string(),
-            LevelPhaseType::Boss { mob_type, .. } => format!("Boss: {}", mob_type.get_name()),
+            LevelPhaseType::FormationSpawn { .. } => localization.get("phase.formation_spawn"),
+            LevelPhaseType::Break { .. } => localization.get("phase.break"),
+            LevelPhaseType::Boss { mob_type, .. } => {
+                localization.format("phase.boss", &[("mob", mob_type.get_name(localization))])
+            }
             LevelPhaseType::Tutorial {
                 tutorial_lesson, ..
-            } => format!("Tutorial: {}", tutorial_lesson.get_name()),
+            } => localization.format(
+                "phase.tutorial",
+                &[("lesson", tutorial_lesson.get_name(localization))],
+            ),
         }
     }
 }
@@ -0,0 +1,242 @@
+//! Holds the run between levels on a summary of the level that was just completed, until every
+//! player has readied up for the next one.
+use bevy::prelude::{
+    EventWriter, NextState, Query, Res, ResMut, Resource, Time, Timer, TimerMode, With,
+};
+use bevy_rapier2d::prelude::RapierConfiguration;
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_interface::{
+    audio::{ChangeBackgroundMusicEvent, PlaySoundEffectEvent, SoundEffectType},
+    game::historical_metrics::{MobKillsByPlayerForCurrentGame, MobsKilledByPlayerCacheT},
+    input::PlayerAction,
+    objective::Objective,
+    player::{PlayerComponent, PlayerIDComponent},
+    run::CyclePhaseEvent,
+    states::GameStates,
+};
+
+use super::{history::RunStatsTrackerResource, level::Level, CurrentRunProgressResource};
+
+/// Seconds before ready-up inputs are accepted, so that players firing when the level ends don't
+/// immediately ready up
+const READY_INPUT_DELAY: f32 = 1.0;
+
+/// Totals over the whole run, used to find how much of each happened in a single level
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct RunTotals {
+    kills: usize,
+    damage_taken: usize,
+}
+
+impl RunTotals {
+    fn new(mob_kills: &MobsKilledByPlayerCacheT, run_stats: &RunStatsTrackerResource) -> Self {
+        Self {
+            kills: mob_kills
+                .values()
+                .flat_map(|player_kills| player_kills.values())
+                .sum(),
+            damage_taken: run_stats.damage_taken,
+        }
+    }
+}
+
+/// Whether a player has readied up for the next level
+#[derive(Debug)]
+pub(crate) struct PlayerReadyUp {
+    pub player_id: PlayerIDComponent,
+    pub ready: bool,
+}
+
+/// Stats of the level that was just completed, shown while in `GameStates::LevelSummary`
+#[derive(Resource, Debug, Default)]
+pub(crate) struct LevelSummaryResource {
+    pub level_time_secs: f32,
+    pub kills: usize,
+    pub damage_taken: usize,
+    /// State of the level's objective when it was completed
+    pub objective: Option<Objective>,
+    pub ready_ups: Vec<PlayerReadyUp>,
+    /// Ignores inputs for a short time after the summary opens
+    pub input_delay: Timer,
+    /// Totals when the completed level started
+    level_start_totals: RunTotals,
+}
+
+impl LevelSummaryResource {
+    /// Summarizes a completed level from the run's totals at the end of it
+    fn summarize_level(
+        &mut self,
+        level: &Level,
+        totals: RunTotals,
+        mut player_ids: Vec<PlayerIDComponent>,
+    ) {
+        player_ids.sort_by_key(|player_id| usize::from(*player_id));
+
+        self.level_time_secs = level.level_time.elapsed_secs();
+        self.kills = totals.kills - self.level_start_totals.kills;
+        self.damage_taken = totals.damage_taken - self.level_start_totals.damage_taken;
+        self.objective = level.objective.clone();
+        self.ready_ups = player_ids
+            .into_iter()
+            .map(|player_id| PlayerReadyUp {
+                player_id,
+                ready: false,
+            })
+            .collect();
+        self.input_delay = Timer::from_seconds(READY_INPUT_DELAY, TimerMode::Once);
+    }
+}
+
+/// Counts the first level from the start of the run
+pub(super) fn reset_level_summary_system(mut summary_res: ResMut<LevelSummaryResource>) {
+    *summary_res = LevelSummaryResource::default();
+}
+
+/// Opens the level summary once the current level is awaiting the next one
+pub(super) fn start_level_summary_system(
+    run_res: Res<CurrentRunProgressResource>,
+    mut summary_res: ResMut<LevelSummaryResource>,
+    mob_kills: Res<MobKillsByPlayerForCurrentGame>,
+    run_stats: Res<RunStatsTrackerResource>,
+    player_query: Query<&PlayerIDComponent, With<PlayerComponent>>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    if !run_res.awaiting_next_level {
+        return;
+    }
+
+    // wait for another state change in the same frame, such as an upgrade draft, to finish first
+    if matches!(*next_game_state, NextState::Pending(_)) {
+        return;
+    }
+
+    let Some(level) = &run_res.current_level else {
+        return;
+    };
+
+    summary_res.summarize_level(
+        level,
+        RunTotals::new(&mob_kills, &run_stats),
+        player_query.iter().copied().collect(),
+    );
+
+    next_game_state.set(GameStates::LevelSummary);
+
+    // suspend the physics engine
+    rapier_config.physics_pipeline_active = false;
+    rapier_config.query_pipeline_active = false;
+}
+
+/// Lets each player ready up. Once every player is ready, the next level starts and the game
+/// resumes.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn level_summary_input_system(
+    mut summary_res: ResMut<LevelSummaryResource>,
+    mut run_res: ResMut<CurrentRunProgressResource>,
+    mob_kills: Res<MobKillsByPlayerForCurrentGame>,
+    run_stats: Res<RunStatsTrackerResource>,
+    player_query: Query<(&PlayerIDComponent, &ActionState<PlayerAction>)>,
+    mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
+    mut cycle_phase_event_writer: EventWriter<CyclePhaseEvent>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    time: Res<Time>,
+) {
+    summary_res.input_delay.tick(time.delta());
+    if !summary_res.input_delay.finished() {
+        return;
+    }
+
+    for ready_up in summary_res
+        .ready_ups
+        .iter_mut()
+        .filter(|ready_up| !ready_up.ready)
+    {
+        let Some((_, action_state)) = player_query
+            .iter()
+            .find(|(player_id, _)| **player_id == ready_up.player_id)
+        else {
+            // players that are no longer around don't hold up the run
+            ready_up.ready = true;
+            continue;
+        };
+
+        if action_state.just_pressed(&PlayerAction::SlotOneAbility) {
+            ready_up.ready = true;
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ButtonConfirm,
+            });
+        }
+    }
+
+    if !summary_res.ready_ups.iter().all(|ready_up| ready_up.ready) {
+        return;
+    }
+
+    summary_res.level_start_totals = RunTotals::new(&mob_kills, &run_stats);
+    run_res.start_next_level(
+        &mut change_bg_music_event_writer,
+        &mut cycle_phase_event_writer,
+    );
+
+    next_game_state.set(GameStates::Playing);
+
+    // resume the physics engine
+    rapier_config.physics_pipeline_active = true;
+    rapier_config.query_pipeline_active = true;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LevelSummaryResource, RunTotals};
+    use crate::run::level::{Level, LevelData};
+    use std::time::Duration;
+    use thetawave_interface::player::PlayerIDComponent;
+
+    #[test]
+    fn test_summary_only_counts_the_completed_level() {
+        let mut level = Level::from(&LevelData {
+            phases: vec![],
+            objective: None,
+            name: "test_level".to_string(),
+        });
+        level.level_time.tick(Duration::from_secs(30));
+
+        let mut summary = LevelSummaryResource::default();
+        summary.summarize_level(
+            &level,
+            RunTotals {
+                kills: 10,
+                damage_taken: 4,
+            },
+            vec![PlayerIDComponent::Two, PlayerIDComponent::One],
+        );
+        assert_eq!(summary.kills, 10);
+        assert_eq!(
+            summary
+                .ready_ups
+                .iter()
+                .map(|ready_up| ready_up.player_id)
+                .collect::<Vec<_>>(),
+            vec![PlayerIDComponent::One, PlayerIDComponent::Two]
+        );
+
+        summary.level_start_totals = RunTotals {
+            kills: 10,
+            damage_taken: 4,
+        };
+        summary.summarize_level(
+            &level,
+            RunTotals {
+                kills: 25,
+                damage_taken: 4,
+            },
+            vec![PlayerIDComponent::One],
+        );
+        assert_eq!(summary.kills, 15);
+        assert_eq!(summary.damage_taken, 0);
+        assert_eq!(summary.level_time_secs, 30.0);
+    }
+}
@@ -5,10 +5,12 @@ use bevy::app::{App, Plugin, Update};
 use bevy::log::info;
 use bevy::prelude::{
     in_state, EventReader, EventWriter, IntoSystemConfigs, NextState, OnEnter, OnExit, Query, Res,
-    ResMut, Resource, With,
+    ResMut, Resource, With, Without,
 };
 use bevy::time::Time;
+use bevy_rapier2d::prelude::RapierConfiguration;
 use leafwing_input_manager::prelude::ActionState;
+use rand::{rngs::StdRng, SeedableRng};
 use ron::de::from_bytes;
 use serde::Deserialize;
 use std::collections::{HashMap, VecDeque};
@@ -16,23 +18,45 @@ use thetawave_interface::input::PlayerAction;
 use thetawave_interface::player::InputRestrictionsAtSpawn;
 use thetawave_interface::{
     audio::{ChangeBackgroundMusicEvent, PlaySoundEffectEvent, SoundEffectType},
+    game::run_history::{CompletedRunResource, LeaderboardsResource, RunHistoryResource},
+    health::HealthComponent,
     objective::{DefenseInteraction, MobReachedBottomGateEvent, Objective},
-    player::PlayerComponent,
-    run::{CyclePhaseEvent, RunDefeatType, RunEndEvent, RunOutcomeType},
+    player::{CoopLivesResource, PlayerComponent, PlayerDownedComponent},
+    run::{BossWarningEvent, CyclePhaseEvent, RunDefeatType, RunEndEvent, RunOutcomeType},
     spawnable::{MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
     states::{AppStates, GameStates},
 };
 
 use crate::{spawnable::BossesDestroyedEvent, GameUpdateSet};
 
+pub(crate) mod difficulty;
 mod formation;
-mod level;
+mod history;
+pub(crate) mod level;
 pub(crate) mod level_phase;
+pub(crate) mod level_summary;
+pub(crate) mod score;
 pub(crate) mod tutorial;
 
 use self::{
-    formation::{spawn_formation_system, FormationPoolsResource, SpawnFormationEvent},
+    difficulty::{DifficultiesResource, SelectedDifficultyResource},
+    formation::{
+        formation_telegraph_system, spawn_formation_system, FormationPoolsResource,
+        SpawnFormationEvent,
+    },
+    history::{
+        record_completed_run_system, start_run_stats_system, track_run_stats_system,
+        RunStatsTrackerResource,
+    },
     level::{Level, PremadeLevelsResource},
+    level_summary::{
+        level_summary_input_system, reset_level_summary_system, start_level_summary_system,
+        LevelSummaryResource,
+    },
+    score::{
+        reset_score_system, score_completed_levels_system, score_mob_kills_system, ScoreResource,
+        ScoringResource,
+    },
 };
 
 /// Contains systems that deal with level progression and transitions of `AppStates`. This includes
@@ -57,25 +81,97 @@ impl Plugin for RunPlugin {
             ))
             .unwrap(),
         )
-        .insert_resource(CurrentRunProgressResource::default());
+        .insert_resource(
+            from_bytes::<DifficultiesResource>(include_bytes!(
+                "../../assets/data/difficulties.ron"
+            ))
+            .unwrap(),
+        )
+        .insert_resource(
+            from_bytes::<ScoringResource>(include_bytes!("../../assets/data/scoring.ron")).unwrap(),
+        )
+        .insert_resource(CurrentRunProgressResource::default())
+        .init_resource::<SelectedDifficultyResource>()
+        .init_resource::<CoopLivesResource>()
+        .init_resource::<RunStatsTrackerResource>()
+        .init_resource::<RestartingRunResource>()
+        .init_resource::<RunRngResource>()
+        .init_resource::<LevelSummaryResource>()
+        .init_resource::<ScoreResource>()
+        .init_resource::<CompletedRunResource>()
+        .init_resource::<RunHistoryResource>()
+        .init_resource::<LeaderboardsResource>();
 
         app.add_event::<SpawnFormationEvent>()
             .add_event::<RunEndEvent>()
-            .add_event::<CyclePhaseEvent>();
+            .add_event::<CyclePhaseEvent>()
+            .add_event::<BossWarningEvent>();
+
+        app.add_systems(
+            OnEnter(AppStates::InitializeRun),
+            (
+                run_reset_system.before(init_run_system),
+                init_run_system,
+                start_run_stats_system,
+                reset_score_system,
+                reset_level_summary_system,
+            ),
+        );
+
+        app.add_systems(
+            Update,
+            (tick_run_system, handle_objective_system)
+                .in_set(GameUpdateSet::Level)
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
 
-        app.add_systems(OnEnter(AppStates::InitializeRun), init_run_system);
+        // runs can also be ended from the pause menu
+        app.add_systems(
+            Update,
+            run_end_system
+                .in_set(GameUpdateSet::Level)
+                .after(handle_objective_system)
+                .run_if(in_state(AppStates::Game)),
+        );
 
         app.add_systems(
             Update,
-            (tick_run_system, handle_objective_system, run_end_system)
+            (score_mob_kills_system, score_completed_levels_system)
                 .in_set(GameUpdateSet::Level)
+                .after(tick_run_system)
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+
+        app.add_systems(
+            Update,
+            start_level_summary_system
+                .in_set(GameUpdateSet::ChangeState)
                 .run_if(in_state(AppStates::Game))
                 .run_if(in_state(GameStates::Playing)),
         );
 
         app.add_systems(
             Update,
-            spawn_formation_system
+            level_summary_input_system
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::LevelSummary)),
+        );
+
+        app.add_systems(
+            Update,
+            track_run_stats_system
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+
+        // the run has ended by the time the game state is exited, unless it was restarted
+        app.add_systems(OnExit(AppStates::Game), record_completed_run_system);
+
+        app.add_systems(
+            Update,
+            (spawn_formation_system, formation_telegraph_system)
                 .in_set(GameUpdateSet::Spawn)
                 .run_if(in_state(AppStates::Game))
                 .run_if(in_state(GameStates::Playing)),
@@ -88,6 +184,29 @@ impl Plugin for RunPlugin {
     }
 }
 
+/// Set when the run is restarted from the pause menu, so that the new run keeps the seed of the
+/// restarted one. The players keep their characters either way.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct RestartingRunResource(pub bool);
+
+/// Rolls the random choices that shape a run, like which formations spawn and which upgrades are
+/// drafted. It is seeded with the run's seed when the run starts, so a restarted run makes the
+/// same choices.
+#[derive(Resource, Debug)]
+pub(crate) struct RunRngResource(pub StdRng);
+
+impl RunRngResource {
+    pub(crate) fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for RunRngResource {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
 #[derive(Resource, Deserialize)]
 pub(super) struct PremadeRunsResource {
     pub runs: HashMap<String, Vec<String>>,
@@ -97,19 +216,26 @@ pub(super) struct PremadeRunsResource {
 /// state required to transition to new sections of the level.
 #[derive(Resource, Debug)]
 pub struct CurrentRunProgressResource {
+    /// Key of the premade run being played
+    pub run_key: String,
     /// List of string level keys that are matched to values in the levelsresource
     pub queued_levels: VecDeque<Level>,
     pub completed_levels: VecDeque<Level>,
     /// Tracks the level currently being played
     pub current_level: Option<Level>,
+    /// Set once the current level's phases are exhausted and there is another level queued. The
+    /// completed level stays current until the players are ready for the next one.
+    pub awaiting_next_level: bool,
 }
 
 impl Default for CurrentRunProgressResource {
     fn default() -> Self {
         CurrentRunProgressResource {
+            run_key: String::new(),
             queued_levels: VecDeque::new(),
             completed_levels: VecDeque::new(),
             current_level: None,
+            awaiting_next_level: false,
         }
     }
 }
@@ -133,6 +259,7 @@ impl CurrentRunProgressResource {
 
         // set levels in the run resource
         self.queued_levels = levels;
+        self.run_key = run_key;
 
         info!("Generated premade level");
     }
@@ -150,6 +277,17 @@ impl CurrentRunProgressResource {
         info!("Level cycled");
     }
 
+    /// Moves on from a level that is awaiting the next one and starts the next level's first phase
+    fn start_next_level(
+        &mut self,
+        change_bg_music_event_writer: &mut EventWriter<ChangeBackgroundMusicEvent>,
+        cycle_phase_event_writer: &mut EventWriter<CyclePhaseEvent>,
+    ) {
+        self.awaiting_next_level = false;
+        self.cycle_level();
+        self.init_current_level(change_bg_music_event_writer, cycle_phase_event_writer);
+    }
+
     fn init_current_level(
         &mut self,
         change_bg_music_event_writer: &mut EventWriter<ChangeBackgroundMusicEvent>,
@@ -181,8 +319,14 @@ impl CurrentRunProgressResource {
         mob_reached_bottom_event: &mut EventReader<MobReachedBottomGateEvent>,
         mob_segment_destroyed_event: &mut EventReader<MobSegmentDestroyedEvent>,
         play_sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+        boss_warning_event_writer: &mut EventWriter<BossWarningEvent>,
         player_spawn_params: ResMut<InputRestrictionsAtSpawn>,
+        rng: &mut StdRng,
     ) {
+        if self.awaiting_next_level {
+            return;
+        }
+
         if let Some(current_level) = &mut self.current_level {
             // cycle level when done with all phases
             if current_level.tick(
@@ -198,10 +342,18 @@ impl CurrentRunProgressResource {
                 mob_reached_bottom_event,
                 mob_segment_destroyed_event,
                 play_sound_effect_event_writer,
+                boss_warning_event_writer,
                 player_spawn_params,
+                rng,
             ) {
-                self.cycle_level();
-                self.init_current_level(change_bg_music_event_writer, cycle_phase_event_writer);
+                // the level summary is shown between levels, the last level goes straight to
+                // victory
+                if self.queued_levels.is_empty() {
+                    self.cycle_level();
+                    self.init_current_level(change_bg_music_event_writer, cycle_phase_event_writer);
+                } else {
+                    self.awaiting_next_level = true;
+                }
             }
         } else {
             run_end_event_writer.send(RunEndEvent {
@@ -218,7 +370,15 @@ fn init_run_system(
     mut next_app_state: ResMut<NextState<AppStates>>,
     mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
     mut cycle_phase_event_writer: EventWriter<CyclePhaseEvent>,
+    difficulties_res: Res<DifficultiesResource>,
+    selected_difficulty: Res<SelectedDifficultyResource>,
+    mut coop_lives: ResMut<CoopLivesResource>,
 ) {
+    // set up the revive and shared lives rules for the selected difficulty
+    if let Some(difficulty_data) = difficulties_res.difficulties.get(&selected_difficulty.0) {
+        *coop_lives = CoopLivesResource::from(difficulty_data.coop.clone());
+    }
+
     // generate the run
     run_res.generate_premade(
         "test_run".to_string(),
@@ -247,7 +407,8 @@ fn tick_run_system(
     time: Res<Time>,
     player_query: Query<&ActionState<PlayerAction>, With<PlayerComponent>>,
     mut spawn_formation_event_writer: EventWriter<SpawnFormationEvent>,
-    formations_res: Res<FormationPoolsResource>,
+    // grouped to stay within the number of parameters a system can take
+    (formations_res, mut run_rng): (Res<FormationPoolsResource>, ResMut<RunRngResource>),
     mut spawn_mob_event_writer: EventWriter<SpawnMobEvent>,
     mut bosses_destroyed_event_reader: EventReader<BossesDestroyedEvent>,
     mut run_end_event_writer: EventWriter<RunEndEvent>,
@@ -257,6 +418,7 @@ fn tick_run_system(
     mut mob_reached_bottom_event_reader: EventReader<MobReachedBottomGateEvent>,
     mut mob_segment_destroyed_event_reader: EventReader<MobSegmentDestroyedEvent>,
     mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut boss_warning_event_writer: EventWriter<BossWarningEvent>,
     player_spawn_params: ResMut<InputRestrictionsAtSpawn>,
 ) {
     run_res.tick(
@@ -273,7 +435,9 @@ fn tick_run_system(
         &mut mob_reached_bottom_event_reader,
         &mut mob_segment_destroyed_event_reader,
         &mut play_sound_effect_event_writer,
+        &mut boss_warning_event_writer,
         player_spawn_params,
+        &mut run_rng.0,
     );
 }
 
@@ -324,6 +488,12 @@ fn handle_objective_system(
 fn run_end_system(
     mut run_end_event_reader: EventReader<RunEndEvent>,
     mut next_app_state: ResMut<NextState<AppStates>>,
+    standing_player_query: Query<
+        &HealthComponent,
+        (With<PlayerComponent>, Without<PlayerDownedComponent>),
+    >,
+    coop_lives: Res<CoopLivesResource>,
+    mut run_stats: ResMut<RunStatsTrackerResource>,
 ) {
     for event in run_end_event_reader.read() {
         match &event.outcome {
@@ -331,14 +501,29 @@ fn run_end_system(
                 next_app_state.set(AppStates::Victory);
             }
             RunOutcomeType::Defeat(defeat_type) => {
-                next_app_state.set(AppStates::GameOver);
-
                 match defeat_type {
-                    RunDefeatType::PlayersDestroyed => info!("Players destroyed"),
+                    RunDefeatType::PlayersDestroyed => {
+                        // a player may have been revived or respawned from the shared lives pool
+                        // after the event was sent
+                        if standing_player_query.iter().any(|health| !health.is_dead()) {
+                            info!("Players destroyed event ignored, a player is still standing");
+                            continue;
+                        }
+
+                        info!(
+                            "Players destroyed after using {} revives and {} shared lives",
+                            coop_lives.revives_used, coop_lives.shared_lives_used
+                        );
+                    }
                     RunDefeatType::DefenseDestroyed => info!("Defense objective failed"),
+                    RunDefeatType::Abandoned => info!("Run abandoned"),
                 };
+
+                next_app_state.set(AppStates::GameOver);
             }
         }
+
+        run_stats.outcome = Some(event.outcome);
     }
 }
 
@@ -346,24 +531,40 @@ fn run_end_system(
 fn run_reset_system(
     mut run_resource: ResMut<CurrentRunProgressResource>,
     mut spawn_restrictions: ResMut<InputRestrictionsAtSpawn>,
+    mut coop_lives: ResMut<CoopLivesResource>,
+    mut rapier_config: ResMut<RapierConfiguration>,
 ) {
     *run_resource = CurrentRunProgressResource::default();
     *spawn_restrictions = InputRestrictionsAtSpawn::default();
+    *coop_lives = CoopLivesResource::default();
+
+    // abandoning a run leaves the physics engine suspended
+    rapier_config.physics_pipeline_active = true;
+    rapier_config.query_pipeline_active = true;
 }
 
 #[cfg(test)]
 mod test {
-    use crate::run::{RunPlugin, SpawnFormationEvent};
+    use crate::run::{RunPlugin, RunRngResource, SpawnFormationEvent};
     use crate::spawnable::{BossesDestroyedEvent, SpawnConsumableEvent};
+    use crate::tools::weighted_rng;
     use bevy::app::App;
     use bevy::log::{Level, LogPlugin};
     use bevy::prelude::{default, NextState, State};
     use bevy::state::app::{AppExtStates, StatesPlugin};
     use bevy::MinimalPlugins;
+    use bevy_rapier2d::prelude::RapierConfiguration;
     use rstest::rstest;
     use thetawave_interface::audio::{ChangeBackgroundMusicEvent, PlaySoundEffectEvent};
+    use thetawave_interface::game::historical_metrics::MobKillsByPlayerForCurrentGame;
+    use thetawave_interface::game::options::GameOptions;
+    use thetawave_interface::health::HealthComponent;
     use thetawave_interface::objective::{DefenseInteraction, MobReachedBottomGateEvent};
-    use thetawave_interface::player::{InputRestrictionsAtSpawn, PlayersResource};
+    use thetawave_interface::player::{
+        InputRestrictionsAtSpawn, PlayerComponent, PlayerDamagedEvent, PlayerDownedComponent,
+        PlayersResource,
+    };
+    use thetawave_interface::run::{RunDefeatType, RunEndEvent, RunOutcomeType};
     use thetawave_interface::spawnable::{
         MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent,
     };
@@ -391,8 +592,12 @@ mod test {
             .add_event::<SpawnMobEvent>()
             .add_event::<MobDestroyedEvent>()
             .add_event::<MobSegmentDestroyedEvent>()
+            .add_event::<PlayerDamagedEvent>()
             .insert_resource(PlayersResource::default())
             .insert_resource(InputRestrictionsAtSpawn::default())
+            .insert_resource(MobKillsByPlayerForCurrentGame::default())
+            .insert_resource(RapierConfiguration::new(1.0))
+            .insert_resource(GameOptions::default())
             .add_plugins(RunPlugin);
 
         app
@@ -443,4 +648,67 @@ mod test {
                 .get()
         );
     }
+
+    #[rstest]
+    #[case::standing_player_ignores_the_event(false, false, AppStates::Game)]
+    #[case::destroyed_player_ends_the_run(true, false, AppStates::GameOver)]
+    #[case::downed_player_ends_the_run(true, true, AppStates::GameOver)]
+    fn test_players_destroyed_is_checked_against_standing_players(
+        #[case] player_destroyed: bool,
+        #[case] player_downed: bool,
+        #[case] want_end_state: AppStates,
+    ) {
+        let mut app = _minimal_app_for_run_progression_defend_gate_objective();
+        app.world_mut()
+            .get_resource_mut::<NextState<AppStates>>()
+            .unwrap()
+            .set(AppStates::InitializeRun);
+        app.world_mut()
+            .get_resource_mut::<NextState<GameStates>>()
+            .unwrap()
+            .set(GameStates::Playing);
+        app.update();
+        app.update();
+
+        // a player may be revived or respawned after the event is sent
+        let mut health = HealthComponent::new(10, 0, 0.0);
+        if player_destroyed {
+            health.take_damage(10);
+        }
+        let player = app.world_mut().spawn((PlayerComponent, health)).id();
+        if player_downed {
+            app.world_mut()
+                .entity_mut(player)
+                .insert(PlayerDownedComponent::new(1.0));
+        }
+
+        app.world_mut().send_event(RunEndEvent {
+            outcome: RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
+        });
+        app.update();
+        app.update();
+        assert_eq!(
+            &want_end_state,
+            app.world()
+                .get_resource::<State<AppStates>>()
+                .unwrap()
+                .get()
+        );
+    }
+
+    #[test]
+    fn test_run_rng_repeats_rolls_for_the_same_seed() {
+        let weights = vec![1.0, 2.0, 3.0, 4.0];
+        let mut first_run_rng = RunRngResource::from_seed(7);
+        let mut second_run_rng = RunRngResource::from_seed(7);
+
+        let first_rolls: Vec<usize> = (0..20)
+            .map(|_| weighted_rng(weights.clone(), &mut first_run_rng.0))
+            .collect();
+        let second_rolls: Vec<usize> = (0..20)
+            .map(|_| weighted_rng(weights.clone(), &mut second_run_rng.0))
+            .collect();
+
+        assert_eq!(first_rolls, second_rolls);
+    }
 }
@@ -0,0 +1,208 @@
+//! Awards points for destroying mobs and completing levels, with a combo multiplier for quick kills
+use bevy::prelude::{EventReader, Res, ResMut, Resource, Time, Timer, TimerMode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use thetawave_interface::{
+    objective::Objective,
+    spawnable::{EnemyMobType, MobDestroyedEvent, MobType},
+};
+
+use super::{level::Level, CurrentRunProgressResource};
+
+/// Point values for scoring a run, loaded from `scoring.ron`
+#[derive(Resource, Deserialize)]
+pub(super) struct ScoringResource {
+    /// Points awarded for destroying each type of enemy mob
+    mob_points: HashMap<EnemyMobType, usize>,
+    /// Points for enemy mobs missing from `mob_points`
+    default_mob_points: usize,
+    boss_points_multiplier: usize,
+    /// Points awarded for completing a level
+    level_completed_points: usize,
+    /// Points awarded for each point of defense left when a defense level is completed
+    points_per_remaining_defense: usize,
+    /// Seconds to complete a level in to earn a time bonus
+    level_par_time: f32,
+    points_per_second_under_par: usize,
+    /// Seconds after a kill that the next kill continues the combo
+    combo_window: f32,
+    /// Multiplier added for each kill in a combo
+    combo_multiplier_step: f32,
+    max_combo_multiplier: f32,
+}
+
+impl ScoringResource {
+    /// Points awarded for a destroyed mob before the combo multiplier, only enemy mobs award points
+    fn get_mob_points(&self, event: &MobDestroyedEvent) -> usize {
+        if let MobType::Enemy(enemy_type) = &event.mob_type {
+            let points = self
+                .mob_points
+                .get(enemy_type)
+                .copied()
+                .unwrap_or(self.default_mob_points);
+
+            if event.is_boss {
+                points * self.boss_points_multiplier
+            } else {
+                points
+            }
+        } else {
+            0
+        }
+    }
+
+    /// Points awarded for completing a level, including the remaining defense and time bonuses
+    fn get_level_points(&self, level: &Level, level_time: f32) -> usize {
+        let defense_bonus = match &level.objective {
+            Some(Objective::Defense(defense_data)) => {
+                defense_data.defense * self.points_per_remaining_defense
+            }
+            None => 0,
+        };
+
+        let time_bonus =
+            (self.level_par_time - level_time).max(0.0) as usize * self.points_per_second_under_par;
+
+        self.level_completed_points + defense_bonus + time_bonus
+    }
+
+    /// Multiplier applied to mob points for the given number of kills in a combo
+    fn get_combo_multiplier(&self, combo: usize) -> f32 {
+        (1.0 + combo.saturating_sub(1) as f32 * self.combo_multiplier_step)
+            .min(self.max_combo_multiplier)
+    }
+}
+
+/// Score of the run being played
+#[derive(Resource, Debug)]
+pub(crate) struct ScoreResource {
+    pub score: usize,
+    /// Number of kills made in quick succession
+    pub combo: usize,
+    /// Multiplier applied to the points of the next kill in the combo
+    pub combo_multiplier: f32,
+    /// Ends the combo when it finishes before the next kill
+    combo_timer: Timer,
+    /// Seconds spent playing the current level
+    level_time: f32,
+    /// Number of completed levels that points have been awarded for
+    levels_scored: usize,
+}
+
+impl Default for ScoreResource {
+    fn default() -> Self {
+        let mut combo_timer = Timer::from_seconds(0.0, TimerMode::Once);
+        combo_timer.tick(combo_timer.duration());
+
+        ScoreResource {
+            score: 0,
+            combo: 0,
+            combo_multiplier: 1.0,
+            combo_timer,
+            level_time: 0.0,
+            levels_scored: 0,
+        }
+    }
+}
+
+impl ScoreResource {
+    /// Awards points for a kill, continuing the combo if it hasn't timed out
+    fn score_kill(&mut self, points: usize, scoring_res: &ScoringResource) {
+        if self.combo_timer.finished() {
+            self.combo = 0;
+        }
+
+        self.combo += 1;
+        self.combo_multiplier = scoring_res.get_combo_multiplier(self.combo);
+        self.score += (points as f32 * self.combo_multiplier) as usize;
+
+        self.combo_timer = Timer::from_seconds(scoring_res.combo_window, TimerMode::Once);
+    }
+}
+
+/// Resets the score for a new run
+pub(super) fn reset_score_system(mut score_res: ResMut<ScoreResource>) {
+    *score_res = ScoreResource::default();
+}
+
+/// Awards points for destroyed mobs and ends combos that have timed out
+pub(super) fn score_mob_kills_system(
+    mut score_res: ResMut<ScoreResource>,
+    scoring_res: Res<ScoringResource>,
+    mut mob_destroyed_event_reader: EventReader<MobDestroyedEvent>,
+    time: Res<Time>,
+) {
+    score_res.combo_timer.tick(time.delta());
+
+    for event in mob_destroyed_event_reader.read() {
+        let points = scoring_res.get_mob_points(event);
+
+        if points > 0 {
+            score_res.score_kill(points, &scoring_res);
+        }
+    }
+
+    if score_res.combo_timer.just_finished() {
+        score_res.combo = 0;
+        score_res.combo_multiplier = 1.0;
+    }
+}
+
+/// Awards points for each level completed since the last time this system ran
+pub(super) fn score_completed_levels_system(
+    mut score_res: ResMut<ScoreResource>,
+    scoring_res: Res<ScoringResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    time: Res<Time>,
+) {
+    score_res.level_time += time.delta_seconds();
+
+    let newly_completed = run_res
+        .completed_levels
+        .len()
+        .saturating_sub(score_res.levels_scored);
+
+    for level in run_res.completed_levels.iter().rev().take(newly_completed) {
+        let points = scoring_res.get_level_points(level, score_res.level_time);
+        score_res.score += points;
+        score_res.level_time = 0.0;
+    }
+
+    score_res.levels_scored = run_res.completed_levels.len();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ScoreResource, ScoringResource};
+    use std::collections::HashMap;
+
+    fn test_scoring() -> ScoringResource {
+        ScoringResource {
+            mob_points: HashMap::new(),
+            default_mob_points: 100,
+            boss_points_multiplier: 2,
+            level_completed_points: 1000,
+            points_per_remaining_defense: 10,
+            level_par_time: 60.0,
+            points_per_second_under_par: 5,
+            combo_window: 1.0,
+            combo_multiplier_step: 0.5,
+            max_combo_multiplier: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_combo_multiplier_grows_until_max() {
+        let scoring = test_scoring();
+        let mut score = ScoreResource::default();
+
+        score.score_kill(100, &scoring);
+        score.score_kill(100, &scoring);
+        score.score_kill(100, &scoring);
+        score.score_kill(100, &scoring);
+
+        assert_eq!(score.combo, 4);
+        assert_eq!(score.combo_multiplier, 2.0);
+        assert_eq!(score.score, 100 + 150 + 200 + 200);
+    }
+}
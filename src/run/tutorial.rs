@@ -10,6 +10,7 @@ use serde::Deserialize;
 use std::ops::Range;
 use thetawave_interface::audio::{PlaySoundEffectEvent, SoundEffectType};
 use thetawave_interface::input::PlayerAction;
+use thetawave_interface::localization::LocalizationResource;
 use thetawave_interface::objective::MobReachedBottomGateEvent;
 use thetawave_interface::player::{InputRestrictionsAtSpawn, PlayerComponent};
 use thetawave_interface::spawnable::{
@@ -68,21 +69,24 @@ pub enum TutorialLesson {
 }
 
 impl TutorialLesson {
-    pub fn get_name(&self) -> String {
+    pub fn get_name(&self, localization: &LocalizationResource) -> String {
         match self {
-            TutorialLesson::Movement { .. } => "Movement".to_string(),
-            TutorialLesson::AbilitySlotOne { .. } => "Ability Slot One".to_string(),
-            TutorialLesson::AbilitySlotTwo { .. } => "Ability Slot Two".to_string(),
+            TutorialLesson::Movement { .. } => localization.get("tutorial.movement"),
+            TutorialLesson::AbilitySlotOne { .. } => localization.get("tutorial.ability_slot_one"),
+            TutorialLesson::AbilitySlotTwo { .. } => localization.get("tutorial.ability_slot_two"),
         }
     }
 
-    pub fn get_ability_strs(&self) -> Vec<(String, bool)> {
+    pub fn get_ability_strs(&self, localization: &LocalizationResource) -> Vec<(String, bool)> {
         vec![if let Self::AbilitySlotTwo {
             mobs_to_destroy, ..
         } = self
         {
             (
-                format!("Destroy drones: {}", mobs_to_destroy,),
+                localization.format(
+                    "tutorial.destroy_drones",
+                    &[("count", mobs_to_destroy.to_string())],
+                ),
                 *mobs_to_destroy == 0,
             )
         } else {
@@ -90,20 +94,23 @@ impl TutorialLesson {
         }]
     }
 
-    pub fn get_attack_strs(&self) -> Vec<(String, bool)> {
+    pub fn get_attack_strs(&self, localization: &LocalizationResource) -> Vec<(String, bool)> {
         vec![
-            self.get_mobs_to_destroy_str(),
-            self.get_mobs_to_protect_str(),
+            self.get_mobs_to_destroy_str(localization),
+            self.get_mobs_to_protect_str(localization),
         ]
     }
 
-    fn get_mobs_to_destroy_str(&self) -> (String, bool) {
+    fn get_mobs_to_destroy_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::AbilitySlotOne {
             mobs_to_destroy, ..
         } = self
         {
             (
-                format!("Destroy drones: {}", mobs_to_destroy,),
+                localization.format(
+                    "tutorial.destroy_drones",
+                    &[("count", mobs_to_destroy.to_string())],
+                ),
                 *mobs_to_destroy == 0,
             )
         } else {
@@ -111,13 +118,16 @@ impl TutorialLesson {
         }
     }
 
-    fn get_mobs_to_protect_str(&self) -> (String, bool) {
+    fn get_mobs_to_protect_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::AbilitySlotOne {
             mobs_to_protect, ..
         } = self
         {
             (
-                format!("Protect haulers: {}", mobs_to_protect,),
+                localization.format(
+                    "tutorial.protect_haulers",
+                    &[("count", mobs_to_protect.to_string())],
+                ),
                 *mobs_to_protect == 0,
             )
         } else {
@@ -125,140 +135,118 @@ impl TutorialLesson {
         }
     }
 
-    pub fn get_movement_timer_strs(&self) -> Vec<(String, bool)> {
+    pub fn get_movement_timer_strs(
+        &self,
+        localization: &LocalizationResource,
+    ) -> Vec<(String, bool)> {
         vec![
-            self.get_up_timer_progress_str(),
-            self.get_down_timer_progress_str(),
-            self.get_left_timer_progress_str(),
-            self.get_right_timer_progress_str(),
-            self.get_up_left_timer_progress_str(),
-            self.get_up_right_timer_progress_str(),
-            self.get_down_left_timer_progress_str(),
-            self.get_down_right_timer_progress_str(),
+            self.get_up_timer_progress_str(localization),
+            self.get_down_timer_progress_str(localization),
+            self.get_left_timer_progress_str(localization),
+            self.get_right_timer_progress_str(localization),
+            self.get_up_left_timer_progress_str(localization),
+            self.get_up_right_timer_progress_str(localization),
+            self.get_down_left_timer_progress_str(localization),
+            self.get_down_right_timer_progress_str(localization),
         ]
     }
 
-    fn get_up_timer_progress_str(&self) -> (String, bool) {
+    /// Text of how long the player has moved in a direction out of how long they need to
+    fn get_timer_progress_str(
+        direction_id: &str,
+        timer: &Timer,
+        localization: &LocalizationResource,
+    ) -> (String, bool) {
+        (
+            localization.format(
+                "tutorial.timer_progress",
+                &[
+                    ("direction", localization.get(direction_id)),
+                    ("elapsed", format!("{:.1}", timer.elapsed_secs())),
+                    ("duration", format!("{:.1}", timer.duration().as_secs_f32())),
+                ],
+            ),
+            timer.finished(),
+        )
+    }
+
+    fn get_up_timer_progress_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::Movement { up_timer, .. } = self {
-            (
-                format!(
-                    "Up: {:.1}/{:.1}",
-                    up_timer.elapsed_secs(),
-                    up_timer.duration().as_secs_f32()
-                ),
-                up_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.up", up_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_down_timer_progress_str(&self) -> (String, bool) {
+    fn get_down_timer_progress_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::Movement { down_timer, .. } = self {
-            (
-                format!(
-                    "Down: {:.1}/{:.1}",
-                    down_timer.elapsed_secs(),
-                    down_timer.duration().as_secs_f32()
-                ),
-                down_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.down", down_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_left_timer_progress_str(&self) -> (String, bool) {
+    fn get_left_timer_progress_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::Movement { left_timer, .. } = self {
-            (
-                format!(
-                    "Left: {:.1}/{:.1}",
-                    left_timer.elapsed_secs(),
-                    left_timer.duration().as_secs_f32()
-                ),
-                left_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.left", left_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_right_timer_progress_str(&self) -> (String, bool) {
+    fn get_right_timer_progress_str(&self, localization: &LocalizationResource) -> (String, bool) {
         if let Self::Movement { right_timer, .. } = self {
-            (
-                format!(
-                    "Right: {:.1}/{:.1}",
-                    right_timer.elapsed_secs(),
-                    right_timer.duration().as_secs_f32()
-                ),
-                right_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.right", right_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_up_left_timer_progress_str(&self) -> (String, bool) {
+    fn get_up_left_timer_progress_str(
+        &self,
+        localization: &LocalizationResource,
+    ) -> (String, bool) {
         if let Self::Movement { up_left_timer, .. } = self {
-            (
-                format!(
-                    "Up+Left: {:.1}/{:.1}",
-                    up_left_timer.elapsed_secs(),
-                    up_left_timer.duration().as_secs_f32()
-                ),
-                up_left_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.up_left", up_left_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_up_right_timer_progress_str(&self) -> (String, bool) {
+    fn get_up_right_timer_progress_str(
+        &self,
+        localization: &LocalizationResource,
+    ) -> (String, bool) {
         if let Self::Movement { up_right_timer, .. } = self {
-            (
-                format!(
-                    "Up+Right: {:.1}/{:.1}",
-                    up_right_timer.elapsed_secs(),
-                    up_right_timer.duration().as_secs_f32()
-                ),
-                up_right_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.up_right", up_right_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_down_left_timer_progress_str(&self) -> (String, bool) {
+    fn get_down_left_timer_progress_str(
+        &self,
+        localization: &LocalizationResource,
+    ) -> (String, bool) {
         if let Self::Movement {
             down_left_timer, ..
         } = self
         {
-            (
-                format!(
-                    "Down+Left: {:.1}/{:.1}",
-                    down_left_timer.elapsed_secs(),
-                    down_left_timer.duration().as_secs_f32()
-                ),
-                down_left_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.down_left", down_left_timer, localization)
         } else {
             ("".to_string(), false)
         }
     }
 
-    fn get_down_right_timer_progress_str(&self) -> (String, bool) {
+    fn get_down_right_timer_progress_str(
+        &self,
+        localization: &LocalizationResource,
+    ) -> (String, bool) {
         if let Self::Movement {
             down_right_timer, ..
         } = self
         {
-            (
-                format!(
-                    "Down+Right: {:.1}/{:.1}",
-                    down_right_timer.elapsed_secs(),
-                    down_right_timer.duration().as_secs_f32()
-                ),
-                down_right_timer.finished(),
-            )
+            Self::get_timer_progress_str("direction.down_right", down_right_timer, localization)
         } else {
             ("".to_string(), false)
         }
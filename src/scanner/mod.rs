@@ -1,64 +1,331 @@
-//! Exposes a plugin that logs whenever the user's mouse hovers over a mob.
+//! Exposes a plugin that finds the mob under the scanner's cursor, so that it can be inspected. The
+//! cursor follows the mouse, or is moved with a gamepad's right stick.
 use bevy::{
     prelude::{
-        in_state, App, Entity, IntoSystemConfigs, Plugin, Query, Res, Transform, Update, Vec2,
-        Vec3Swizzles, Window, With,
+        in_state, App, Axis, Camera, Camera2d, Color, Commands, Component, Entity, EventReader,
+        Gamepad, GamepadAxis, GamepadAxisType, Gamepads, GlobalTransform, IntoSystemConfigs,
+        OnEnter, OnExit, OrthographicProjection, Plugin, Quat, Query, Res, ResMut, Resource,
+        Sprite, SpriteBundle, Time, Transform, Update, Vec2, Vec3Swizzles, Visibility, Window,
+        With,
     },
-    utils::tracing::debug,
-    window::PrimaryWindow,
+    utils::{default, tracing::debug},
+    window::{CursorMoved, PrimaryWindow},
 };
-use thetawave_interface::health::HealthComponent;
+use std::f32::consts::FRAC_PI_4;
+use thetawave_interface::{
+    health::HealthComponent,
+    states::{self, GameCleanup},
+};
+
+use crate::{arena::ARENA_HALF_SIZE, game::GameParametersResource, spawnable::MobComponent};
 
-use crate::{game::GameParametersResource, spawnable::MobComponent};
-use thetawave_interface::states;
+/// Speed of the gamepad cursor in world units per second, when the camera isn't zoomed
+const GAMEPAD_CURSOR_SPEED: f32 = 600.0;
+/// Right stick tilt that is ignored, so that a resting stick doesn't take over from the mouse
+const GAMEPAD_CURSOR_DEADZONE: f32 = 0.2;
+const GAMEPAD_CURSOR_SIZE: f32 = 14.0;
+const GAMEPAD_CURSOR_COLOR: Color = Color::srgba(0.4, 1.0, 0.6, 0.8);
+const GAMEPAD_CURSOR_Z: f32 = 25.0;
 
-/// Debug logs whenever the cursor is hovering over a mob.
+/// Finds the mob under the cursor and shows the gamepad cursor while it is in use
 pub(super) struct ScannerPlugin;
 
 impl Plugin for ScannerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<ScannerResource>();
+
+        app.add_systems(
+            OnEnter(states::AppStates::Game),
+            setup_gamepad_cursor_system,
+        );
+
         app.add_systems(
             Update,
-            scanner_system.run_if(in_state(states::AppStates::Game)),
+            (
+                update_scanner_cursor_system,
+                scanner_system,
+                update_gamepad_cursor_system,
+            )
+                .chain()
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::Playing)),
+        );
+
+        // nothing is inspected while the game is paused or between levels
+        app.add_systems(
+            OnExit(states::GameStates::Playing),
+            clear_scanned_mob_system,
         );
     }
 }
 
-/// Manages scanning of entities using the cursor
-fn scanner_system(
-    //windows: Res<Windows>,
+/// Device that last moved the scanner's cursor
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ScannerCursorSource {
+    #[default]
+    Mouse,
+    Gamepad,
+}
+
+/// Where the scanner is pointed, and the mob found there
+#[derive(Resource, Default, Debug)]
+pub(crate) struct ScannerResource {
+    /// World position of the cursor, None while the mouse is outside of the window
+    pub cursor_position: Option<Vec2>,
+    pub cursor_source: ScannerCursorSource,
+    /// Mob closest to the cursor, within the scan range
+    pub scanned_mob: Option<Entity>,
+}
+
+/// Reticle drawn at the scanner's cursor while it is moved with a gamepad
+#[derive(Component)]
+struct GamepadCursorComponent;
+
+/// Starts the scanner with the mouse, and spawns the hidden gamepad cursor
+fn setup_gamepad_cursor_system(mut commands: Commands, mut scanner: ResMut<ScannerResource>) {
+    *scanner = ScannerResource::default();
+
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: GAMEPAD_CURSOR_COLOR,
+                custom_size: Some(Vec2::splat(GAMEPAD_CURSOR_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, GAMEPAD_CURSOR_Z)
+                .with_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+            visibility: Visibility::Hidden,
+            ..default()
+        })
+        .insert(GamepadCursorComponent)
+        .insert(GameCleanup);
+}
+
+/// Moves the scanner's cursor with whichever of the mouse or a right stick was last used. Positions
+/// are converted with the gameplay camera, so they stay correct while it is zoomed out or shaking.
+fn update_scanner_cursor_system(
+    mut scanner: ResMut<ScannerResource>,
+    mut cursor_moved_event_reader: EventReader<CursorMoved>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform, &OrthographicProjection), With<Camera2d>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    time: Res<Time>,
+) {
+    let Ok((camera, camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    if cursor_moved_event_reader.read().last().is_some() {
+        scanner.cursor_source = ScannerCursorSource::Mouse;
+    }
+
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| get_right_stick(gamepad, &gamepad_axes))
+        .sum::<Vec2>()
+        .clamp_length_max(1.0);
+
+    if stick.length() > GAMEPAD_CURSOR_DEADZONE {
+        scanner.cursor_source = ScannerCursorSource::Gamepad;
+        // pick up from where the mouse left the cursor
+        scanner.cursor_position = Some(move_gamepad_cursor(
+            scanner.cursor_position.unwrap_or_default(),
+            stick,
+            projection.scale,
+            time.delta_seconds(),
+        ));
+    } else if scanner.cursor_source == ScannerCursorSource::Mouse {
+        scanner.cursor_position = windows
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position())
+            .and_then(|mouse_pos| camera.viewport_to_world_2d(camera_transform, mouse_pos));
+    }
+}
+
+/// Moves the gamepad cursor by the stick's tilt, keeping it within the part of the arena that the
+/// camera shows. Both the speed and the bounds grow as the camera zooms out.
+fn move_gamepad_cursor(
+    cursor_position: Vec2,
+    stick: Vec2,
+    projection_scale: f32,
+    delta_seconds: f32,
+) -> Vec2 {
+    let visible_half_size = ARENA_HALF_SIZE * projection_scale;
+
+    (cursor_position + stick * GAMEPAD_CURSOR_SPEED * projection_scale * delta_seconds)
+        .clamp(-visible_half_size, visible_half_size)
+}
+
+/// Tilt of a gamepad's right stick
+fn get_right_stick(gamepad: Gamepad, gamepad_axes: &Axis<GamepadAxis>) -> Vec2 {
+    Vec2::new(
+        gamepad_axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+            .unwrap_or_default(),
+        gamepad_axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+            .unwrap_or_default(),
+    )
+}
+
+/// The closest of the given positions to the cursor, if any of them is within the scan range
+fn get_closest_in_range<T>(
+    cursor_position: Vec2,
+    scan_range: f32,
+    candidates: impl Iterator<Item = (T, Vec2)>,
+) -> Option<T> {
+    candidates
+        .map(|(candidate, position)| (candidate, cursor_position.distance(position)))
+        .filter(|(_, distance)| *distance < scan_range)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(candidate, _)| candidate)
+}
+
+/// Scans the mob closest to the cursor, if it is in scanning range
+fn scanner_system(
+    mut scanner: ResMut<ScannerResource>,
     game_params: Res<GameParametersResource>,
     mob_query: Query<(Entity, &HealthComponent, &Transform), With<MobComponent>>,
 ) {
-    // get the primary window
-    let primary_window = windows.get_single().unwrap();
-
-    // get the cursor position in the window
-    if let Some(mouse_pos) = primary_window.cursor_position() {
-        // query the mobs
-        for (mob_entity, health_component, transform) in mob_query.iter() {
-            // check if the mob is in scanning range of the mouse
-            if mouse_pos_to_rapier_pos(mouse_pos, primary_window)
-                .distance(transform.translation.xy())
-                < game_params.scan_range
-            {
-                debug!(
-                    "Mob near mouse: Entity: {:?}\t Health: {}/{}",
-                    mob_entity,
-                    health_component.get_health(),
-                    health_component.get_max_health()
-                );
-                return;
-            }
+    let scanned_mob = scanner.cursor_position.and_then(|cursor_position| {
+        get_closest_in_range(
+            cursor_position,
+            game_params.scan_range,
+            mob_query
+                .iter()
+                .map(|(mob_entity, health_component, transform)| {
+                    ((mob_entity, health_component), transform.translation.xy())
+                }),
+        )
+    });
+
+    let scanned_entity = scanned_mob.map(|(mob_entity, _)| mob_entity);
+    if scanned_entity == scanner.scanned_mob {
+        return;
+    }
+
+    if let Some((mob_entity, health_component)) = scanned_mob {
+        debug!(
+            "Mob scanned: Entity: {:?}\t Health: {}/{}",
+            mob_entity,
+            health_component.get_health(),
+            health_component.get_max_health()
+        );
+    }
+    scanner.scanned_mob = scanned_entity;
+}
+
+/// Draws the gamepad cursor while a gamepad is moving the scanner's cursor
+fn update_gamepad_cursor_system(
+    scanner: Res<ScannerResource>,
+    mut cursor_query: Query<(&mut Transform, &mut Visibility), With<GamepadCursorComponent>>,
+) {
+    let Ok((mut transform, mut visibility)) = cursor_query.get_single_mut() else {
+        return;
+    };
+
+    match (scanner.cursor_source, scanner.cursor_position) {
+        (ScannerCursorSource::Gamepad, Some(cursor_position)) => {
+            transform.translation = cursor_position.extend(GAMEPAD_CURSOR_Z);
+            *visibility = Visibility::Inherited;
         }
+        _ => *visibility = Visibility::Hidden,
     }
 }
 
-/// Converts mouse position units to in-game physics units
-fn mouse_pos_to_rapier_pos(mouse_pos: Vec2, window: &Window) -> Vec2 {
-    Vec2::new(
-        mouse_pos.x - (window.width() / 2.0),
-        mouse_pos.y - (window.height() / 2.0),
-    )
+fn clear_scanned_mob_system(mut scanner: ResMut<ScannerResource>) {
+    scanner.scanned_mob = None;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_closest_in_range, move_gamepad_cursor, GAMEPAD_CURSOR_SPEED};
+    use crate::arena::ARENA_HALF_SIZE;
+    use bevy::prelude::Vec2;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::not_zoomed(1.0)]
+    #[case::zoomed_out(2.0)]
+    fn test_gamepad_cursor_speed_scales_with_zoom(#[case] projection_scale: f32) {
+        let cursor_position = move_gamepad_cursor(Vec2::ZERO, Vec2::X, projection_scale, 0.01);
+
+        assert_eq!(
+            cursor_position,
+            Vec2::new(GAMEPAD_CURSOR_SPEED * projection_scale * 0.01, 0.0)
+        );
+    }
+
+    #[rstest]
+    #[case::not_zoomed(1.0)]
+    #[case::zoomed_out(2.0)]
+    fn test_gamepad_cursor_stays_within_the_visible_arena(#[case] projection_scale: f32) {
+        let visible_half_size = ARENA_HALF_SIZE * projection_scale;
+
+        // pushed past the top right corner in one long frame
+        let cursor_position = move_gamepad_cursor(
+            visible_half_size - Vec2::ONE,
+            Vec2::ONE.normalize(),
+            projection_scale,
+            1.0,
+        );
+        assert_eq!(cursor_position, visible_half_size);
+
+        // pushed past the bottom left corner
+        let cursor_position = move_gamepad_cursor(
+            -visible_half_size + Vec2::ONE,
+            -Vec2::ONE.normalize(),
+            projection_scale,
+            1.0,
+        );
+        assert_eq!(cursor_position, -visible_half_size);
+    }
+
+    #[test]
+    fn test_gamepad_cursor_moved_by_the_mouse_out_of_bounds_is_pulled_back() {
+        let cursor_position = move_gamepad_cursor(ARENA_HALF_SIZE * 3.0, Vec2::X, 1.0, 0.01);
+
+        assert_eq!(cursor_position, ARENA_HALF_SIZE);
+    }
+
+    #[test]
+    fn test_scanner_picks_the_closest_mob_in_range() {
+        let mobs = [
+            ("far", Vec2::new(40.0, 0.0)),
+            ("closest", Vec2::new(0.0, -10.0)),
+            ("close", Vec2::new(15.0, 0.0)),
+        ];
+
+        assert_eq!(
+            get_closest_in_range(Vec2::ZERO, 50.0, mobs.into_iter()),
+            Some("closest")
+        );
+    }
+
+    #[test]
+    fn test_scanner_ignores_mobs_out_of_range() {
+        let mobs = [
+            ("at_range", Vec2::new(50.0, 0.0)),
+            ("far", Vec2::new(0.0, 80.0)),
+        ];
+
+        assert_eq!(
+            get_closest_in_range(Vec2::ZERO, 50.0, mobs.into_iter()),
+            None
+        );
+        assert_eq!(
+            get_closest_in_range(Vec2::new(0.0, 40.0), 50.0, mobs.into_iter()),
+            Some("far")
+        );
+    }
+
+    #[test]
+    fn test_scanner_finds_nothing_without_mobs() {
+        assert_eq!(
+            get_closest_in_range::<()>(Vec2::ZERO, 50.0, std::iter::empty()),
+            None
+        );
+    }
 }
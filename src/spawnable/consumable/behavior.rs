@@ -13,7 +13,7 @@ use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
     health::HealthComponent,
     player::{PlayerComponent, PlayerInventoryComponent, PlayerOutgoingDamageComponent},
-    spawnable::{ConsumableType, EffectType, TextEffectType},
+    spawnable::{ConsumableCollectedEvent, ConsumableType, EffectType, TextEffectType},
 };
 
 use super::ConsumableEffect;
@@ -42,6 +42,7 @@ pub fn consumable_execute_behavior_system(
     mut collision_events: EventReader<SortedCollisionEvent>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut consumable_collected_event_writer: EventWriter<ConsumableCollectedEvent>,
     game_parameters_res: Res<GameParametersResource>,
 ) {
     // put all collision events in a vector first (so that they can be looked at multiple times)
@@ -67,6 +68,7 @@ pub fn consumable_execute_behavior_system(
                         &game_parameters_res,
                         consumable_component.consumable_type.clone(),
                         &mut sound_effect_event_writer,
+                        &mut consumable_collected_event_writer,
                     );
                 }
             }
@@ -96,6 +98,7 @@ fn apply_effects_on_impact(
     game_parameters_res: &GameParametersResource,
     consumable_type: ConsumableType,
     sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+    consumable_collected_event_writer: &mut EventWriter<ConsumableCollectedEvent>,
 ) {
     for collision_event in collision_events.iter() {
         if let SortedCollisionEvent::PlayerToConsumableIntersection {
@@ -150,6 +153,11 @@ fn apply_effects_on_impact(
                             sound_effect_type: SoundEffectType::ConsumablePickup,
                         });
 
+                        consumable_collected_event_writer.send(ConsumableCollectedEvent {
+                            consumable_type: consumable_type.clone(),
+                            player_entity: *player_entity,
+                        });
+
                         // apply the effects to the player
                         for consumable_effect in consumable_effects {
                             match consumable_effect {
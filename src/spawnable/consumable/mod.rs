@@ -18,6 +18,7 @@ use std::collections::HashMap;
 use thetawave_assets::ConsumableAssets;
 use thetawave_interface::{
     game::options::GameOptions,
+    localization::LocalizationResource,
     spawnable::{ConsumableType, SpawnableType},
     states::GameCleanup,
 };
@@ -39,6 +40,20 @@ pub enum ConsumableEffect {
     GainProjectiles(usize),
 }
 
+impl ConsumableEffect {
+    pub fn describe(&self, localization: &LocalizationResource) -> String {
+        let (id, amount) = match self {
+            ConsumableEffect::GainHealth(amount) => ("consumable_effect.gain_health", amount),
+            ConsumableEffect::GainArmor(amount) => ("consumable_effect.gain_armor", amount),
+            ConsumableEffect::GainMoney(amount) => ("consumable_effect.gain_money", amount),
+            ConsumableEffect::GainProjectiles(amount) => {
+                ("consumable_effect.gain_projectiles", amount)
+            }
+        };
+        localization.format(id, &[("amount", amount.to_string())])
+    }
+}
+
 /// Core component for a consumable
 #[derive(Component)]
 pub struct ConsumableComponent {
@@ -119,6 +134,14 @@ impl ConsumableData {
             1.0 + self.bloom_color.blue * bloom_intensity,
         )
     }
+
+    /// What collecting the consumable does, in plain words for the compendium
+    pub fn describe_effects(&self, localization: &LocalizationResource) -> Vec<String> {
+        self.consumable_effects
+            .iter()
+            .map(|effect| effect.describe(localization))
+            .collect()
+    }
 }
 
 /// Consumable resource stores data about all consumables
@@ -159,7 +182,8 @@ pub fn spawn_consumable(
     consumable
         .insert(SpriteBundle {
             sprite: Sprite {
-                color: consumable_data.affine_bloom_transformation(game_options.bloom_intensity),
+                color: consumable_data
+                    .affine_bloom_transformation(game_options.get_bloom_intensity()),
                 ..Default::default()
             },
             texture: consumable_assets.get_image(consumable_type),
@@ -172,7 +172,9 @@ fn spawn_effect(
         .insert(SpriteBundle {
             texture: effect_assets.get_image(effect_type).unwrap_or_default(),
             sprite: Sprite {
-                color: effect_data.affine_bloom_transformation(game_options.bloom_intensity),
+                // effects like explosions flash with bloom unless motion is reduced
+                color: effect_data
+                    .affine_bloom_transformation(game_options.get_flash_bloom_intensity()),
                 ..Default::default()
             },
             ..default()
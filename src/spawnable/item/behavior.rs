@@ -4,7 +4,8 @@ use bevy::prelude::{
 };
 use serde::Deserialize;
 use thetawave_interface::{
-    health::HealthComponent, player::PlayerComponent, spawnable::ItemComponent, states,
+    health::HealthComponent, localization::LocalizationResource, player::PlayerComponent,
+    spawnable::ItemComponent, states,
 };
 
 use crate::collision::SortedCollisionEvent;
@@ -33,6 +34,18 @@ pub enum ItemBehavior {
     OnCollectFullHeal,
 }
 
+impl ItemBehavior {
+    pub fn describe(&self, localization: &LocalizationResource) -> String {
+        match self {
+            ItemBehavior::OnCollectIncreaseMaxHealth(amount) => localization.format(
+                "item_behavior.increase_max_health",
+                &[("amount", amount.to_string())],
+            ),
+            ItemBehavior::OnCollectFullHeal => localization.get("item_behavior.full_heal"),
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct OnCollectIncreaseMaxHealth(pub usize);
 
@@ -8,6 +8,7 @@ use bevy::{
 };
 use ron::de::from_bytes;
 use serde::Deserialize;
+use thetawave_interface::localization::LocalizationResource;
 use thetawave_interface::spawnable::{ItemComponent, ItemType, SpawnItemEvent, SpawnableType};
 
 use crate::animation::AnimationData;
@@ -68,6 +69,16 @@ pub struct ItemData {
     pub z_level: f32,
 }
 
+impl ItemData {
+    /// What collecting the item does, in plain words for the compendium
+    pub fn describe_behaviors(&self, localization: &LocalizationResource) -> Vec<String> {
+        self.item_behaviors
+            .iter()
+            .map(|behavior| behavior.describe(localization))
+            .collect()
+    }
+}
+
 impl From<&ItemData> for ItemComponent {
     fn from(item_data: &ItemData) -> Self {
         ItemComponent {
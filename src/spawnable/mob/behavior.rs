@@ -9,7 +9,7 @@ use bevy::{
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::{DamageDealtEvent, HealthComponent},
+    health::{DamageDealtEvent, DamageSource, HealthComponent, LastDamagedByPlayerComponent},
     player::PlayerIncomingDamageComponent,
     spawnable::{EffectType, MobDestroyedEvent, SpawnItemEvent, SpawnMobEvent, SpawnPosition},
 };
@@ -32,6 +32,19 @@ pub enum MobBehavior {
     DieAtZeroHealth,
 }
 
+impl MobBehavior {
+    /// String id of the behavior's description, in plain words for the compendium and scanner
+    pub fn describe_id(&self) -> &'static str {
+        match self {
+            MobBehavior::SpawnMob(_) => "mob_behavior.spawn_mob",
+            MobBehavior::ExplodeOnImpact => "mob_behavior.explode_on_impact",
+            MobBehavior::DealDamageToPlayerOnImpact => "mob_behavior.deal_damage_on_impact",
+            MobBehavior::ReceiveDamageOnImpact => "mob_behavior.receive_damage_on_impact",
+            MobBehavior::DieAtZeroHealth => "mob_behavior.die_at_zero_health",
+        }
+    }
+}
+
 #[derive(Deserialize, Hash, PartialEq, Eq, Clone)]
 pub enum MobSegmentControlBehavior {
     FerritharaxProtectHead,
@@ -50,6 +63,7 @@ pub fn mob_execute_behavior_system(
         &Transform,
         &HealthComponent,
         Option<&BossComponent>,
+        Option<&LastDamagedByPlayerComponent>,
     )>,
     player_query: Query<(Entity, &PlayerIncomingDamageComponent)>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
@@ -69,7 +83,9 @@ pub fn mob_execute_behavior_system(
     }
 
     // Iterate through all spawnable entities and execute their behavior
-    for (entity, mut mob_component, mob_transform, mob_health, boss_tag) in mob_query.iter_mut() {
+    for (entity, mut mob_component, mob_transform, mob_health, boss_tag, last_damaged_by) in
+        mob_query.iter_mut()
+    {
         let behaviors = mob_component.behaviors.clone();
         for behavior in behaviors {
             match behavior {
@@ -172,6 +188,7 @@ pub fn mob_execute_behavior_system(
                             entity,
                             mob_type: mob_component.mob_type.clone(),
                             is_boss: boss_tag.is_some(),
+                            killed_by: last_damaged_by.and_then(|damaged_by| damaged_by.0),
                         });
                     }
                 }
@@ -201,6 +218,7 @@ fn receive_damage_on_impact(
                             damage_dealt_event_writer.send(DamageDealtEvent {
                                 damage: *player_damage,
                                 target: *mob_entity,
+                                source: DamageSource::Player(*player_entity),
                             });
                         }
                     }
@@ -208,25 +226,28 @@ fn receive_damage_on_impact(
             }
             SortedCollisionEvent::MobToMobContact {
                 mob_entity_1,
+                mob_entity_2,
                 mob_damage_2,
             } => {
                 if entity == *mob_entity_1 && *mob_damage_2 > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_damage_2,
                         target: *mob_entity_1,
+                        source: DamageSource::Mob(*mob_entity_2),
                     });
                 }
             }
             SortedCollisionEvent::MobToMobSegmentContact {
                 mob_entity,
                 mob_damage: _,
-                mob_segment_entity: _,
+                mob_segment_entity,
                 mob_segment_damage,
             } => {
                 if entity == *mob_entity && *mob_segment_damage > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_segment_damage,
                         target: *mob_entity,
+                        source: DamageSource::MobSegment(*mob_segment_entity),
                     });
                 }
             }
@@ -260,6 +281,7 @@ fn deal_damage_to_player_on_impact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage,
                             target: player_entity_q,
+                            source: DamageSource::Mob(*mob_entity),
                         });
                     }
                 }
@@ -314,6 +336,7 @@ fn explode_on_impact(
             SortedCollisionEvent::MobToMobContact {
                 mob_entity_1,
                 mob_damage_2: _,
+                ..
             } => {
                 sound_effect_event_writer.send(PlaySoundEffectEvent {
                     sound_effect_type: SoundEffectType::MobExplosion,
@@ -11,7 +11,7 @@ use rand::{thread_rng, Rng};
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::{DamageDealtEvent, HealthComponent},
+    health::{DamageDealtEvent, DamageSource, HealthComponent},
     player::PlayerIncomingDamageComponent,
     spawnable::{
         EffectType, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnItemEvent, SpawnPosition,
@@ -320,6 +320,7 @@ fn deal_damage_to_player_on_impact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage,
                             target: player_entity_q,
+                            source: DamageSource::MobSegment(*mob_segment_entity),
                         });
                     }
                 }
@@ -349,6 +350,7 @@ fn receive_damage_on_impact(
                             damage_dealt_event_writer.send(DamageDealtEvent {
                                 damage: *player_damage,
                                 target: *mob_segment_entity,
+                                source: DamageSource::Player(*player_entity),
                             });
                         }
                     }
@@ -357,24 +359,27 @@ fn receive_damage_on_impact(
             SortedCollisionEvent::MobToMobSegmentContact {
                 mob_segment_entity,
                 mob_segment_damage: _,
-                mob_entity: _,
+                mob_entity,
                 mob_damage,
             } => {
                 if entity == *mob_segment_entity && *mob_damage > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_damage,
                         target: *mob_segment_entity,
+                        source: DamageSource::Mob(*mob_entity),
                     });
                 }
             }
             SortedCollisionEvent::MobSegmentToMobSegmentContact {
                 mob_segment_entity_1,
+                mob_segment_entity_2,
                 mob_segment_damage_2,
             } => {
                 if entity == *mob_segment_entity_1 && *mob_segment_damage_2 > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_segment_damage_2,
                         target: *mob_segment_entity_1,
+                        source: DamageSource::MobSegment(*mob_segment_entity_2),
                     });
                 }
             }
@@ -46,7 +46,8 @@ use crate::collision::{
 use thetawave_interface::{
     audio::CollisionSoundType,
     game::options::GameOptions,
-    health::HealthComponent,
+    health::{HealthComponent, LastDamagedByPlayerComponent},
+    localization::LocalizationResource,
     objective::DefenseInteraction,
     spawnable::{MobDestroyedEvent, MobSegmentType, MobType, SpawnMobEvent, SpawnPosition},
     states::GameCleanup,
@@ -237,6 +238,23 @@ impl MobData {
     pub fn get_weapon_component(&self) -> Option<WeaponsComponent> {
         self.weapons.clone().map(WeaponsComponent::from)
     }
+
+    /// What the mob does, in plain words for the compendium
+    pub fn describe_behaviors(&self, localization: &LocalizationResource) -> Vec<String> {
+        let mut description_ids: Vec<&str> = self
+            .mob_behaviors
+            .iter()
+            .map(|behavior| behavior.describe_id())
+            .collect();
+        if self.weapons.is_some() {
+            description_ids.push("mob_behavior.fire_projectiles");
+        }
+        description_ids.dedup();
+        description_ids
+            .into_iter()
+            .map(|id| localization.get(id))
+            .collect()
+    }
 }
 
 #[derive(Deserialize, Clone)]
@@ -388,6 +406,7 @@ pub fn spawn_mob(
     })
     .insert(MobComponent::from(mob_data))
     .insert(HealthComponent::from(mob_data))
+    .insert(LastDamagedByPlayerComponent::default())
     .insert(SpawnableComponent::from(mob_data))
     .insert(ActiveEvents::COLLISION_EVENTS)
     .insert(GameCleanup)
@@ -414,7 +433,8 @@ pub fn spawn_mob(
                     texture: mob_assets.get_thruster_image(mob_type).unwrap(),
                     transform: Transform::from_xyz(0.0, thruster.y_offset, -1.0),
                     sprite: Sprite {
-                        color: thruster.affine_bloom_transformation(game_options.bloom_intensity),
+                        color: thruster
+                            .affine_bloom_transformation(game_options.get_bloom_intensity()),
                         ..default()
                     },
                     ..default()
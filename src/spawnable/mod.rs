@@ -18,10 +18,11 @@ use rand::{thread_rng, Rng};
 use ron::de::from_bytes;
 use serde::Deserialize;
 use std::collections::HashMap;
-use thetawave_interface::spawnable::{ConsumableType, MobType, ProjectileType};
 use thetawave_interface::spawnable::{
-    MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent, SpawnableType,
+    ConsumableCollectedEvent, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent,
+    SpawnableType,
 };
+use thetawave_interface::spawnable::{ConsumableType, MobType, ProjectileType};
 use thetawave_interface::states;
 mod behavior;
 mod behavior_sequence;
@@ -48,8 +49,9 @@ pub(crate) use self::consumable::{
     ConsumableComponent, ConsumableData, ConsumableResource, SpawnConsumableEvent,
 };
 pub(crate) use self::effect::{EffectsResource, SpawnEffectEvent};
+pub(crate) use self::item::ItemResource;
 pub(crate) use self::mob::{
-    BossComponent, BossesDestroyedEvent, MobComponent, MobSegmentComponent,
+    BossComponent, BossesDestroyedEvent, MobComponent, MobSegmentComponent, MobsResource,
 };
 pub(crate) use self::projectile::{
     FireWeaponEvent, ProjectileComponent, ProjectileData, ProjectileResource,
@@ -91,6 +93,7 @@ impl Plugin for SpawnablePlugin {
         });
 
         app.add_event::<SpawnConsumableEvent>()
+            .add_event::<ConsumableCollectedEvent>()
             .add_event::<FireWeaponEvent>()
             .add_event::<SpawnMobEvent>()
             .add_event::<MobBehaviorUpdateEvent>()
@@ -5,14 +5,14 @@ use crate::{
 use bevy::{
     prelude::{
         default, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Query, Res,
-        Transform,
+        Transform, Vec3Swizzles,
     },
     time::Time,
 };
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::DamageDealtEvent,
+    health::{DamageDealtEvent, DamageSource},
     player::PlayerComponent,
     spawnable::{EffectType, Faction, ProjectileType},
 };
@@ -50,6 +50,7 @@ pub fn projectile_execute_behavior_system(
         projectile_query.iter_mut()
     {
         let projectile_type = projectile_component.projectile_type;
+        let projectile_source = projectile_component.source;
         for behavior in projectile_component.behaviors.clone() {
             match behavior {
                 ProjectileBehavior::ExplodeOnIntersection => explode_on_intersection(
@@ -70,6 +71,8 @@ pub fn projectile_execute_behavior_system(
                 ),
                 ProjectileBehavior::DealDamageOnContact => deal_damage_on_contact(
                     projectile_entity,
+                    projectile_transform,
+                    projectile_source,
                     &collision_events_vec,
                     &player_query,
                     &mob_query,
@@ -79,11 +82,12 @@ pub fn projectile_execute_behavior_system(
                 ),
                 ProjectileBehavior::DealDamageOnIntersection => deal_damage_on_intersection(
                     projectile_entity,
+                    projectile_transform,
+                    projectile_source,
                     &collision_events_vec,
                     &player_query,
                     &mob_query,
                     &mob_segment_query,
-                    &mut sound_effect_event_writer,
                     &mut damage_dealt_event_writer,
                 ),
                 ProjectileBehavior::TimedDespawn { despawn_time } => {
@@ -154,6 +158,8 @@ pub fn projectile_execute_behavior_system(
 #[allow(clippy::too_many_arguments)]
 fn deal_damage_on_contact(
     projectile: Entity,
+    transform: &Transform,
+    source: Entity,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerComponent)>,
     mob_query: &Query<(Entity, &MobComponent)>,
@@ -161,6 +167,12 @@ fn deal_damage_on_contact(
     sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
     damage_dealt_event_writer: &mut EventWriter<DamageDealtEvent>,
 ) {
+    let damage_source = DamageSource::Projectile {
+        entity: projectile,
+        position: transform.translation.xy(),
+        source,
+    };
+
     for collision_event in collision_events.iter() {
         match collision_event {
             SortedCollisionEvent::PlayerToProjectileContact {
@@ -172,14 +184,12 @@ fn deal_damage_on_contact(
                 if projectile == *projectile_entity
                     && matches!(*projectile_faction, Faction::Neutral | Faction::Enemy)
                 {
-                    // deal damage to player
-                    sound_effect_event_writer.send(PlaySoundEffectEvent {
-                        sound_effect_type: SoundEffectType::PlayerHit,
-                    });
+                    // deal damage to player, the hit sound is played when the damage is applied
                     if player_query.contains(*player_entity) && *projectile_damage > 0 {
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage: *projectile_damage,
                             target: *player_entity,
+                            source: damage_source,
                         });
                     }
                 }
@@ -207,6 +217,7 @@ fn deal_damage_on_contact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage: *projectile_damage,
                             target: *mob_entity,
+                            source: damage_source,
                         });
                     }
                 }
@@ -233,6 +244,7 @@ fn deal_damage_on_contact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage: *projectile_damage,
                             target: *mob_segment_entity,
+                            source: damage_source,
                         });
                     }
                 }
@@ -245,13 +257,20 @@ fn deal_damage_on_contact(
 #[allow(clippy::too_many_arguments)]
 fn deal_damage_on_intersection(
     projectile: Entity,
+    transform: &Transform,
+    source: Entity,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerComponent)>,
     mob_query: &Query<(Entity, &MobComponent)>,
     mob_segment_query: &Query<(Entity, &MobSegmentComponent)>,
-    sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
     damage_dealt_event_writer: &mut EventWriter<DamageDealtEvent>,
 ) {
+    let damage_source = DamageSource::Projectile {
+        entity: projectile,
+        position: transform.translation.xy(),
+        source,
+    };
+
     for collision_event in collision_events.iter() {
         match collision_event {
             SortedCollisionEvent::PlayerToProjectileIntersection {
@@ -265,13 +284,11 @@ fn deal_damage_on_intersection(
                     && player_query.contains(*player_entity)
                     && *projectile_damage > 0
                 {
-                    // deal damage to player
+                    // deal damage to player, the hit sound is played when the damage is applied
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *projectile_damage,
                         target: *player_entity,
-                    });
-                    sound_effect_event_writer.send(PlaySoundEffectEvent {
-                        sound_effect_type: SoundEffectType::PlayerHit,
+                        source: damage_source,
                     });
                 }
             }
@@ -293,6 +310,7 @@ fn deal_damage_on_intersection(
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *projectile_damage,
                         target: *mob_entity,
+                        source: damage_source,
                     });
                 }
             }
@@ -312,6 +330,7 @@ fn deal_damage_on_intersection(
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *projectile_damage,
                         target: *mob_segment_entity,
+                        source: damage_source,
                     });
                 }
             }
@@ -29,6 +29,7 @@ use crate::collision::{
 use crate::{
     animation::{AnimationComponent, AnimationData},
     game::GameParametersResource,
+    options::accessibility::{get_palette_colors, tint_color},
     spawnable::{SpawnableBehavior, SpawnableComponent},
     weapon::WeaponProjectileInitialVelocitiesExt,
 };
@@ -211,9 +212,12 @@ pub fn spawn_projectile_from_weapon(
             .insert(SpriteBundle {
                 texture: projectile_assets.get_image(&weapon_projectile_data.ammunition),
                 sprite: Sprite {
-                    color: bevy::prelude::Color::Srgba(
-                        projectile_data.affine_bloom_transformation(game_options.bloom_intensity),
-                    ),
+                    color: bevy::prelude::Color::Srgba(tint_color(
+                        projectile_data
+                            .affine_bloom_transformation(game_options.get_bloom_intensity()),
+                        get_palette_colors(game_options.color_palette)
+                            .get_faction_color(&weapon_projectile_data.ammunition.get_faction()),
+                    )),
                     ..default()
                 },
                 ..default()
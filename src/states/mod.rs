@@ -11,13 +11,21 @@ use bevy_asset_loader::standard_dynamic_asset::StandardDynamicAssetCollection;
 use leafwing_input_manager::prelude::ActionState;
 use thetawave_interface::input::MainMenuExplorer;
 use thetawave_interface::input::MenuAction;
+use thetawave_interface::states::AchievementsCleanup;
 use thetawave_interface::states::CharacterSelectionCleanup;
+use thetawave_interface::states::CompendiumCleanup;
+use thetawave_interface::states::ControlsCleanup;
 use thetawave_interface::states::GameCleanup;
 use thetawave_interface::states::GameOverCleanup;
+use thetawave_interface::states::LeaderboardCleanup;
 use thetawave_interface::states::MainMenuCleanup;
+use thetawave_interface::states::OptionsCleanup;
 use thetawave_interface::states::PauseCleanup;
+use thetawave_interface::states::ProfilesCleanup;
+use thetawave_interface::states::StatsCleanup;
 use thetawave_interface::states::VictoryCleanup;
 use thetawave_interface::states::{AppStates, GameStates};
+use thetawave_interface::states::{LevelSummaryCleanup, LevelUpDraftCleanup};
 
 mod pause_menu;
 
@@ -28,7 +36,7 @@ use thetawave_assets::{
     ProjectileAssets, UiAssets,
 };
 
-use self::pause_menu::{close_pause_menu_system, open_pause_menu_system};
+use self::pause_menu::open_pause_menu_system;
 /// Includes systems that handle state transitions for `AppStates` and `GameStates`. Also includes
 /// an asset loading state.
 pub(super) struct StatesPlugin;
@@ -120,6 +128,38 @@ impl Plugin for StatesPlugin {
             clear_state_system::<MainMenuCleanup>,
         );
 
+        app.add_systems(
+            OnExit(AppStates::Leaderboard),
+            clear_state_system::<LeaderboardCleanup>,
+        );
+
+        app.add_systems(OnExit(AppStates::Stats), clear_state_system::<StatsCleanup>);
+
+        app.add_systems(
+            OnExit(AppStates::Profiles),
+            clear_state_system::<ProfilesCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(AppStates::Achievements),
+            clear_state_system::<AchievementsCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(AppStates::Options),
+            clear_state_system::<OptionsCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(AppStates::Controls),
+            clear_state_system::<ControlsCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(AppStates::Compendium),
+            clear_state_system::<CompendiumCleanup>,
+        );
+
         app.add_systems(OnExit(AppStates::Game), clear_state_system::<GameCleanup>);
 
         app.add_systems(
@@ -142,26 +182,37 @@ impl Plugin for StatesPlugin {
             clear_state_system::<PauseCleanup>,
         );
 
+        // an abandoned run ends while the game is still paused
+        app.add_systems(OnExit(AppStates::Game), clear_state_system::<PauseCleanup>);
+
         app.add_systems(
-            Update,
-            close_pause_menu_system.run_if(in_state(GameStates::Paused)),
+            OnExit(GameStates::PausedOptions),
+            clear_state_system::<OptionsCleanup>,
         );
 
         app.add_systems(
-            Update,
-            start_mainmenu_system.run_if(in_state(AppStates::Victory)),
+            OnExit(GameStates::PausedControls),
+            clear_state_system::<ControlsCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(GameStates::LevelUpDraft),
+            clear_state_system::<LevelUpDraftCleanup>,
+        );
+
+        app.add_systems(
+            OnExit(GameStates::LevelSummary),
+            clear_state_system::<LevelSummaryCleanup>,
         );
 
         app.add_systems(
             Update,
-            start_mainmenu_system.run_if(in_state(AppStates::GameOver)),
+            start_mainmenu_system.run_if(in_state(AppStates::Victory)),
         );
 
         app.add_systems(
             Update,
-            start_mainmenu_system
-                .run_if(in_state(AppStates::Game))
-                .run_if(in_state(GameStates::Paused)),
+            start_mainmenu_system.run_if(in_state(AppStates::GameOver)),
         );
     }
 }
@@ -0,0 +1,278 @@
+//! Systems to draw the achievements screen, opened from the main menu, and the toasts announcing
+//! achievements as they are unlocked.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
+    prelude::NextState,
+    text::{JustifyText, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, BackgroundColor, FlexDirection, JustifyContent, PositionType, Style, UiRect,
+        Val, ZIndex,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    game::achievements::{AchievementData, AchievementUnlockedEvent, AchievementsProgressResource},
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    states::{AchievementsCleanup, AppStates},
+};
+
+use crate::game::achievements::AchievementsResource;
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const HEADER_FONT_SIZE: f32 = 28.0;
+const ENTRY_FONT_SIZE: f32 = 18.0;
+const TOAST_TITLE_FONT_SIZE: f32 = 22.0;
+const TOAST_FONT_SIZE: f32 = 16.0;
+/// Seconds that an unlock toast stays on screen
+const TOAST_DURATION: f32 = 4.0;
+
+/// A toast announcing an unlocked achievement, despawned when its timer finishes
+#[derive(Component)]
+pub(super) struct AchievementToastComponent {
+    timer: Timer,
+}
+
+/// Spawns a column of unlocked achievements and a column of locked achievements with progress
+pub(super) fn setup_achievements_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    achievements_res: Res<AchievementsResource>,
+    progress: Res<AchievementsProgressResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+
+    let (unlocked, locked): (Vec<&AchievementData>, Vec<&AchievementData>) = achievements_res
+        .achievements
+        .iter()
+        .partition(|achievement| progress.is_unlocked(&achievement.key));
+
+    let unlocked_text = unlocked
+        .iter()
+        .map(|achievement| {
+            format!(
+                "{} - {}",
+                achievement.get_name(&localization),
+                achievement.get_description(&localization)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let locked_text = locked
+        .iter()
+        .map(|achievement| {
+            format!(
+                "{} - {} ({}/{})",
+                achievement.get_name(&localization),
+                achievement.get_description(&localization),
+                progress.get_progress(&achievement.key),
+                achievement.goal
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(AchievementsCleanup)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.format(
+                    "achievements.title",
+                    &[
+                        ("unlocked", unlocked.len().to_string()),
+                        ("total", achievements_res.achievements.len().to_string()),
+                    ],
+                ),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Vw(3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (title, entries_text, color) in [
+                        (
+                            localization.get("achievements.unlocked"),
+                            unlocked_text,
+                            Color::WHITE,
+                        ),
+                        (
+                            localization.get("achievements.locked"),
+                            locked_text,
+                            Color::srgb(0.6, 0.6, 0.6),
+                        ),
+                    ] {
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Val::Px(10.0)),
+                                row_gap: Val::Px(8.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor::from(Color::BLACK.with_alpha(0.9)),
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column.spawn(TextBundle::from_section(
+                                title,
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: HEADER_FONT_SIZE,
+                                    color: Color::WHITE,
+                                },
+                            ));
+
+                            column.spawn(
+                                TextBundle::from_section(
+                                    if entries_text.is_empty() {
+                                        localization.get("achievements.none")
+                                    } else {
+                                        entries_text
+                                    },
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: ENTRY_FONT_SIZE,
+                                        color,
+                                    },
+                                )
+                                .with_text_justify(JustifyText::Left),
+                            );
+                        });
+                    }
+                });
+
+            parent.spawn(TextBundle::from_section(
+                localization.get("menu.back_hint"),
+                TextStyle {
+                    font,
+                    font_size: ENTRY_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Returns to the main menu
+pub(super) fn achievements_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+) {
+    if let Ok(action_state) = menu_input_query.get_single() {
+        if action_state.just_released(&MenuAction::Back) {
+            next_app_state.set(AppStates::MainMenu);
+        }
+    }
+}
+
+/// Spawns a toast at the top of the screen for each achievement unlocked. Toasts aren't tied to a
+/// state, so an achievement unlocked as a run ends is still announced on the following screen.
+pub(super) fn spawn_achievement_toast_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    achievements_res: Res<AchievementsResource>,
+    mut achievement_unlocked_event_reader: EventReader<AchievementUnlockedEvent>,
+    toast_query: Query<&AchievementToastComponent>,
+    localization: Res<LocalizationResource>,
+) {
+    // toasts unlocked together are stacked below the ones already showing
+    let mut n_toasts = toast_query.iter().count();
+
+    for event in achievement_unlocked_event_reader.read() {
+        let Some(achievement) = achievements_res.get_achievement(&event.key) else {
+            continue;
+        };
+
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0 + 70.0 * n_toasts as f32),
+                    left: Val::Percent(35.0),
+                    width: Val::Percent(30.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: Color::BLACK.with_alpha(0.9).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            })
+            .insert(AchievementToastComponent {
+                timer: Timer::from_seconds(TOAST_DURATION, TimerMode::Once),
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    localization.format(
+                        "achievements.toast",
+                        &[("name", achievement.get_name(&localization))],
+                    ),
+                    TextStyle {
+                        font: ui_assets.lunchds_font.clone(),
+                        font_size: TOAST_TITLE_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ));
+                parent.spawn(TextBundle::from_section(
+                    achievement.get_description(&localization),
+                    TextStyle {
+                        font: ui_assets.lunchds_font.clone(),
+                        font_size: TOAST_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+
+        n_toasts += 1;
+    }
+}
+
+/// Despawns toasts once they have been shown for long enough
+pub(super) fn despawn_achievement_toast_system(
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut AchievementToastComponent)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in toast_query.iter_mut() {
+        if toast.timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
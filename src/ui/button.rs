@@ -8,7 +8,6 @@ use bevy::{
         system::ResMut,
     },
     hierarchy::{BuildChildren, ChildBuilder},
-    log::info,
     prelude::{ImageBundle, NextState},
     render::texture::Image,
     sprite::{TextureAtlas, TextureAtlasLayout},
@@ -20,7 +19,9 @@ use bevy::{
     utils::default,
 };
 use thetawave_assets::UiAssets;
-use thetawave_interface::{player::PlayerInput, states::AppStates};
+use thetawave_interface::{
+    localization::LocalizationResource, player::PlayerInput, states::AppStates,
+};
 
 /// Event and Component for giving and sending menu buttons actions to move the user from
 /// `AppStates::MainMenu` to `AppStates::CharacterSelection`, plus possibly a few digressions and
@@ -32,6 +33,10 @@ pub(super) enum ButtonActionType {
     CharacterSelectRight(u8),
     CharacterSelectLeft(u8),
     EnterCharacterSelection,
+    EnterLeaderboard,
+    EnterStats,
+    EnterProfiles,
+    EnterAchievements,
     EnterOptions,
     EnterCompendium,
     QuitGame,
@@ -60,18 +65,22 @@ impl From<ButtonActionType> for ButtonActionEvent {
 }
 
 impl ButtonActionComponent {
-    /// The label that will show on the main menu screen for the button representing this
-    /// option/action
-    fn text(&self) -> Option<&'static str> {
+    /// String id of the label that will show on the main menu screen for the button representing
+    /// this option/action
+    fn text_id(&self) -> Option<&'static str> {
         match self.action {
-            ButtonActionType::EnterCharacterSelection => Some("Start Game"),
-            ButtonActionType::EnterOptions => Some("Options"),
-            ButtonActionType::EnterCompendium => Some("Compendium"),
-            ButtonActionType::QuitGame => Some("Exit Game"),
+            ButtonActionType::EnterCharacterSelection => Some("button.start_game"),
+            ButtonActionType::EnterLeaderboard => Some("button.leaderboard"),
+            ButtonActionType::EnterStats => Some("button.stats"),
+            ButtonActionType::EnterProfiles => Some("button.profiles"),
+            ButtonActionType::EnterAchievements => Some("button.achievements"),
+            ButtonActionType::EnterOptions => Some("button.options"),
+            ButtonActionType::EnterCompendium => Some("button.compendium"),
+            ButtonActionType::QuitGame => Some("button.exit_game"),
             ButtonActionType::CharacterSelectLeft(_) => None,
             ButtonActionType::CharacterSelectRight(_) => None,
-            ButtonActionType::CharacterSelectJoin => Some("Join"),
-            ButtonActionType::CharacterSelectReady(_) => Some("Ready"),
+            ButtonActionType::CharacterSelectJoin => Some("button.join"),
+            ButtonActionType::CharacterSelectReady(_) => Some("button.ready"),
         }
     }
 
@@ -115,6 +124,10 @@ impl ButtonActionComponent {
     fn get_external_style(&self) -> Style {
         match self.action {
             ButtonActionType::EnterCharacterSelection
+            | ButtonActionType::EnterLeaderboard
+            | ButtonActionType::EnterStats
+            | ButtonActionType::EnterProfiles
+            | ButtonActionType::EnterAchievements
             | ButtonActionType::EnterOptions
             | ButtonActionType::EnterCompendium
             | ButtonActionType::QuitGame => Style {
@@ -153,6 +166,10 @@ impl ButtonActionComponent {
     fn get_internal_style(&self) -> Style {
         match self.action {
             ButtonActionType::EnterCharacterSelection
+            | ButtonActionType::EnterLeaderboard
+            | ButtonActionType::EnterStats
+            | ButtonActionType::EnterProfiles
+            | ButtonActionType::EnterAchievements
             | ButtonActionType::EnterOptions
             | ButtonActionType::EnterCompendium
             | ButtonActionType::QuitGame
@@ -186,6 +203,10 @@ impl ButtonActionComponent {
     fn asset(&self, ui_assets: &UiAssets) -> (Handle<Image>, Handle<TextureAtlasLayout>) {
         match self.action {
             ButtonActionType::EnterCharacterSelection
+            | ButtonActionType::EnterLeaderboard
+            | ButtonActionType::EnterStats
+            | ButtonActionType::EnterProfiles
+            | ButtonActionType::EnterAchievements
             | ButtonActionType::EnterOptions
             | ButtonActionType::EnterCompendium
             | ButtonActionType::QuitGame => (
@@ -224,8 +245,24 @@ pub(super) fn button_action_change_state_system(
             ButtonActionType::EnterCharacterSelection => {
                 next_app_state.set(AppStates::CharacterSelection);
             }
-            ButtonActionType::EnterOptions => info!("Enter options menu."),
-            ButtonActionType::EnterCompendium => info!("Enter compendium."),
+            ButtonActionType::EnterLeaderboard => {
+                next_app_state.set(AppStates::Leaderboard);
+            }
+            ButtonActionType::EnterStats => {
+                next_app_state.set(AppStates::Stats);
+            }
+            ButtonActionType::EnterProfiles => {
+                next_app_state.set(AppStates::Profiles);
+            }
+            ButtonActionType::EnterAchievements => {
+                next_app_state.set(AppStates::Achievements);
+            }
+            ButtonActionType::EnterOptions => {
+                next_app_state.set(AppStates::Options);
+            }
+            ButtonActionType::EnterCompendium => {
+                next_app_state.set(AppStates::Compendium);
+            }
             ButtonActionType::QuitGame => {
                 exit.send(AppExit::Success);
             }
@@ -243,6 +280,7 @@ pub(super) trait UiButtonChildBuilderExt {
         font: Handle<Font>,
         action: ButtonActionComponent,
         player_input: Option<&PlayerInput>,
+        localization: &LocalizationResource,
     );
 }
 
@@ -253,6 +291,7 @@ impl UiButtonChildBuilderExt for ChildBuilder<'_> {
         font: Handle<Font>,
         action: ButtonActionComponent,
         player_input: Option<&PlayerInput>,
+        localization: &LocalizationResource,
     ) {
         // Spawn button bundle entity, with a child entity containing the texture
         self.spawn(ButtonBundle {
@@ -275,10 +314,10 @@ impl UiButtonChildBuilderExt for ChildBuilder<'_> {
                     ..default()
                 })
                 .with_children(|parent| {
-                    if let Some(text) = action.text() {
+                    if let Some(text_id) = action.text_id() {
                         parent.spawn(
                             TextBundle::from_section(
-                                text,
+                                localization.get(text_id),
                                 TextStyle {
                                     font: font.clone(),
                                     font_size: 30.0,
@@ -4,7 +4,10 @@
 use super::button::{
     ButtonActionComponent, ButtonActionEvent, ButtonActionType, UiButtonChildBuilderExt,
 };
-use crate::{game::GameParametersResource, player::CharactersResource};
+use crate::{
+    game::GameParametersResource, player::CharactersResource,
+    run::difficulty::SelectedDifficultyResource,
+};
 use bevy::{
     app::{App, Plugin, Update},
     asset::{AssetServer, Handle},
@@ -19,6 +22,7 @@ use bevy::{
     },
     hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt},
     input::gamepad::{Gamepad, GamepadButtonChangedEvent},
+    log::info,
     prelude::{in_state, NextState, OnEnter},
     text::{Font, Text, TextStyle},
     ui::{
@@ -36,7 +40,9 @@ use thetawave_assets::{PlayerAssets, UiAssets};
 use thetawave_interface::{
     abilities::AbilityDescriptionsResource,
     character::{Character, CharacterStatType},
+    game::{profiles::ProfilesResource, unlocks::CharacterUnlocksResource},
     input::{InputsResource, MainMenuExplorer, MenuAction, MenuExplorer},
+    localization::LocalizationResource,
     states::{self, AppStates},
 };
 use thetawave_interface::{
@@ -62,6 +68,12 @@ impl Plugin for CharacterSelectionPlugin {
                 init_carousel_ui_system,
                 player_ready_system,
                 check_players_ready_system,
+                update_locked_characters_ui_system,
+                cycle_player_profile_system,
+                update_player_profile_text_system,
+                cycle_difficulty_system,
+                update_difficulty_text_system,
+                update_purchase_prompt_text_system,
             )
                 .run_if(in_state(AppStates::CharacterSelection)),
         );
@@ -73,6 +85,28 @@ impl Plugin for CharacterSelectionPlugin {
     }
 }
 
+/// Color multiplied over the image of a locked character in the middle of a carousel
+const LOCKED_CHARACTER_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 1.0);
+
+/// Gets the text shown above a character's description, which includes what it takes to unlock
+/// the character if it is locked
+fn get_character_name_text(
+    character_type: &CharacterType,
+    character: &Character,
+    character_unlocks: &CharacterUnlocksResource,
+    localization: &LocalizationResource,
+) -> String {
+    if character_unlocks.is_unlocked(character_type, character) {
+        character.name.clone()
+    } else {
+        format!(
+            "{}\n{}",
+            localization.format("character.locked", &[("name", character.name.clone())]),
+            character_unlocks.get_lock_description(character, localization)
+        )
+    }
+}
+
 /// Component for tagging a ui node entity as the center
 /// of a player's character selection ui.
 ///
@@ -136,19 +170,44 @@ struct CharacterStatsDescriptions;
 #[derive(Component)]
 struct CharacterCarouselSlot(u8);
 
+/// Text showing the profile that a player's stats are saved to. Everything else from the run is
+/// saved to the first player's profile.
+#[derive(Component)]
+struct PlayerProfileText(u8);
+
+/// Text showing the difficulty that the run will be played on
+#[derive(Component)]
+struct DifficultyText;
+
+/// Text asking a player to confirm the purchase of a locked character, or telling them that they
+/// can't afford it
+#[derive(Component)]
+struct PurchasePromptText(u8);
+
+/// Locked characters are bought with a second press of ready, after their cost is shown
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum CharacterPurchase {
+    /// Waiting for ready to be pressed again to spend the currency
+    Confirming(CharacterType),
+    /// The character costs more than the player has, or can't be bought at all
+    CannotAfford(CharacterType),
+}
+
 /// Component for tracking if a player has toggled the ready button
 #[derive(Component)]
 struct PlayerReadyNode {
     player_idx: u8,
     is_ready: bool,
+    /// Purchase of the locked character the player last tried to ready up with
+    purchase: Option<CharacterPurchase>,
 }
 
 impl CharacterCarousel {
     /// Creates a new `CharacterCarousel` for a given player index.
-    fn new(player_idx: u8) -> Self {
+    fn new(player_idx: u8, characters_res: &CharactersResource) -> Self {
         CharacterCarousel {
             player_idx,
-            characters: CharacterType::iter().collect(),
+            characters: characters_res.get_character_types().into(),
         }
     }
 
@@ -177,7 +236,7 @@ impl CharacterCarousel {
             self.characters.front(),
             self.characters.get(1),
         ) {
-            Some([*left, *middle, *right])
+            Some([left.clone(), middle.clone(), right.clone()])
         } else {
             None
         }
@@ -248,13 +307,20 @@ impl CharacterExt for Character {
 }
 
 trait UiPlayerJoinChildBuilderExt {
-    fn spawn_player_join_row(&mut self, ui_assets: &UiAssets, font: Handle<Font>, players: Vec<u8>);
+    fn spawn_player_join_row(
+        &mut self,
+        ui_assets: &UiAssets,
+        font: Handle<Font>,
+        players: Vec<u8>,
+        localization: &LocalizationResource,
+    );
     fn spawn_ability_descriptions(
         &mut self,
         ui_assets: &UiAssets,
         font: Handle<Font>,
         character: &Character,
         abilities_desc_res: &AbilityDescriptionsResource,
+        localization: &LocalizationResource,
     );
     fn spawn_stats(&mut self, ui_assets: &UiAssets, character: &Character);
 }
@@ -317,6 +383,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
         font: Handle<Font>,
         character: &Character,
         abilities_desc_res: &AbilityDescriptionsResource,
+        localization: &LocalizationResource,
     ) {
         // Check if the character has a slot 1 ability
         if let Some(slot_1_ability_type) = &character.slot_1_ability {
@@ -353,7 +420,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
                     // Spawn text for the slot 1 ability description
                     parent.spawn(TextBundle {
                         text: Text::from_section(
-                            ability_desc,
+                            localization.get(ability_desc),
                             TextStyle {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -409,7 +476,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
                     // Spawn text for the slot 2 ability description
                     parent.spawn(TextBundle {
                         text: Text::from_section(
-                            ability_desc,
+                            localization.get(ability_desc),
                             TextStyle {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -436,6 +503,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
         ui_assets: &UiAssets,
         font: Handle<Font>,
         players: Vec<u8>,
+        localization: &LocalizationResource,
     ) {
         // Spawn a node for the player join row container
         self.spawn(NodeBundle {
@@ -525,6 +593,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
                                                     ButtonActionType::CharacterSelectJoin,
                                                 ),
                                                 None,
+                                                localization,
                                             );
                                         }
                                     });
@@ -542,6 +611,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
                                     .insert(PlayerReadyNode {
                                         player_idx,
                                         is_ready: false,
+                                        purchase: None,
                                     });
                             });
 
@@ -573,6 +643,8 @@ fn setup_character_selection_system(
     asset_server: Res<AssetServer>,
     game_params_res: Res<GameParametersResource>,
     ui_assets: Res<UiAssets>,
+    localization: Res<LocalizationResource>,
+    selected_difficulty: Res<SelectedDifficultyResource>,
 ) {
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
 
@@ -597,6 +669,17 @@ fn setup_character_selection_system(
         })
         .insert(CharacterSelectionCleanup)
         .with_children(|parent| {
+            parent
+                .spawn(TextBundle::from_section(
+                    get_difficulty_text(&selected_difficulty, &localization),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(DifficultyText);
+
             // Create vectors of player indices to use for spawning player join rows
             let mut top_row_player_idxs = vec![];
             let mut bottom_row_player_idxs = vec![];
@@ -612,12 +695,22 @@ fn setup_character_selection_system(
 
             // Spawn player join row for the top row players if not empty
             if !top_row_player_idxs.is_empty() {
-                parent.spawn_player_join_row(&ui_assets, font.clone(), top_row_player_idxs);
+                parent.spawn_player_join_row(
+                    &ui_assets,
+                    font.clone(),
+                    top_row_player_idxs,
+                    &localization,
+                );
             }
 
             // Spawn player join row for the bottom row players if not empty
             if !bottom_row_player_idxs.is_empty() {
-                parent.spawn_player_join_row(&ui_assets, font.clone(), bottom_row_player_idxs);
+                parent.spawn_player_join_row(
+                    &ui_assets,
+                    font.clone(),
+                    bottom_row_player_idxs,
+                    &localization,
+                );
             }
         });
 }
@@ -635,7 +728,16 @@ fn player_join_system(
     mut players_resource: ResMut<PlayersResource>,
     mut gamepad_events: EventReader<GamepadButtonChangedEvent>,
     mut player_join_event: EventWriter<PlayerJoinEvent>,
+    characters_res: Res<CharactersResource>,
+    profiles: Res<ProfilesResource>,
 ) {
+    // Players start on the first character of the carousel
+    let default_character = characters_res
+        .get_character_types()
+        .first()
+        .cloned()
+        .unwrap_or_default();
+
     // Check if the join button was pressed
     if let Some(button) = button_mouse_movements.iter().find(|(button_action, _, _)| {
         matches!(button_action.action, ButtonActionType::CharacterSelectJoin)
@@ -666,9 +768,12 @@ fn player_join_system(
                 }
             } {
                 // Push the new player to the players resource
+                let profile_id =
+                    profiles.get_profile_for_new_player(&players_resource.get_used_profile_ids());
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: default_character.clone(),
                     input: player_input,
+                    profile_id,
                 }));
 
                 // Send player join event and button action event
@@ -705,9 +810,12 @@ fn player_join_system(
                 }
             } {
                 // Push the new player to the players resource
+                let profile_id =
+                    profiles.get_profile_for_new_player(&players_resource.get_used_profile_ids());
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: default_character.clone(),
                     input: player_input,
+                    profile_id,
                 }));
 
                 // Send player join event and button action event
@@ -737,9 +845,12 @@ fn player_join_system(
                 _ => None,
             } {
                 // Push the new player to the players resource
+                let profile_id =
+                    profiles.get_profile_for_new_player(&players_resource.get_used_profile_ids());
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: default_character.clone(),
                     input: player_input,
+                    profile_id,
                 }));
 
                 // Send player join event and button action event
@@ -775,6 +886,8 @@ fn update_ui_system(
     buttons: Query<(&ButtonActionComponent, Entity), With<Button>>,
     ui_assets: Res<UiAssets>,
     inputs_res: Res<InputsResource>,
+    characters_res: Res<CharactersResource>,
+    localization: Res<LocalizationResource>,
 ) {
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
 
@@ -799,6 +912,28 @@ fn update_ui_system(
 
                 // Spawn a character selection carousel
                 commands.entity(entity).with_children(|parent| {
+                    parent
+                        .spawn(TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 18.0,
+                                color: Color::WHITE,
+                            },
+                        ))
+                        .insert(PlayerProfileText(*player_idx));
+
+                    parent
+                        .spawn(TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 18.0,
+                                color: GOLD.into(),
+                            },
+                        ))
+                        .insert(PurchasePromptText(*player_idx));
+
                     parent
                         .spawn(NodeBundle {
                             style: Style {
@@ -812,7 +947,7 @@ fn update_ui_system(
                             },
                             ..default()
                         })
-                        .insert(CharacterCarousel::new(*player_idx));
+                        .insert(CharacterCarousel::new(*player_idx, &characters_res));
 
                     parent
                         .spawn(NodeBundle {
@@ -919,6 +1054,7 @@ fn update_ui_system(
                             *player_idx,
                         )),
                         None,
+                        &localization,
                     )
                 });
             };
@@ -932,6 +1068,7 @@ fn update_ui_system(
                             *player_idx,
                         )),
                         None,
+                        &localization,
                     )
                 });
             };
@@ -945,6 +1082,7 @@ fn update_ui_system(
                             *player_idx,
                         )),
                         Some(input),
+                        &localization,
                     );
                 });
             }
@@ -1108,6 +1246,138 @@ fn keyboard_and_gamepad_input_system(
     }
 }
 
+/// Gets the direction that a player stepped through a list with the up/down inputs of the device
+/// they joined with, -1 for up and 1 for down
+fn get_vertical_step(action_state: &ActionState<MenuAction>, is_gamepad: bool) -> Option<isize> {
+    let (up_action, down_action) = if is_gamepad {
+        (
+            MenuAction::NavigateUpGamepad,
+            MenuAction::NavigateDownGamepad,
+        )
+    } else {
+        (
+            MenuAction::NavigateUpKeyboard,
+            MenuAction::NavigateDownKeyboard,
+        )
+    };
+
+    if action_state.just_released(&up_action) {
+        Some(-1)
+    } else if action_state.just_released(&down_action) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Lets the first player pick the difficulty of the run with up/down, since they can't switch
+/// profiles
+fn cycle_difficulty_system(
+    menu_input_query: Query<(&ActionState<MenuAction>, &MenuExplorer)>,
+    players_resource: Res<PlayersResource>,
+    mut selected_difficulty: ResMut<SelectedDifficultyResource>,
+) {
+    let is_gamepad = matches!(
+        players_resource.get_used_inputs().first(),
+        Some(PlayerInput::Gamepad(_))
+    );
+
+    if let Some(step) = menu_input_query
+        .iter()
+        .find(|(_, MenuExplorer(player_idx))| *player_idx == 0)
+        .and_then(|(action_state, _)| get_vertical_step(action_state, is_gamepad))
+    {
+        selected_difficulty.0 = selected_difficulty.0.cycle(step);
+    }
+}
+
+/// Shows the difficulty that the run will be played on
+fn update_difficulty_text_system(
+    mut difficulty_text_query: Query<&mut Text, With<DifficultyText>>,
+    selected_difficulty: Res<SelectedDifficultyResource>,
+    localization: Res<LocalizationResource>,
+) {
+    if !selected_difficulty.is_changed() {
+        return;
+    }
+
+    for mut text in difficulty_text_query.iter_mut() {
+        text.sections[0].value = get_difficulty_text(&selected_difficulty, &localization);
+    }
+}
+
+fn get_difficulty_text(
+    selected_difficulty: &SelectedDifficultyResource,
+    localization: &LocalizationResource,
+) -> String {
+    localization.format(
+        "character_selection.difficulty",
+        &[("difficulty", selected_difficulty.0.get_name(localization))],
+    )
+}
+
+/// Lets every player after the first switch between the profiles that aren't used by another
+/// player, to pick where their stats are saved. The first player always uses the profile selected
+/// in the main menu, which also keeps the run's unlocks, achievements and history.
+fn cycle_player_profile_system(
+    menu_input_query: Query<(&ActionState<MenuAction>, &MenuExplorer)>,
+    mut players_resource: ResMut<PlayersResource>,
+    profiles: Res<ProfilesResource>,
+) {
+    let used_inputs = players_resource.get_used_inputs();
+
+    for (action_state, MenuExplorer(player_idx)) in menu_input_query.iter() {
+        let player_idx = *player_idx as usize;
+        if player_idx == 0 {
+            continue;
+        }
+
+        let is_gamepad = matches!(used_inputs.get(player_idx), Some(PlayerInput::Gamepad(_)));
+        let Some(step) = get_vertical_step(action_state, is_gamepad) else {
+            continue;
+        };
+
+        let other_profile_ids: Vec<usize> = players_resource
+            .player_data
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != player_idx)
+            .filter_map(|(_, data)| data.as_ref().map(|data| data.profile_id))
+            .collect();
+
+        if let Some(Some(player_data)) = players_resource.player_data.get_mut(player_idx) {
+            // skip over profiles used by other players, staying put if there are none left
+            let mut profile_id = player_data.profile_id;
+            for _ in 0..profiles.profiles.len() {
+                profile_id = profiles.cycle_profile(profile_id, step);
+                if !other_profile_ids.contains(&profile_id) {
+                    player_data.profile_id = profile_id;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Shows the name of each joined player's profile
+fn update_player_profile_text_system(
+    mut profile_text_query: Query<(&mut Text, &PlayerProfileText)>,
+    players_resource: Res<PlayersResource>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
+) {
+    for (mut text, PlayerProfileText(player_idx)) in profile_text_query.iter_mut() {
+        if let Some(Some(player_data)) = players_resource.player_data.get(*player_idx as usize) {
+            let name = profiles.get_name(player_data.profile_id);
+            text.sections[0].value = if *player_idx == 0 {
+                localization.format("character_selection.profile", &[("name", name)])
+            } else {
+                localization.format("character_selection.profile_cycle", &[("name", name)])
+            };
+        }
+    }
+}
+
 /// Initializes the character carousel UI elements.
 ///
 /// This function sets up the initial characters in the carousel for each player,
@@ -1127,6 +1397,8 @@ fn init_carousel_ui_system(
     asset_server: Res<AssetServer>,
     mut players_res: ResMut<PlayersResource>,
     abilities_desc_res: Res<AbilityDescriptionsResource>,
+    character_unlocks: Res<CharacterUnlocksResource>,
+    localization: Res<LocalizationResource>,
 ) {
     // Load the font for UI text elements
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
@@ -1154,9 +1426,20 @@ fn init_carousel_ui_system(
                     })
                     .insert(CharacterCarouselSlot(0));
 
+                // Darken the middle character if it is locked
+                let middle_color = match characters_res.characters.get(&visible_characters[1]) {
+                    Some(character)
+                        if !character_unlocks.is_unlocked(&visible_characters[1], character) =>
+                    {
+                        LOCKED_CHARACTER_COLOR
+                    }
+                    _ => Color::WHITE,
+                };
+
                 parent
                     .spawn(ImageBundle {
-                        image: player_assets.get_asset(&visible_characters[1]).into(),
+                        image: UiImage::new(player_assets.get_asset(&visible_characters[1]))
+                            .with_color(middle_color),
                         style: Style {
                             height: Val::Percent(100.0),
                             margin: UiRect {
@@ -1206,9 +1489,12 @@ fn init_carousel_ui_system(
                         if let Ok(mut character_name_text) =
                             character_names.get_mut(*char_desc_child)
                         {
-                            character_name_text.sections[0]
-                                .value
-                                .clone_from(&character.name);
+                            character_name_text.sections[0].value = get_character_name_text(
+                                &visible_characters[1],
+                                character,
+                                &character_unlocks,
+                                &localization,
+                            );
                         }
 
                         // Update character abilities and stats
@@ -1226,6 +1512,7 @@ fn init_carousel_ui_system(
                                                 font.clone(),
                                                 character,
                                                 &abilities_desc_res,
+                                                &localization,
                                             );
                                         });
                                 // Update character stat descriptions
@@ -1247,7 +1534,7 @@ fn init_carousel_ui_system(
                 .player_data
                 .get_mut(carousel.player_idx as usize)
             {
-                player_data.character = visible_characters[1];
+                player_data.character = visible_characters[1].clone();
             }
         }
     }
@@ -1275,6 +1562,8 @@ fn carousel_ui_system(
     ui_assets: Res<UiAssets>,
     asset_server: Res<AssetServer>,
     player_ready_node: Query<&PlayerReadyNode>,
+    character_unlocks: Res<CharacterUnlocksResource>,
+    localization: Res<LocalizationResource>,
 ) {
     // Load the font for UI text elements
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
@@ -1320,6 +1609,15 @@ fn carousel_ui_system(
 
                             if idx != 1 {
                                 ui_image.color = Color::srgba(0.60, 0.60, 0.60, 0.60);
+                            } else if characters_res
+                                .characters
+                                .get(&visible_characters[1])
+                                .is_some_and(|character| {
+                                    !character_unlocks
+                                        .is_unlocked(&visible_characters[1], character)
+                                })
+                            {
+                                ui_image.color = LOCKED_CHARACTER_COLOR;
                             }
                         }
                     }
@@ -1341,9 +1639,12 @@ fn carousel_ui_system(
                                     character_names.get_mut(*char_desc_child)
                                 {
                                     // Replace the character name with the name of the new middle character
-                                    character_name_text.sections[0]
-                                        .value
-                                        .clone_from(&character.name);
+                                    character_name_text.sections[0].value = get_character_name_text(
+                                        &visible_characters[1],
+                                        character,
+                                        &character_unlocks,
+                                        &localization,
+                                    );
                                 } else if let Ok(char_info_children) =
                                     character_info.get(*char_desc_child)
                                 {
@@ -1365,6 +1666,7 @@ fn carousel_ui_system(
                                                         font.clone(),
                                                         character,
                                                         &abilities_desc_res,
+                                                        &localization,
                                                     );
                                                 },
                                             );
@@ -1395,7 +1697,7 @@ fn carousel_ui_system(
                         .player_data
                         .get_mut(carousel.player_idx as usize)
                     {
-                        player_data.character = visible_characters[1];
+                        player_data.character = visible_characters[1].clone();
                     }
                 }
             }
@@ -1406,12 +1708,16 @@ fn carousel_ui_system(
 /// Updates the player ready state and button color based on player actions.
 ///
 /// This function listens for player ready events and updates the player ready state and the color
-/// of the ready button to indicate the player is ready.
+/// of the ready button to indicate the player is ready. Players ready up with a locked character by
+/// pressing ready a second time to buy it.
 fn player_ready_system(
     mut button_reader: EventReader<ButtonActionEvent>,
     mut player_ready: Query<(&mut PlayerReadyNode, &Children)>,
     ready_button_parents: Query<&Children>,
     mut ready_button_images: Query<&mut UiImage>,
+    players_res: Res<PlayersResource>,
+    characters_res: Res<CharactersResource>,
+    mut character_unlocks: ResMut<CharacterUnlocksResource>,
 ) {
     // Iterate over each button action event
     for event in button_reader.read() {
@@ -1421,6 +1727,47 @@ fn player_ready_system(
                 if player_idx == player_ready_node.player_idx {
                     // If the player is not ready, update the state to ready and change button color
                     if !player_ready_node.is_ready {
+                        // A locked character has to be bought before the player can ready up with it
+                        if let Some(Some(player_data)) =
+                            players_res.player_data.get(player_idx as usize)
+                        {
+                            if let Some(character) =
+                                characters_res.characters.get(&player_data.character)
+                            {
+                                if !character_unlocks.is_unlocked(&player_data.character, character)
+                                {
+                                    // the first press shows the cost, the second one spends it
+                                    let confirmed = player_ready_node.purchase
+                                        == Some(CharacterPurchase::Confirming(
+                                            player_data.character.clone(),
+                                        ));
+
+                                    if confirmed
+                                        && character_unlocks
+                                            .purchase(&player_data.character, character)
+                                    {
+                                        info!("Purchased character {}", character.name);
+                                        player_ready_node.purchase = None;
+                                    } else {
+                                        player_ready_node.purchase = Some(
+                                            if character.unlock_cost.is_some_and(|cost| {
+                                                cost <= character_unlocks.meta_currency
+                                            }) {
+                                                CharacterPurchase::Confirming(
+                                                    player_data.character.clone(),
+                                                )
+                                            } else {
+                                                CharacterPurchase::CannotAfford(
+                                                    player_data.character.clone(),
+                                                )
+                                            },
+                                        );
+                                        continue;
+                                    }
+                                }
+                            }
+                        }
+
                         player_ready_node.is_ready = true;
 
                         // Get the first child of the ready node, which is the parent entity of the ready button
@@ -1449,6 +1796,136 @@ fn player_ready_system(
     }
 }
 
+/// Shows the cost of the locked character that a player is buying, or that they can't afford it,
+/// until they switch to another character
+fn update_purchase_prompt_text_system(
+    mut prompt_text_query: Query<(&mut Text, &PurchasePromptText)>,
+    player_ready_nodes: Query<&PlayerReadyNode>,
+    players_res: Res<PlayersResource>,
+    characters_res: Res<CharactersResource>,
+    character_unlocks: Res<CharacterUnlocksResource>,
+    localization: Res<LocalizationResource>,
+) {
+    for (mut text, PurchasePromptText(player_idx)) in prompt_text_query.iter_mut() {
+        let selected_character = players_res
+            .player_data
+            .get(*player_idx as usize)
+            .cloned()
+            .flatten()
+            .map(|player_data| player_data.character);
+
+        let purchase = player_ready_nodes
+            .iter()
+            .find(|node| node.player_idx == *player_idx)
+            .and_then(|node| node.purchase.clone());
+
+        let prompt = match purchase {
+            Some(
+                CharacterPurchase::Confirming(character_type)
+                | CharacterPurchase::CannotAfford(character_type),
+            ) if Some(&character_type) != selected_character.as_ref() => String::new(),
+            Some(CharacterPurchase::Confirming(character_type)) => characters_res
+                .characters
+                .get(&character_type)
+                .map(|character| {
+                    localization.format(
+                        "character_selection.confirm_purchase",
+                        &[
+                            ("name", character.name.clone()),
+                            (
+                                "cost",
+                                character.unlock_cost.unwrap_or_default().to_string(),
+                            ),
+                        ],
+                    )
+                })
+                .unwrap_or_default(),
+            Some(CharacterPurchase::CannotAfford(character_type)) => characters_res
+                .characters
+                .get(&character_type)
+                .map(|character| match character.unlock_cost {
+                    Some(cost) => localization.format(
+                        "character_selection.not_enough_currency",
+                        &[
+                            ("name", character.name.clone()),
+                            ("cost", cost.to_string()),
+                            ("available", character_unlocks.meta_currency.to_string()),
+                        ],
+                    ),
+                    None => localization.format(
+                        "character_selection.cannot_purchase",
+                        &[("name", character.name.clone())],
+                    ),
+                })
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+
+        if text.sections[0].value != prompt {
+            text.sections[0].value = prompt;
+        }
+    }
+}
+
+/// Refreshes the name and image of the middle character of every carousel when characters are
+/// unlocked or bought.
+fn update_locked_characters_ui_system(
+    character_unlocks: Res<CharacterUnlocksResource>,
+    characters_res: Res<CharactersResource>,
+    character_carousels: Query<(&CharacterCarousel, &Children)>,
+    mut carousel_slots: Query<(&mut UiImage, &CharacterCarouselSlot)>,
+    character_descriptions: Query<(&CharacterDescription, &Children)>,
+    mut character_names: Query<&mut Text, With<CharacterName>>,
+    localization: Res<LocalizationResource>,
+) {
+    if !character_unlocks.is_changed() {
+        return;
+    }
+
+    for (carousel, carousel_children) in character_carousels.iter() {
+        if let Some(visible_characters) = carousel.get_visible_characters() {
+            if let Some(character) = characters_res.characters.get(&visible_characters[1]) {
+                // Update the color of the middle character
+                for carousel_child in carousel_children.iter() {
+                    if let Ok((mut ui_image, slot)) = carousel_slots.get_mut(*carousel_child) {
+                        if slot.0 == 1 {
+                            ui_image.color = if character_unlocks
+                                .is_unlocked(&visible_characters[1], character)
+                            {
+                                Color::WHITE
+                            } else {
+                                LOCKED_CHARACTER_COLOR
+                            };
+                        }
+                    }
+                }
+
+                // Update the character name text with the lock status
+                if let Some((_, char_desc_children)) =
+                    character_descriptions
+                        .iter()
+                        .find(|(character_description, _)| {
+                            character_description.0 == carousel.player_idx
+                        })
+                {
+                    for char_desc_child in char_desc_children.iter() {
+                        if let Ok(mut character_name_text) =
+                            character_names.get_mut(*char_desc_child)
+                        {
+                            character_name_text.sections[0].value = get_character_name_text(
+                                &visible_characters[1],
+                                character,
+                                &character_unlocks,
+                                &localization,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Checks if all players are ready and updates the application state accordingly.
 ///
 /// This function iterates through all player ready nodes to determine if all players
@@ -0,0 +1,445 @@
+//! Systems to draw the compendium, opened from the main menu, which describes every mob, item and
+//! consumable that has been encountered.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        component::Component,
+        query::{Changed, With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::BuildChildren,
+    prelude::{NextState, Visibility},
+    sprite::TextureAtlas,
+    text::{JustifyText, Text, TextStyle},
+    ui::{
+        node_bundles::{ImageBundle, NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, Style, UiImage, UiRect, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::{ConsumableAssets, ItemAssets, MobAssets, UiAssets};
+use thetawave_interface::{
+    game::compendium::{CompendiumEntry, EncountersResource},
+    game::historical_metrics::MobKillsByPlayerForCompletedGames,
+    game::profiles::ProfilesResource,
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    spawnable::{EnemyMobType, MobType},
+    states::{AppStates, CompendiumCleanup},
+};
+
+use super::spaced_name;
+use crate::spawnable::{ConsumableResource, ItemResource, MobsResource};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const LIST_FONT_SIZE: f32 = 20.0;
+const DETAIL_FONT_SIZE: f32 = 22.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+const SPRITE_HEIGHT: f32 = 128.0;
+/// Name shown for entries that haven't been encountered yet
+const HIDDEN_NAME: &str = "???";
+
+/// One row of the compendium. Mob variants sharing a name, like left and right strafers, are
+/// listed once and count as encountered when any of them has been.
+struct CompendiumRow {
+    name: String,
+    entries: Vec<CompendiumEntry>,
+}
+
+impl CompendiumRow {
+    fn is_encountered(&self, encounters: &EncountersResource) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| encounters.has_encountered(entry))
+    }
+}
+
+/// State of the compendium screen
+#[derive(Component)]
+pub(super) struct CompendiumMenu {
+    rows: Vec<CompendiumRow>,
+    selected_idx: usize,
+}
+
+/// Text listing every row of the compendium
+#[derive(Component)]
+pub(super) struct CompendiumListText;
+
+/// Text describing the selected row
+#[derive(Component)]
+pub(super) struct CompendiumDetailText;
+
+/// Sprite of the selected row, hidden until it has been encountered
+#[derive(Component)]
+pub(super) struct CompendiumSprite;
+
+/// Mobs, then items, then consumables, each sorted by name
+fn build_compendium_rows(
+    mobs_res: &MobsResource,
+    items_res: &ItemResource,
+    consumables_res: &ConsumableResource,
+    localization: &LocalizationResource,
+) -> Vec<CompendiumRow> {
+    let mut mob_rows: Vec<CompendiumRow> = vec![];
+    let mut mob_types: Vec<&MobType> = mobs_res.mobs.keys().collect();
+    mob_types.sort_by_key(|mob_type| format!("{mob_type:?}"));
+    for mob_type in mob_types {
+        let entry = CompendiumEntry::Mob(mob_type.clone());
+        let name = mob_type.get_name(localization);
+        match mob_rows.iter_mut().find(|row| row.name == name) {
+            Some(row) => row.entries.push(entry),
+            None => mob_rows.push(CompendiumRow {
+                name,
+                entries: vec![entry],
+            }),
+        }
+    }
+
+    let mut item_rows: Vec<CompendiumRow> = items_res
+        .items
+        .keys()
+        .map(|item_type| CompendiumRow {
+            name: spaced_name(&item_type.to_string()),
+            entries: vec![CompendiumEntry::Item(item_type.clone())],
+        })
+        .collect();
+
+    let mut consumable_rows: Vec<CompendiumRow> = consumables_res
+        .consumables
+        .keys()
+        .map(|consumable_type| CompendiumRow {
+            name: spaced_name(&consumable_type.to_string()),
+            entries: vec![CompendiumEntry::Consumable(consumable_type.clone())],
+        })
+        .collect();
+
+    for rows in [&mut mob_rows, &mut item_rows, &mut consumable_rows] {
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    mob_rows
+        .into_iter()
+        .chain(item_rows)
+        .chain(consumable_rows)
+        .collect()
+}
+
+/// Spawns the list of entries next to the details of the selected entry
+pub(super) fn setup_compendium_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    mobs_res: Res<MobsResource>,
+    items_res: Res<ItemResource>,
+    consumables_res: Res<ConsumableResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(CompendiumCleanup)
+        .insert(CompendiumMenu {
+            rows: build_compendium_rows(&mobs_res, &items_res, &consumables_res, &localization),
+            selected_idx: 0,
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.get("compendium.title"),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Vw(3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: LIST_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_text_justify(JustifyText::Left),
+                    )
+                    .insert(CompendiumListText);
+
+                    row.spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::all(Val::Px(10.0)),
+                            row_gap: Val::Px(8.0),
+                            min_width: Val::Vw(30.0),
+                            ..default()
+                        },
+                        background_color: Color::BLACK.with_alpha(0.9).into(),
+                        ..default()
+                    })
+                    .with_children(|details| {
+                        details
+                            .spawn(ImageBundle {
+                                style: Style {
+                                    height: Val::Px(SPRITE_HEIGHT),
+                                    ..default()
+                                },
+                                visibility: Visibility::Hidden,
+                                ..default()
+                            })
+                            .insert(TextureAtlas::default())
+                            .insert(CompendiumSprite);
+
+                        details
+                            .spawn(
+                                TextBundle::from_section(
+                                    "",
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: DETAIL_FONT_SIZE,
+                                        color: Color::WHITE,
+                                    },
+                                )
+                                .with_text_justify(JustifyText::Left),
+                            )
+                            .insert(CompendiumDetailText);
+                    });
+                });
+
+            parent.spawn(TextBundle::from_section(
+                localization.get("compendium.hint"),
+                TextStyle {
+                    font,
+                    font_size: HINT_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Moves the selection and returns to the main menu
+pub(super) fn compendium_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut compendium_menu_query: Query<&mut CompendiumMenu>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+) {
+    let (Ok(action_state), Ok(mut compendium_menu)) = (
+        menu_input_query.get_single(),
+        compendium_menu_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let n_rows = compendium_menu.rows.len().max(1);
+
+    if action_state.just_released(&MenuAction::NavigateUpKeyboard)
+        || action_state.just_released(&MenuAction::NavigateUpGamepad)
+    {
+        compendium_menu.selected_idx = (compendium_menu.selected_idx + n_rows - 1) % n_rows;
+    } else if action_state.just_released(&MenuAction::NavigateDownKeyboard)
+        || action_state.just_released(&MenuAction::NavigateDownGamepad)
+    {
+        compendium_menu.selected_idx = (compendium_menu.selected_idx + 1) % n_rows;
+    } else if action_state.just_released(&MenuAction::Back) {
+        next_app_state.set(AppStates::MainMenu);
+    }
+}
+
+/// Lines describing an encountered entry, below its name
+fn describe_entry(
+    entry: &CompendiumEntry,
+    mobs_res: &MobsResource,
+    items_res: &ItemResource,
+    consumables_res: &ConsumableResource,
+    localization: &LocalizationResource,
+) -> Vec<String> {
+    match entry {
+        CompendiumEntry::Mob(mob_type) => {
+            let Some(mob_data) = mobs_res.mobs.get(mob_type) else {
+                return vec![];
+            };
+            [
+                mob_type.get_faction().get_name(localization),
+                localization.format(
+                    "compendium.health",
+                    &[("health", mob_data.health.to_string())],
+                ),
+                localization.format(
+                    "compendium.damage",
+                    &[("damage", mob_data.collision_damage.to_string())],
+                ),
+            ]
+            .into_iter()
+            .chain(mob_data.describe_behaviors(localization))
+            .collect()
+        }
+        CompendiumEntry::Item(item_type) => [localization.get("compendium.item")]
+            .into_iter()
+            .chain(
+                items_res
+                    .items
+                    .get(item_type)
+                    .map(|item_data| item_data.describe_behaviors(localization))
+                    .unwrap_or_default(),
+            )
+            .collect(),
+        CompendiumEntry::Consumable(consumable_type) => [localization.get("compendium.consumable")]
+            .into_iter()
+            .chain(
+                consumables_res
+                    .consumables
+                    .get(consumable_type)
+                    .map(|consumable_data| consumable_data.describe_effects(localization))
+                    .unwrap_or_default(),
+            )
+            .collect(),
+    }
+}
+
+/// Redraws the list of entries and the details and sprite of the selected entry
+#[allow(clippy::too_many_arguments)]
+pub(super) fn update_compendium_ui_system(
+    compendium_menu_query: Query<&CompendiumMenu, Changed<CompendiumMenu>>,
+    mut list_text_query: Query<
+        &mut Text,
+        (With<CompendiumListText>, Without<CompendiumDetailText>),
+    >,
+    mut detail_text_query: Query<
+        &mut Text,
+        (With<CompendiumDetailText>, Without<CompendiumListText>),
+    >,
+    mut sprite_query: Query<
+        (&mut UiImage, &mut TextureAtlas, &mut Visibility),
+        With<CompendiumSprite>,
+    >,
+    encounters: Res<EncountersResource>,
+    mob_kills: Res<MobKillsByPlayerForCompletedGames>,
+    profiles: Res<ProfilesResource>,
+    mobs_res: Res<MobsResource>,
+    items_res: Res<ItemResource>,
+    consumables_res: Res<ConsumableResource>,
+    mob_assets: Res<MobAssets>,
+    item_assets: Res<ItemAssets>,
+    consumable_assets: Res<ConsumableAssets>,
+    localization: Res<LocalizationResource>,
+) {
+    let (Ok(compendium_menu), Ok(mut list_text), Ok(mut detail_text), Ok(sprite)) = (
+        compendium_menu_query.get_single(),
+        list_text_query.get_single_mut(),
+        detail_text_query.get_single_mut(),
+        sprite_query.get_single_mut(),
+    ) else {
+        return;
+    };
+    let (mut sprite_image, mut sprite_atlas, mut sprite_visibility) = sprite;
+
+    list_text.sections[0].value = compendium_menu
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            format!(
+                "{}{}",
+                if idx == compendium_menu.selected_idx {
+                    "> "
+                } else {
+                    "  "
+                },
+                if row.is_encountered(&encounters) {
+                    row.name.as_str()
+                } else {
+                    HIDDEN_NAME
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let Some(row) = compendium_menu
+        .rows
+        .get(compendium_menu.selected_idx)
+        .filter(|row| row.is_encountered(&encounters))
+    else {
+        detail_text.sections[0].value = format!(
+            "{HIDDEN_NAME}\n{}",
+            localization.get("compendium.not_encountered")
+        );
+        *sprite_visibility = Visibility::Hidden;
+        return;
+    };
+    // the first variant that was encountered stands in for the others
+    let Some(entry) = row
+        .entries
+        .iter()
+        .find(|entry| encounters.has_encountered(entry))
+    else {
+        return;
+    };
+
+    let mut lines = vec![row.name.clone()];
+    lines.extend(describe_entry(
+        entry,
+        &mobs_res,
+        &items_res,
+        &consumables_res,
+        &localization,
+    ));
+    // kills are only counted across sessions when they are saved
+    let enemy_types: Vec<&EnemyMobType> = row
+        .entries
+        .iter()
+        .filter_map(|entry| match entry {
+            CompendiumEntry::Mob(MobType::Enemy(enemy_type)) => Some(enemy_type),
+            _ => None,
+        })
+        .collect();
+    if cfg!(feature = "storage") && !enemy_types.is_empty() {
+        let profile_kills = mob_kills.get(&profiles.active_profile_id);
+        let n_killed: usize = enemy_types
+            .iter()
+            .filter_map(|enemy_type| profile_kills.and_then(|kills| kills.get(*enemy_type)))
+            .sum();
+        lines.push(localization.format("compendium.destroyed", &[("count", n_killed.to_string())]));
+    }
+    detail_text.sections[0].value = lines.join("\n");
+
+    let (image, layout) = match entry {
+        CompendiumEntry::Mob(mob_type) => (
+            mob_assets.get_mob_image(mob_type),
+            mob_assets.get_mob_texture_atlas_layout(mob_type),
+        ),
+        CompendiumEntry::Item(item_type) => (
+            item_assets.get_image(item_type),
+            item_assets.get_texture_atlas_layout(item_type),
+        ),
+        CompendiumEntry::Consumable(consumable_type) => (
+            consumable_assets.get_image(consumable_type),
+            consumable_assets.get_texture_atlas_layout(consumable_type),
+        ),
+    };
+    *sprite_image = UiImage::new(image);
+    *sprite_atlas = TextureAtlas { layout, index: 0 };
+    *sprite_visibility = Visibility::Inherited;
+}
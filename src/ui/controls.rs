@@ -0,0 +1,482 @@
+//! Systems to draw the controls screen, opened from the options screen, where every menu and
+//! player action can be rebound for the keyboard, mouse and gamepad.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        component::Component,
+        event::EventWriter,
+        query::{With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::BuildChildren,
+    input::{gamepad::GamepadButton, keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    log::error,
+    prelude::{NextState, State},
+    text::{JustifyText, Text, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, Style, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use strum::IntoEnumIterator;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    game::options::{GameOptions, GameOptionsChangedEvent},
+    input::{MainMenuExplorer, MenuAction, PlayerAction},
+    localization::LocalizationResource,
+    states::{AppStates, ControlsCleanup, GameStates},
+};
+
+use super::spaced_name;
+use crate::options::input::{
+    get_current_input_bindings, save_input_bindings, BindableAction, BoundInput, InputBindings,
+    InputDevice,
+};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const ROW_FONT_SIZE: f32 = 16.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+/// Seconds to wait for a press before binding is cancelled
+const BINDING_TIMEOUT: f32 = 5.0;
+/// The columns of the controls screen, from left to right
+const DEVICE_COLUMNS: [InputDevice; 3] = [
+    InputDevice::Keyboard,
+    InputDevice::Mouse,
+    InputDevice::Gamepad,
+];
+
+/// A row of the controls screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ControlsRow {
+    Binding(BindableAction),
+    ResetToDefaults,
+}
+
+/// Player actions first, since they are the ones most often rebound
+fn get_controls_rows() -> Vec<ControlsRow> {
+    PlayerAction::iter()
+        .map(BindableAction::Player)
+        .chain(MenuAction::iter().map(BindableAction::Menu))
+        .map(ControlsRow::Binding)
+        .chain([ControlsRow::ResetToDefaults])
+        .collect()
+}
+
+fn bindable_action_label(action: &BindableAction, localization: &LocalizationResource) -> String {
+    match action {
+        BindableAction::Menu(action) => localization.format(
+            "controls.menu_action",
+            &[("action", spaced_name(&format!("{action:?}")))],
+        ),
+        BindableAction::Player(action) => spaced_name(&format!("{action:?}")),
+    }
+}
+
+fn input_device_name(device: InputDevice, localization: &LocalizationResource) -> String {
+    localization.get(match device {
+        InputDevice::Keyboard => "input_device.keyboard",
+        InputDevice::Mouse => "input_device.mouse",
+        InputDevice::Gamepad => "input_device.gamepad",
+    })
+}
+
+fn bound_input_label(input: &BoundInput, localization: &LocalizationResource) -> String {
+    match input {
+        BoundInput::Key(key) => format!("{key:?}"),
+        BoundInput::Mouse(button) => {
+            localization.format("controls.mouse_click", &[("button", format!("{button:?}"))])
+        }
+        BoundInput::Gamepad(button) => format!("{button:?}"),
+    }
+}
+
+/// State of the controls screen
+#[derive(Component)]
+pub(super) struct ControlsMenu {
+    /// Bindings being edited, saved after every change
+    bindings: InputBindings,
+    /// Bindings that ship with the game, used to reset and to tell which shared bindings conflict
+    defaults: InputBindings,
+    rows: Vec<ControlsRow>,
+    selected_idx: usize,
+    /// Index into `DEVICE_COLUMNS`
+    selected_column: usize,
+    /// Set while waiting for the press to bind the selected action to
+    binding_timer: Option<Timer>,
+    /// Set after binding until every key and button has been released, so that the press being
+    /// bound isn't also taken as a menu input
+    awaiting_release: bool,
+    /// Result of the last change, shown in the hint
+    message: Option<String>,
+}
+
+/// Text listing every action and its bindings
+#[derive(Component)]
+pub(super) struct ControlsListText;
+
+/// Text with the controls, or the result of the last change
+#[derive(Component)]
+pub(super) struct ControlsHintText;
+
+/// Spawns the list of actions with the first row selected
+pub(super) fn setup_controls_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    game_options: Res<GameOptions>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(2.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(ControlsCleanup)
+        .insert(ControlsMenu {
+            bindings: get_current_input_bindings(&game_options),
+            defaults: InputBindings::default_bindings(),
+            rows: get_controls_rows(),
+            selected_idx: 0,
+            selected_column: 0,
+            binding_timer: None,
+            awaiting_release: false,
+            message: None,
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.get("controls.title"),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: ROW_FONT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_text_justify(JustifyText::Left),
+                )
+                .insert(ControlsListText);
+
+            parent
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: HINT_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(ControlsHintText);
+        });
+}
+
+/// Saves the edited bindings with the game options, which rebuilds the input maps, and to
+/// `config/input.ron`
+fn save_bindings(
+    bindings: &InputBindings,
+    game_options: &GameOptions,
+    game_options_changed_event_writer: &mut EventWriter<GameOptionsChangedEvent>,
+) {
+    match bindings.to_ron() {
+        Ok(key_bindings) => {
+            game_options_changed_event_writer.send(GameOptionsChangedEvent(GameOptions {
+                key_bindings: Some(key_bindings),
+                ..game_options.clone()
+            }));
+            save_input_bindings(bindings);
+        }
+        Err(e) => error!("Failed to serialize key bindings. {e}"),
+    }
+}
+
+/// The first key or button of the device that was pressed this frame
+fn get_just_pressed_input(
+    device: InputDevice,
+    keyboard: &ButtonInput<KeyCode>,
+    mouse: &ButtonInput<MouseButton>,
+    gamepad: &ButtonInput<GamepadButton>,
+) -> Option<BoundInput> {
+    match device {
+        InputDevice::Keyboard => keyboard
+            .get_just_pressed()
+            .next()
+            .copied()
+            .map(BoundInput::Key),
+        InputDevice::Mouse => mouse
+            .get_just_pressed()
+            .next()
+            .copied()
+            .map(BoundInput::Mouse),
+        InputDevice::Gamepad => gamepad
+            .get_just_pressed()
+            .next()
+            .map(|button| BoundInput::Gamepad(button.button_type)),
+    }
+}
+
+/// Moves the selection, binds the selected action to the next press, unbinds it, and resets every
+/// binding to the defaults
+#[allow(clippy::too_many_arguments)]
+pub(super) fn controls_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut controls_menu_query: Query<&mut ControlsMenu>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepad: Res<ButtonInput<GamepadButton>>,
+    time: Res<Time>,
+    game_options: Res<GameOptions>,
+    mut game_options_changed_event_writer: EventWriter<GameOptionsChangedEvent>,
+    app_state: Res<State<AppStates>>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    localization: Res<LocalizationResource>,
+) {
+    let (Ok(action_state), Ok(mut controls_menu)) = (
+        menu_input_query.get_single(),
+        controls_menu_query.get_single_mut(),
+    ) else {
+        return;
+    };
+    let controls_menu = &mut *controls_menu;
+
+    if controls_menu.awaiting_release {
+        if keyboard.get_pressed().next().is_none()
+            && mouse.get_pressed().next().is_none()
+            && gamepad.get_pressed().next().is_none()
+        {
+            controls_menu.awaiting_release = false;
+        }
+        return;
+    }
+
+    let selected_row = controls_menu.rows[controls_menu.selected_idx];
+    let selected_device = DEVICE_COLUMNS[controls_menu.selected_column];
+
+    if let Some(binding_timer) = controls_menu.binding_timer.as_mut() {
+        let ControlsRow::Binding(action) = selected_row else {
+            controls_menu.binding_timer = None;
+            return;
+        };
+
+        if let Some(input) = get_just_pressed_input(selected_device, &keyboard, &mouse, &gamepad) {
+            controls_menu.bindings.rebind(action, input);
+            let conflicts =
+                controls_menu
+                    .bindings
+                    .get_conflicts(action, input, &controls_menu.defaults);
+            controls_menu.message = Some(if conflicts.is_empty() {
+                localization.format(
+                    "controls.bound",
+                    &[
+                        ("action", bindable_action_label(&action, &localization)),
+                        ("input", bound_input_label(&input, &localization)),
+                    ],
+                )
+            } else {
+                localization.format(
+                    "controls.conflict",
+                    &[
+                        ("input", bound_input_label(&input, &localization)),
+                        (
+                            "actions",
+                            conflicts
+                                .iter()
+                                .map(|action| bindable_action_label(action, &localization))
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                        ),
+                    ],
+                )
+            });
+            save_bindings(
+                &controls_menu.bindings,
+                &game_options,
+                &mut game_options_changed_event_writer,
+            );
+            controls_menu.binding_timer = None;
+            controls_menu.awaiting_release = true;
+        } else if binding_timer.tick(time.delta()).finished() {
+            controls_menu.binding_timer = None;
+            controls_menu.message = Some(localization.get("controls.binding_cancelled"));
+        }
+        return;
+    }
+
+    let n_rows = controls_menu.rows.len();
+    let n_columns = DEVICE_COLUMNS.len();
+
+    if action_state.just_released(&MenuAction::NavigateUpKeyboard)
+        || action_state.just_released(&MenuAction::NavigateUpGamepad)
+    {
+        controls_menu.selected_idx = (controls_menu.selected_idx + n_rows - 1) % n_rows;
+    } else if action_state.just_released(&MenuAction::NavigateDownKeyboard)
+        || action_state.just_released(&MenuAction::NavigateDownGamepad)
+    {
+        controls_menu.selected_idx = (controls_menu.selected_idx + 1) % n_rows;
+    } else if action_state.just_released(&MenuAction::NavigateLeftKeyboard)
+        || action_state.just_released(&MenuAction::NavigateLeftGamepad)
+    {
+        controls_menu.selected_column = (controls_menu.selected_column + n_columns - 1) % n_columns;
+    } else if action_state.just_released(&MenuAction::NavigateRightKeyboard)
+        || action_state.just_released(&MenuAction::NavigateRightGamepad)
+    {
+        controls_menu.selected_column = (controls_menu.selected_column + 1) % n_columns;
+    } else if action_state.just_released(&MenuAction::Confirm) {
+        match selected_row {
+            ControlsRow::Binding(BindableAction::Menu(_))
+                if selected_device == InputDevice::Mouse =>
+            {
+                controls_menu.message = Some(localization.get("controls.no_mouse_menus"));
+            }
+            ControlsRow::Binding(_) => {
+                controls_menu.binding_timer =
+                    Some(Timer::from_seconds(BINDING_TIMEOUT, TimerMode::Once));
+                controls_menu.message = None;
+            }
+            ControlsRow::ResetToDefaults => {
+                controls_menu.bindings = controls_menu.defaults.clone();
+                controls_menu.message = Some(localization.get("controls.reset_done"));
+                save_bindings(
+                    &controls_menu.bindings,
+                    &game_options,
+                    &mut game_options_changed_event_writer,
+                );
+            }
+        }
+    } else if action_state.just_released(&MenuAction::Delete) {
+        if let ControlsRow::Binding(action) = selected_row {
+            controls_menu.bindings.unbind(action, selected_device);
+            controls_menu.message = Some(localization.format(
+                "controls.unbound",
+                &[
+                    ("action", bindable_action_label(&action, &localization)),
+                    ("device", input_device_name(selected_device, &localization)),
+                ],
+            ));
+            save_bindings(
+                &controls_menu.bindings,
+                &game_options,
+                &mut game_options_changed_event_writer,
+            );
+        }
+    } else if action_state.just_released(&MenuAction::Back) {
+        if *app_state.get() == AppStates::Game {
+            next_game_state.set(GameStates::PausedOptions);
+        } else {
+            next_app_state.set(AppStates::Options);
+        }
+    }
+}
+
+/// Redraws the list of actions and the hint
+pub(super) fn update_controls_ui_system(
+    controls_menu_query: Query<&ControlsMenu>,
+    mut list_text_query: Query<&mut Text, (With<ControlsListText>, Without<ControlsHintText>)>,
+    mut hint_text_query: Query<&mut Text, (With<ControlsHintText>, Without<ControlsListText>)>,
+    localization: Res<LocalizationResource>,
+) {
+    let (Ok(controls_menu), Ok(mut list_text), Ok(mut hint_text)) = (
+        controls_menu_query.get_single(),
+        list_text_query.get_single_mut(),
+        hint_text_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let lines: Vec<String> = controls_menu
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            let is_selected_row = idx == controls_menu.selected_idx;
+            let cursor = if is_selected_row { "> " } else { "  " };
+            let ControlsRow::Binding(action) = row else {
+                return format!("{cursor}{}", localization.get("controls.reset"));
+            };
+
+            let columns = DEVICE_COLUMNS
+                .iter()
+                .enumerate()
+                .map(|(column, device)| {
+                    let inputs = controls_menu.bindings.get_bindings(*action, *device);
+                    let mut column_text = match (action, device) {
+                        (BindableAction::Menu(_), InputDevice::Mouse) => "-".to_string(),
+                        _ if inputs.is_empty() => localization.get("controls.none"),
+                        _ => inputs
+                            .iter()
+                            .map(|input| {
+                                // conflicting bindings are marked
+                                if controls_menu
+                                    .bindings
+                                    .get_conflicts(*action, *input, &controls_menu.defaults)
+                                    .is_empty()
+                                {
+                                    bound_input_label(input, &localization)
+                                } else {
+                                    format!("{}(!)", bound_input_label(input, &localization))
+                                }
+                            })
+                            .collect::<Vec<String>>()
+                            .join(" / "),
+                    };
+                    if is_selected_row && column == controls_menu.selected_column {
+                        column_text = if controls_menu.binding_timer.is_some() {
+                            format!("< {} >", localization.get("controls.press"))
+                        } else {
+                            format!("< {column_text} >")
+                        };
+                    }
+                    format!(
+                        "{}: {column_text}",
+                        input_device_name(*device, &localization)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("   ");
+
+            format!(
+                "{cursor}{}   {columns}",
+                bindable_action_label(action, &localization)
+            )
+        })
+        .collect();
+    list_text.sections[0].value = lines.join("\n");
+
+    hint_text.sections[0].value = if controls_menu.binding_timer.is_some() {
+        localization.format(
+            "controls.press_to_bind",
+            &[(
+                "device",
+                input_device_name(DEVICE_COLUMNS[controls_menu.selected_column], &localization),
+            )],
+        )
+    } else {
+        let controls_hint = localization.get("controls.hint");
+        match &controls_menu.message {
+            Some(message) => format!("{message}\n{controls_hint}"),
+            None => controls_hint,
+        }
+    };
+}
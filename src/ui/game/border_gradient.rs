@@ -1,8 +1,9 @@
 use std::{f32::consts::PI, time::Duration};
 
 use super::parent::BorderGradientCommandsExt;
+use crate::options::accessibility::get_palette_colors;
 use bevy::{
-    color::Color,
+    color::{Alpha, Color},
     ecs::{
         component::Component,
         event::{Event, EventReader, EventWriter},
@@ -19,7 +20,9 @@ use bevy::{
 };
 use thetawave_assets::UiAssets;
 use thetawave_interface::{
+    game::options::GameOptions,
     objective::{DefenseInteraction, MobReachedBottomGateEvent},
+    player::PlayerDamagedEvent,
     states::GameCleanup,
 };
 
@@ -95,19 +98,21 @@ pub(super) fn border_gradient_start_system(
 }
 
 /// Sets the alpha of a border gradient's background color based on the time reamining
-/// in the border gradient component's timer
+/// in the border gradient component's timer, tinted by the color palette option
 pub(super) fn border_gradient_update_system(
     mut bg_query: Query<(&mut BorderGradientComponent, &mut UiImage)>,
     time: Res<Time>,
+    game_options: Res<GameOptions>,
 ) {
+    let palette_colors = get_palette_colors(game_options.color_palette);
     for (mut bg_component, mut ui_image) in bg_query.iter_mut() {
         bg_component.timer.tick(time.delta());
-        ui_image.color = Color::srgba(
-            1.0,
-            1.0,
-            1.0,
-            MAX_ALPHA * f32::sin(PI * bg_component.timer.fraction()),
-        );
+        let tint = match bg_component.bg_type {
+            BorderGradientType::Warning => palette_colors.warning_gradient,
+            BorderGradientType::Defense => palette_colors.defense_gradient,
+        };
+        ui_image.color =
+            Color::Srgba(tint.with_alpha(MAX_ALPHA * f32::sin(PI * bg_component.timer.fraction())));
     }
 }
 
@@ -124,3 +129,13 @@ pub(super) fn border_gradient_on_gate_interaction_system(
         };
     }
 }
+
+/// Trigger a warning border gradient when a player takes damage
+pub(super) fn border_gradient_on_player_damaged_system(
+    mut player_damaged_events: EventReader<PlayerDamagedEvent>,
+    mut bg_event_writer: EventWriter<BorderGradientEvent>,
+) {
+    if player_damaged_events.read().count() > 0 {
+        bg_event_writer.send(BorderGradientEvent::Warning);
+    }
+}
@@ -0,0 +1,128 @@
+//! Systems that point out enemies coming in from off screen, with indicators at the edge of the
+//! arena.
+use bevy::{
+    color::{Alpha, Srgba},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Without,
+        system::{Commands, Query},
+    },
+    math::{Quat, Vec2},
+    sprite::{Sprite, SpriteBundle},
+    transform::components::Transform,
+    utils::default,
+};
+use std::collections::HashMap;
+use thetawave_interface::{spawnable::MobType, states::GameCleanup};
+
+use crate::{arena::ARENA_HALF_SIZE, spawnable::MobComponent};
+
+/// Distance from the edge of the arena that the indicators are shown at
+const INDICATOR_EDGE_MARGIN: f32 = 15.0;
+const INDICATOR_SIZE: Vec2 = Vec2::new(24.0, 8.0);
+const INDICATOR_COLOR: Srgba = Srgba::rgb(1.0, 0.25, 0.2);
+const INDICATOR_Z: f32 = 20.0;
+/// Distance past the edge of the arena at which indicators are their faintest
+const INDICATOR_FADE_DISTANCE: f32 = 300.0;
+const MIN_INDICATOR_ALPHA: f32 = 0.3;
+const MAX_INDICATOR_ALPHA: f32 = 0.9;
+
+/// Points from the edge of the arena toward an off screen mob
+#[derive(Component)]
+pub(super) struct EdgeIndicatorComponent {
+    mob: Entity,
+}
+
+/// Transform and color of the indicator of a mob at the given position
+fn get_indicator_sprite(mob_position: Vec2) -> (Transform, Srgba) {
+    let edge_position = mob_position.clamp(
+        -ARENA_HALF_SIZE + INDICATOR_EDGE_MARGIN,
+        ARENA_HALF_SIZE - INDICATOR_EDGE_MARGIN,
+    );
+    let to_mob = mob_position - edge_position;
+
+    // indicators get brighter as their mobs get closer
+    let closeness = 1.0 - (to_mob.length() / INDICATOR_FADE_DISTANCE).clamp(0.0, 1.0);
+    let alpha = MIN_INDICATOR_ALPHA + (MAX_INDICATOR_ALPHA - MIN_INDICATOR_ALPHA) * closeness;
+
+    (
+        Transform::from_translation(edge_position.extend(INDICATOR_Z))
+            .with_rotation(Quat::from_rotation_z(to_mob.y.atan2(to_mob.x))),
+        INDICATOR_COLOR.with_alpha(alpha),
+    )
+}
+
+/// Keeps an indicator at the edge of the arena for each enemy that is coming in from off screen.
+/// Indicators are despawned once their mobs are on screen or destroyed.
+pub(super) fn edge_indicator_system(
+    mut commands: Commands,
+    mob_query: Query<(Entity, &MobComponent, &Transform), Without<EdgeIndicatorComponent>>,
+    mut indicator_query: Query<(Entity, &EdgeIndicatorComponent, &mut Transform, &mut Sprite)>,
+) {
+    // mobs past the bottom of the arena are leaving, not coming in
+    let mut off_screen_mobs: HashMap<Entity, Vec2> = mob_query
+        .iter()
+        .filter(|(_, mob, _)| matches!(mob.mob_type, MobType::Enemy(_)))
+        .map(|(entity, _, transform)| (entity, transform.translation.truncate()))
+        .filter(|(_, position)| {
+            (position.x.abs() > ARENA_HALF_SIZE.x || position.y > ARENA_HALF_SIZE.y)
+                && position.y > -ARENA_HALF_SIZE.y
+        })
+        .collect();
+
+    for (entity, indicator, mut transform, mut sprite) in indicator_query.iter_mut() {
+        if let Some(mob_position) = off_screen_mobs.remove(&indicator.mob) {
+            let (indicator_transform, color) = get_indicator_sprite(mob_position);
+            *transform = indicator_transform;
+            sprite.color = color.into();
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (mob, mob_position) in off_screen_mobs {
+        let (transform, color) = get_indicator_sprite(mob_position);
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: color.into(),
+                    custom_size: Some(INDICATOR_SIZE),
+                    ..default()
+                },
+                transform,
+                ..default()
+            })
+            .insert(EdgeIndicatorComponent { mob })
+            .insert(GameCleanup);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_indicator_sprite, INDICATOR_EDGE_MARGIN, MAX_INDICATOR_ALPHA};
+    use crate::arena::ARENA_HALF_SIZE;
+    use bevy::{
+        color::Alpha,
+        math::{Quat, Vec2},
+    };
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_indicator_is_at_the_edge_pointing_at_the_mob() {
+        let (transform, color) = get_indicator_sprite(Vec2::new(100.0, ARENA_HALF_SIZE.y + 10.0));
+        assert_eq!(transform.translation.x, 100.0);
+        assert_eq!(
+            transform.translation.y,
+            ARENA_HALF_SIZE.y - INDICATOR_EDGE_MARGIN
+        );
+        // pointing straight up
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 0.001));
+
+        let (_, far_color) = get_indicator_sprite(Vec2::new(100.0, 2000.0));
+        assert!(far_color.alpha() < color.alpha());
+        assert!(color.alpha() <= MAX_INDICATOR_ALPHA);
+    }
+}
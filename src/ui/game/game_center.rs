@@ -16,7 +16,10 @@ use bevy::{
     utils::default,
 };
 use std::time::Duration;
-use thetawave_interface::run::CyclePhaseEvent;
+use thetawave_interface::{
+    localization::LocalizationResource,
+    run::{BossWarningEvent, CyclePhaseEvent},
+};
 
 use super::parent::GameCenterUiChildBuilderExt;
 
@@ -73,6 +76,7 @@ pub(super) fn update_center_text_ui_system(
         (&mut Text, &mut BackgroundColor, &mut FadeOutUiComponent),
         With<CenterTextUi>,
     >,
+    localization: Res<LocalizationResource>,
 ) {
     // if phase has been cycled update the text
     if cycle_phase_event_reader.read().next().is_some() {
@@ -81,9 +85,12 @@ pub(super) fn update_center_text_ui_system(
                 if let Ok((mut text, mut bg_color, mut fade_out)) =
                     center_text_query.get_single_mut()
                 {
-                    if let Some(intro_text) = phase.intro_text.clone() {
-                        text.sections[0].value = intro_text;
+                    if let Some(intro_text) = &phase.intro_text {
+                        text.sections[0].value = localization.get(intro_text);
                         *bg_color = BACKGROUND_COLOR.with_alpha(BASE_BACKGROUND_ALPHA).into();
+                        fade_out
+                            .timer
+                            .set_duration(Duration::from_secs_f32(DEFAULT_FADE_TIME));
                         fade_out.timer.reset();
                     }
                 }
@@ -92,6 +99,29 @@ pub(super) fn update_center_text_ui_system(
     }
 }
 
+/// Shows a warning banner that fades out as the boss arrives
+pub(super) fn boss_warning_center_text_ui_system(
+    mut boss_warning_event_reader: EventReader<BossWarningEvent>,
+    mut center_text_query: Query<
+        (&mut Text, &mut BackgroundColor, &mut FadeOutUiComponent),
+        With<CenterTextUi>,
+    >,
+    localization: Res<LocalizationResource>,
+) {
+    let Some(event) = boss_warning_event_reader.read().last() else {
+        return;
+    };
+
+    if let Ok((mut text, mut bg_color, mut fade_out)) = center_text_query.get_single_mut() {
+        text.sections[0].value = localization.get("phase.boss_warning");
+        *bg_color = BACKGROUND_COLOR.with_alpha(BASE_BACKGROUND_ALPHA).into();
+        fade_out
+            .timer
+            .set_duration(Duration::from_secs_f32(event.lead_time));
+        fade_out.timer.reset();
+    }
+}
+
 /// Gradually fade out text entities with a `FadeOutUIComponent`. This should be run every frame.
 pub(super) fn text_fade_out_system(
     mut background_color_query: Query<(&mut Text, &mut BackgroundColor, &mut FadeOutUiComponent)>,
@@ -20,6 +20,8 @@ use bevy::{
     utils::default,
 };
 
+use thetawave_interface::localization::LocalizationResource;
+
 use super::parent::LevelUiChildBuilderExt;
 
 const NODE_WIDTH: Val = Val::Percent(50.0);
@@ -101,10 +103,11 @@ pub(super) fn update_level_ui_system(
     level_data_ui_query: Query<Entity, With<LevelDataUi>>,
     mut level_name_ui_query: Query<&mut Text, With<LevelNameUi>>,
     run_resource: Res<CurrentRunProgressResource>,
+    localization: Res<LocalizationResource>,
 ) {
     if let Some(current_level) = &run_resource.current_level {
         if let Ok(mut text) = level_name_ui_query.get_single_mut() {
-            text.sections[0].value = current_level.get_name();
+            text.sections[0].value = current_level.get_name(&localization);
         }
 
         if let Ok(entity) = level_data_ui_query.get_single() {
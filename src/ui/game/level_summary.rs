@@ -0,0 +1,190 @@
+//! Systems to draw and update the summary shown between levels, which waits for every player to
+//! ready up.
+use bevy::{
+    color::{Color, Srgba},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::BuildChildren,
+    text::{JustifyText, Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, PositionType, Style, Val,
+    },
+    utils::default,
+};
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    localization::LocalizationResource, objective::Objective, player::PlayerIDComponent,
+    states::LevelSummaryCleanup,
+};
+
+use crate::run::{level_summary::LevelSummaryResource, CurrentRunProgressResource};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const STATS_FONT_SIZE: f32 = 24.0;
+const READY_FONT_SIZE: f32 = 24.0;
+const WAITING_COLOR: Srgba = Srgba::WHITE;
+const READY_COLOR: Srgba = Srgba::GREEN;
+
+/// Ready-up text of a player
+#[derive(Component)]
+pub(super) struct PlayerReadyUpUi(PlayerIDComponent);
+
+fn get_stats_text(
+    summary_res: &LevelSummaryResource,
+    run_res: &CurrentRunProgressResource,
+    localization: &LocalizationResource,
+) -> String {
+    let mut lines = vec![
+        localization.format(
+            "level_summary.time",
+            &[("secs", format!("{:.0}", summary_res.level_time_secs))],
+        ),
+        localization.format(
+            "level_summary.enemies_destroyed",
+            &[("count", summary_res.kills.to_string())],
+        ),
+        localization.format(
+            "level_summary.damage_taken",
+            &[("damage", summary_res.damage_taken.to_string())],
+        ),
+    ];
+
+    if let Some(Objective::Defense(defense_data)) = &summary_res.objective {
+        lines.push(localization.format(
+            "level_summary.defense_held",
+            &[
+                ("defense", defense_data.defense.to_string()),
+                ("max", defense_data.max_defense.to_string()),
+            ],
+        ));
+    }
+
+    if let Some(next_level) = run_res.queued_levels.front() {
+        lines.push(String::new());
+        lines.push(localization.format(
+            "level_summary.next_level",
+            &[("level", next_level.get_name(localization))],
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn get_ready_up_text(
+    player_id: PlayerIDComponent,
+    ready: bool,
+    localization: &LocalizationResource,
+) -> String {
+    format!(
+        "{}: {}",
+        localization.format(
+            "level_summary.player",
+            &[("number", (usize::from(player_id) + 1).to_string())],
+        ),
+        localization.get(if ready {
+            "level_summary.ready"
+        } else {
+            "level_summary.waiting"
+        })
+    )
+}
+
+/// Spawns the completed level's stats, the upcoming level and each player's ready-up
+pub(super) fn setup_level_summary_ui_system(
+    mut commands: Commands,
+    summary_res: Res<LevelSummaryResource>,
+    run_res: Res<CurrentRunProgressResource>,
+    ui_assets: Res<UiAssets>,
+    localization: Res<LocalizationResource>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..default()
+        })
+        .insert(LevelSummaryCleanup)
+        .with_children(|parent| {
+            if let Some(level) = &run_res.current_level {
+                parent.spawn(TextBundle::from_section(
+                    localization.format("level_summary.title", &[("level", level.name.clone())]),
+                    TextStyle {
+                        font: ui_assets.lunchds_font.clone(),
+                        font_size: TITLE_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ));
+            }
+
+            parent.spawn(
+                TextBundle::from_section(
+                    get_stats_text(&summary_res, &run_res, &localization),
+                    TextStyle {
+                        font: ui_assets.lunchds_font.clone(),
+                        font_size: STATS_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+
+            for ready_up in summary_res.ready_ups.iter() {
+                parent
+                    .spawn(TextBundle {
+                        text: Text::from_section(
+                            get_ready_up_text(ready_up.player_id, ready_up.ready, &localization),
+                            TextStyle {
+                                font: ui_assets.lunchds_font.clone(),
+                                font_size: READY_FONT_SIZE,
+                                color: WAITING_COLOR.into(),
+                            },
+                        ),
+                        ..default()
+                    })
+                    .insert(PlayerReadyUpUi(ready_up.player_id));
+            }
+        });
+}
+
+/// Shows which players have readied up
+pub(super) fn update_level_summary_ui_system(
+    summary_res: Res<LevelSummaryResource>,
+    mut ready_up_query: Query<(&PlayerReadyUpUi, &mut Text)>,
+    localization: Res<LocalizationResource>,
+) {
+    if !summary_res.is_changed() {
+        return;
+    }
+
+    for (ready_up_ui, mut text) in ready_up_query.iter_mut() {
+        let Some(ready_up) = summary_res
+            .ready_ups
+            .iter()
+            .find(|ready_up| ready_up.player_id == ready_up_ui.0)
+        else {
+            continue;
+        };
+
+        text.sections[0].value =
+            get_ready_up_text(ready_up.player_id, ready_up.ready, &localization);
+        text.sections[0].style.color = if ready_up.ready {
+            READY_COLOR
+        } else {
+            WAITING_COLOR
+        }
+        .into();
+    }
+}
@@ -0,0 +1,187 @@
+//! Systems to draw and update the upgrade draft shown each time the players level up.
+use bevy::{
+    color::{Color, Srgba},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::BuildChildren,
+    text::{Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, BorderColor, FlexDirection, JustifyContent, PositionType, Style, UiRect, Val,
+    },
+    utils::default,
+};
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    localization::LocalizationResource, player::PlayerIDComponent, states::LevelUpDraftCleanup,
+};
+
+use crate::level_up::{ExperienceResource, UpgradeDraftResource, UpgradeRarity};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const NAME_FONT_SIZE: f32 = 24.0;
+const DESCRIPTION_FONT_SIZE: f32 = 16.0;
+const CARD_WIDTH: Val = Val::Px(200.0);
+const CARD_HEIGHT: Val = Val::Px(120.0);
+const CARD_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.85);
+const SELECTED_BORDER_COLOR: Srgba = Srgba::WHITE;
+const CONFIRMED_BORDER_COLOR: Srgba = Srgba::GREEN;
+const UNSELECTED_BORDER_COLOR: Color = Color::NONE;
+
+/// An upgrade option in the draft, identified by the player it was offered to and its index
+#[derive(Component)]
+pub(super) struct UpgradeCardUi {
+    player_id: PlayerIDComponent,
+    option_idx: usize,
+}
+
+fn get_rarity_color(rarity: UpgradeRarity) -> Srgba {
+    match rarity {
+        UpgradeRarity::Common => Srgba::WHITE,
+        UpgradeRarity::Rare => Srgba::rgb(0.3, 0.6, 1.0),
+        UpgradeRarity::Epic => Srgba::rgb(0.8, 0.4, 1.0),
+    }
+}
+
+/// Spawns a row of upgrade cards for each player in the draft
+pub(super) fn setup_upgrade_draft_ui_system(
+    mut commands: Commands,
+    draft_res: Res<UpgradeDraftResource>,
+    experience_res: Res<ExperienceResource>,
+    ui_assets: Res<UiAssets>,
+    localization: Res<LocalizationResource>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.5, 0.5, 0.5, 0.1).into(),
+            ..default()
+        })
+        .insert(LevelUpDraftCleanup)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.format(
+                    "level_up.title",
+                    &[("level", experience_res.level.to_string())],
+                ),
+                TextStyle {
+                    font: ui_assets.lunchds_font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            for draft in draft_res.drafts.iter() {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Vw(1.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(TextBundle::from_section(
+                            localization.format(
+                                "level_up.player",
+                                &[("number", (usize::from(draft.player_id) + 1).to_string())],
+                            ),
+                            TextStyle {
+                                font: ui_assets.lunchds_font.clone(),
+                                font_size: NAME_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+
+                        for (option_idx, upgrade) in draft.options.iter().enumerate() {
+                            row.spawn(NodeBundle {
+                                style: Style {
+                                    width: CARD_WIDTH,
+                                    height: CARD_HEIGHT,
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    border: UiRect::all(Val::Px(3.0)),
+                                    padding: UiRect::all(Val::Px(8.0)),
+                                    ..default()
+                                },
+                                background_color: CARD_BACKGROUND_COLOR.into(),
+                                border_color: UNSELECTED_BORDER_COLOR.into(),
+                                ..default()
+                            })
+                            .insert(UpgradeCardUi {
+                                player_id: draft.player_id,
+                                option_idx,
+                            })
+                            .with_children(|card| {
+                                card.spawn(TextBundle::from_section(
+                                    upgrade.get_name(&localization),
+                                    TextStyle {
+                                        font: ui_assets.lunchds_font.clone(),
+                                        font_size: NAME_FONT_SIZE,
+                                        color: get_rarity_color(upgrade.rarity).into(),
+                                    },
+                                ));
+                                card.spawn(TextBundle {
+                                    text: Text::from_section(
+                                        upgrade.get_description(&localization),
+                                        TextStyle {
+                                            font: ui_assets.lunchds_font.clone(),
+                                            font_size: DESCRIPTION_FONT_SIZE,
+                                            color: Color::WHITE,
+                                        },
+                                    ),
+                                    style: Style {
+                                        margin: UiRect::top(Val::Px(8.0)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                });
+                            });
+                        }
+                    });
+            }
+        });
+}
+
+/// Outlines each player's highlighted upgrade, changing color once they confirm it
+pub(super) fn update_upgrade_draft_ui_system(
+    draft_res: Res<UpgradeDraftResource>,
+    mut card_query: Query<(&UpgradeCardUi, &mut BorderColor)>,
+) {
+    if !draft_res.is_changed() {
+        return;
+    }
+
+    for (card, mut border_color) in card_query.iter_mut() {
+        let Some(draft) = draft_res
+            .drafts
+            .iter()
+            .find(|draft| draft.player_id == card.player_id)
+        else {
+            continue;
+        };
+
+        border_color.0 = if draft.selected != card.option_idx {
+            UNSELECTED_BORDER_COLOR
+        } else if draft.confirmed {
+            CONFIRMED_BORDER_COLOR.into()
+        } else {
+            SELECTED_BORDER_COLOR.into()
+        };
+    }
+}
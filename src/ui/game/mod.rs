@@ -8,11 +8,16 @@ use thetawave_interface::states;
 use crate::GameEnterSet;
 
 mod border_gradient;
+mod edge_indicator;
 mod game_center;
 mod level;
+mod level_summary;
+mod level_up;
 mod parent;
 mod phase;
 mod player;
+mod scanner;
+mod score;
 
 pub(super) struct GameUiPlugin;
 
@@ -22,7 +27,17 @@ impl Plugin for GameUiPlugin {
 
         app.add_systems(
             OnEnter(states::AppStates::Game),
-            parent::setup_game_ui_system.after(GameEnterSet::BuildUi),
+            (
+                parent::setup_game_ui_system,
+                scanner::setup_scanner_tooltip_system,
+            )
+                .after(GameEnterSet::BuildUi),
+        );
+
+        // runs while paused too, so that the tooltip is hidden once nothing is scanned
+        app.add_systems(
+            Update,
+            scanner::update_scanner_tooltip_system.run_if(in_state(states::AppStates::Game)),
         );
 
         app.add_systems(
@@ -32,16 +47,46 @@ impl Plugin for GameUiPlugin {
                 player::update_player_shields_ui_system,
                 player::update_player_armor_ui_system,
                 player::update_player_abilities_ui_system,
+                player::update_player_coop_lives_ui_system,
                 phase::update_phase_ui_system,
                 level::update_level_ui_system,
+                score::update_score_ui_system,
                 game_center::update_center_text_ui_system,
+                game_center::boss_warning_center_text_ui_system
+                    .after(game_center::update_center_text_ui_system),
                 game_center::text_fade_out_system,
+                edge_indicator::edge_indicator_system,
                 border_gradient::border_gradient_start_system,
                 border_gradient::border_gradient_update_system,
                 border_gradient::border_gradient_on_gate_interaction_system,
+                border_gradient::border_gradient_on_player_damaged_system,
             )
                 .run_if(in_state(states::AppStates::Game))
                 .run_if(in_state(states::GameStates::Playing)),
         );
+
+        app.add_systems(
+            OnEnter(states::GameStates::LevelUpDraft),
+            level_up::setup_upgrade_draft_ui_system,
+        );
+
+        app.add_systems(
+            Update,
+            level_up::update_upgrade_draft_ui_system
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::LevelUpDraft)),
+        );
+
+        app.add_systems(
+            OnEnter(states::GameStates::LevelSummary),
+            level_summary::setup_level_summary_ui_system,
+        );
+
+        app.add_systems(
+            Update,
+            level_summary::update_level_summary_ui_system
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::LevelSummary)),
+        );
     }
 }
@@ -15,25 +15,32 @@ use thetawave_assets::UiAssets;
 use thetawave_interface::{
     abilities::AbilitySlotIDComponent,
     character::Character,
+    game::options::GameOptions,
     player::{PlayerIDComponent, PlayersResource},
     states::GameCleanup,
 };
 
+use crate::options::accessibility::{get_palette_colors, PaletteColors};
 use crate::player::CharactersResource;
 
 use super::border_gradient::BorderGradientType;
 
-const TOP_ROW_HEIGHT: Val = Val::Percent(13.0);
-const TOP_CORNER_WIDTH: Val = Val::Percent(10.0);
-const TOP_CENTER_WIDTH: Val = Val::Percent(80.0);
-const BOTTOM_ROW_HEIGHT: Val = Val::Percent(13.0);
-const BOTTOM_CORNER_WIDTH: Val = Val::Percent(10.0);
-const BOTTOM_CENTER_WIDTH: Val = Val::Percent(80.0);
+/// Percent of the window height taken by each of the top and bottom rows at a UI scale of 1
+const ROW_HEIGHT_PERCENT: f32 = 13.0;
+/// Percent of the window width taken by each of the left and right columns at a UI scale of 1
+const SIDE_WIDTH_PERCENT: f32 = 10.0;
 const OUTSIDE_BORDER_BG_COLOR: BackgroundColor =
     BackgroundColor(Color::Srgba(Srgba::new(0.0, 0.0, 0.0, 0.0)));
-const MIDDLE_ROW_HEIGHT: Val = Val::Percent(74.0);
-const MIDDLE_SIDE_WIDTH: Val = Val::Percent(10.0);
-const MIDDLE_CENTER_WIDTH: Val = Val::Percent(80.0);
+
+/// Sizes of the HUD on both sides of the arena, grown by the UI scale option, and of the space left
+/// between them
+fn get_scaled_hud_sizes(side_percent: f32, ui_scale: f32) -> (Val, Val) {
+    let scaled_side_percent = side_percent * ui_scale;
+    (
+        Val::Percent(scaled_side_percent),
+        Val::Percent(100.0 - 2.0 * scaled_side_percent),
+    )
+}
 
 pub(super) trait BorderGradientCommandsExt {
     fn spawn_border_gradient(&mut self, ui_assets: &UiAssets, bg_type: BorderGradientType);
@@ -47,6 +54,10 @@ pub(super) trait LevelUiChildBuilderExt {
     fn spawn_level_ui(&mut self, font: Handle<Font>);
 }
 
+pub(super) trait ScoreUiChildBuilderExt {
+    fn spawn_score_ui(&mut self, font: Handle<Font>);
+}
+
 pub(super) trait GameCenterUiChildBuilderExt {
     fn spawn_game_center_ui(&mut self, font: Handle<Font>);
 }
@@ -58,8 +69,9 @@ pub(super) trait PlayerUiChildBuilderExt {
         id: PlayerIDComponent,
         players_res: &PlayersResource,
         ui_assets: &UiAssets,
+        palette_colors: &PaletteColors,
     );
-    fn spawn_inner_player_ui(&mut self, id: PlayerIDComponent);
+    fn spawn_inner_player_ui(&mut self, id: PlayerIDComponent, palette_colors: &PaletteColors);
     fn spawn_outer_player_ui(
         &mut self,
         character: &Character,
@@ -89,8 +101,14 @@ pub(super) fn setup_game_ui_system(
     ui_assets: Res<UiAssets>,
     players_resource: Res<PlayersResource>,
     characters_resource: Res<CharactersResource>,
+    game_options: Res<GameOptions>,
 ) {
     let font: Handle<Font> = ui_assets.lunchds_font.clone();
+    let palette_colors = get_palette_colors(game_options.color_palette);
+    let (row_height, middle_row_height) =
+        get_scaled_hud_sizes(ROW_HEIGHT_PERCENT, game_options.ui_scale);
+    let (side_width, center_width) =
+        get_scaled_hud_sizes(SIDE_WIDTH_PERCENT, game_options.ui_scale);
 
     commands.spawn_border_gradient(&ui_assets, BorderGradientType::Defense);
     commands.spawn_border_gradient(&ui_assets, BorderGradientType::Warning);
@@ -108,11 +126,11 @@ pub(super) fn setup_game_ui_system(
         })
         .insert(GameCleanup)
         .with_children(|game| {
-            // Parent node for top row containing the phase ui
+            // Parent node for top row containing the phase and score ui
             game.spawn(NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
-                    height: TOP_ROW_HEIGHT,
+                    height: row_height,
                     flex_direction: FlexDirection::Row,
                     ..default()
                 },
@@ -122,7 +140,7 @@ pub(super) fn setup_game_ui_system(
                 // Top left corner
                 top_row.spawn(NodeBundle {
                     style: Style {
-                        width: TOP_CORNER_WIDTH,
+                        width: side_width,
                         height: Val::Percent(100.0),
                         ..default()
                     },
@@ -134,7 +152,7 @@ pub(super) fn setup_game_ui_system(
                 top_row
                     .spawn(NodeBundle {
                         style: Style {
-                            width: TOP_CENTER_WIDTH,
+                            width: center_width,
                             height: Val::Percent(100.0),
                             flex_direction: FlexDirection::Row,
                             ..default()
@@ -148,22 +166,27 @@ pub(super) fn setup_game_ui_system(
                     });
 
                 // Top right corner
-                top_row.spawn(NodeBundle {
-                    style: Style {
-                        width: TOP_CORNER_WIDTH,
-                        height: Val::Percent(100.0),
+                top_row
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: side_width,
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: OUTSIDE_BORDER_BG_COLOR,
                         ..default()
-                    },
-                    background_color: OUTSIDE_BORDER_BG_COLOR,
-                    ..default()
-                });
+                    })
+                    .with_children(|top_right| {
+                        // spawn the score ui
+                        top_right.spawn_score_ui(font.clone());
+                    });
             });
 
             // Middle Row
             game.spawn(NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
-                    height: MIDDLE_ROW_HEIGHT,
+                    height: middle_row_height,
                     flex_direction: FlexDirection::Row,
                     ..default()
                 },
@@ -174,7 +197,7 @@ pub(super) fn setup_game_ui_system(
                 middle_row
                     .spawn(NodeBundle {
                         style: Style {
-                            width: MIDDLE_SIDE_WIDTH,
+                            width: side_width,
                             height: Val::Percent(100.0),
                             flex_direction: FlexDirection::Column,
                             ..default()
@@ -189,6 +212,7 @@ pub(super) fn setup_game_ui_system(
                             PlayerIDComponent::One,
                             &players_resource,
                             &ui_assets,
+                            palette_colors,
                         );
                     });
 
@@ -196,7 +220,7 @@ pub(super) fn setup_game_ui_system(
                 middle_row
                     .spawn(NodeBundle {
                         style: Style {
-                            width: MIDDLE_CENTER_WIDTH,
+                            width: center_width,
                             height: Val::Percent(100.0),
                             align_items: AlignItems::Center,
                             justify_content: JustifyContent::Center,
@@ -213,7 +237,7 @@ pub(super) fn setup_game_ui_system(
                 middle_row
                     .spawn(NodeBundle {
                         style: Style {
-                            width: MIDDLE_SIDE_WIDTH,
+                            width: side_width,
                             height: Val::Percent(100.0),
                             flex_direction: FlexDirection::Column,
                             ..default()
@@ -228,6 +252,7 @@ pub(super) fn setup_game_ui_system(
                             PlayerIDComponent::Two,
                             &players_resource,
                             &ui_assets,
+                            palette_colors,
                         );
                     });
             });
@@ -235,7 +260,7 @@ pub(super) fn setup_game_ui_system(
             game.spawn(NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
-                    height: BOTTOM_ROW_HEIGHT,
+                    height: row_height,
                     flex_direction: FlexDirection::Row,
                     ..default()
                 },
@@ -245,7 +270,7 @@ pub(super) fn setup_game_ui_system(
                 // Top left corner
                 bottom_row.spawn(NodeBundle {
                     style: Style {
-                        width: BOTTOM_CORNER_WIDTH,
+                        width: side_width,
                         height: Val::Percent(100.0),
                         ..default()
                     },
@@ -257,7 +282,7 @@ pub(super) fn setup_game_ui_system(
                 bottom_row
                     .spawn(NodeBundle {
                         style: Style {
-                            width: BOTTOM_CENTER_WIDTH,
+                            width: center_width,
                             height: Val::Percent(100.0),
                             flex_direction: FlexDirection::Row,
                             ..default()
@@ -273,7 +298,7 @@ pub(super) fn setup_game_ui_system(
                 // Bottom right corner
                 bottom_row.spawn(NodeBundle {
                     style: Style {
-                        width: BOTTOM_CORNER_WIDTH,
+                        width: side_width,
                         height: Val::Percent(100.0),
                         ..default()
                     },
@@ -1,3 +1,4 @@
+use crate::options::accessibility::get_palette_colors;
 use crate::run::level_phase::LevelPhaseType;
 use crate::run::tutorial::TutorialLesson;
 use crate::{run::CurrentRunProgressResource, spawnable::BossComponent};
@@ -19,7 +20,9 @@ use bevy::{
     utils::default,
 };
 use thetawave_assets::UiAssets;
-use thetawave_interface::health::HealthComponent;
+use thetawave_interface::{
+    game::options::GameOptions, health::HealthComponent, localization::LocalizationResource,
+};
 
 use super::parent::PhaseUiChildBuilderExt;
 
@@ -32,7 +35,6 @@ const PHASE_DATA_PADDING: UiRect =
     UiRect::new(Val::Vw(1.0), Val::Vw(1.0), Val::Vh(2.0), Val::Vh(2.0));
 const BOSS_HEALTH_WIDTH: Val = Val::Percent(80.0);
 const BOSS_HEALTH_HEIGHT: Val = Val::Percent(60.0);
-const BOSS_HEALTH_COLOR: Srgba = Srgba::RED;
 const BOSS_HEALTH_EMPTY_ALPHA: f32 = 0.05;
 const BOSS_HEALTH_FILLED_ALPHA: f32 = 0.75;
 const TUTORIAL_TEXT_SECTION_HEIGHT: Val = Val::Px(30.0);
@@ -101,6 +103,7 @@ impl PhaseUiChildBuilderExt for ChildBuilder<'_> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn update_phase_ui_system(
     ui_assets: Res<UiAssets>,
     mut commands: Commands,
@@ -108,11 +111,15 @@ pub(super) fn update_phase_ui_system(
     phase_data_ui_query: Query<Entity, With<PhaseDataUi>>,
     run_resource: Res<CurrentRunProgressResource>,
     boss_mobs_query: Query<&HealthComponent, With<BossComponent>>,
+    localization: Res<LocalizationResource>,
+    game_options: Res<GameOptions>,
 ) {
+    let boss_health_color = get_palette_colors(game_options.color_palette).boss_health;
+
     if let Some(current_level) = &run_resource.current_level {
         if let Some(current_phase) = &current_level.current_phase {
             if let Ok(mut text) = phase_name_ui_query.get_single_mut() {
-                text.sections[0].value = current_phase.phase_type.get_name()
+                text.sections[0].value = current_phase.phase_type.get_name(&localization)
             }
 
             if let Ok(entity) = phase_data_ui_query.get_single() {
@@ -166,7 +173,7 @@ pub(super) fn update_phase_ui_system(
                                             flex_direction: FlexDirection::Row,
                                             ..default()
                                         },
-                                        background_color: BOSS_HEALTH_COLOR
+                                        background_color: boss_health_color
                                             .with_alpha(BOSS_HEALTH_EMPTY_ALPHA)
                                             .into(),
                                         ..default()
@@ -180,7 +187,7 @@ pub(super) fn update_phase_ui_system(
                                                 height: Val::Percent(100.0),
                                                 ..default()
                                             },
-                                            background_color: BOSS_HEALTH_COLOR
+                                            background_color: boss_health_color
                                                 .with_alpha(BOSS_HEALTH_FILLED_ALPHA)
                                                 .into(),
                                             ..default()
@@ -208,8 +215,9 @@ pub(super) fn update_phase_ui_system(
                                 })
                                 .with_children(|phase_data_list_ui| match tutorial_lesson {
                                     TutorialLesson::Movement { .. } => {
-                                        for (progress_str, completed) in
-                                            tutorial_lesson.get_movement_timer_strs().iter()
+                                        for (progress_str, completed) in tutorial_lesson
+                                            .get_movement_timer_strs(&localization)
+                                            .iter()
                                         {
                                             phase_data_list_ui.spawn(TextBundle {
                                                 style: Style {
@@ -237,7 +245,7 @@ pub(super) fn update_phase_ui_system(
                                     }
                                     TutorialLesson::AbilitySlotOne { .. } => {
                                         for (progress_str, completed) in
-                                            tutorial_lesson.get_attack_strs().iter()
+                                            tutorial_lesson.get_attack_strs(&localization).iter()
                                         {
                                             phase_data_list_ui.spawn(TextBundle {
                                                 style: Style {
@@ -265,7 +273,7 @@ pub(super) fn update_phase_ui_system(
                                     }
                                     TutorialLesson::AbilitySlotTwo { .. } => {
                                         for (progress_str, completed) in
-                                            tutorial_lesson.get_ability_strs().iter()
+                                            tutorial_lesson.get_ability_strs(&localization).iter()
                                         {
                                             phase_data_list_ui.spawn(TextBundle {
                                                 style: Style {
@@ -1,19 +1,17 @@
 use bevy::{
     asset::Handle,
-    color::{
-        palettes::css::{AQUA, CRIMSON, GOLD},
-        Alpha, Srgba,
-    },
+    color::{palettes::css::GOLD, Alpha, Srgba},
     ecs::{
         component::Component,
         entity::Entity,
         query::{Changed, With},
-        system::{Commands, Query},
+        system::{Commands, Query, Res},
     },
     hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt},
     render::texture::Image,
+    text::{Text, TextStyle},
     ui::{
-        node_bundles::{ImageBundle, NodeBundle},
+        node_bundles::{ImageBundle, NodeBundle, TextBundle},
         FlexDirection, Style, UiRect, Val,
     },
     utils::default,
@@ -23,9 +21,14 @@ use thetawave_interface::{
     abilities::{AbilityCooldownComponent, AbilitySlotIDComponent},
     character::Character,
     health::HealthComponent,
-    player::{PlayerComponent, PlayerIDComponent, PlayersResource},
+    localization::LocalizationResource,
+    player::{
+        CoopLivesResource, PlayerComponent, PlayerDownedComponent, PlayerIDComponent,
+        PlayersResource,
+    },
 };
 
+use crate::options::accessibility::PaletteColors;
 use crate::player::CharactersResource;
 
 use super::parent::PlayerUiChildBuilderExt;
@@ -35,11 +38,9 @@ const INNER_WIDTH: Val = Val::Percent(35.0);
 const OUTER_PADDING: UiRect = UiRect::all(Val::Percent(5.0));
 const OUTER_WIDTH: Val = Val::Percent(65.0);
 const HEALTH_HEIGHT: Val = Val::Percent(55.0);
-const HEALTH_COLOR: Srgba = CRIMSON;
 const HEALTH_EMPTY_ALPHA: f32 = 0.05;
 const HEALTH_FILLED_ALPHA: f32 = 0.75;
 const SHIELDS_HEIGHT: Val = Val::Percent(25.0);
-const SHIELDS_COLOR: Srgba = AQUA;
 const SHIELDS_EMPTY_ALPHA: f32 = 0.05;
 const SHIELDS_FILLED_ALPHA: f32 = 0.75;
 const ARMOR_HEIGHT: Val = Val::Percent(20.0);
@@ -55,6 +56,8 @@ const ARMOR_COUNTER_MARGIN: UiRect =
 const ARMOR_COUNTER_COLOR: Srgba = GOLD;
 const ARMOR_COUNTER_ALPHA: f32 = 0.75;
 const ABILITY_VALUE_COLOR: Srgba = Srgba::new(0.0, 0.0, 0.0, 0.85);
+const COOP_LIVES_FONT_SIZE: f32 = 16.0;
+const COOP_LIVES_COLOR: Srgba = GOLD;
 
 // Player data Uis
 #[derive(Component)]
@@ -84,6 +87,9 @@ pub(super) struct AbilityIconUi;
 #[derive(Component)]
 pub(super) struct AbilityValueUi;
 
+#[derive(Component)]
+pub(super) struct CoopLivesUi;
+
 #[derive(Component)]
 pub(super) struct PlayerUi;
 
@@ -114,6 +120,7 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
         id: PlayerIDComponent,
         players_res: &PlayersResource,
         ui_assets: &UiAssets,
+        palette_colors: &PaletteColors,
     ) {
         // Only spawn ui for player with id if its player slot is filled
         if let Some(Some(player_data)) = &players_res.player_data.get(id as usize) {
@@ -136,17 +143,17 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
             .insert(PlayerUi)
             .with_children(|player| {
                 if id.has_flipped_ui() {
-                    player.spawn_inner_player_ui(id);
+                    player.spawn_inner_player_ui(id, palette_colors);
                     player.spawn_outer_player_ui(character, id, ui_assets);
                 } else {
                     player.spawn_outer_player_ui(character, id, ui_assets);
-                    player.spawn_inner_player_ui(id);
+                    player.spawn_inner_player_ui(id, palette_colors);
                 }
             });
         }
     }
 
-    fn spawn_inner_player_ui(&mut self, id: PlayerIDComponent) {
+    fn spawn_inner_player_ui(&mut self, id: PlayerIDComponent, palette_colors: &PaletteColors) {
         self.spawn(NodeBundle {
             style: Style {
                 width: INNER_WIDTH,
@@ -167,7 +174,7 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                         flex_direction: FlexDirection::ColumnReverse,
                         ..default()
                     },
-                    background_color: HEALTH_COLOR.with_alpha(HEALTH_EMPTY_ALPHA).into(),
+                    background_color: palette_colors.health.with_alpha(HEALTH_EMPTY_ALPHA).into(),
                     ..default()
                 })
                 .insert(HealthUi)
@@ -179,7 +186,10 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                                 height: Val::Percent(100.0),
                                 ..default()
                             },
-                            background_color: HEALTH_COLOR.with_alpha(HEALTH_FILLED_ALPHA).into(),
+                            background_color: palette_colors
+                                .health
+                                .with_alpha(HEALTH_FILLED_ALPHA)
+                                .into(),
                             ..default()
                         })
                         .insert(HealthValueUi)
@@ -194,7 +204,10 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                         flex_direction: FlexDirection::ColumnReverse,
                         ..default()
                     },
-                    background_color: SHIELDS_COLOR.with_alpha(SHIELDS_EMPTY_ALPHA).into(),
+                    background_color: palette_colors
+                        .shields
+                        .with_alpha(SHIELDS_EMPTY_ALPHA)
+                        .into(),
                     ..default()
                 })
                 .insert(ShieldsUi)
@@ -206,7 +219,10 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                                 height: Val::Percent(100.0),
                                 ..default()
                             },
-                            background_color: SHIELDS_COLOR.with_alpha(SHIELDS_FILLED_ALPHA).into(),
+                            background_color: palette_colors
+                                .shields
+                                .with_alpha(SHIELDS_FILLED_ALPHA)
+                                .into(),
                             ..default()
                         })
                         .insert(ShieldsValueUi)
@@ -264,6 +280,26 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                 id.has_flipped_ui(),
                 ui_assets,
             );
+
+            // Revives, shared lives and downed status above the ability slots
+            outer
+                .spawn(TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: ui_assets.lunchds_font.clone(),
+                            font_size: COOP_LIVES_FONT_SIZE,
+                            color: COOP_LIVES_COLOR.into(),
+                        },
+                    ),
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(CoopLivesUi)
+                .insert(id);
         });
     }
 
@@ -439,3 +475,56 @@ pub(super) fn update_player_armor_ui_system(
         }
     }
 }
+
+/// Updates each player's revive, shared lives and downed status text
+pub(super) fn update_player_coop_lives_ui_system(
+    player_query: Query<
+        (&PlayerIDComponent, Option<&PlayerDownedComponent>),
+        With<PlayerComponent>,
+    >,
+    mut coop_lives_ui: Query<(&mut Text, &PlayerIDComponent), With<CoopLivesUi>>,
+    coop_lives: Res<CoopLivesResource>,
+    players_res: Res<PlayersResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let is_multiplayer = players_res.player_data.get(1).is_some();
+
+    for (mut text, ui_id) in coop_lives_ui.iter_mut() {
+        let value = match player_query
+            .iter()
+            .find(|(player_id, _)| *player_id == ui_id)
+        {
+            // player was destroyed for good
+            None => localization.get("hud.destroyed"),
+            Some((_, Some(downed))) => localization.format(
+                "hud.downed",
+                &[(
+                    "percent",
+                    format!("{:.0}", 100.0 * downed.revive_timer.fraction()),
+                )],
+            ),
+            Some((_, None)) => {
+                let mut lines = vec![];
+
+                if is_multiplayer && coop_lives.rules.max_revives > 0 {
+                    lines.push(localization.format(
+                        "hud.revives",
+                        &[("count", coop_lives.get_revives_remaining().to_string())],
+                    ));
+                }
+
+                if let Some(shared_lives) = coop_lives.get_shared_lives_remaining() {
+                    lines.push(
+                        localization.format("hud.lives", &[("count", shared_lives.to_string())]),
+                    );
+                }
+
+                lines.join("\n")
+            }
+        };
+
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Systems to draw a tooltip next to the mob being scanned, with its name, faction, health,
+//! shields and behaviors.
+use bevy::{
+    color::{Alpha, Color},
+    core_pipeline::core_2d::Camera2d,
+    ecs::{
+        component::Component,
+        query::{With, Without},
+        system::{Commands, Query, Res},
+    },
+    hierarchy::{BuildChildren, ChildBuilder},
+    math::Vec2,
+    render::camera::Camera,
+    text::{Text, TextStyle},
+    transform::components::GlobalTransform,
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        Display, FlexDirection, Node, PositionType, Style, UiRect, UiScale, Val, ZIndex,
+    },
+    utils::default,
+};
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    game::options::GameOptions, health::HealthComponent, localization::LocalizationResource,
+    states::GameCleanup,
+};
+
+use crate::{
+    options::accessibility::get_palette_colors, scanner::ScannerResource, spawnable::MobComponent,
+};
+
+const TOOLTIP_WIDTH: f32 = 220.0;
+/// Distance from the scanned mob to the corner of the tooltip
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(24.0, 24.0);
+const TOOLTIP_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const NAME_FONT_SIZE: f32 = 20.0;
+const DETAILS_FONT_SIZE: f32 = 14.0;
+const BAR_HEIGHT: f32 = 8.0;
+const BAR_EMPTY_ALPHA: f32 = 0.15;
+const BAR_FILLED_ALPHA: f32 = 0.85;
+
+/// Root of the scanner tooltip
+#[derive(Component)]
+pub(super) struct ScannerTooltipUi;
+
+/// Text of the scanner tooltip
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ScannerTooltipText {
+    Name,
+    Faction,
+    Health,
+    Shields,
+    Behaviors,
+}
+
+/// Filled part of a bar of the scanner tooltip
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ScannerTooltipBar {
+    Health,
+    Shields,
+}
+
+fn spawn_tooltip_text(
+    parent: &mut ChildBuilder,
+    tooltip_text: ScannerTooltipText,
+    style: TextStyle,
+) {
+    parent
+        .spawn(TextBundle::from_section("", style))
+        .insert(tooltip_text);
+}
+
+fn spawn_tooltip_bar(parent: &mut ChildBuilder, tooltip_bar: ScannerTooltipBar, color: Color) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Px(BAR_HEIGHT),
+                margin: UiRect::bottom(Val::Px(4.0)),
+                ..default()
+            },
+            background_color: color.with_alpha(BAR_EMPTY_ALPHA).into(),
+            ..default()
+        })
+        .with_children(|bar| {
+            bar.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: color.with_alpha(BAR_FILLED_ALPHA).into(),
+                ..default()
+            })
+            .insert(tooltip_bar);
+        });
+}
+
+/// Spawns the hidden scanner tooltip
+pub(super) fn setup_scanner_tooltip_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    game_options: Res<GameOptions>,
+) {
+    let palette_colors = get_palette_colors(game_options.color_palette);
+    let name_style = TextStyle {
+        font: ui_assets.lunchds_font.clone(),
+        font_size: NAME_FONT_SIZE,
+        color: Color::WHITE,
+    };
+    let details_style = TextStyle {
+        font_size: DETAILS_FONT_SIZE,
+        ..name_style.clone()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Px(TOOLTIP_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            background_color: TOOLTIP_BACKGROUND_COLOR.into(),
+            z_index: ZIndex::Global(1),
+            ..default()
+        })
+        .insert(ScannerTooltipUi)
+        .insert(GameCleanup)
+        .with_children(|tooltip| {
+            spawn_tooltip_text(tooltip, ScannerTooltipText::Name, name_style);
+            spawn_tooltip_text(tooltip, ScannerTooltipText::Faction, details_style.clone());
+            spawn_tooltip_text(tooltip, ScannerTooltipText::Health, details_style.clone());
+            spawn_tooltip_bar(
+                tooltip,
+                ScannerTooltipBar::Health,
+                palette_colors.health.into(),
+            );
+            spawn_tooltip_text(tooltip, ScannerTooltipText::Shields, details_style.clone());
+            spawn_tooltip_bar(
+                tooltip,
+                ScannerTooltipBar::Shields,
+                palette_colors.shields.into(),
+            );
+            spawn_tooltip_text(tooltip, ScannerTooltipText::Behaviors, details_style);
+        });
+}
+
+/// Shows the tooltip next to the scanned mob, kept inside of the screen, and hides it when
+/// nothing is scanned
+#[allow(clippy::too_many_arguments)]
+pub(super) fn update_scanner_tooltip_system(
+    scanner: Res<ScannerResource>,
+    mob_query: Query<(&MobComponent, &HealthComponent, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut tooltip_query: Query<(&mut Style, &Node), With<ScannerTooltipUi>>,
+    mut text_query: Query<(&ScannerTooltipText, &mut Text)>,
+    mut bar_query: Query<(&ScannerTooltipBar, &mut Style), Without<ScannerTooltipUi>>,
+    ui_scale: Res<UiScale>,
+    localization: Res<LocalizationResource>,
+) {
+    let Ok((mut tooltip_style, tooltip_node)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    // the tooltip is placed on the screen where the gameplay camera shows the mob
+    let scanned = scanner
+        .scanned_mob
+        .and_then(|mob_entity| mob_query.get(mob_entity).ok())
+        .zip(camera_query.get_single().ok())
+        .and_then(
+            |((mob, health, mob_transform), (camera, camera_transform))| {
+                let screen_position =
+                    camera.world_to_viewport(camera_transform, mob_transform.translation())?;
+                let screen_size = camera.logical_viewport_size()?;
+                Some((mob, health, screen_position, screen_size))
+            },
+        );
+
+    let Some((mob, health, screen_position, screen_size)) = scanned else {
+        if tooltip_style.display != Display::None {
+            tooltip_style.display = Display::None;
+        }
+        return;
+    };
+
+    // `Val::Px` sizes are multiplied by the UI scale
+    let max_position = (screen_size - tooltip_node.size()).max(Vec2::ZERO) / ui_scale.0;
+    let position = (screen_position / ui_scale.0 + TOOLTIP_OFFSET).min(max_position);
+    tooltip_style.display = Display::Flex;
+    tooltip_style.left = Val::Px(position.x);
+    tooltip_style.top = Val::Px(position.y);
+
+    let mut behavior_ids: Vec<&str> = mob
+        .behaviors
+        .iter()
+        .map(|behavior| behavior.describe_id())
+        .collect();
+    behavior_ids.dedup();
+
+    for (tooltip_text, mut text) in text_query.iter_mut() {
+        let value = match tooltip_text {
+            ScannerTooltipText::Name => mob.mob_type.get_name(&localization),
+            ScannerTooltipText::Faction => mob.mob_type.get_faction().get_name(&localization),
+            ScannerTooltipText::Health => localization.format(
+                "scanner.health",
+                &[
+                    ("health", health.get_health().to_string()),
+                    ("max", health.get_max_health().to_string()),
+                ],
+            ),
+            ScannerTooltipText::Shields => localization.format(
+                "scanner.shields",
+                &[
+                    ("shields", health.get_shields().to_string()),
+                    ("max", health.get_max_shields().to_string()),
+                ],
+            ),
+            ScannerTooltipText::Behaviors => behavior_ids
+                .iter()
+                .map(|id| localization.get(id))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        };
+
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    for (tooltip_bar, mut style) in bar_query.iter_mut() {
+        let width = Val::Percent(
+            100.0
+                * match tooltip_bar {
+                    ScannerTooltipBar::Health => health.get_health_percentage(),
+                    ScannerTooltipBar::Shields => health.get_shields_percentage(),
+                },
+        );
+
+        if style.width != width {
+            style.width = width;
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Systems to draw and update the score of the run and the current kill combo
+use crate::run::score::ScoreResource;
+use bevy::{
+    asset::Handle,
+    color::{Color, Srgba},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        query::{With, Without},
+        system::{Query, Res},
+    },
+    hierarchy::{BuildChildren, ChildBuilder},
+    text::{Font, Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, Style, Val,
+    },
+    utils::default,
+};
+
+use super::parent::ScoreUiChildBuilderExt;
+
+const SCORE_FONT_SIZE: f32 = 32.0;
+const COMBO_FONT_SIZE: f32 = 20.0;
+const SCORE_TEXT_COLOR: Srgba = Srgba::WHITE;
+const COMBO_TEXT_COLOR: Srgba = Srgba::rgb(1.0, 0.8, 0.2);
+
+/// Used for querying UI for displaying the score
+#[derive(Component)]
+pub(super) struct ScoreUi;
+
+/// Used for querying UI for displaying the combo
+#[derive(Component)]
+pub(super) struct ComboUi;
+
+impl ScoreUiChildBuilderExt for ChildBuilder<'_> {
+    fn spawn_score_ui(&mut self, font: Handle<Font>) {
+        self.spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|score| {
+            score
+                .spawn(TextBundle::from_section(
+                    "0",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: SCORE_FONT_SIZE,
+                        color: Color::Srgba(SCORE_TEXT_COLOR),
+                    },
+                ))
+                .insert(ScoreUi);
+
+            score
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: COMBO_FONT_SIZE,
+                        color: Color::Srgba(COMBO_TEXT_COLOR),
+                    },
+                ))
+                .insert(ComboUi);
+        });
+    }
+}
+
+/// Updates the score and shows the combo multiplier while a combo is going
+pub(super) fn update_score_ui_system(
+    score_res: Res<ScoreResource>,
+    mut score_ui_query: Query<&mut Text, (With<ScoreUi>, Without<ComboUi>)>,
+    mut combo_ui_query: Query<&mut Text, (With<ComboUi>, Without<ScoreUi>)>,
+) {
+    if !score_res.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = score_ui_query.get_single_mut() {
+        text.sections[0].value = score_res.score.to_string();
+    }
+
+    if let Ok(mut text) = combo_ui_query.get_single_mut() {
+        text.sections[0].value = if score_res.combo > 1 {
+            format!("x{:.1}", score_res.combo_multiplier)
+        } else {
+            String::new()
+        };
+    }
+}
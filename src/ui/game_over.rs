@@ -12,7 +12,7 @@ use bevy::{
     time::{Timer, TimerMode},
     ui::{
         node_bundles::{ImageBundle, NodeBundle, TextBundle},
-        BackgroundColor, FlexDirection, JustifyContent, Style, UiRect, Val,
+        AlignItems, BackgroundColor, FlexDirection, JustifyContent, Style, UiRect, Val,
     },
     utils::default,
 };
@@ -20,32 +20,33 @@ use std::time::Duration;
 use thetawave_assets::UiAssets;
 use thetawave_interface::{
     audio::ChangeBackgroundMusicEvent,
-    game::historical_metrics::{
-        MobKillsByPlayerForCurrentGame, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
-    },
+    game::historical_metrics::StatsByPlayerForCurrentGame,
+    game::profiles::ProfilesResource,
+    game::run_history::{CompletedRunResource, RunHistoryResource, RunRecord},
+    localization::LocalizationResource,
+    player::PlayersResource,
     states::GameOverCleanup,
 };
 
 /// Spawn the styled UI elements for the game over screen. It should tell the player how they did.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn setup_game_over_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     ui_assets: Res<UiAssets>,
     mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
-    current_game_shot_counts: Res<UserStatsByPlayerForCurrentGameCache>,
-    current_game_enemy_mob_kill_counts: Res<MobKillsByPlayerForCurrentGame>,
+    stats_by_player: Res<StatsByPlayerForCurrentGame>,
+    players_resource: Res<PlayersResource>,
     playing_on_arcade: Res<PlayingOnArcadeResource>,
+    profiles: Res<ProfilesResource>,
+    completed_run: Res<CompletedRunResource>,
+    run_history: Res<RunHistoryResource>,
+    localization: Res<LocalizationResource>,
 ) {
-    let maybe_current_game_stats = (**current_game_shot_counts).get(&DEFAULT_USER_ID);
-    let (accuracy_rate, total_shots_fired): (f32, usize) = match maybe_current_game_stats {
-        None => (100.0, 0),
-        Some(current_game_shot_counts) => {
-            let accuracy = (current_game_shot_counts.total_shots_hit as f32
-                / current_game_shot_counts.total_shots_fired as f32)
-                * 100.0;
-            (accuracy, current_game_shot_counts.total_shots_fired)
-        }
-    };
+    let run_comparison_text = completed_run
+        .as_ref()
+        .map(|run| get_run_comparison_text(run, &run_history, &localization))
+        .unwrap_or_default();
 
     // fade music out
     change_bg_music_event_writer.send(ChangeBackgroundMusicEvent {
@@ -93,7 +94,8 @@ pub(super) fn setup_game_over_system(
                                     left: Val::Auto,
                                 },
                                 padding: UiRect::all(Val::Px(10.0)),
-
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
                                 justify_content: JustifyContent::Center,
                                 ..Default::default()
                             },
@@ -102,32 +104,26 @@ pub(super) fn setup_game_over_system(
                         })
                         .with_children(|parent| {
                             parent.spawn(TextBundle {
-                                style: Style {
-                                    width: Val::Auto,
-                                    height: Val::Auto,
-                                    margin: UiRect::all(Val::Auto),
-                                    ..Default::default()
-                                },
-
                                 text: Text::from_section(
-                                    format!(
-                                        "Projectiles fired: {}\nAccuracy: {:.2}%\n\nEnemies destroyed:\n{}",
-                                        total_shots_fired,
-                                        accuracy_rate,
-                                        super::pprint_mob_kills_from_data(
-                                            &current_game_enemy_mob_kill_counts
-                                        ),
-                                    ),
+                                    run_comparison_text,
                                     TextStyle {
-                                        font,
+                                        font: font.clone(),
                                         font_size: 32.0,
                                         color: Color::WHITE,
                                     },
                                 )
                                 .with_justify(JustifyText::Center),
-
                                 ..default()
                             });
+
+                            super::spawn_player_stats_columns(
+                                parent,
+                                font,
+                                &stats_by_player,
+                                &players_resource,
+                                &profiles,
+                                &localization,
+                            );
                         });
 
                     parent
@@ -160,3 +156,54 @@ pub(super) fn setup_game_over_system(
                 });
         });
 }
+
+/// Compares a run against the personal bests from previously saved runs
+fn get_run_comparison_text(
+    run: &RunRecord,
+    run_history: &RunHistoryResource,
+    localization: &LocalizationResource,
+) -> String {
+    let bests = &run_history.personal_bests;
+    let score = localization.format("game_over.score", &[("score", run.score.to_string())]);
+    let secs = format!("{:.0}", run.duration_secs);
+
+    // there is nothing to compare against on the first run
+    if bests.total_runs == 0 {
+        return format!(
+            "{score}\n{}\n{}\n\n",
+            localization.format(
+                "game_over.levels_completed",
+                &[("count", run.levels_completed.to_string())]
+            ),
+            localization.format("game_over.time_survived", &[("secs", secs)]),
+        );
+    }
+
+    let new_best_marker = |is_new_best: bool| {
+        if is_new_best {
+            format!(" - {}", localization.get("game_over.new_best"))
+        } else {
+            String::new()
+        }
+    };
+
+    format!(
+        "{score}\n{}{}\n{}{}\n\n",
+        localization.format(
+            "game_over.levels_completed_with_best",
+            &[
+                ("count", run.levels_completed.to_string()),
+                ("best", bests.most_levels_completed.to_string()),
+            ]
+        ),
+        new_best_marker(run.levels_completed > bests.most_levels_completed),
+        localization.format(
+            "game_over.time_survived_with_best",
+            &[
+                ("secs", secs),
+                ("best", format!("{:.0}", bests.longest_run_secs)),
+            ]
+        ),
+        new_best_marker(run.duration_secs > bests.longest_run_secs),
+    )
+}
@@ -0,0 +1,179 @@
+//! Systems to draw the leaderboard screen, opened from the main menu.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::BuildChildren,
+    prelude::NextState,
+    text::{JustifyText, Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, BackgroundColor, FlexDirection, JustifyContent, Style, UiRect, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    game::run_history::{Leaderboard, LeaderboardsResource, RunRecord},
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    run::RunOutcomeType,
+    states::{AppStates, LeaderboardCleanup},
+};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const HEADER_FONT_SIZE: f32 = 28.0;
+const ENTRY_FONT_SIZE: f32 = 18.0;
+
+/// Spawns a column for each leaderboard, listing its highest scoring runs
+pub(super) fn setup_leaderboard_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    leaderboards: Res<LeaderboardsResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(LeaderboardCleanup)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.get("leaderboard.title"),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Vw(3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    if leaderboards.is_empty() {
+                        row.spawn(TextBundle::from_section(
+                            localization.get("leaderboard.empty"),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: HEADER_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    }
+
+                    for leaderboard in leaderboards.iter() {
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Val::Px(10.0)),
+                                row_gap: Val::Px(8.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor::from(Color::BLACK.with_alpha(0.9)),
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column.spawn(TextBundle::from_section(
+                                get_leaderboard_title(leaderboard, &localization),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: HEADER_FONT_SIZE,
+                                    color: Color::WHITE,
+                                },
+                            ));
+
+                            column.spawn(
+                                TextBundle::from_section(
+                                    get_leaderboard_entries_text(&leaderboard.runs, &localization),
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: ENTRY_FONT_SIZE,
+                                        color: Color::WHITE,
+                                    },
+                                )
+                                .with_text_justify(JustifyText::Left),
+                            );
+                        });
+                    }
+                });
+
+            parent.spawn(TextBundle::from_section(
+                localization.get("menu.back_hint"),
+                TextStyle {
+                    font,
+                    font_size: ENTRY_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Returns to the main menu
+pub(super) fn leaderboard_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+) {
+    if let Ok(action_state) = menu_input_query.get_single() {
+        if action_state.just_released(&MenuAction::Back) {
+            next_app_state.set(AppStates::MainMenu);
+        }
+    }
+}
+
+fn get_leaderboard_title(leaderboard: &Leaderboard, localization: &LocalizationResource) -> String {
+    format!(
+        "{} ({})",
+        leaderboard.run_key.replace('_', " "),
+        leaderboard.difficulty.get_name(localization)
+    )
+}
+
+/// One line for each run, highest score first
+fn get_leaderboard_entries_text(runs: &[RunRecord], localization: &LocalizationResource) -> String {
+    runs.iter()
+        .enumerate()
+        .map(|(rank, run)| {
+            format!(
+                "{}. {} - {} - {}",
+                rank + 1,
+                run.score,
+                run.characters
+                    .iter()
+                    .map(|character| character.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                match run.outcome {
+                    RunOutcomeType::Victory => localization.get("run.victory"),
+                    RunOutcomeType::Defeat(_) => localization.format(
+                        "run.reached_level",
+                        &[("level", (run.levels_completed + 1).to_string())]
+                    ),
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
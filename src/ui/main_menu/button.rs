@@ -1,4 +1,4 @@
-//! Provides the layout (trait on `bevy::hierarchy::ChildBUilder`) and behavior (systems) to put 4
+//! Provides the layout (trait on `bevy::hierarchy::ChildBUilder`) and behavior (systems) to put 6
 //! vertically layed out on the main menu, and change the state from
 //! `thetawave_interface::states::AppStates::MainMenu` to
 //! `thetawave_interface::states::AppStates::CharacterSelection`
@@ -23,6 +23,7 @@ use thetawave_assets::UiAssets;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
     input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
 };
 
 const BUTTON_TEXTURE_PADDING: UiRect =
@@ -31,8 +32,12 @@ const BUTTON_TEXTURE_PADDING_HOVERED: UiRect =
     UiRect::new(Val::ZERO, Val::ZERO, Val::Percent(8.5), Val::ZERO);
 
 /// This is the order (vertical, going down) of the buttons shown on the main menu UI.
-const MAIN_MENU_BUTTON_ORDER: [ButtonActionType; 4] = [
+const MAIN_MENU_BUTTON_ORDER: [ButtonActionType; 8] = [
     ButtonActionType::EnterCharacterSelection,
+    ButtonActionType::EnterLeaderboard,
+    ButtonActionType::EnterStats,
+    ButtonActionType::EnterProfiles,
+    ButtonActionType::EnterAchievements,
     ButtonActionType::EnterOptions,
     ButtonActionType::EnterCompendium,
     ButtonActionType::QuitGame,
@@ -41,17 +46,28 @@ const MAIN_MENU_BUTTON_ORDER: [ButtonActionType; 4] = [
 /// Extension trait for spawning customized UI elements for Thetawave
 pub(super) trait UiChildBuilderExt {
     // Spawn 1 menu button for each element of `MainMenuButtonActionComponent`
-    fn spawn_main_menu_buttons(&mut self, ui_assets: &UiAssets, font: Handle<Font>) -> &mut Self;
+    fn spawn_main_menu_buttons(
+        &mut self,
+        ui_assets: &UiAssets,
+        font: Handle<Font>,
+        localization: &LocalizationResource,
+    ) -> &mut Self;
 }
 
 impl UiChildBuilderExt for ChildBuilder<'_> {
-    fn spawn_main_menu_buttons(&mut self, ui_assets: &UiAssets, font: Handle<Font>) -> &mut Self {
+    fn spawn_main_menu_buttons(
+        &mut self,
+        ui_assets: &UiAssets,
+        font: Handle<Font>,
+        localization: &LocalizationResource,
+    ) -> &mut Self {
         for action in MAIN_MENU_BUTTON_ORDER.iter() {
             self.spawn_button(
                 ui_assets,
                 font.clone(),
                 ButtonActionComponent::from(*action),
                 None,
+                localization,
             );
         }
 
@@ -2,20 +2,28 @@
 //! guides the user into the `thetawave_interface::states::AppStates::CharacterSelection` state.
 use crate::animation::{AnimationComponent, AnimationDirection};
 use bevy::{
-    color::Srgba,
+    color::{Color, Srgba},
     ecs::{
+        component::Component,
         event::EventWriter,
-        system::{Commands, Res},
+        query::With,
+        system::{Commands, Query, Res},
     },
     hierarchy::BuildChildren,
     prelude::{in_state, App, ImageBundle, IntoSystemConfigs, OnEnter, Plugin, Update},
     sprite::TextureAtlas,
+    text::{Text, TextStyle},
     time::{Timer, TimerMode},
-    ui::{node_bundles::NodeBundle, AlignItems, FlexDirection, JustifyContent, Style, Val},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, Style, Val,
+    },
     utils::default,
 };
 use std::time::Duration;
 use thetawave_interface::audio::{BGMusicType, ChangeBackgroundMusicEvent};
+use thetawave_interface::game::profiles::ProfilesResource;
+use thetawave_interface::localization::LocalizationResource;
 use thetawave_interface::states::{AppStates, MainMenuCleanup};
 mod button;
 use self::button::main_menu_button_selection_and_click_system;
@@ -31,12 +39,40 @@ impl Plugin for MainMenuUIPlugin {
         app.add_systems(OnEnter(AppStates::MainMenu), setup_main_menu_system)
             .add_systems(
                 Update,
-                (main_menu_button_selection_and_click_system,)
+                (
+                    main_menu_button_selection_and_click_system,
+                    update_profile_text_system,
+                )
                     .run_if(in_state(AppStates::MainMenu)),
             );
     }
 }
 
+/// Text showing the active profile
+#[derive(Component)]
+struct MainMenuProfileText;
+
+fn get_profile_text(profiles: &ProfilesResource, localization: &LocalizationResource) -> String {
+    localization.format(
+        "main_menu.profile",
+        &[("name", profiles.get_name(profiles.active_profile_id))],
+    )
+}
+
+/// Keeps the active profile text up to date, profiles can be switched just before the menu opens
+fn update_profile_text_system(
+    mut profile_text_query: Query<&mut Text, With<MainMenuProfileText>>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let profile_text = get_profile_text(&profiles, &localization);
+    for mut text in profile_text_query.iter_mut() {
+        if text.sections[0].value != profile_text {
+            text.sections[0].value = profile_text.clone();
+        }
+    }
+}
+
 /// Spawn the intiial components of the main menu UI to be rendered. This only needs to be called
 /// once whenever we want to overlay the main menu. Despawning entities with the`MainMenuCleanup`
 /// component is the main way to undo the side effects of this system.
@@ -44,6 +80,8 @@ fn setup_main_menu_system(
     mut commands: Commands,
     mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
     ui_assets: Res<UiAssets>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
 ) {
     let font = ui_assets.lunchds_font.clone();
 
@@ -129,7 +167,18 @@ fn setup_main_menu_system(
                             ..default()
                         })
                         .with_children(|parent| {
-                            parent.spawn_main_menu_buttons(&ui_assets, font.clone());
+                            parent.spawn_main_menu_buttons(&ui_assets, font.clone(), &localization);
+
+                            parent
+                                .spawn(TextBundle::from_section(
+                                    get_profile_text(&profiles, &localization),
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 20.0,
+                                        color: Color::WHITE,
+                                    },
+                                ))
+                                .insert(MainMenuProfileText);
                         });
                 });
         });
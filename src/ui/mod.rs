@@ -1,27 +1,64 @@
 //! Exposes a plugin that handles layout, rendering, and styling for each of the major game states.
 use bevy::{
     app::{App, Plugin, Update},
-    prelude::{Component, OnEnter, Query, Res, Time, Timer, Transform},
+    asset::Handle,
+    color::Color,
+    hierarchy::{BuildChildren, ChildBuilder},
+    prelude::{
+        default, in_state, resource_exists, Component, IntoSystemConfigs, OnEnter, Query, Res,
+        Time, Timer, Transform,
+    },
+    text::{Font, JustifyText, Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        FlexDirection, JustifyContent, Style, Val,
+    },
 };
-use thetawave_interface::game::historical_metrics::{MobsKilledByPlayerCacheT, DEFAULT_USER_ID};
+use thetawave_assets::UiAssets;
+use thetawave_interface::game::historical_metrics::{
+    MobsKilledBy1PlayerCacheT, PlayerGameStat, StatsByPlayerForCurrentGame,
+};
+use thetawave_interface::game::profiles::ProfilesResource;
+use thetawave_interface::localization::LocalizationResource;
+use thetawave_interface::player::{PlayerIDComponent, PlayersResource};
+use thetawave_interface::spawnable::{EnemyMobType, MobType};
 
 use thetawave_interface::states;
 
+use crate::GameUpdateSet;
+
+mod achievements;
 mod button;
 mod character_selection;
+mod compendium;
+mod controls;
 mod game;
 mod game_over;
+mod leaderboard;
 mod main_menu;
+mod options;
 mod pause_menu;
+mod profiles;
+mod stats;
 mod victory;
 
 use self::{
+    achievements::{
+        achievements_input_system, despawn_achievement_toast_system, setup_achievements_system,
+        spawn_achievement_toast_system,
+    },
     button::{button_action_change_state_system, ButtonActionEvent},
     character_selection::CharacterSelectionPlugin,
+    compendium::{compendium_input_system, setup_compendium_system, update_compendium_ui_system},
+    controls::{controls_input_system, setup_controls_system, update_controls_ui_system},
     game::GameUiPlugin,
     game_over::setup_game_over_system,
+    leaderboard::{leaderboard_input_system, setup_leaderboard_system},
     main_menu::MainMenuUIPlugin,
-    pause_menu::setup_pause_system,
+    options::{options_input_system, setup_options_system, update_options_ui_system},
+    pause_menu::{pause_menu_input_system, setup_pause_system, update_pause_menu_ui_system},
+    profiles::{profiles_input_system, setup_profiles_system, update_profiles_ui_system},
+    stats::{setup_stats_system, stats_input_system},
     victory::setup_victory_system,
 };
 
@@ -44,20 +81,247 @@ impl Plugin for UiPlugin {
 
         app.add_systems(OnEnter(states::AppStates::Victory), setup_victory_system);
 
+        app.add_systems(
+            OnEnter(states::AppStates::Leaderboard),
+            setup_leaderboard_system,
+        );
+
+        app.add_systems(
+            Update,
+            leaderboard_input_system.run_if(in_state(states::AppStates::Leaderboard)),
+        );
+
+        app.add_systems(OnEnter(states::AppStates::Stats), setup_stats_system);
+
+        app.add_systems(
+            Update,
+            stats_input_system.run_if(in_state(states::AppStates::Stats)),
+        );
+
+        app.add_systems(OnEnter(states::AppStates::Profiles), setup_profiles_system);
+
+        app.add_systems(
+            Update,
+            (profiles_input_system, update_profiles_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Profiles)),
+        );
+
+        app.add_systems(
+            OnEnter(states::AppStates::Achievements),
+            setup_achievements_system,
+        );
+
+        app.add_systems(
+            Update,
+            achievements_input_system.run_if(in_state(states::AppStates::Achievements)),
+        );
+
+        app.add_systems(OnEnter(states::AppStates::Options), setup_options_system);
+
+        app.add_systems(
+            Update,
+            (options_input_system, update_options_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Options)),
+        );
+
+        app.add_systems(OnEnter(states::AppStates::Controls), setup_controls_system);
+
+        app.add_systems(
+            Update,
+            (controls_input_system, update_controls_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Controls)),
+        );
+
+        app.add_systems(
+            OnEnter(states::AppStates::Compendium),
+            setup_compendium_system,
+        );
+
+        app.add_systems(
+            Update,
+            (compendium_input_system, update_compendium_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Compendium)),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                spawn_achievement_toast_system.run_if(resource_exists::<UiAssets>),
+                despawn_achievement_toast_system,
+            ),
+        );
+
         app.add_systems(OnEnter(states::GameStates::Paused), setup_pause_system);
+
+        // runs abandoned from the pause menu end before the next frame is played
+        app.add_systems(
+            Update,
+            (pause_menu_input_system, update_pause_menu_ui_system)
+                .chain()
+                .before(GameUpdateSet::Level)
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::Paused)),
+        );
+
+        // the options and controls screens can also be opened from the pause menu
+        app.add_systems(
+            OnEnter(states::GameStates::PausedOptions),
+            setup_options_system,
+        );
+
+        app.add_systems(
+            Update,
+            (options_input_system, update_options_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::PausedOptions)),
+        );
+
+        app.add_systems(
+            OnEnter(states::GameStates::PausedControls),
+            setup_controls_system,
+        );
+
+        app.add_systems(
+            Update,
+            (controls_input_system, update_controls_ui_system)
+                .chain()
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::PausedControls)),
+        );
+    }
+}
+
+/// Splits a type name like `HealthWrench` into words
+fn spaced_name(type_name: &str) -> String {
+    let mut name = String::new();
+    for c in type_name.chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push(' ');
+        }
+        name.push(c);
     }
+    name
 }
 
-// Consistently format mob+kill-count pairs.
-fn pprint_mob_kills_from_data(data: &MobsKilledByPlayerCacheT) -> String {
-    match (*data).get(&DEFAULT_USER_ID) {
-        None => String::from("No mobs killed"),
-        Some(mob_kill_counts) => mob_kill_counts
-            .iter()
-            .map(|(mobtype, n)| format!("{mobtype}: {n}"))
+/// Kills of the enemies sharing a name id, like left and right strafers, most killed first
+fn get_kills_by_name(
+    mob_kills: &MobsKilledBy1PlayerCacheT,
+) -> Vec<(&'static str, EnemyMobType, usize)> {
+    let mut kills_by_name: Vec<(&'static str, EnemyMobType, usize)> = vec![];
+    for (enemy_type, n_killed) in mob_kills.iter() {
+        let name_id = MobType::Enemy(*enemy_type).get_name_id();
+        match kills_by_name
+            .iter_mut()
+            .find(|(other, _, _)| *other == name_id)
+        {
+            Some((_, _, total)) => *total += n_killed,
+            None => kills_by_name.push((name_id, *enemy_type, *n_killed)),
+        }
+    }
+    kills_by_name.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(b.0)));
+    kills_by_name
+}
+
+// Consistently format a profile's mob+kill-count pairs.
+/// Text for one player's column on the game over and victory screens
+fn pprint_player_game_stat(
+    stat: Option<&PlayerGameStat>,
+    localization: &LocalizationResource,
+) -> String {
+    let (accuracy_rate, total_shots_fired) = match stat {
+        Some(stat) if stat.user_stat.total_shots_fired > 0 => (
+            (stat.user_stat.total_shots_hit as f32 / stat.user_stat.total_shots_fired as f32)
+                * 100.0,
+            stat.user_stat.total_shots_fired,
+        ),
+        _ => (100.0, 0),
+    };
+    let mob_kills = match stat {
+        Some(stat) if !stat.mobs_killed.is_empty() => get_kills_by_name(&stat.mobs_killed)
+            .into_iter()
+            .map(|(name_id, _, n)| format!("{}: {n}", localization.get(name_id)))
             .collect::<Vec<String>>()
             .join("\n"),
+        _ => localization.get("game_over.no_enemies_destroyed"),
+    };
+    format!(
+        "{}\n{}\n\n{}\n{}",
+        localization.format(
+            "game_over.projectiles_fired",
+            &[("count", total_shots_fired.to_string())]
+        ),
+        localization.format(
+            "game_over.accuracy",
+            &[("percent", format!("{accuracy_rate:.2}"))]
+        ),
+        localization.get("game_over.enemies_destroyed"),
+        mob_kills
+    )
+}
+
+/// Spawns a column with the stats of each joined player for the game that just ended
+fn spawn_player_stats_columns(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    stats_by_player: &StatsByPlayerForCurrentGame,
+    players_resource: &PlayersResource,
+    profiles: &ProfilesResource,
+    localization: &LocalizationResource,
+) {
+    let mut players: Vec<(PlayerIDComponent, usize)> = players_resource
+        .player_data
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, data)| {
+            data.as_ref()
+                .map(|data| (PlayerIDComponent::from(idx), data.profile_id))
+        })
+        .collect();
+    if players.is_empty() {
+        players.push((PlayerIDComponent::One, profiles.active_profile_id));
     }
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(40.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for (player_id, profile_id) in players {
+                parent.spawn(TextBundle {
+                    text: Text::from_section(
+                        format!(
+                            "{}\n\n{}",
+                            localization.format(
+                                "game_over.player",
+                                &[
+                                    ("number", (usize::from(player_id) + 1).to_string()),
+                                    ("profile", profiles.get_name(profile_id)),
+                                ]
+                            ),
+                            pprint_player_game_stat(stats_by_player.get(&player_id), localization),
+                        ),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 32.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_justify(JustifyText::Center),
+                    ..default()
+                });
+            }
+        });
 }
 
 /// A component that will cause the assocaited entity to bounce up and down at a rate determined by
@@ -89,3 +353,32 @@ fn bouncing_prompt_system(
         transform.scale.y = scale;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{get_kills_by_name, spaced_name};
+    use thetawave_interface::game::historical_metrics::MobsKilledBy1PlayerCacheT;
+    use thetawave_interface::spawnable::EnemyMobType;
+
+    #[test]
+    fn test_type_names_are_split_into_words() {
+        assert_eq!(spaced_name("HealthWrench"), "Health Wrench");
+        assert_eq!(spaced_name("Money1"), "Money1");
+    }
+
+    #[test]
+    fn test_kills_of_variants_are_added_together() {
+        let kills_by_name = get_kills_by_name(&MobsKilledBy1PlayerCacheT::from([
+            (EnemyMobType::StraferLeft, 2),
+            (EnemyMobType::StraferRight, 3),
+            (EnemyMobType::Drone, 4),
+        ]));
+        assert_eq!(
+            kills_by_name
+                .iter()
+                .map(|(name_id, _, n_killed)| (*name_id, *n_killed))
+                .collect::<Vec<(&str, usize)>>(),
+            vec![("mob.strafer", 5), ("mob.drone", 4)]
+        );
+    }
+}
@@ -0,0 +1,556 @@
+//! Systems to draw the options screen, opened from the main menu or the pause menu. Options are
+//! changed with the left/right inputs, confirm or a mouse click, and are applied (and saved) as
+//! soon as they change.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        component::Component,
+        event::EventWriter,
+        query::{Changed, With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::{BuildChildren, Children},
+    prelude::{NextState, State},
+    text::{Text, TextStyle},
+    ui::{
+        node_bundles::{ButtonBundle, NodeBundle, TextBundle},
+        widget::Button,
+        AlignItems, FlexDirection, Interaction, JustifyContent, Style, UiRect, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use strum::IntoEnumIterator;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    audio::{PlaySoundEffectEvent, SoundEffectType},
+    game::options::{ColorPalette, DisplayMode, GameOptions, GameOptionsChangedEvent},
+    input::{MainMenuExplorer, MenuAction},
+    localization::{Language, LocalizationResource},
+    states::{AppStates, GameStates, OptionsCleanup},
+};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const ROW_FONT_SIZE: f32 = 28.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+const SELECTED_ROW_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// Amount that volumes, bloom intensity and screen shake change by per step
+const OPTION_STEP: f32 = 0.1;
+const MAX_BLOOM_INTENSITY: f32 = 2.0;
+
+/// Window sizes that can be chosen while windowed
+const WINDOW_RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1280, 800),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+];
+
+/// UI scales that can be chosen
+const UI_SCALES: [f32; 4] = [0.75, 1.0, 1.25, 1.5];
+
+/// A row of the options screen
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum OptionsRow {
+    Bloom,
+    BloomIntensity,
+    Tutorials,
+    MusicVolume,
+    SoundEffectsVolume,
+    MenuVolume,
+    DisplayMode,
+    WindowResolution,
+    ScreenShake,
+    ColorPalette,
+    ReducedMotion,
+    UiScale,
+    Language,
+    Controls,
+    Back,
+}
+
+/// This is the order (vertical, going down) of the rows shown on the options screen
+const OPTIONS_ROW_ORDER: [OptionsRow; 15] = [
+    OptionsRow::Bloom,
+    OptionsRow::BloomIntensity,
+    OptionsRow::Tutorials,
+    OptionsRow::MusicVolume,
+    OptionsRow::SoundEffectsVolume,
+    OptionsRow::MenuVolume,
+    OptionsRow::DisplayMode,
+    OptionsRow::WindowResolution,
+    OptionsRow::ScreenShake,
+    OptionsRow::ColorPalette,
+    OptionsRow::ReducedMotion,
+    OptionsRow::UiScale,
+    OptionsRow::Language,
+    OptionsRow::Controls,
+    OptionsRow::Back,
+];
+
+fn on_off_text(enabled: bool, localization: &LocalizationResource) -> String {
+    localization.get(if enabled { "options.on" } else { "options.off" })
+}
+
+fn percent_text(value: f32) -> String {
+    format!("{:.0}%", value * 100.0)
+}
+
+/// Steps a value between 0 and `max`. Stepping past either end wraps around when `wrap` is set,
+/// otherwise the value stops there.
+fn step_value(value: f32, step: i8, max: f32, wrap: bool) -> f32 {
+    let n_steps = (max / OPTION_STEP).round() as i32;
+    let current_step = (value / OPTION_STEP).round() as i32;
+    let next_step = if wrap {
+        (current_step + step as i32).rem_euclid(n_steps + 1)
+    } else {
+        (current_step + step as i32).clamp(0, n_steps)
+    };
+    next_step as f32 * OPTION_STEP
+}
+
+/// Steps through the window resolutions, where the first choice is the size from
+/// `config/display.ron`
+fn step_window_resolution(
+    window_resolution: Option<(u32, u32)>,
+    step: i8,
+    wrap: bool,
+) -> Option<(u32, u32)> {
+    let n_choices = WINDOW_RESOLUTIONS.len() as i32 + 1;
+    let current_choice = window_resolution
+        .and_then(|resolution| WINDOW_RESOLUTIONS.iter().position(|r| *r == resolution))
+        .map_or(0, |idx| idx as i32 + 1);
+    let next_choice = if wrap {
+        (current_choice + step as i32).rem_euclid(n_choices)
+    } else {
+        (current_choice + step as i32).clamp(0, n_choices - 1)
+    };
+    match next_choice {
+        0 => None,
+        choice => Some(WINDOW_RESOLUTIONS[choice as usize - 1]),
+    }
+}
+
+/// Steps through a list of choices, starting from the first if `value` isn't one of them
+fn step_choice<T: PartialEq + Copy>(choices: &[T], value: T, step: i8, wrap: bool) -> T {
+    let n_choices = choices.len() as i32;
+    let current_choice = choices.iter().position(|c| *c == value).unwrap_or(0) as i32;
+    let next_choice = if wrap {
+        (current_choice + step as i32).rem_euclid(n_choices)
+    } else {
+        (current_choice + step as i32).clamp(0, n_choices - 1)
+    };
+    choices[next_choice as usize]
+}
+
+impl OptionsRow {
+    fn label_id(&self) -> &'static str {
+        match self {
+            OptionsRow::Bloom => "options.bloom",
+            OptionsRow::BloomIntensity => "options.bloom_intensity",
+            OptionsRow::Tutorials => "options.tutorials",
+            OptionsRow::MusicVolume => "options.music_volume",
+            OptionsRow::SoundEffectsVolume => "options.sound_effects_volume",
+            OptionsRow::MenuVolume => "options.menu_volume",
+            OptionsRow::DisplayMode => "options.fullscreen",
+            OptionsRow::WindowResolution => "options.resolution",
+            OptionsRow::ScreenShake => "options.screen_shake",
+            OptionsRow::ColorPalette => "options.color_palette",
+            OptionsRow::ReducedMotion => "options.reduced_motion",
+            OptionsRow::UiScale => "options.ui_scale",
+            OptionsRow::Language => "options.language",
+            OptionsRow::Controls => "options.controls",
+            OptionsRow::Back => "options.back",
+        }
+    }
+
+    /// The text shown for a row, with arrows around the value of the selected row
+    fn text(
+        &self,
+        game_options: &GameOptions,
+        is_selected: bool,
+        localization: &LocalizationResource,
+    ) -> String {
+        let label = localization.get(self.label_id());
+        let value = match self {
+            OptionsRow::Bloom => on_off_text(game_options.bloom_enabled, localization),
+            OptionsRow::BloomIntensity => percent_text(game_options.bloom_intensity),
+            OptionsRow::Tutorials => on_off_text(game_options.tutorials_enabled, localization),
+            OptionsRow::MusicVolume => percent_text(game_options.music_volume),
+            OptionsRow::SoundEffectsVolume => percent_text(game_options.sound_effects_volume),
+            OptionsRow::MenuVolume => percent_text(game_options.menu_volume),
+            OptionsRow::DisplayMode => on_off_text(
+                game_options.display_mode == DisplayMode::BorderlessFullscreen,
+                localization,
+            ),
+            OptionsRow::WindowResolution => match game_options.window_resolution {
+                Some((width, height)) => format!("{width}x{height}"),
+                None => localization.get("options.default"),
+            },
+            OptionsRow::ScreenShake => percent_text(game_options.screen_shake_intensity),
+            OptionsRow::ColorPalette => localization.get(match game_options.color_palette {
+                ColorPalette::Standard => "palette.standard",
+                ColorPalette::Deuteranopia => "palette.deuteranopia",
+                ColorPalette::Protanopia => "palette.protanopia",
+                ColorPalette::Tritanopia => "palette.tritanopia",
+            }),
+            OptionsRow::ReducedMotion => on_off_text(game_options.reduced_motion, localization),
+            OptionsRow::UiScale => percent_text(game_options.ui_scale),
+            OptionsRow::Language => game_options.language.get_native_name().to_string(),
+            OptionsRow::Controls | OptionsRow::Back => return label,
+        };
+
+        if is_selected {
+            format!("{label}: < {value} >")
+        } else {
+            format!("{label}: {value}")
+        }
+    }
+
+    /// The options after stepping this row's value up or down. On/off options are toggled by any
+    /// step. Returns `None` for rows that don't have a value.
+    fn stepped(&self, game_options: &GameOptions, step: i8, wrap: bool) -> Option<GameOptions> {
+        let mut game_options = game_options.clone();
+        match self {
+            OptionsRow::Bloom => game_options.bloom_enabled = !game_options.bloom_enabled,
+            OptionsRow::BloomIntensity => {
+                game_options.bloom_intensity = step_value(
+                    game_options.bloom_intensity,
+                    step,
+                    MAX_BLOOM_INTENSITY,
+                    wrap,
+                )
+            }
+            OptionsRow::Tutorials => {
+                game_options.tutorials_enabled = !game_options.tutorials_enabled
+            }
+            OptionsRow::MusicVolume => {
+                game_options.music_volume = step_value(game_options.music_volume, step, 1.0, wrap)
+            }
+            OptionsRow::SoundEffectsVolume => {
+                game_options.sound_effects_volume =
+                    step_value(game_options.sound_effects_volume, step, 1.0, wrap)
+            }
+            OptionsRow::MenuVolume => {
+                game_options.menu_volume = step_value(game_options.menu_volume, step, 1.0, wrap)
+            }
+            OptionsRow::DisplayMode => {
+                game_options.display_mode = match game_options.display_mode {
+                    DisplayMode::Windowed => DisplayMode::BorderlessFullscreen,
+                    DisplayMode::BorderlessFullscreen => DisplayMode::Windowed,
+                }
+            }
+            OptionsRow::WindowResolution => {
+                game_options.window_resolution =
+                    step_window_resolution(game_options.window_resolution, step, wrap)
+            }
+            OptionsRow::ScreenShake => {
+                game_options.screen_shake_intensity =
+                    step_value(game_options.screen_shake_intensity, step, 1.0, wrap)
+            }
+            OptionsRow::ColorPalette => {
+                let color_palettes: Vec<ColorPalette> = ColorPalette::iter().collect();
+                game_options.color_palette =
+                    step_choice(&color_palettes, game_options.color_palette, step, wrap)
+            }
+            OptionsRow::ReducedMotion => game_options.reduced_motion = !game_options.reduced_motion,
+            OptionsRow::UiScale => {
+                game_options.ui_scale = step_choice(&UI_SCALES, game_options.ui_scale, step, wrap)
+            }
+            OptionsRow::Language => {
+                let languages: Vec<Language> = Language::iter().collect();
+                game_options.language = step_choice(&languages, game_options.language, step, wrap)
+            }
+            OptionsRow::Controls | OptionsRow::Back => return None,
+        }
+        Some(game_options)
+    }
+}
+
+/// State of the options screen
+#[derive(Component, Default)]
+pub(super) struct OptionsMenu {
+    /// Index into `OPTIONS_ROW_ORDER`
+    selected_idx: usize,
+}
+
+/// Text above and below the rows, redrawn when the language changes
+#[derive(Component, Clone, Copy)]
+pub(super) enum OptionsHeadingText {
+    Title,
+    Hint,
+}
+
+impl OptionsHeadingText {
+    fn text_id(&self) -> &'static str {
+        match self {
+            OptionsHeadingText::Title => "options.title",
+            OptionsHeadingText::Hint => "options.hint",
+        }
+    }
+}
+
+/// Spawns a row for each option, with the first row selected
+pub(super) fn setup_options_system(mut commands: Commands, ui_assets: Res<UiAssets>) {
+    let font = ui_assets.lunchds_font.clone();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(1.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(OptionsCleanup)
+        .insert(OptionsMenu::default())
+        .with_children(|parent| {
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: TITLE_FONT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::bottom(Val::Vh(2.0)),
+                        ..default()
+                    }),
+                )
+                .insert(OptionsHeadingText::Title);
+
+            for row in OPTIONS_ROW_ORDER {
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                            ..default()
+                        },
+                        background_color: Color::NONE.into(),
+                        ..default()
+                    })
+                    .insert(row)
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: ROW_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            }
+
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font,
+                            font_size: HINT_FONT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Vh(2.0)),
+                        ..default()
+                    }),
+                )
+                .insert(OptionsHeadingText::Hint);
+        });
+}
+
+/// Selects rows with the up/down inputs or by hovering over them with the mouse, and changes the
+/// selected option with the left/right inputs, confirm or a click
+#[allow(clippy::too_many_arguments)]
+pub(super) fn options_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut options_menu_query: Query<&mut OptionsMenu>,
+    row_interaction_query: Query<(&OptionsRow, &Interaction), With<Button>>,
+    row_changed_interaction_query: Query<
+        (&OptionsRow, &Interaction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    game_options: Res<GameOptions>,
+    mut game_options_changed_event_writer: EventWriter<GameOptionsChangedEvent>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    app_state: Res<State<AppStates>>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+) {
+    let (Ok(action_state), Ok(mut options_menu)) = (
+        menu_input_query.get_single(),
+        options_menu_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let n_rows = OPTIONS_ROW_ORDER.len();
+    let previous_idx = options_menu.selected_idx;
+
+    // hovering overrides the up/down inputs
+    let hovered_row = row_interaction_query
+        .iter()
+        .find_map(|(row, interaction)| (*interaction != Interaction::None).then_some(*row));
+    let clicked_row = row_changed_interaction_query
+        .iter()
+        .find_map(|(row, interaction)| (*interaction == Interaction::Pressed).then_some(*row));
+
+    if let Some(idx) = hovered_row.and_then(|row| OPTIONS_ROW_ORDER.iter().position(|x| *x == row))
+    {
+        options_menu.selected_idx = idx;
+    } else if action_state.just_pressed(&MenuAction::NavigateUpKeyboard)
+        || action_state.just_pressed(&MenuAction::NavigateUpGamepad)
+    {
+        options_menu.selected_idx = (options_menu.selected_idx + n_rows - 1) % n_rows;
+    } else if action_state.just_pressed(&MenuAction::NavigateDownKeyboard)
+        || action_state.just_pressed(&MenuAction::NavigateDownGamepad)
+    {
+        options_menu.selected_idx = (options_menu.selected_idx + 1) % n_rows;
+    }
+
+    if options_menu.selected_idx != previous_idx {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: SoundEffectType::ButtonSelect,
+        });
+    }
+
+    let selected_row = OPTIONS_ROW_ORDER[options_menu.selected_idx];
+    let confirmed = clicked_row.is_some() || action_state.just_released(&MenuAction::Confirm);
+
+    if action_state.just_released(&MenuAction::Back)
+        || (confirmed && selected_row == OptionsRow::Back)
+    {
+        if *app_state.get() == AppStates::Game {
+            next_game_state.set(GameStates::Paused);
+        } else {
+            next_app_state.set(AppStates::MainMenu);
+        }
+        return;
+    }
+
+    if confirmed && selected_row == OptionsRow::Controls {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: SoundEffectType::ButtonConfirm,
+        });
+        if *app_state.get() == AppStates::Game {
+            next_game_state.set(GameStates::PausedControls);
+        } else {
+            next_app_state.set(AppStates::Controls);
+        }
+        return;
+    }
+
+    let changed_game_options = if action_state.just_pressed(&MenuAction::NavigateLeftKeyboard)
+        || action_state.just_pressed(&MenuAction::NavigateLeftGamepad)
+    {
+        selected_row.stepped(&game_options, -1, false)
+    } else if action_state.just_pressed(&MenuAction::NavigateRightKeyboard)
+        || action_state.just_pressed(&MenuAction::NavigateRightGamepad)
+    {
+        selected_row.stepped(&game_options, 1, false)
+    } else if confirmed {
+        selected_row.stepped(&game_options, 1, true)
+    } else {
+        None
+    };
+
+    if let Some(changed_game_options) = changed_game_options {
+        if changed_game_options != *game_options {
+            sound_effect_event_writer.send(PlaySoundEffectEvent {
+                sound_effect_type: SoundEffectType::ButtonConfirm,
+            });
+            game_options_changed_event_writer.send(GameOptionsChangedEvent(changed_game_options));
+        }
+    }
+}
+
+/// Redraws each row with its current value, highlighting the selected row. Text is redrawn in the
+/// chosen language as soon as the language option changes.
+pub(super) fn update_options_ui_system(
+    options_menu_query: Query<&OptionsMenu>,
+    row_query: Query<(&OptionsRow, &Children)>,
+    mut text_query: Query<&mut Text, Without<OptionsHeadingText>>,
+    mut heading_text_query: Query<(&OptionsHeadingText, &mut Text)>,
+    game_options: Res<GameOptions>,
+    localization: Res<LocalizationResource>,
+) {
+    let Ok(options_menu) = options_menu_query.get_single() else {
+        return;
+    };
+
+    for (heading, mut text) in heading_text_query.iter_mut() {
+        let heading_text = localization.get(heading.text_id());
+        if text.sections[0].value != heading_text {
+            text.sections[0].value = heading_text;
+        }
+    }
+
+    for (row, children) in row_query.iter() {
+        let is_selected = OPTIONS_ROW_ORDER[options_menu.selected_idx] == *row;
+        let Some(mut text) = children
+            .first()
+            .and_then(|child| text_query.get_mut(*child).ok())
+        else {
+            continue;
+        };
+
+        let row_text = row.text(&game_options, is_selected, &localization);
+        if text.sections[0].value != row_text {
+            text.sections[0].value = row_text;
+        }
+        text.sections[0].style.color = if is_selected {
+            SELECTED_ROW_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{step_choice, step_value, step_window_resolution, UI_SCALES, WINDOW_RESOLUTIONS};
+    use thetawave_interface::localization::Language;
+
+    #[test]
+    fn test_values_stop_at_their_limits_unless_wrapped() {
+        assert_eq!(step_value(1.0, 1, 1.0, false), 1.0);
+        assert_eq!(step_value(1.0, 1, 1.0, true), 0.0);
+        assert_eq!(step_value(0.0, -1, 1.0, false), 0.0);
+        assert!((step_value(0.5, -1, 1.0, false) - 0.4).abs() < f32::EPSILON);
+
+        assert_eq!(step_window_resolution(None, -1, false), None);
+        assert_eq!(
+            step_window_resolution(None, 1, false),
+            Some(WINDOW_RESOLUTIONS[0])
+        );
+        assert_eq!(
+            step_window_resolution(WINDOW_RESOLUTIONS.last().copied(), 1, true),
+            None
+        );
+
+        let languages = [Language::English, Language::Spanish];
+        assert_eq!(
+            step_choice(&languages, Language::English, -1, false),
+            Language::English
+        );
+        assert_eq!(
+            step_choice(&languages, Language::English, -1, true),
+            Language::Spanish
+        );
+        assert_eq!(step_choice(&UI_SCALES, 1.5, 1, false), 1.5);
+        assert_eq!(step_choice(&UI_SCALES, 1.1, 1, false), 1.0);
+    }
+}
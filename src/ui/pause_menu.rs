@@ -1,68 +1,501 @@
+//! Systems to draw the pause menu and take its actions. Actions that end or leave the run ask to be
+//! confirmed first.
 use bevy::{
-    asset::AssetServer,
-    color::Color,
+    app::AppExit,
+    color::{Alpha, Color},
     ecs::{
         component::Component,
-        system::{Commands, Res},
+        event::EventWriter,
+        query::{Changed, With, Without},
+        system::{Commands, Query, Res, ResMut},
     },
-    hierarchy::BuildChildren,
-    time::{Timer, TimerMode},
+    hierarchy::{BuildChildren, Children},
+    prelude::NextState,
+    text::{JustifyText, Text, TextStyle},
     ui::{
-        node_bundles::{ImageBundle, NodeBundle},
-        PositionType, Style, UiRect, Val,
+        node_bundles::{ButtonBundle, NodeBundle, TextBundle},
+        widget::Button,
+        AlignItems, FlexDirection, Interaction, JustifyContent, PositionType, Style, UiRect, Val,
     },
+    utils::default,
 };
-use thetawave_interface::states::PauseCleanup;
+use bevy_rapier2d::prelude::RapierConfiguration;
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    audio::{PlaySoundEffectEvent, SoundEffectType},
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    run::{RunDefeatType, RunEndEvent, RunOutcomeType},
+    states::{AppStates, GameStates, PauseCleanup},
+};
+
+use crate::run::RestartingRunResource;
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const ROW_FONT_SIZE: f32 = 28.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+const SELECTED_ROW_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// A row of the pause menu
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum PauseMenuRow {
+    Resume,
+    RestartRun,
+    Options,
+    AbandonRun,
+    QuitToDesktop,
+}
+
+/// This is the order (vertical, going down) of the rows shown on the pause menu
+const PAUSE_MENU_ROW_ORDER: [PauseMenuRow; 5] = [
+    PauseMenuRow::Resume,
+    PauseMenuRow::RestartRun,
+    PauseMenuRow::Options,
+    PauseMenuRow::AbandonRun,
+    PauseMenuRow::QuitToDesktop,
+];
+
+impl PauseMenuRow {
+    fn text_id(&self) -> &'static str {
+        match self {
+            PauseMenuRow::Resume => "pause.resume",
+            PauseMenuRow::RestartRun => "pause.restart_run",
+            PauseMenuRow::Options => "pause.options",
+            PauseMenuRow::AbandonRun => "pause.abandon_run",
+            PauseMenuRow::QuitToDesktop => "pause.quit_to_desktop",
+        }
+    }
+
+    /// String id of the question asked before the row's action is taken. Rows without one act
+    /// as soon as they are chosen.
+    fn confirm_prompt_id(&self) -> Option<&'static str> {
+        match self {
+            PauseMenuRow::Resume | PauseMenuRow::Options => None,
+            PauseMenuRow::RestartRun => Some("pause.confirm_restart_run"),
+            PauseMenuRow::AbandonRun => Some("pause.confirm_abandon_run"),
+            PauseMenuRow::QuitToDesktop => Some("pause.confirm_quit_to_desktop"),
+        }
+    }
+}
+
+/// State of the pause menu
+#[derive(Component, Default, Debug)]
+pub(super) struct PauseMenu {
+    /// Index into `PAUSE_MENU_ROW_ORDER`
+    selected_idx: usize,
+    /// Row whose action is waiting to be confirmed
+    confirming: Option<PauseMenuRow>,
+}
+
+/// Inputs to the pause menu from a single frame
+#[derive(Default)]
+struct PauseMenuInput {
+    /// Row under the mouse, which overrides the up/down inputs
+    hovered_row: Option<PauseMenuRow>,
+    clicked_row: Option<PauseMenuRow>,
+    confirm: bool,
+    cancel: bool,
+    navigate_up: bool,
+    navigate_down: bool,
+}
+
+impl PauseMenu {
+    /// Moves the selection and opens or closes the confirmation prompt, returns the row whose
+    /// action was chosen
+    fn handle_input(&mut self, input: &PauseMenuInput) -> Option<PauseMenuRow> {
+        if let Some(confirming_row) = self.confirming {
+            if input.cancel {
+                self.confirming = None;
+                return None;
+            }
+
+            // only the row being confirmed can be clicked to confirm it
+            if input.confirm || input.clicked_row == Some(confirming_row) {
+                self.confirming = None;
+                return Some(confirming_row);
+            }
+
+            return None;
+        }
+
+        if input.cancel {
+            return Some(PauseMenuRow::Resume);
+        }
+
+        let n_rows = PAUSE_MENU_ROW_ORDER.len();
+        if let Some(idx) = input
+            .hovered_row
+            .and_then(|row| PAUSE_MENU_ROW_ORDER.iter().position(|x| *x == row))
+        {
+            self.selected_idx = idx;
+        } else if input.navigate_up {
+            self.selected_idx = (self.selected_idx + n_rows - 1) % n_rows;
+        } else if input.navigate_down {
+            self.selected_idx = (self.selected_idx + 1) % n_rows;
+        }
+
+        if !(input.confirm || input.clicked_row.is_some()) {
+            return None;
+        }
 
-use crate::{options::PlayingOnArcadeResource, ui::BouncingPromptComponent};
+        let selected_row = PAUSE_MENU_ROW_ORDER[self.selected_idx];
+        if selected_row.confirm_prompt_id().is_some() {
+            self.confirming = Some(selected_row);
+            return None;
+        }
 
+        Some(selected_row)
+    }
+}
+
+/// Text below the rows, which asks to confirm the chosen action or lists the inputs
 #[derive(Component)]
-pub struct PauseUI;
+pub(super) struct PausePromptText;
 
+/// Spawns a row for each action over the paused game, with the first row selected
 pub fn setup_pause_system(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    playing_on_arcade: Res<PlayingOnArcadeResource>,
+    ui_assets: Res<UiAssets>,
+    localization: Res<LocalizationResource>,
 ) {
+    let font = ui_assets.lunchds_font.clone();
+
     commands
         .spawn(NodeBundle {
             style: Style {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
                 position_type: PositionType::Absolute,
-                ..Default::default()
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(1.0),
+                ..default()
             },
-            background_color: Color::srgba(0.5, 0.5, 0.5, 0.1).into(),
-            ..Default::default()
+            background_color: Color::BLACK.with_alpha(0.6).into(),
+            ..default()
         })
         .insert(PauseCleanup)
-        .insert(PauseUI)
+        .insert(PauseMenu::default())
         .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    localization.get("pause.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: TITLE_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Vh(2.0)),
+                    ..default()
+                }),
+            );
+
+            for row in PAUSE_MENU_ROW_ORDER {
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                            ..default()
+                        },
+                        background_color: Color::NONE.into(),
+                        ..default()
+                    })
+                    .insert(row)
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            localization.get(row.text_id()),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: ROW_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            }
+
             parent
-                .spawn(ImageBundle {
-                    image: asset_server
-                        .load(if **playing_on_arcade {
-                            "texture/restart_game_prompt_arcade.png"
-                        } else {
-                            "texture/restart_game_prompt_keyboard.png"
-                        })
-                        .into(),
-                    style: Style {
-                        width: Val::Px(400.0),
-                        height: Val::Px(100.0),
-                        margin: UiRect {
-                            left: Val::Auto,
-                            right: Val::Auto,
-                            top: Val::Auto,
-                            bottom: Val::Auto,
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font,
+                            font_size: HINT_FONT_SIZE,
+                            color: Color::WHITE,
                         },
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(BouncingPromptComponent {
-                    flash_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
-                    is_active: true,
-                });
+                    )
+                    .with_text_justify(JustifyText::Center)
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Vh(2.0)),
+                        ..default()
+                    }),
+                )
+                .insert(PausePromptText);
+        });
+}
+
+/// Selects rows with the up/down inputs or by hovering over them with the mouse, and takes the
+/// selected row's action with confirm or a click. Back and the pause input cancel a prompt, or
+/// otherwise resume the game.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn pause_menu_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut pause_menu_query: Query<&mut PauseMenu>,
+    row_interaction_query: Query<(&PauseMenuRow, &Interaction), With<Button>>,
+    row_changed_interaction_query: Query<
+        (&PauseMenuRow, &Interaction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut run_end_event_writer: EventWriter<RunEndEvent>,
+    mut exit: EventWriter<AppExit>,
+    mut restarting_run: ResMut<RestartingRunResource>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+    mut next_game_state: ResMut<NextState<GameStates>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    let (Ok(action_state), Ok(mut pause_menu)) = (
+        menu_input_query.get_single(),
+        pause_menu_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let input = PauseMenuInput {
+        hovered_row: row_interaction_query
+            .iter()
+            .find_map(|(row, interaction)| (*interaction != Interaction::None).then_some(*row)),
+        clicked_row: row_changed_interaction_query
+            .iter()
+            .find_map(|(row, interaction)| (*interaction == Interaction::Pressed).then_some(*row)),
+        confirm: action_state.just_released(&MenuAction::Confirm),
+        cancel: action_state.just_released(&MenuAction::Back)
+            || action_state.just_released(&MenuAction::ExitPauseMenu),
+        navigate_up: action_state.just_pressed(&MenuAction::NavigateUpKeyboard)
+            || action_state.just_pressed(&MenuAction::NavigateUpGamepad),
+        navigate_down: action_state.just_pressed(&MenuAction::NavigateDownKeyboard)
+            || action_state.just_pressed(&MenuAction::NavigateDownGamepad),
+    };
+
+    let previous_idx = pause_menu.selected_idx;
+    let previous_confirming = pause_menu.confirming;
+    let chosen_row = pause_menu.handle_input(&input);
+
+    let prompt_cancelled = previous_confirming.is_some() && pause_menu.confirming.is_none();
+    if pause_menu.selected_idx != previous_idx || (prompt_cancelled && chosen_row.is_none()) {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: SoundEffectType::ButtonSelect,
+        });
+    }
+
+    let prompt_opened = previous_confirming.is_none() && pause_menu.confirming.is_some();
+    if prompt_opened || chosen_row.is_some() {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: SoundEffectType::ButtonConfirm,
         });
+    }
+
+    let Some(chosen_row) = chosen_row else {
+        return;
+    };
+
+    match chosen_row {
+        PauseMenuRow::Options => {
+            next_game_state.set(GameStates::PausedOptions);
+            return;
+        }
+        PauseMenuRow::QuitToDesktop => {
+            exit.send(AppExit::Success);
+            return;
+        }
+        PauseMenuRow::Resume => {}
+        PauseMenuRow::RestartRun => {
+            restarting_run.0 = true;
+            next_app_state.set(AppStates::InitializeRun);
+        }
+        PauseMenuRow::AbandonRun => {
+            // the run ends from the paused state, so the game isn't resumed
+            run_end_event_writer.send(RunEndEvent {
+                outcome: RunOutcomeType::Defeat(RunDefeatType::Abandoned),
+            });
+            return;
+        }
+    }
+
+    next_game_state.set(GameStates::Playing);
+
+    // resume the physics engine
+    rapier_config.physics_pipeline_active = true;
+    rapier_config.query_pipeline_active = true;
+}
+
+/// Highlights the selected row and shows the prompt of the action being confirmed
+pub(super) fn update_pause_menu_ui_system(
+    pause_menu_query: Query<&PauseMenu, Changed<PauseMenu>>,
+    row_query: Query<(&PauseMenuRow, &Children)>,
+    mut text_query: Query<&mut Text, Without<PausePromptText>>,
+    mut prompt_text_query: Query<&mut Text, With<PausePromptText>>,
+    localization: Res<LocalizationResource>,
+) {
+    let Ok(pause_menu) = pause_menu_query.get_single() else {
+        return;
+    };
+
+    for (row, children) in row_query.iter() {
+        let is_selected = PAUSE_MENU_ROW_ORDER[pause_menu.selected_idx] == *row;
+        if let Some(mut text) = children
+            .first()
+            .and_then(|child| text_query.get_mut(*child).ok())
+        {
+            text.sections[0].style.color = if is_selected {
+                SELECTED_ROW_COLOR
+            } else {
+                Color::WHITE
+            };
+        }
+    }
+
+    let Ok(mut prompt_text) = prompt_text_query.get_single_mut() else {
+        return;
+    };
+    prompt_text.sections[0].value = match pause_menu
+        .confirming
+        .and_then(|row| row.confirm_prompt_id())
+    {
+        Some(prompt_id) => format!(
+            "{}\n{}",
+            localization.get(prompt_id),
+            localization.get("pause.confirm_hint")
+        ),
+        None => localization.get("pause.hint"),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PauseMenu, PauseMenuInput, PauseMenuRow, PAUSE_MENU_ROW_ORDER};
+
+    const CONFIRM: PauseMenuInput = PauseMenuInput {
+        hovered_row: None,
+        clicked_row: None,
+        confirm: true,
+        cancel: false,
+        navigate_up: false,
+        navigate_down: false,
+    };
+
+    const CANCEL: PauseMenuInput = PauseMenuInput {
+        hovered_row: None,
+        clicked_row: None,
+        confirm: false,
+        cancel: true,
+        navigate_up: false,
+        navigate_down: false,
+    };
+
+    fn select(pause_menu: &mut PauseMenu, row: PauseMenuRow) {
+        pause_menu.handle_input(&PauseMenuInput {
+            hovered_row: Some(row),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_navigation_wraps_around_the_rows() {
+        let mut pause_menu = PauseMenu::default();
+
+        pause_menu.handle_input(&PauseMenuInput {
+            navigate_up: true,
+            ..Default::default()
+        });
+        assert_eq!(pause_menu.selected_idx, PAUSE_MENU_ROW_ORDER.len() - 1);
+
+        pause_menu.handle_input(&PauseMenuInput {
+            navigate_down: true,
+            ..Default::default()
+        });
+        assert_eq!(pause_menu.selected_idx, 0);
+    }
+
+    #[test]
+    fn test_rows_without_a_prompt_act_at_once() {
+        let mut pause_menu = PauseMenu::default();
+        assert_eq!(
+            pause_menu.handle_input(&CONFIRM),
+            Some(PauseMenuRow::Resume)
+        );
+
+        select(&mut pause_menu, PauseMenuRow::Options);
+        assert_eq!(
+            pause_menu.handle_input(&CONFIRM),
+            Some(PauseMenuRow::Options)
+        );
+
+        // back resumes the game while nothing is being confirmed
+        assert_eq!(pause_menu.handle_input(&CANCEL), Some(PauseMenuRow::Resume));
+    }
+
+    #[test]
+    fn test_run_ending_rows_wait_for_a_second_confirm() {
+        for row in [
+            PauseMenuRow::RestartRun,
+            PauseMenuRow::AbandonRun,
+            PauseMenuRow::QuitToDesktop,
+        ] {
+            let mut pause_menu = PauseMenu::default();
+            select(&mut pause_menu, row);
+
+            assert_eq!(pause_menu.handle_input(&CONFIRM), None);
+            assert_eq!(pause_menu.confirming, Some(row));
+
+            // other inputs don't take the action
+            assert_eq!(pause_menu.handle_input(&PauseMenuInput::default()), None);
+
+            assert_eq!(pause_menu.handle_input(&CONFIRM), Some(row));
+            assert_eq!(pause_menu.confirming, None);
+        }
+    }
+
+    #[test]
+    fn test_prompt_is_cancelled_without_taking_the_action() {
+        let mut pause_menu = PauseMenu::default();
+        select(&mut pause_menu, PauseMenuRow::AbandonRun);
+        pause_menu.handle_input(&CONFIRM);
+
+        // clicking a different row neither confirms nor cancels
+        assert_eq!(
+            pause_menu.handle_input(&PauseMenuInput {
+                clicked_row: Some(PauseMenuRow::Resume),
+                ..Default::default()
+            }),
+            None
+        );
+        assert_eq!(pause_menu.confirming, Some(PauseMenuRow::AbandonRun));
+
+        assert_eq!(pause_menu.handle_input(&CANCEL), None);
+        assert_eq!(pause_menu.confirming, None);
+        assert_eq!(
+            PAUSE_MENU_ROW_ORDER[pause_menu.selected_idx],
+            PauseMenuRow::AbandonRun
+        );
+    }
+
+    #[test]
+    fn test_clicking_the_prompted_row_confirms_it() {
+        let mut pause_menu = PauseMenu::default();
+        let click_abandon = PauseMenuInput {
+            hovered_row: Some(PauseMenuRow::AbandonRun),
+            clicked_row: Some(PauseMenuRow::AbandonRun),
+            ..Default::default()
+        };
+
+        assert_eq!(pause_menu.handle_input(&click_abandon), None);
+        assert_eq!(
+            pause_menu.handle_input(&click_abandon),
+            Some(PauseMenuRow::AbandonRun)
+        );
+    }
 }
@@ -0,0 +1,279 @@
+//! Systems to draw the profiles screen, opened from the main menu, where profiles are created,
+//! selected and deleted.
+use bevy::{
+    color::{Alpha, Color},
+    ecs::{
+        component::Component,
+        event::{EventReader, EventWriter},
+        query::{With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::BuildChildren,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::NextState,
+    text::{JustifyText, Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, JustifyContent, Style, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::UiAssets;
+use thetawave_interface::{
+    game::profiles::{ProfileEvent, ProfilesResource, MAX_PROFILE_NAME_LEN},
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    states::{AppStates, ProfilesCleanup},
+};
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const ENTRY_FONT_SIZE: f32 = 28.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+
+/// State of the profiles screen. The row after the last profile creates a new profile.
+#[derive(Component, Default)]
+pub(super) struct ProfilesMenu {
+    selected_idx: usize,
+    /// Name being typed for a new profile
+    new_name: Option<String>,
+    /// Set after the first delete press, deleting the selected profile takes a second press
+    delete_pending: bool,
+}
+
+/// Text listing the profiles
+#[derive(Component)]
+pub(super) struct ProfilesListText;
+
+/// Text with the controls, or a prompt for the action in progress
+#[derive(Component)]
+pub(super) struct ProfilesHintText;
+
+/// Spawns the list of profiles with the active profile selected
+pub(super) fn setup_profiles_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(ProfilesCleanup)
+        .insert(ProfilesMenu {
+            selected_idx: profiles
+                .profiles
+                .iter()
+                .position(|profile| profile.id == profiles.active_profile_id)
+                .unwrap_or_default(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.get("profiles.title"),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: ENTRY_FONT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_text_justify(JustifyText::Left),
+                )
+                .insert(ProfilesListText);
+
+            parent
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: HINT_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(ProfilesHintText);
+        });
+}
+
+/// Moves the selection, selects or deletes the selected profile, and starts naming a new profile
+pub(super) fn profiles_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut profiles_menu_query: Query<&mut ProfilesMenu>,
+    mut keyboard_input_reader: EventReader<KeyboardInput>,
+    mut profile_event_writer: EventWriter<ProfileEvent>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let (Ok(action_state), Ok(mut profiles_menu)) = (
+        menu_input_query.get_single(),
+        profiles_menu_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    if let Some(new_name) = profiles_menu.new_name.as_mut() {
+        for event in keyboard_input_reader.read() {
+            if event.state != ButtonState::Pressed {
+                continue;
+            }
+            match &event.logical_key {
+                Key::Character(chars) => new_name.push_str(chars),
+                Key::Space => new_name.push(' '),
+                Key::Backspace => {
+                    new_name.pop();
+                }
+                _ => {}
+            }
+        }
+        *new_name = new_name.chars().take(MAX_PROFILE_NAME_LEN).collect();
+
+        if action_state.just_released(&MenuAction::Confirm) && !new_name.trim().is_empty() {
+            profile_event_writer.send(ProfileEvent::Create(new_name.clone()));
+            next_app_state.set(AppStates::MainMenu);
+        } else if action_state.just_released(&MenuAction::Back) {
+            profiles_menu.new_name = None;
+        }
+        return;
+    }
+    keyboard_input_reader.clear();
+
+    // the extra row is for creating a new profile
+    let n_rows = profiles.profiles.len() + 1;
+    let selected_profile = profiles.profiles.get(profiles_menu.selected_idx);
+
+    if action_state.just_released(&MenuAction::NavigateUpKeyboard)
+        || action_state.just_released(&MenuAction::NavigateUpGamepad)
+    {
+        profiles_menu.selected_idx = (profiles_menu.selected_idx + n_rows - 1) % n_rows;
+        profiles_menu.delete_pending = false;
+    } else if action_state.just_released(&MenuAction::NavigateDownKeyboard)
+        || action_state.just_released(&MenuAction::NavigateDownGamepad)
+    {
+        profiles_menu.selected_idx = (profiles_menu.selected_idx + 1) % n_rows;
+        profiles_menu.delete_pending = false;
+    } else if action_state.just_released(&MenuAction::Confirm) {
+        match selected_profile {
+            Some(profile) => {
+                profile_event_writer.send(ProfileEvent::Select(profile.id));
+                next_app_state.set(AppStates::MainMenu);
+            }
+            None => {
+                profiles_menu.new_name = Some(
+                    localization.format("profiles.default_name", &[("number", n_rows.to_string())]),
+                );
+            }
+        }
+    } else if action_state.just_released(&MenuAction::Delete) {
+        if let Some(profile) = selected_profile {
+            if profiles_menu.delete_pending {
+                profile_event_writer.send(ProfileEvent::Delete(profile.id));
+                profiles_menu.delete_pending = false;
+                profiles_menu.selected_idx = profiles_menu.selected_idx.saturating_sub(1);
+            } else if profiles.profiles.len() > 1 {
+                profiles_menu.delete_pending = true;
+            }
+        }
+    } else if action_state.just_released(&MenuAction::Back) {
+        next_app_state.set(AppStates::MainMenu);
+    }
+}
+
+/// Redraws the list of profiles and the controls hint
+pub(super) fn update_profiles_ui_system(
+    profiles_menu_query: Query<&ProfilesMenu>,
+    mut list_text_query: Query<&mut Text, (With<ProfilesListText>, Without<ProfilesHintText>)>,
+    mut hint_text_query: Query<&mut Text, (With<ProfilesHintText>, Without<ProfilesListText>)>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let (Ok(profiles_menu), Ok(mut list_text), Ok(mut hint_text)) = (
+        profiles_menu_query.get_single(),
+        list_text_query.get_single_mut(),
+        hint_text_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let get_cursor = |idx: usize| {
+        if idx == profiles_menu.selected_idx {
+            "> "
+        } else {
+            "  "
+        }
+    };
+
+    let mut lines: Vec<String> = profiles
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(idx, profile)| {
+            if profile.id == profiles.active_profile_id {
+                format!(
+                    "{}{}",
+                    get_cursor(idx),
+                    localization.format("profiles.active", &[("name", profile.name.clone())])
+                )
+            } else {
+                format!("{}{}", get_cursor(idx), profile.name)
+            }
+        })
+        .collect();
+    lines.push(match &profiles_menu.new_name {
+        Some(new_name) => format!(
+            "{}{}",
+            get_cursor(lines.len()),
+            localization.format("profiles.new_name", &[("name", new_name.clone())])
+        ),
+        None => format!(
+            "{}{}",
+            get_cursor(lines.len()),
+            localization.get("profiles.new_profile")
+        ),
+    });
+    list_text.sections[0].value = lines.join("\n");
+
+    hint_text.sections[0].value = if profiles_menu.new_name.is_some() {
+        localization.get("profiles.naming_hint")
+    } else if profiles_menu.delete_pending {
+        localization.format(
+            "profiles.confirm_delete",
+            &[(
+                "name",
+                profiles
+                    .profiles
+                    .get(profiles_menu.selected_idx)
+                    .map(|profile| profile.name.clone())
+                    .unwrap_or_default(),
+            )],
+        )
+    } else {
+        localization.get("profiles.hint")
+    };
+}
@@ -0,0 +1,313 @@
+//! Systems to draw the stats screen, opened from the main menu, which totals up the active
+//! profile's completed games.
+use bevy::{
+    asset::Handle,
+    color::{Alpha, Color},
+    ecs::{
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::{BuildChildren, ChildBuilder},
+    prelude::NextState,
+    sprite::TextureAtlas,
+    text::{Font, JustifyText, TextStyle},
+    ui::{
+        node_bundles::{ImageBundle, NodeBundle, TextBundle},
+        AlignItems, FlexDirection, FlexWrap, JustifyContent, Style, UiImage, UiRect, Val,
+    },
+    utils::default,
+};
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_assets::{MobAssets, UiAssets};
+use thetawave_interface::{
+    game::{
+        historical_metrics::{
+            MobKillsByPlayerForCompletedGames, MobsKilledBy1PlayerCacheT, UserStat,
+            UserStatsByPlayerForCompletedGamesCache,
+        },
+        profiles::ProfilesResource,
+        run_history::{PersonalBests, RunHistoryResource, RunRecord},
+    },
+    input::{MainMenuExplorer, MenuAction},
+    localization::LocalizationResource,
+    run::RunOutcomeType,
+    spawnable::MobType,
+    states::{AppStates, StatsCleanup},
+};
+
+use super::get_kills_by_name;
+use crate::player::CharactersResource;
+
+const TITLE_FONT_SIZE: f32 = 48.0;
+const SUMMARY_FONT_SIZE: f32 = 24.0;
+const KILLS_FONT_SIZE: f32 = 18.0;
+const HINT_FONT_SIZE: f32 = 18.0;
+const MOB_SPRITE_HEIGHT: f32 = 64.0;
+
+fn get_summary_text(
+    user_stat: &UserStat,
+    personal_bests: &PersonalBests,
+    characters: &CharactersResource,
+    localization: &LocalizationResource,
+) -> String {
+    let games_played = user_stat.total_games_won + user_stat.total_games_lost;
+    let mut lines = vec![
+        localization.format(
+            "stats.games_played",
+            &[
+                ("count", games_played.to_string()),
+                ("won", user_stat.total_games_won.to_string()),
+                ("lost", user_stat.total_games_lost.to_string()),
+            ],
+        ),
+        if user_stat.total_shots_fired > 0 {
+            localization.format(
+                "stats.accuracy",
+                &[
+                    (
+                        "percent",
+                        format!(
+                            "{:.2}",
+                            (user_stat.total_shots_hit as f32 / user_stat.total_shots_fired as f32)
+                                * 100.0
+                        ),
+                    ),
+                    ("hit", user_stat.total_shots_hit.to_string()),
+                    ("fired", user_stat.total_shots_fired.to_string()),
+                ],
+            )
+        } else {
+            localization.get("stats.no_accuracy")
+        },
+        localization.format(
+            "stats.levels_completed",
+            &[
+                ("count", user_stat.total_levels_completed.to_string()),
+                ("most", user_stat.most_levels_completed.to_string()),
+            ],
+        ),
+    ];
+
+    // runs are only kept when they are saved
+    if cfg!(feature = "storage") {
+        lines.push(
+            localization.format(
+                "stats.favorite_character",
+                &[(
+                    "character",
+                    match &personal_bests.favorite_character {
+                        Some(character_type) => characters
+                            .characters
+                            .get(character_type)
+                            .map(|character| character.name.clone())
+                            .unwrap_or_else(|| character_type.as_str().to_string()),
+                        None => "-".to_string(),
+                    },
+                )],
+            ),
+        );
+        lines.push(localization.format(
+            "stats.best_run",
+            &[(
+                "run",
+                match &personal_bests.best_run {
+                    Some(run) => get_best_run_text(run, localization),
+                    None => "-".to_string(),
+                },
+            )],
+        ));
+    } else {
+        lines.push(String::new());
+        lines.push(localization.get("stats.saving_disabled"));
+    }
+
+    lines.join("\n")
+}
+
+fn get_best_run_text(run: &RunRecord, localization: &LocalizationResource) -> String {
+    localization.format(
+        "stats.best_run_summary",
+        &[
+            ("score", run.score.to_string()),
+            ("run", run.run_key.replace('_', " ")),
+            ("difficulty", run.difficulty.get_name(localization)),
+            (
+                "outcome",
+                match run.outcome {
+                    RunOutcomeType::Victory => localization.get("run.victory"),
+                    RunOutcomeType::Defeat(_) => localization.format(
+                        "run.reached_level",
+                        &[("level", (run.levels_completed + 1).to_string())],
+                    ),
+                },
+            ),
+        ],
+    )
+}
+
+/// Spawns a sprite and kill count for each type of enemy that has been destroyed
+fn spawn_mob_kills(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    mob_kills: &MobsKilledBy1PlayerCacheT,
+    mob_assets: &MobAssets,
+    localization: &LocalizationResource,
+) {
+    let kills_by_name = get_kills_by_name(mob_kills);
+    if kills_by_name.is_empty() {
+        parent.spawn(TextBundle::from_section(
+            localization.get("stats.no_kills"),
+            TextStyle {
+                font,
+                font_size: KILLS_FONT_SIZE,
+                color: Color::WHITE,
+            },
+        ));
+        return;
+    }
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                max_width: Val::Percent(80.0),
+                column_gap: Val::Px(24.0),
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|row| {
+            for (name_id, enemy_type, n_killed) in kills_by_name {
+                let mob_type = MobType::Enemy(enemy_type);
+                row.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|column| {
+                    column
+                        .spawn(ImageBundle {
+                            style: Style {
+                                height: Val::Px(MOB_SPRITE_HEIGHT),
+                                ..default()
+                            },
+                            image: UiImage::new(mob_assets.get_mob_image(&mob_type)),
+                            ..default()
+                        })
+                        .insert(TextureAtlas {
+                            layout: mob_assets.get_mob_texture_atlas_layout(&mob_type),
+                            index: 0,
+                        });
+
+                    column.spawn(
+                        TextBundle::from_section(
+                            format!("{}\n{n_killed}", localization.get(name_id)),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: KILLS_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_text_justify(JustifyText::Center),
+                    );
+                });
+            }
+        });
+}
+
+/// Spawns the active profile's totals over its completed games and the enemies it has destroyed
+#[allow(clippy::too_many_arguments)]
+pub(super) fn setup_stats_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    mob_assets: Res<MobAssets>,
+    profiles: Res<ProfilesResource>,
+    user_stats: Res<UserStatsByPlayerForCompletedGamesCache>,
+    mob_kills: Res<MobKillsByPlayerForCompletedGames>,
+    run_history: Res<RunHistoryResource>,
+    characters: Res<CharactersResource>,
+    localization: Res<LocalizationResource>,
+) {
+    let font = ui_assets.lunchds_font.clone();
+    let profile_id = profiles.active_profile_id;
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Vh(3.0),
+                padding: UiRect::all(Val::Vh(2.0)),
+                ..default()
+            },
+            background_color: Color::BLACK.with_alpha(0.8).into(),
+            ..default()
+        })
+        .insert(StatsCleanup)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.format("stats.title", &[("profile", profiles.get_name(profile_id))]),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent.spawn(
+                TextBundle::from_section(
+                    get_summary_text(
+                        &user_stats.get(&profile_id).cloned().unwrap_or_default(),
+                        &run_history.personal_bests,
+                        &characters,
+                        &localization,
+                    ),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: SUMMARY_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_text_justify(JustifyText::Left),
+            );
+
+            spawn_mob_kills(
+                parent,
+                font.clone(),
+                &mob_kills.get(&profile_id).cloned().unwrap_or_default(),
+                &mob_assets,
+                &localization,
+            );
+
+            parent.spawn(TextBundle::from_section(
+                localization.get("menu.back_hint"),
+                TextStyle {
+                    font,
+                    font_size: HINT_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Returns to the main menu
+pub(super) fn stats_input_system(
+    menu_input_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
+    mut next_app_state: ResMut<NextState<AppStates>>,
+) {
+    if let Ok(action_state) = menu_input_query.get_single() {
+        if action_state.just_released(&MenuAction::Back) {
+            next_app_state.set(AppStates::MainMenu);
+        }
+    }
+}
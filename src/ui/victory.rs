@@ -9,10 +9,9 @@ use bevy::{
         system::{Commands, Res},
     },
     hierarchy::BuildChildren,
-    text::{JustifyText, Text, TextStyle},
     time::{Timer, TimerMode},
     ui::{
-        node_bundles::{ImageBundle, NodeBundle, TextBundle},
+        node_bundles::{ImageBundle, NodeBundle},
         BackgroundColor, FlexDirection, JustifyContent, Style, UiRect, Val,
     },
     utils::default,
@@ -20,24 +19,25 @@ use bevy::{
 use std::time::Duration;
 use thetawave_assets::UiAssets;
 use thetawave_interface::{
-    audio::ChangeBackgroundMusicEvent,
-    game::historical_metrics::{
-        MobKillsByPlayerForCurrentGame, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
-    },
+    audio::ChangeBackgroundMusicEvent, game::historical_metrics::StatsByPlayerForCurrentGame,
+    game::profiles::ProfilesResource, localization::LocalizationResource, player::PlayersResource,
     states::VictoryCleanup,
 };
 
 #[derive(Component)]
 pub struct VictoryUI;
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup_victory_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     ui_assets: Res<UiAssets>,
     mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
-    current_game_shot_counts: Res<UserStatsByPlayerForCurrentGameCache>,
-    current_game_enemy_mob_kill_counts: Res<MobKillsByPlayerForCurrentGame>,
+    stats_by_player: Res<StatsByPlayerForCurrentGame>,
+    players_resource: Res<PlayersResource>,
     playing_on_arcade: Res<PlayingOnArcadeResource>,
+    profiles: Res<ProfilesResource>,
+    localization: Res<LocalizationResource>,
 ) {
     // fade music out
     change_bg_music_event_writer.send(ChangeBackgroundMusicEvent {
@@ -45,17 +45,6 @@ pub fn setup_victory_system(
         ..default()
     });
 
-    let maybe_current_game_stats = (**current_game_shot_counts).get(&DEFAULT_USER_ID);
-    let (accuracy_rate, total_shots_fired): (f32, usize) = match maybe_current_game_stats {
-        None => (100.0, 0),
-        Some(current_game_shot_counts) => {
-            let accuracy = (current_game_shot_counts.total_shots_hit as f32
-                / current_game_shot_counts.total_shots_fired as f32)
-                * 100.0;
-            (accuracy, current_game_shot_counts.total_shots_fired)
-        }
-    };
-
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -105,33 +94,14 @@ pub fn setup_victory_system(
                             ..default()
                         })
                         .with_children(|parent| {
-                            parent.spawn(TextBundle {
-                                style: Style {
-                                    width: Val::Auto,
-                                    height: Val::Auto,
-                                    margin: UiRect::all(Val::Auto),
-                                    ..Default::default()
-                                },
-
-                                text: Text::from_section(
-                                    format!(
-                                        "Projectiles fired: {}\nAccuracy: {:.2}%\n\nEnemies destroyed:\n{}",
-                                        total_shots_fired,
-                                        accuracy_rate,
-                                        super::pprint_mob_kills_from_data(
-                                            &current_game_enemy_mob_kill_counts
-                                        ),
-                                    ),
-                                    TextStyle {
-                                        font,
-                                        font_size: 32.0,
-                                        color: Color::WHITE,
-                                    },
-                                )
-                                    .with_justify(JustifyText::Center),
-
-                                ..default()
-                            });
+                            super::spawn_player_stats_columns(
+                                parent,
+                                font,
+                                &stats_by_player,
+                                &players_resource,
+                                &profiles,
+                                &localization,
+                            );
                         });
 
                     parent